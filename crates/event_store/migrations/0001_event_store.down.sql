@@ -1,4 +1,7 @@
+DROP TABLE relay_backfill_checkpoint;
+DROP TABLE order_projection;
 DROP TABLE projection_cursor;
+DROP TABLE nostr_event_tombstone;
 DROP TABLE nostr_event_head;
 DROP TABLE relay_event_seen;
 DROP TABLE nostr_event_tag;
@@ -13,13 +13,17 @@ CREATE TABLE nostr_event (
   contract_id TEXT,
   event_class TEXT,
   projection_eligible INTEGER NOT NULL,
+  deleted_by_event_id TEXT,
+  expires_at INTEGER,
   inserted_at_ms INTEGER NOT NULL,
   updated_at_ms INTEGER NOT NULL
 );
 
 CREATE INDEX nostr_event_kind_created_idx ON nostr_event(kind, created_at, event_id);
+CREATE INDEX nostr_event_created_id_idx ON nostr_event(created_at, event_id);
 CREATE INDEX nostr_event_contract_idx ON nostr_event(contract_id, seq);
 CREATE INDEX nostr_event_projection_idx ON nostr_event(projection_eligible, seq);
+CREATE INDEX nostr_event_expires_at_idx ON nostr_event(expires_at) WHERE expires_at IS NOT NULL;
 CREATE INDEX nostr_event_verification_contract_idx
 ON nostr_event(verification_status, contract_status, seq);
 
@@ -75,9 +79,54 @@ WHERE coordinate_type = 'addressable';
 
 CREATE INDEX nostr_event_head_event_idx ON nostr_event_head(event_id);
 
+CREATE TABLE nostr_event_tombstone (
+  deletion_event_id TEXT NOT NULL REFERENCES nostr_event(event_id) ON DELETE CASCADE,
+  target_type TEXT NOT NULL CHECK (target_type IN ('event', 'coordinate')),
+  target TEXT NOT NULL,
+  pubkey TEXT NOT NULL,
+  deleted_at INTEGER NOT NULL,
+  recorded_at_ms INTEGER NOT NULL,
+  PRIMARY KEY (deletion_event_id, target_type, target)
+);
+
+CREATE INDEX nostr_event_tombstone_target_idx
+ON nostr_event_tombstone(target_type, target, pubkey, deleted_at);
+
 CREATE TABLE projection_cursor (
   projection_id TEXT PRIMARY KEY NOT NULL,
   projection_version INTEGER NOT NULL DEFAULT 1,
   last_event_seq INTEGER NOT NULL DEFAULT 0,
   updated_at_ms INTEGER NOT NULL
 );
+
+CREATE TABLE order_projection (
+  order_id TEXT PRIMARY KEY NOT NULL,
+  status TEXT NOT NULL,
+  listing_addr TEXT,
+  buyer_pubkey TEXT,
+  seller_pubkey TEXT,
+  request_event_id TEXT,
+  last_event_id TEXT,
+  lifecycle_terminal INTEGER NOT NULL,
+  event_count INTEGER NOT NULL,
+  issue_count INTEGER NOT NULL,
+  last_event_seq INTEGER NOT NULL,
+  updated_at_ms INTEGER NOT NULL
+);
+
+CREATE INDEX order_projection_status_idx
+ON order_projection(status, last_event_seq);
+
+CREATE TABLE relay_backfill_checkpoint (
+  relay_url TEXT NOT NULL,
+  filter_key TEXT NOT NULL,
+  since_floor INTEGER NOT NULL,
+  until_cursor INTEGER NOT NULL,
+  window_secs INTEGER NOT NULL,
+  pages INTEGER NOT NULL,
+  events_fetched INTEGER NOT NULL,
+  truncated_seconds INTEGER NOT NULL,
+  completed INTEGER NOT NULL,
+  updated_at_ms INTEGER NOT NULL,
+  PRIMARY KEY (relay_url, filter_key)
+);
@@ -0,0 +1,636 @@
+use crate::RadrootsEventStoreError;
+use crate::model::{
+    RadrootsEventHeadStoreDecision, RadrootsEventIngest, RadrootsRelayObservationType,
+};
+use crate::store::{RadrootsEventStore, u32_from_i64};
+use radroots_events::RadrootsNostrEvent;
+use radroots_nostr::prelude::{RadrootsNostrEventVerification, radroots_nostr_verify_event};
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Row};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+
+pub const RADROOTS_EVENT_ARCHIVE_DEFAULT_PAGE_SIZE: u32 = 500;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RadrootsEventArchiveCompression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl RadrootsEventArchiveCompression {
+    pub fn detect(prefix: &[u8]) -> Self {
+        if prefix.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if prefix.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventArchiveFilter {
+    pub kinds: Vec<u32>,
+    pub authors: Vec<String>,
+    pub since: Option<u32>,
+    pub until: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsEventArchiveExportOptions {
+    pub filter: RadrootsEventArchiveFilter,
+    pub compression: RadrootsEventArchiveCompression,
+    pub include_observations: bool,
+    pub page_size: u32,
+}
+
+impl Default for RadrootsEventArchiveExportOptions {
+    fn default() -> Self {
+        Self {
+            filter: RadrootsEventArchiveFilter::default(),
+            compression: RadrootsEventArchiveCompression::None,
+            include_observations: true,
+            page_size: RADROOTS_EVENT_ARCHIVE_DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventArchiveExportReport {
+    pub events_written: u64,
+    pub observations_written: u64,
+    pub last_event_seq: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsEventArchiveLineErrorKind {
+    Json,
+    IdMismatch,
+    SignatureInvalid,
+    MalformedEnvelope,
+    InvalidObservation,
+    Store,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsEventArchiveLineError {
+    pub line: u64,
+    pub event_id: Option<String>,
+    pub kind: RadrootsEventArchiveLineErrorKind,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventArchiveImportReport {
+    pub lines_read: u64,
+    pub events_inserted: u64,
+    pub events_duplicate: u64,
+    pub events_skipped: u64,
+    pub observations_imported: u64,
+    pub errors: Vec<RadrootsEventArchiveLineError>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsEventArchiveObservation {
+    pub relay_url: String,
+    pub observation_type: String,
+    pub first_seen_at_ms: i64,
+    pub last_seen_at_ms: i64,
+    pub observation_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_message: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ArchiveLine {
+    id: String,
+    pubkey: String,
+    created_at: u32,
+    kind: u32,
+    tags: Vec<Vec<String>>,
+    content: String,
+    sig: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    radroots_observations: Vec<RadrootsEventArchiveObservation>,
+}
+
+impl ArchiveLine {
+    fn into_parts(self) -> (RadrootsNostrEvent, Vec<RadrootsEventArchiveObservation>) {
+        (
+            RadrootsNostrEvent {
+                id: self.id,
+                author: self.pubkey,
+                created_at: self.created_at,
+                kind: self.kind,
+                tags: self.tags,
+                content: self.content,
+                sig: self.sig,
+            },
+            self.radroots_observations,
+        )
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn export_archive<W: Write + Send>(
+        &self,
+        writer: W,
+        options: &RadrootsEventArchiveExportOptions,
+    ) -> Result<RadrootsEventArchiveExportReport, RadrootsEventStoreError> {
+        let mut writer = ArchiveWriter::new(writer, options.compression)?;
+        let mut report = RadrootsEventArchiveExportReport::default();
+        loop {
+            let rows = export_page_query(&options.filter, report.last_event_seq, options.page_size)
+                .build()
+                .fetch_all(self.pool())
+                .await?;
+            let Some(last_seq) = rows
+                .last()
+                .map(|row| row.try_get::<i64, _>("seq"))
+                .transpose()?
+            else {
+                break;
+            };
+            let mut observations = if options.include_observations {
+                page_observations(self, report.last_event_seq, last_seq).await?
+            } else {
+                BTreeMap::new()
+            };
+            for row in rows {
+                let event_id: String = row.try_get("event_id")?;
+                let tags_json: String = row.try_get("tags_json")?;
+                let line = ArchiveLine {
+                    radroots_observations: observations.remove(&event_id).unwrap_or_default(),
+                    id: event_id,
+                    pubkey: row.try_get("pubkey")?,
+                    created_at: u32_from_i64("created_at", row.try_get("created_at")?)?,
+                    kind: u32_from_i64("kind", row.try_get("kind")?)?,
+                    tags: serde_json::from_str(tags_json.as_str())?,
+                    content: row.try_get("content")?,
+                    sig: row.try_get("sig")?,
+                };
+                serde_json::to_writer(&mut writer, &line)?;
+                writer.write_all(b"\n")?;
+                report.events_written += 1;
+                report.observations_written += line.radroots_observations.len() as u64;
+            }
+            report.last_event_seq = last_seq;
+        }
+        writer.finish()?;
+        Ok(report)
+    }
+
+    pub async fn import_archive<R: Read + Send>(
+        &self,
+        reader: R,
+        imported_at_ms: i64,
+    ) -> Result<RadrootsEventArchiveImportReport, RadrootsEventStoreError> {
+        let mut reader = BufReader::new(reader);
+        let compression = RadrootsEventArchiveCompression::detect(reader.fill_buf()?);
+        let reader: Box<dyn BufRead + Send> = match compression {
+            RadrootsEventArchiveCompression::None => Box::new(reader),
+            RadrootsEventArchiveCompression::Gzip => {
+                Box::new(BufReader::new(flate2::read::GzDecoder::new(reader)))
+            }
+            RadrootsEventArchiveCompression::Zstd => Box::new(BufReader::new(
+                zstd::stream::read::Decoder::with_buffer(reader)?,
+            )),
+        };
+        let mut report = RadrootsEventArchiveImportReport::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            report.lines_read += 1;
+            let line_number = index as u64 + 1;
+            if let Err(error) = self
+                .import_archive_line(line.as_str(), imported_at_ms, &mut report)
+                .await
+            {
+                report.errors.push(RadrootsEventArchiveLineError {
+                    line: line_number,
+                    ..error
+                });
+            }
+        }
+        Ok(report)
+    }
+
+    async fn import_archive_line(
+        &self,
+        line: &str,
+        imported_at_ms: i64,
+        report: &mut RadrootsEventArchiveImportReport,
+    ) -> Result<(), RadrootsEventArchiveLineError> {
+        let archived = serde_json::from_str::<ArchiveLine>(line)
+            .map_err(|error| line_error(None, RadrootsEventArchiveLineErrorKind::Json, error))?;
+        let (event, observations) = archived.into_parts();
+        let event_id = Some(event.id.clone());
+        let verification_error = match radroots_nostr_verify_event(&event) {
+            RadrootsNostrEventVerification::Verified => None,
+            RadrootsNostrEventVerification::IdMismatch => {
+                Some(RadrootsEventArchiveLineErrorKind::IdMismatch)
+            }
+            RadrootsNostrEventVerification::IdVerified
+            | RadrootsNostrEventVerification::SignatureInvalid => {
+                Some(RadrootsEventArchiveLineErrorKind::SignatureInvalid)
+            }
+            RadrootsNostrEventVerification::MalformedEnvelope => {
+                Some(RadrootsEventArchiveLineErrorKind::MalformedEnvelope)
+            }
+        };
+        if let Some(kind) = verification_error {
+            return Err(line_error(event_id, kind, "event failed verification"));
+        }
+        for observation in &observations {
+            RadrootsRelayObservationType::parse(observation.observation_type.as_str()).map_err(
+                |error| {
+                    line_error(
+                        event_id.clone(),
+                        RadrootsEventArchiveLineErrorKind::InvalidObservation,
+                        error,
+                    )
+                },
+            )?;
+        }
+        let receipt = self
+            .ingest_event(RadrootsEventIngest::new(event, imported_at_ms))
+            .await
+            .map_err(|error| {
+                line_error(
+                    event_id.clone(),
+                    RadrootsEventArchiveLineErrorKind::Store,
+                    error,
+                )
+            })?;
+        match (receipt.inserted, &receipt.head_decision) {
+            (true, _) => report.events_inserted += 1,
+            (
+                false,
+                RadrootsEventHeadStoreDecision::Deleted | RadrootsEventHeadStoreDecision::Expired,
+            ) => report.events_skipped += 1,
+            (false, _) => report.events_duplicate += 1,
+        }
+        if receipt.seq == 0 {
+            return Ok(());
+        }
+        for observation in &observations {
+            merge_archived_observation(self, receipt.event_id.as_str(), observation)
+                .await
+                .map_err(|error| {
+                    line_error(
+                        event_id.clone(),
+                        RadrootsEventArchiveLineErrorKind::Store,
+                        error,
+                    )
+                })?;
+            report.observations_imported += 1;
+        }
+        Ok(())
+    }
+}
+
+enum ArchiveWriter<W: Write> {
+    Plain(W),
+    Gzip(flate2::write::GzEncoder<W>),
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    fn new(
+        writer: W,
+        compression: RadrootsEventArchiveCompression,
+    ) -> Result<Self, RadrootsEventStoreError> {
+        Ok(match compression {
+            RadrootsEventArchiveCompression::None => Self::Plain(writer),
+            RadrootsEventArchiveCompression::Gzip => Self::Gzip(flate2::write::GzEncoder::new(
+                writer,
+                flate2::Compression::default(),
+            )),
+            RadrootsEventArchiveCompression::Zstd => {
+                Self::Zstd(zstd::stream::write::Encoder::new(writer, 0)?)
+            }
+        })
+    }
+
+    fn finish(self) -> Result<(), RadrootsEventStoreError> {
+        match self {
+            Self::Plain(mut writer) => writer.flush()?,
+            Self::Gzip(encoder) => encoder.finish()?.flush()?,
+            Self::Zstd(encoder) => encoder.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for ArchiveWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(writer) => writer.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(writer) => writer.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+fn export_page_query<'a>(
+    filter: &'a RadrootsEventArchiveFilter,
+    after_seq: i64,
+    page_size: u32,
+) -> QueryBuilder<'a, sqlx::Sqlite> {
+    let mut query = QueryBuilder::new(
+        "SELECT seq, event_id, pubkey, created_at, kind, tags_json, content, sig FROM nostr_event WHERE seq > ",
+    );
+    query.push_bind(after_seq);
+    if !filter.kinds.is_empty() {
+        query.push(" AND kind IN (");
+        let mut kinds = query.separated(", ");
+        for kind in &filter.kinds {
+            kinds.push_bind(i64::from(*kind));
+        }
+        query.push(")");
+    }
+    if !filter.authors.is_empty() {
+        query.push(" AND pubkey IN (");
+        let mut authors = query.separated(", ");
+        for author in &filter.authors {
+            authors.push_bind(author.as_str());
+        }
+        query.push(")");
+    }
+    if let Some(since) = filter.since {
+        query
+            .push(" AND created_at >= ")
+            .push_bind(i64::from(since));
+    }
+    if let Some(until) = filter.until {
+        query
+            .push(" AND created_at <= ")
+            .push_bind(i64::from(until));
+    }
+    query
+        .push(" ORDER BY seq ASC LIMIT ")
+        .push_bind(i64::from(page_size.max(1)));
+    query
+}
+
+async fn page_observations(
+    store: &RadrootsEventStore,
+    after_seq: i64,
+    last_seq: i64,
+) -> Result<BTreeMap<String, Vec<RadrootsEventArchiveObservation>>, RadrootsEventStoreError> {
+    let rows = sqlx::query(
+        "SELECT o.event_id, o.relay_url, o.observation_type, o.first_seen_at_ms, o.last_seen_at_ms, o.observation_count, o.last_message FROM relay_event_seen o JOIN nostr_event e ON e.event_id = o.event_id WHERE e.seq > ? AND e.seq <= ? ORDER BY o.event_id, o.relay_url, o.observation_type",
+    )
+    .bind(after_seq)
+    .bind(last_seq)
+    .fetch_all(store.pool())
+    .await?;
+    let mut observations = BTreeMap::<String, Vec<RadrootsEventArchiveObservation>>::new();
+    for row in rows {
+        observations
+            .entry(row.try_get("event_id")?)
+            .or_default()
+            .push(RadrootsEventArchiveObservation {
+                relay_url: row.try_get("relay_url")?,
+                observation_type: row.try_get("observation_type")?,
+                first_seen_at_ms: row.try_get("first_seen_at_ms")?,
+                last_seen_at_ms: row.try_get("last_seen_at_ms")?,
+                observation_count: row.try_get("observation_count")?,
+                last_message: row.try_get("last_message")?,
+            });
+    }
+    Ok(observations)
+}
+
+async fn merge_archived_observation(
+    store: &RadrootsEventStore,
+    event_id: &str,
+    observation: &RadrootsEventArchiveObservation,
+) -> Result<(), RadrootsEventStoreError> {
+    sqlx::query(
+        "INSERT INTO relay_event_seen(event_id, relay_url, observation_type, first_seen_at_ms, last_seen_at_ms, observation_count, last_message) VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT(event_id, relay_url, observation_type) DO UPDATE SET first_seen_at_ms = MIN(relay_event_seen.first_seen_at_ms, excluded.first_seen_at_ms), last_seen_at_ms = MAX(relay_event_seen.last_seen_at_ms, excluded.last_seen_at_ms), observation_count = MAX(relay_event_seen.observation_count, excluded.observation_count), last_message = COALESCE(excluded.last_message, relay_event_seen.last_message)",
+    )
+    .bind(event_id)
+    .bind(observation.relay_url.as_str())
+    .bind(observation.observation_type.as_str())
+    .bind(observation.first_seen_at_ms)
+    .bind(observation.last_seen_at_ms)
+    .bind(observation.observation_count.max(1))
+    .bind(observation.last_message.as_deref())
+    .execute(store.pool())
+    .await?;
+    Ok(())
+}
+
+fn line_error(
+    event_id: Option<String>,
+    kind: RadrootsEventArchiveLineErrorKind,
+    message: impl ToString,
+) -> RadrootsEventArchiveLineError {
+    RadrootsEventArchiveLineError {
+        line: 0,
+        event_id,
+        kind,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::RadrootsRelayObservation;
+    use radroots_events::kinds::{KIND_POST, KIND_PROFILE};
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+
+    fn signed_event(kind: u32, created_at: u32, content: &str) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(kind, content, Vec::<Vec<String>>::new())
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    async fn source_store() -> (RadrootsEventStore, Vec<RadrootsNostrEvent>) {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let events = vec![
+            signed_event(KIND_PROFILE, 10, "{}"),
+            signed_event(KIND_POST, 11, "first"),
+            signed_event(KIND_POST, 12, "second"),
+        ];
+        for event in &events {
+            store
+                .ingest_event(
+                    RadrootsEventIngest::new(event.clone(), 1_000).with_observation(
+                        RadrootsRelayObservation::new(
+                            "wss://relay.example",
+                            RadrootsRelayObservationType::Fetch,
+                            1_000,
+                        ),
+                    ),
+                )
+                .await
+                .expect("ingest");
+        }
+        (store, events)
+    }
+
+    async fn export(
+        store: &RadrootsEventStore,
+        options: &RadrootsEventArchiveExportOptions,
+    ) -> Vec<u8> {
+        let mut archive = Vec::new();
+        store
+            .export_archive(&mut archive, options)
+            .await
+            .expect("export");
+        archive
+    }
+
+    #[tokio::test]
+    async fn archives_round_trip_with_observations_in_every_compression() {
+        let (source, events) = source_store().await;
+        for compression in [
+            RadrootsEventArchiveCompression::None,
+            RadrootsEventArchiveCompression::Gzip,
+            RadrootsEventArchiveCompression::Zstd,
+        ] {
+            let archive = export(
+                &source,
+                &RadrootsEventArchiveExportOptions {
+                    compression,
+                    page_size: 2,
+                    ..RadrootsEventArchiveExportOptions::default()
+                },
+            )
+            .await;
+            assert_eq!(
+                RadrootsEventArchiveCompression::detect(&archive),
+                compression
+            );
+
+            let target = RadrootsEventStore::open_memory().await.expect("open");
+            let report = target
+                .import_archive(archive.as_slice(), 2_000)
+                .await
+                .expect("import");
+            assert_eq!(report.lines_read, 3);
+            assert_eq!(report.events_inserted, 3);
+            assert_eq!(report.observations_imported, 3);
+            assert!(report.errors.is_empty());
+
+            let observations = target
+                .observations_for_event(events[1].id.as_str())
+                .await
+                .expect("observations");
+            assert_eq!(observations.len(), 1);
+            assert_eq!(observations[0].relay_url, "wss://relay.example");
+            assert_eq!(observations[0].first_seen_at_ms, 1_000);
+
+            let again = target
+                .import_archive(archive.as_slice(), 3_000)
+                .await
+                .expect("reimport");
+            assert_eq!(again.events_inserted, 0);
+            assert_eq!(again.events_duplicate, 3);
+            let observations = target
+                .observations_for_event(events[1].id.as_str())
+                .await
+                .expect("observations");
+            assert_eq!(observations[0].observation_count, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn export_applies_filters_and_lines_are_nip01_events() {
+        let (source, events) = source_store().await;
+        let archive = export(
+            &source,
+            &RadrootsEventArchiveExportOptions {
+                filter: RadrootsEventArchiveFilter {
+                    kinds: vec![KIND_POST],
+                    since: Some(12),
+                    ..RadrootsEventArchiveFilter::default()
+                },
+                include_observations: false,
+                ..RadrootsEventArchiveExportOptions::default()
+            },
+        )
+        .await;
+        let text = String::from_utf8(archive).expect("utf8");
+        let lines = text.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 1);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).expect("json");
+        assert_eq!(value["id"], events[2].id.as_str());
+        assert_eq!(value["pubkey"], events[2].author.as_str());
+        assert!(value.get("radroots_observations").is_none());
+    }
+
+    #[tokio::test]
+    async fn import_reports_per_line_errors_and_continues() {
+        let (source, events) = source_store().await;
+        let archive = export(&source, &RadrootsEventArchiveExportOptions::default()).await;
+        let text = String::from_utf8(archive).expect("utf8");
+        let mut lines = text.lines().map(str::to_owned).collect::<Vec<_>>();
+        let mut tampered: serde_json::Value = serde_json::from_str(&lines[1]).expect("json");
+        tampered["content"] = serde_json::Value::String("edited".to_owned());
+        lines[1] = tampered.to_string();
+        lines.insert(0, "{not json".to_owned());
+        let input = lines.join("\n");
+
+        let target = RadrootsEventStore::open_memory().await.expect("open");
+        let report = target
+            .import_archive(input.as_bytes(), 2_000)
+            .await
+            .expect("import");
+
+        assert_eq!(report.lines_read, 4);
+        assert_eq!(report.events_inserted, 2);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[0].line, 1);
+        assert_eq!(
+            report.errors[0].kind,
+            RadrootsEventArchiveLineErrorKind::Json
+        );
+        assert_eq!(report.errors[1].line, 3);
+        assert_eq!(
+            report.errors[1].kind,
+            RadrootsEventArchiveLineErrorKind::IdMismatch
+        );
+        assert_eq!(
+            report.errors[1].event_id.as_deref(),
+            Some(events[1].id.as_str())
+        );
+        assert!(
+            target
+                .get_event(events[1].id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::RadrootsEventStoreError;
+use crate::store::RadrootsEventStore;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayBackfillCheckpoint {
+    pub relay_url: String,
+    pub filter_key: String,
+    pub since_floor: u32,
+    pub until_cursor: u32,
+    pub window_secs: u32,
+    pub pages: u64,
+    pub events_fetched: u64,
+    pub truncated_seconds: u64,
+    pub completed: bool,
+    pub updated_at_ms: i64,
+}
+
+const CHECKPOINT_COLUMNS: &str = "relay_url, filter_key, since_floor, until_cursor, window_secs, pages, events_fetched, truncated_seconds, completed, updated_at_ms";
+
+impl RadrootsEventStore {
+    pub async fn relay_backfill_checkpoint(
+        &self,
+        relay_url: &str,
+        filter_key: &str,
+    ) -> Result<Option<RadrootsRelayBackfillCheckpoint>, RadrootsEventStoreError> {
+        let row = sqlx::query(&format!(
+            "SELECT {CHECKPOINT_COLUMNS} FROM relay_backfill_checkpoint WHERE relay_url = ? AND filter_key = ?"
+        ))
+        .bind(relay_url)
+        .bind(filter_key)
+        .fetch_optional(self.pool())
+        .await?;
+        row.map(checkpoint_from_row).transpose()
+    }
+
+    pub async fn relay_backfill_checkpoints(
+        &self,
+    ) -> Result<Vec<RadrootsRelayBackfillCheckpoint>, RadrootsEventStoreError> {
+        sqlx::query(&format!(
+            "SELECT {CHECKPOINT_COLUMNS} FROM relay_backfill_checkpoint ORDER BY relay_url, filter_key"
+        ))
+        .fetch_all(self.pool())
+        .await?
+        .into_iter()
+        .map(checkpoint_from_row)
+        .collect()
+    }
+
+    pub async fn save_relay_backfill_checkpoint(
+        &self,
+        checkpoint: &RadrootsRelayBackfillCheckpoint,
+    ) -> Result<(), RadrootsEventStoreError> {
+        sqlx::query(&format!(
+            "INSERT INTO relay_backfill_checkpoint ({CHECKPOINT_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(relay_url, filter_key) DO UPDATE SET since_floor = excluded.since_floor, until_cursor = excluded.until_cursor, window_secs = excluded.window_secs, pages = excluded.pages, events_fetched = excluded.events_fetched, truncated_seconds = excluded.truncated_seconds, completed = excluded.completed, updated_at_ms = excluded.updated_at_ms"
+        ))
+        .bind(checkpoint.relay_url.as_str())
+        .bind(checkpoint.filter_key.as_str())
+        .bind(i64::from(checkpoint.since_floor))
+        .bind(i64::from(checkpoint.until_cursor))
+        .bind(i64::from(checkpoint.window_secs))
+        .bind(count_to_i64("pages", checkpoint.pages)?)
+        .bind(count_to_i64("events_fetched", checkpoint.events_fetched)?)
+        .bind(count_to_i64(
+            "truncated_seconds",
+            checkpoint.truncated_seconds,
+        )?)
+        .bind(checkpoint.completed)
+        .bind(checkpoint.updated_at_ms)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_relay_backfill_checkpoint(
+        &self,
+        relay_url: &str,
+        filter_key: &str,
+    ) -> Result<bool, RadrootsEventStoreError> {
+        let result = sqlx::query(
+            "DELETE FROM relay_backfill_checkpoint WHERE relay_url = ? AND filter_key = ?",
+        )
+        .bind(relay_url)
+        .bind(filter_key)
+        .execute(self.pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn checkpoint_from_row(
+    row: SqliteRow,
+) -> Result<RadrootsRelayBackfillCheckpoint, RadrootsEventStoreError> {
+    Ok(RadrootsRelayBackfillCheckpoint {
+        relay_url: row.try_get("relay_url")?,
+        filter_key: row.try_get("filter_key")?,
+        since_floor: u32_from_i64("since_floor", row.try_get("since_floor")?)?,
+        until_cursor: u32_from_i64("until_cursor", row.try_get("until_cursor")?)?,
+        window_secs: u32_from_i64("window_secs", row.try_get("window_secs")?)?,
+        pages: u64_from_i64("pages", row.try_get("pages")?)?,
+        events_fetched: u64_from_i64("events_fetched", row.try_get("events_fetched")?)?,
+        truncated_seconds: u64_from_i64("truncated_seconds", row.try_get("truncated_seconds")?)?,
+        completed: row.try_get("completed")?,
+        updated_at_ms: row.try_get("updated_at_ms")?,
+    })
+}
+
+fn u32_from_i64(field: &'static str, value: i64) -> Result<u32, RadrootsEventStoreError> {
+    u32::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
+}
+
+fn u64_from_i64(field: &'static str, value: i64) -> Result<u64, RadrootsEventStoreError> {
+    u64::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
+}
+
+fn count_to_i64(field: &'static str, value: u64) -> Result<i64, RadrootsEventStoreError> {
+    i64::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange {
+        field,
+        value: i64::MAX,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(until_cursor: u32, completed: bool) -> RadrootsRelayBackfillCheckpoint {
+        RadrootsRelayBackfillCheckpoint {
+            relay_url: "wss://relay.example.com".to_owned(),
+            filter_key: r#"{"kinds":[1]}"#.to_owned(),
+            since_floor: 100,
+            until_cursor,
+            window_secs: 3_600,
+            pages: 2,
+            events_fetched: 40,
+            truncated_seconds: 0,
+            completed,
+            updated_at_ms: 5_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn backfill_checkpoints_upsert_list_and_delete() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let relay_url = "wss://relay.example.com";
+        let filter_key = r#"{"kinds":[1]}"#;
+        assert!(
+            store
+                .relay_backfill_checkpoint(relay_url, filter_key)
+                .await
+                .expect("missing")
+                .is_none()
+        );
+
+        store
+            .save_relay_backfill_checkpoint(&checkpoint(9_000, false))
+            .await
+            .expect("save");
+        store
+            .save_relay_backfill_checkpoint(&checkpoint(4_000, true))
+            .await
+            .expect("update");
+        assert_eq!(
+            store
+                .relay_backfill_checkpoint(relay_url, filter_key)
+                .await
+                .expect("load"),
+            Some(checkpoint(4_000, true))
+        );
+        assert_eq!(
+            store
+                .relay_backfill_checkpoints()
+                .await
+                .expect("list")
+                .len(),
+            1
+        );
+
+        assert!(
+            store
+                .delete_relay_backfill_checkpoint(relay_url, filter_key)
+                .await
+                .expect("delete")
+        );
+        assert!(
+            !store
+                .delete_relay_backfill_checkpoint(relay_url, filter_key)
+                .await
+                .expect("delete again")
+        );
+    }
+}
@@ -8,6 +8,8 @@ pub enum RadrootsEventStoreError {
     Sqlx(#[from] sqlx::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("contract match error: {0:?}")]
     ContractMatch(RadrootsContractMatchError),
     #[error("event-head malformed: {0:?}")]
@@ -20,4 +22,10 @@ pub enum RadrootsEventStoreError {
     InvalidStoredEnum { field: &'static str, value: String },
     #[error("integer value `{value}` is outside {field} range")]
     IntegerRange { field: &'static str, value: i64 },
+    #[cfg(feature = "negentropy")]
+    #[error("negentropy error: {0}")]
+    Negentropy(#[from] negentropy::Error),
+    #[cfg(feature = "negentropy")]
+    #[error("negentropy message is not valid hex")]
+    NegentropyMessage,
 }
@@ -1,14 +1,39 @@
 #![forbid(unsafe_code)]
 
+#[cfg(all(feature = "sqlite", feature = "archive"))]
+mod archive;
+#[cfg(feature = "sqlite")]
+mod backfill;
 #[cfg(feature = "sqlite")]
 mod error;
 #[cfg(feature = "sqlite")]
 mod migrations;
 #[cfg(feature = "sqlite")]
 mod model;
+#[cfg(all(feature = "sqlite", feature = "negentropy"))]
+mod negentropy;
+#[cfg(all(feature = "sqlite", feature = "trade"))]
+mod order_projection;
+#[cfg(feature = "sqlite")]
+mod projection;
+#[cfg(feature = "sqlite")]
+mod query;
+#[cfg(feature = "sqlite")]
+mod relay_stats;
+#[cfg(feature = "sqlite")]
+mod retention;
 #[cfg(feature = "sqlite")]
 mod store;
 
+#[cfg(all(feature = "sqlite", feature = "archive"))]
+pub use archive::{
+    RADROOTS_EVENT_ARCHIVE_DEFAULT_PAGE_SIZE, RadrootsEventArchiveCompression,
+    RadrootsEventArchiveExportOptions, RadrootsEventArchiveExportReport,
+    RadrootsEventArchiveFilter, RadrootsEventArchiveImportReport, RadrootsEventArchiveLineError,
+    RadrootsEventArchiveLineErrorKind, RadrootsEventArchiveObservation,
+};
+#[cfg(feature = "sqlite")]
+pub use backfill::RadrootsRelayBackfillCheckpoint;
 #[cfg(feature = "sqlite")]
 pub use error::RadrootsEventStoreError;
 #[cfg(feature = "sqlite")]
@@ -16,9 +41,35 @@ pub use migrations::{EVENT_STORE_MIGRATION_DOWN, EVENT_STORE_MIGRATION_UP};
 #[cfg(feature = "sqlite")]
 pub use model::{
     RadrootsEventContractStatus, RadrootsEventHeadStoreDecision, RadrootsEventIngest,
-    RadrootsEventIngestReceipt, RadrootsEventVerificationStatus, RadrootsProjectionCursor,
-    RadrootsRelayObservation, RadrootsRelayObservationType, RadrootsStoredEvent,
-    RadrootsStoredEventHead, RadrootsStoredEventTag, StoredEventClass,
+    RadrootsEventIngestReceipt, RadrootsEventTombstone, RadrootsEventTombstoneTarget,
+    RadrootsEventVerificationStatus, RadrootsProjectionCursor, RadrootsRelayObservation,
+    RadrootsRelayObservationType, RadrootsStoredEvent, RadrootsStoredEventHead,
+    RadrootsStoredEventTag, StoredEventClass,
+};
+#[cfg(all(feature = "sqlite", feature = "negentropy"))]
+pub use negentropy::{
+    RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT, RadrootsNegentropyFilter,
+    RadrootsNegentropyRound, RadrootsNegentropySession,
+};
+#[cfg(all(feature = "sqlite", feature = "trade"))]
+pub use order_projection::{
+    RADROOTS_ORDER_PROJECTION_ID, RADROOTS_ORDER_PROJECTION_VERSION, RadrootsOrderEventProjection,
+    RadrootsProjectedOrder,
+};
+#[cfg(feature = "sqlite")]
+pub use projection::{
+    RADROOTS_PROJECTION_DEFAULT_BATCH_SIZE, RadrootsProjection, RadrootsProjectionBatch,
+    RadrootsProjectionFuture, RadrootsProjectionLag, RadrootsProjectionRunReport,
+    RadrootsProjectionRunner, RadrootsProjectionTransaction,
+};
+#[cfg(feature = "sqlite")]
+pub use query::RadrootsEventQuery;
+#[cfg(feature = "sqlite")]
+pub use relay_stats::RadrootsRelayDeliveryBucket;
+#[cfg(feature = "sqlite")]
+pub use retention::{
+    RadrootsEventKindMaxAge, RadrootsEventRetentionPolicy, RadrootsEventRetentionReason,
+    RadrootsEventRetentionRemoval, RadrootsEventRetentionReport,
 };
 #[cfg(feature = "sqlite")]
-pub use store::RadrootsEventStore;
+pub use store::{RadrootsEventStore, RadrootsRelayObservationRow};
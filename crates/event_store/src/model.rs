@@ -141,6 +141,19 @@ impl RadrootsRelayObservationType {
             Self::Import => "import",
         }
     }
+
+    pub fn parse(value: &str) -> Result<Self, RadrootsEventStoreError> {
+        match value {
+            "fetch" => Ok(Self::Fetch),
+            "subscription" => Ok(Self::Subscription),
+            "publish_ack" => Ok(Self::PublishAck),
+            "import" => Ok(Self::Import),
+            _ => Err(RadrootsEventStoreError::InvalidStoredEnum {
+                field: "observation_type",
+                value: value.to_owned(),
+            }),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -211,6 +224,8 @@ pub enum RadrootsEventHeadStoreDecision {
     SkippedSameTimestampHigherEventId,
     Malformed,
     Unsupported,
+    Deleted,
+    Expired,
 }
 
 impl RadrootsEventHeadStoreDecision {
@@ -255,10 +270,25 @@ pub struct RadrootsStoredEvent {
     pub contract_id: Option<String>,
     pub event_class: Option<StoredEventClass>,
     pub projection_eligible: bool,
+    pub expires_at: Option<u64>,
     pub inserted_at_ms: i64,
     pub updated_at_ms: i64,
 }
 
+impl RadrootsStoredEvent {
+    pub fn to_nostr_event(&self) -> Result<RadrootsNostrEvent, RadrootsEventStoreError> {
+        Ok(RadrootsNostrEvent {
+            id: self.event_id.clone(),
+            author: self.pubkey.clone(),
+            created_at: self.created_at,
+            kind: self.kind,
+            tags: serde_json::from_str(self.tags_json.as_str())?,
+            content: self.content.clone(),
+            sig: self.sig.clone(),
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RadrootsStoredEventTag {
     pub event_id: String,
@@ -282,6 +312,42 @@ pub struct RadrootsStoredEventHead {
     pub updated_at_ms: i64,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RadrootsEventTombstoneTarget {
+    Event,
+    Coordinate,
+}
+
+impl RadrootsEventTombstoneTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Event => "event",
+            Self::Coordinate => "coordinate",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, RadrootsEventStoreError> {
+        match value {
+            "event" => Ok(Self::Event),
+            "coordinate" => Ok(Self::Coordinate),
+            _ => Err(RadrootsEventStoreError::InvalidStoredEnum {
+                field: "target_type",
+                value: value.to_owned(),
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsEventTombstone {
+    pub deletion_event_id: String,
+    pub target_type: RadrootsEventTombstoneTarget,
+    pub target: String,
+    pub pubkey: String,
+    pub deleted_at: u32,
+    pub recorded_at_ms: i64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RadrootsProjectionCursor {
     pub projection_id: String,
@@ -296,6 +362,7 @@ pub fn tag_semantic_name(value: RadrootsTagSemantic) -> &'static str {
         RadrootsTagSemantic::Category => "category",
         RadrootsTagSemantic::Counterparty => "counterparty",
         RadrootsTagSemantic::EventPointer => "event_pointer",
+        RadrootsTagSemantic::Expiration => "expiration",
         RadrootsTagSemantic::GroupId => "group_id",
         RadrootsTagSemantic::Identifier => "identifier",
         RadrootsTagSemantic::Image => "image",
@@ -0,0 +1,230 @@
+use crate::RadrootsEventStoreError;
+use crate::store::{RadrootsEventStore, u32_from_i64};
+use negentropy::{Id, Negentropy, NegentropyStorageVector};
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Row};
+
+pub const RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT: u64 = 60_000;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsNegentropyFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsNegentropyRound {
+    pub next_message: Option<String>,
+    pub have_ids: Vec<String>,
+    pub need_ids: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct RadrootsNegentropySession {
+    negentropy: Negentropy<'static, NegentropyStorageVector>,
+    item_count: usize,
+}
+
+impl RadrootsNegentropySession {
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    pub fn is_initiator(&self) -> bool {
+        self.negentropy.is_initiator()
+    }
+
+    pub fn initiate(&mut self) -> Result<String, RadrootsEventStoreError> {
+        Ok(hex::encode(self.negentropy.initiate()?))
+    }
+
+    pub fn reconcile(&mut self, query_hex: &str) -> Result<String, RadrootsEventStoreError> {
+        let query = decode_message(query_hex)?;
+        Ok(hex::encode(self.negentropy.reconcile(&query)?))
+    }
+
+    pub fn reconcile_with_ids(
+        &mut self,
+        message_hex: &str,
+    ) -> Result<RadrootsNegentropyRound, RadrootsEventStoreError> {
+        let message = decode_message(message_hex)?;
+        let mut have_ids = Vec::new();
+        let mut need_ids = Vec::new();
+        let next_message =
+            self.negentropy
+                .reconcile_with_ids(&message, &mut have_ids, &mut need_ids)?;
+        Ok(RadrootsNegentropyRound {
+            next_message: next_message.map(hex::encode),
+            have_ids: have_ids
+                .iter()
+                .map(|id| hex::encode(id.as_bytes()))
+                .collect(),
+            need_ids: need_ids
+                .iter()
+                .map(|id| hex::encode(id.as_bytes()))
+                .collect(),
+        })
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn negentropy_session(
+        &self,
+        filter: &RadrootsNegentropyFilter,
+        frame_size_limit: u64,
+    ) -> Result<RadrootsNegentropySession, RadrootsEventStoreError> {
+        let rows = negentropy_items_query(filter)
+            .build()
+            .fetch_all(self.pool())
+            .await?;
+        let mut storage = NegentropyStorageVector::with_capacity(rows.len());
+        for row in &rows {
+            let created_at = u32_from_i64("created_at", row.try_get("created_at")?)?;
+            let event_id: String = row.try_get("event_id")?;
+            let id_bytes = hex::decode(event_id.as_str())
+                .map_err(|_| RadrootsEventStoreError::NegentropyMessage)?;
+            storage.insert(u64::from(created_at), Id::from_slice(&id_bytes)?)?;
+        }
+        storage.seal()?;
+        Ok(RadrootsNegentropySession {
+            negentropy: Negentropy::owned(storage, frame_size_limit)?,
+            item_count: rows.len(),
+        })
+    }
+}
+
+fn negentropy_items_query(filter: &RadrootsNegentropyFilter) -> QueryBuilder<'_, sqlx::Sqlite> {
+    let mut query = QueryBuilder::new(
+        "SELECT created_at, event_id FROM nostr_event WHERE verification_status = 'verified'",
+    );
+    if !filter.kinds.is_empty() {
+        query.push(" AND kind IN (");
+        let mut kinds = query.separated(", ");
+        for kind in &filter.kinds {
+            kinds.push_bind(i64::from(*kind));
+        }
+        query.push(")");
+    }
+    if !filter.authors.is_empty() {
+        query.push(" AND pubkey IN (");
+        let mut authors = query.separated(", ");
+        for author in &filter.authors {
+            authors.push_bind(author.as_str());
+        }
+        query.push(")");
+    }
+    if let Some(since) = filter.since {
+        query
+            .push(" AND created_at >= ")
+            .push_bind(i64::from(since));
+    }
+    if let Some(until) = filter.until {
+        query
+            .push(" AND created_at <= ")
+            .push_bind(i64::from(until));
+    }
+    query.push(" ORDER BY created_at ASC, event_id ASC");
+    query
+}
+
+fn decode_message(message_hex: &str) -> Result<Vec<u8>, RadrootsEventStoreError> {
+    hex::decode(message_hex).map_err(|_| RadrootsEventStoreError::NegentropyMessage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadrootsEventIngest;
+    use radroots_events::RadrootsNostrEvent;
+    use radroots_events::kinds::KIND_POST;
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+    use std::collections::BTreeSet;
+
+    fn signed_post(created_at: u32) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(
+            KIND_POST,
+            format!("post {created_at}"),
+            Vec::<Vec<String>>::new(),
+        )
+        .expect("builder")
+        .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+        .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+        .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    async fn store_with(events: &[RadrootsNostrEvent]) -> RadrootsEventStore {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        for event in events {
+            store
+                .ingest_event(RadrootsEventIngest::new(event.clone(), 1_000))
+                .await
+                .expect("ingest");
+        }
+        store
+    }
+
+    #[tokio::test]
+    async fn sessions_reconcile_have_and_need_ids_between_stores() {
+        let events = (1..=6).map(|n| signed_post(100 + n)).collect::<Vec<_>>();
+        let local = store_with(&events[..4]).await;
+        let remote = store_with(&events[2..]).await;
+        let filter = RadrootsNegentropyFilter {
+            kinds: vec![KIND_POST],
+            ..RadrootsNegentropyFilter::default()
+        };
+
+        let mut initiator = local
+            .negentropy_session(&filter, RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT)
+            .await
+            .expect("initiator");
+        let mut responder = remote
+            .negentropy_session(&filter, RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT)
+            .await
+            .expect("responder");
+        assert_eq!(initiator.item_count(), 4);
+        assert!(!responder.is_initiator());
+
+        let mut have_ids = BTreeSet::new();
+        let mut need_ids = BTreeSet::new();
+        let mut message = initiator.initiate().expect("initiate");
+        assert!(initiator.is_initiator());
+        for _ in 0..8 {
+            let reply = responder.reconcile(message.as_str()).expect("respond");
+            let round = initiator
+                .reconcile_with_ids(reply.as_str())
+                .expect("reconcile");
+            have_ids.extend(round.have_ids);
+            need_ids.extend(round.need_ids);
+            match round.next_message {
+                Some(next) => message = next,
+                None => break,
+            }
+        }
+
+        let ids = |range: &[RadrootsNostrEvent]| {
+            range
+                .iter()
+                .map(|event| event.id.clone())
+                .collect::<BTreeSet<_>>()
+        };
+        assert_eq!(have_ids, ids(&events[..2]));
+        assert_eq!(need_ids, ids(&events[4..]));
+        assert!(matches!(
+            initiator.reconcile_with_ids("zz"),
+            Err(RadrootsEventStoreError::NegentropyMessage)
+        ));
+    }
+}
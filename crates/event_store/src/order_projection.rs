@@ -0,0 +1,427 @@
+use crate::RadrootsEventStoreError;
+use crate::model::RadrootsStoredEvent;
+use crate::projection::{
+    RadrootsProjection, RadrootsProjectionBatch, RadrootsProjectionFuture,
+    RadrootsProjectionTransaction,
+};
+use crate::store::{RadrootsEventStore, stored_event_from_row};
+use radroots_events::ids::RadrootsOrderId;
+use radroots_events::kinds::{
+    KIND_ORDER_CANCELLATION, KIND_ORDER_DECISION, KIND_ORDER_FULFILLMENT_UPDATE,
+    KIND_ORDER_PAYMENT_RECORD, KIND_ORDER_RECEIPT, KIND_ORDER_REQUEST,
+    KIND_ORDER_REVISION_DECISION, KIND_ORDER_REVISION_PROPOSAL, KIND_ORDER_SETTLEMENT_DECISION,
+};
+use radroots_events::tags::TAG_D;
+use radroots_trade::order::RadrootsOrderProjection;
+use radroots_trade::order_events::{order_event_record_from_event, reduce_order_records};
+use sqlx::Row;
+use std::collections::BTreeSet;
+
+pub const RADROOTS_ORDER_PROJECTION_ID: &str = "radroots_trade.order";
+pub const RADROOTS_ORDER_PROJECTION_VERSION: u32 = 1;
+
+const ORDER_KINDS: [u32; 9] = [
+    KIND_ORDER_REQUEST,
+    KIND_ORDER_DECISION,
+    KIND_ORDER_REVISION_PROPOSAL,
+    KIND_ORDER_REVISION_DECISION,
+    KIND_ORDER_FULFILLMENT_UPDATE,
+    KIND_ORDER_CANCELLATION,
+    KIND_ORDER_RECEIPT,
+    KIND_ORDER_PAYMENT_RECORD,
+    KIND_ORDER_SETTLEMENT_DECISION,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsProjectedOrder {
+    pub order_id: String,
+    pub status: String,
+    pub listing_addr: Option<String>,
+    pub buyer_pubkey: Option<String>,
+    pub seller_pubkey: Option<String>,
+    pub request_event_id: Option<String>,
+    pub last_event_id: Option<String>,
+    pub lifecycle_terminal: bool,
+    pub event_count: u32,
+    pub issue_count: u32,
+    pub last_event_seq: i64,
+    pub updated_at_ms: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsOrderEventProjection;
+
+impl RadrootsOrderEventProjection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RadrootsProjection for RadrootsOrderEventProjection {
+    fn projection_id(&self) -> &str {
+        RADROOTS_ORDER_PROJECTION_ID
+    }
+
+    fn projection_version(&self) -> u32 {
+        RADROOTS_ORDER_PROJECTION_VERSION
+    }
+
+    fn apply_batch<'a>(
+        &'a self,
+        tx: &'a mut RadrootsProjectionTransaction<'static>,
+        batch: &'a RadrootsProjectionBatch,
+    ) -> RadrootsProjectionFuture<'a> {
+        Box::pin(async move {
+            for order_id in affected_order_ids(&batch.events) {
+                project_order(tx, &order_id, batch).await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn reset<'a>(
+        &'a self,
+        tx: &'a mut RadrootsProjectionTransaction<'static>,
+    ) -> RadrootsProjectionFuture<'a> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM order_projection")
+                .execute(&mut **tx)
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn projected_order(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<RadrootsProjectedOrder>, RadrootsEventStoreError> {
+        let row = sqlx::query(
+            "SELECT order_id, status, listing_addr, buyer_pubkey, seller_pubkey, request_event_id, last_event_id, lifecycle_terminal, event_count, issue_count, last_event_seq, updated_at_ms FROM order_projection WHERE order_id = ?",
+        )
+        .bind(order_id)
+        .fetch_optional(self.pool())
+        .await?;
+        row.map(projected_order_from_row).transpose()
+    }
+
+    pub async fn projected_orders_by_status(
+        &self,
+        status: &str,
+    ) -> Result<Vec<RadrootsProjectedOrder>, RadrootsEventStoreError> {
+        let rows = sqlx::query(
+            "SELECT order_id, status, listing_addr, buyer_pubkey, seller_pubkey, request_event_id, last_event_id, lifecycle_terminal, event_count, issue_count, last_event_seq, updated_at_ms FROM order_projection WHERE status = ? ORDER BY last_event_seq ASC, order_id ASC",
+        )
+        .bind(status)
+        .fetch_all(self.pool())
+        .await?;
+        rows.into_iter().map(projected_order_from_row).collect()
+    }
+}
+
+fn affected_order_ids(events: &[RadrootsStoredEvent]) -> BTreeSet<String> {
+    events
+        .iter()
+        .filter(|event| ORDER_KINDS.contains(&event.kind))
+        .filter_map(|event| {
+            let tags: Vec<Vec<String>> = serde_json::from_str(event.tags_json.as_str()).ok()?;
+            tags.into_iter()
+                .find(|tag| tag.first().is_some_and(|name| name == TAG_D))
+                .and_then(|tag| tag.get(1).cloned())
+        })
+        .collect()
+}
+
+async fn project_order(
+    tx: &mut RadrootsProjectionTransaction<'static>,
+    order_id: &str,
+    batch: &RadrootsProjectionBatch,
+) -> Result<(), RadrootsEventStoreError> {
+    let Ok(parsed_order_id) = RadrootsOrderId::parse(order_id) else {
+        return Ok(());
+    };
+    let rows = sqlx::query(
+        "SELECT e.seq, e.event_id, e.pubkey, e.created_at, e.kind, e.tags_json, e.content, e.sig, e.raw_json, e.verification_status, e.contract_status, e.contract_id, e.event_class, e.projection_eligible, e.expires_at, e.inserted_at_ms, e.updated_at_ms FROM nostr_event e WHERE e.projection_eligible = 1 AND e.deleted_by_event_id IS NULL AND e.seq <= ? AND e.kind IN (?, ?, ?, ?, ?, ?, ?, ?, ?) AND EXISTS (SELECT 1 FROM nostr_event_tag t WHERE t.event_id = e.event_id AND t.tag_name = ? AND t.tag_value = ?) ORDER BY e.seq ASC",
+    )
+    .bind(batch.last_event_seq);
+    let rows = ORDER_KINDS
+        .iter()
+        .fold(rows, |query, kind| query.bind(i64::from(*kind)))
+        .bind(TAG_D)
+        .bind(order_id)
+        .fetch_all(&mut **tx)
+        .await?;
+    let events = rows
+        .into_iter()
+        .map(stored_event_from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+    let Some(last_event_seq) = events.last().map(|event| event.seq) else {
+        sqlx::query("DELETE FROM order_projection WHERE order_id = ?")
+            .bind(order_id)
+            .execute(&mut **tx)
+            .await?;
+        return Ok(());
+    };
+    let mut records = Vec::with_capacity(events.len());
+    for event in &events {
+        if let Ok(record) = order_event_record_from_event(&event.to_nostr_event()?) {
+            records.push(record);
+        }
+    }
+    let event_count = records.len();
+    let projection = reduce_order_records(&parsed_order_id, records);
+    upsert_projected_order(tx, &projection, event_count, last_event_seq, batch).await
+}
+
+async fn upsert_projected_order(
+    tx: &mut RadrootsProjectionTransaction<'static>,
+    projection: &RadrootsOrderProjection,
+    event_count: usize,
+    last_event_seq: i64,
+    batch: &RadrootsProjectionBatch,
+) -> Result<(), RadrootsEventStoreError> {
+    sqlx::query(
+        "INSERT INTO order_projection(order_id, status, listing_addr, buyer_pubkey, seller_pubkey, request_event_id, last_event_id, lifecycle_terminal, event_count, issue_count, last_event_seq, updated_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(order_id) DO UPDATE SET status = excluded.status, listing_addr = excluded.listing_addr, buyer_pubkey = excluded.buyer_pubkey, seller_pubkey = excluded.seller_pubkey, request_event_id = excluded.request_event_id, last_event_id = excluded.last_event_id, lifecycle_terminal = excluded.lifecycle_terminal, event_count = excluded.event_count, issue_count = excluded.issue_count, last_event_seq = excluded.last_event_seq, updated_at_ms = excluded.updated_at_ms",
+    )
+    .bind(projection.order_id.as_str())
+    .bind(projection.status.as_str())
+    .bind(projection.listing_addr.as_ref().map(|value| value.as_str()))
+    .bind(projection.buyer_pubkey.as_ref().map(|value| value.as_str()))
+    .bind(projection.seller_pubkey.as_ref().map(|value| value.as_str()))
+    .bind(projection.request_event_id.as_ref().map(|value| value.as_str()))
+    .bind(projection.last_event_id.as_ref().map(|value| value.as_str()))
+    .bind(i64::from(projection.lifecycle_terminal))
+    .bind(i64::try_from(event_count).unwrap_or(i64::MAX))
+    .bind(i64::try_from(projection.issues.len()).unwrap_or(i64::MAX))
+    .bind(last_event_seq)
+    .bind(batch.applied_at_ms)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn projected_order_from_row(
+    row: sqlx::sqlite::SqliteRow,
+) -> Result<RadrootsProjectedOrder, RadrootsEventStoreError> {
+    Ok(RadrootsProjectedOrder {
+        order_id: row.try_get("order_id")?,
+        status: row.try_get("status")?,
+        listing_addr: row.try_get("listing_addr")?,
+        buyer_pubkey: row.try_get("buyer_pubkey")?,
+        seller_pubkey: row.try_get("seller_pubkey")?,
+        request_event_id: row.try_get("request_event_id")?,
+        last_event_id: row.try_get("last_event_id")?,
+        lifecycle_terminal: row.try_get::<i64, _>("lifecycle_terminal")? != 0,
+        event_count: count_from_i64("event_count", row.try_get("event_count")?)?,
+        issue_count: count_from_i64("issue_count", row.try_get("issue_count")?)?,
+        last_event_seq: row.try_get("last_event_seq")?,
+        updated_at_ms: row.try_get("updated_at_ms")?,
+    })
+}
+
+fn count_from_i64(field: &'static str, value: i64) -> Result<u32, RadrootsEventStoreError> {
+    u32::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::RadrootsEventIngest;
+    use crate::projection::RadrootsProjectionRunner;
+    use radroots_core::{
+        RadrootsCoreCurrency, RadrootsCoreDecimal, RadrootsCoreMoney, RadrootsCoreUnit,
+    };
+    use radroots_events::ids::{
+        RadrootsEventId, RadrootsInventoryBinId, RadrootsListingAddress, RadrootsOrderQuoteId,
+        RadrootsPublicKey,
+    };
+    use radroots_events::kinds::KIND_LISTING;
+    use radroots_events::order::{
+        RadrootsOrderDecision, RadrootsOrderDecisionOutcome, RadrootsOrderEconomicItem,
+        RadrootsOrderEconomics, RadrootsOrderInventoryCommitment, RadrootsOrderItem,
+        RadrootsOrderPricingBasis, RadrootsOrderRequest,
+    };
+    use radroots_events::{RadrootsNostrEvent, RadrootsNostrEventPtr};
+    use radroots_events_codec::order::{order_decision_event_build, order_request_event_build};
+    use radroots_events_codec::wire::WireEventParts;
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::{
+        FIXTURE_ALICE_PUBLIC_KEY_HEX, FIXTURE_ALICE_SECRET_KEY_HEX, FIXTURE_BOB_PUBLIC_KEY_HEX,
+        FIXTURE_BOB_SECRET_KEY_HEX,
+    };
+
+    const NOW_MS: i64 = 50_000;
+
+    fn keys(secret_key_hex: &str) -> RadrootsNostrKeys {
+        RadrootsNostrKeys::new(RadrootsNostrSecretKey::from_hex(secret_key_hex).expect("secret"))
+    }
+
+    fn sign(parts: WireEventParts, secret_key_hex: &str, created_at: u32) -> RadrootsNostrEvent {
+        let raw_event = radroots_nostr_build_event(parts.kind, parts.content, parts.tags)
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&keys(secret_key_hex))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    fn usd(raw: &str) -> RadrootsCoreMoney {
+        RadrootsCoreMoney::new(decimal(raw), RadrootsCoreCurrency::USD)
+    }
+
+    fn decimal(raw: &str) -> RadrootsCoreDecimal {
+        raw.parse().expect("decimal")
+    }
+
+    fn bin_id() -> RadrootsInventoryBinId {
+        RadrootsInventoryBinId::parse("bin-1").expect("bin id")
+    }
+
+    fn listing_address() -> RadrootsListingAddress {
+        RadrootsListingAddress::parse(format!(
+            "{KIND_LISTING}:{FIXTURE_BOB_PUBLIC_KEY_HEX}:AAAAAAAAAAAAAAAAAAAAAg"
+        ))
+        .expect("listing address")
+    }
+
+    fn request_event(order_id: &str) -> RadrootsNostrEvent {
+        let payload = RadrootsOrderRequest {
+            order_id: RadrootsOrderId::parse(order_id).expect("order id"),
+            listing_addr: listing_address(),
+            buyer_pubkey: RadrootsPublicKey::parse(FIXTURE_ALICE_PUBLIC_KEY_HEX).expect("buyer"),
+            seller_pubkey: RadrootsPublicKey::parse(FIXTURE_BOB_PUBLIC_KEY_HEX).expect("seller"),
+            items: vec![RadrootsOrderItem {
+                bin_id: bin_id(),
+                bin_count: 2,
+            }],
+            economics: RadrootsOrderEconomics {
+                quote_id: RadrootsOrderQuoteId::parse("quote-1").expect("quote id"),
+                quote_version: 1,
+                pricing_basis: RadrootsOrderPricingBasis::ListingEvent,
+                currency: RadrootsCoreCurrency::USD,
+                items: vec![RadrootsOrderEconomicItem {
+                    bin_id: bin_id(),
+                    bin_count: 2,
+                    quantity_amount: decimal("1"),
+                    quantity_unit: RadrootsCoreUnit::Each,
+                    unit_price_amount: decimal("5"),
+                    unit_price_currency: RadrootsCoreCurrency::USD,
+                    line_subtotal: usd("10"),
+                }],
+                discounts: Vec::new(),
+                adjustments: Vec::new(),
+                subtotal: usd("10"),
+                discount_total: usd("0"),
+                adjustment_total: usd("0"),
+                total: usd("10"),
+            },
+        };
+        let listing_event = RadrootsNostrEventPtr {
+            id: "c".repeat(64),
+            relays: None,
+        };
+        let parts = order_request_event_build(&listing_event, &payload).expect("request");
+        sign(parts, FIXTURE_ALICE_SECRET_KEY_HEX, 10)
+    }
+
+    fn decision_event(order_id: &str, request: &RadrootsNostrEvent) -> RadrootsNostrEvent {
+        let payload = RadrootsOrderDecision {
+            order_id: RadrootsOrderId::parse(order_id).expect("order id"),
+            listing_addr: listing_address(),
+            buyer_pubkey: RadrootsPublicKey::parse(FIXTURE_ALICE_PUBLIC_KEY_HEX).expect("buyer"),
+            seller_pubkey: RadrootsPublicKey::parse(FIXTURE_BOB_PUBLIC_KEY_HEX).expect("seller"),
+            decision: RadrootsOrderDecisionOutcome::Accepted {
+                inventory_commitments: vec![RadrootsOrderInventoryCommitment {
+                    bin_id: bin_id(),
+                    bin_count: 2,
+                }],
+            },
+        };
+        let request_id = RadrootsEventId::parse(request.id.as_str()).expect("request id");
+        let parts =
+            order_decision_event_build(&request_id, &request_id, &payload).expect("decision");
+        sign(parts, FIXTURE_BOB_SECRET_KEY_HEX, 11)
+    }
+
+    async fn ingest(store: &RadrootsEventStore, event: &RadrootsNostrEvent) {
+        store
+            .ingest_event(RadrootsEventIngest::new(event.clone(), 1_000))
+            .await
+            .expect("ingest");
+    }
+
+    #[tokio::test]
+    async fn order_projection_follows_order_lifecycle() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let runner = RadrootsProjectionRunner::new(store.clone());
+        let projection = RadrootsOrderEventProjection::new();
+        let request = request_event("order-1");
+        ingest(&store, &request).await;
+
+        let report = runner.run(&projection, NOW_MS).await.expect("run");
+        assert_eq!(report.events_applied, 1);
+        let requested = store
+            .projected_order("order-1")
+            .await
+            .expect("projected")
+            .expect("order row");
+        assert_eq!(requested.status, "requested");
+        assert_eq!(
+            requested.buyer_pubkey.as_deref(),
+            Some(FIXTURE_ALICE_PUBLIC_KEY_HEX)
+        );
+        assert_eq!(
+            requested.request_event_id.as_deref(),
+            Some(request.id.as_str())
+        );
+        assert_eq!(requested.event_count, 1);
+        assert_eq!(requested.updated_at_ms, NOW_MS);
+
+        let decision = decision_event("order-1", &request);
+        ingest(&store, &decision).await;
+        runner.run(&projection, NOW_MS + 1).await.expect("run");
+        let accepted = store
+            .projected_order("order-1")
+            .await
+            .expect("projected")
+            .expect("order row");
+        assert_eq!(accepted.status, "accepted");
+        assert_eq!(
+            accepted.seller_pubkey.as_deref(),
+            Some(FIXTURE_BOB_PUBLIC_KEY_HEX)
+        );
+        assert_eq!(accepted.event_count, 2);
+        assert_eq!(accepted.issue_count, 0);
+        assert_eq!(
+            store
+                .projected_orders_by_status("accepted")
+                .await
+                .expect("by status"),
+            vec![accepted.clone()]
+        );
+
+        let rebuilt = runner
+            .rebuild(&projection, NOW_MS + 2)
+            .await
+            .expect("rebuild");
+        assert!(rebuilt.rebuilt);
+        let replayed = store
+            .projected_order("order-1")
+            .await
+            .expect("projected")
+            .expect("order row");
+        assert_eq!(replayed.status, "accepted");
+        assert_eq!(replayed.last_event_seq, accepted.last_event_seq);
+        assert!(
+            runner
+                .lag(RADROOTS_ORDER_PROJECTION_ID, NOW_MS + 2)
+                .await
+                .expect("lag")
+                .is_caught_up()
+        );
+    }
+}
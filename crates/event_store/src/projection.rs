@@ -0,0 +1,465 @@
+use crate::RadrootsEventStoreError;
+use crate::model::{RadrootsProjectionCursor, RadrootsStoredEvent};
+use crate::store::{
+    RadrootsEventStore, projectable_events_after, read_projection_cursor, write_projection_cursor,
+};
+use sqlx::Row;
+use std::future::Future;
+use std::pin::Pin;
+
+pub const RADROOTS_PROJECTION_DEFAULT_BATCH_SIZE: u32 = 256;
+
+pub type RadrootsProjectionTransaction<'c> = sqlx::Transaction<'c, sqlx::Sqlite>;
+
+pub type RadrootsProjectionFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<(), RadrootsEventStoreError>> + Send + 'a>>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsProjectionBatch {
+    pub events: Vec<RadrootsStoredEvent>,
+    pub first_event_seq: i64,
+    pub last_event_seq: i64,
+    pub applied_at_ms: i64,
+}
+
+pub trait RadrootsProjection: Send + Sync {
+    fn projection_id(&self) -> &str;
+
+    fn projection_version(&self) -> u32;
+
+    fn apply_batch<'a>(
+        &'a self,
+        tx: &'a mut RadrootsProjectionTransaction<'static>,
+        batch: &'a RadrootsProjectionBatch,
+    ) -> RadrootsProjectionFuture<'a>;
+
+    fn reset<'a>(
+        &'a self,
+        tx: &'a mut RadrootsProjectionTransaction<'static>,
+    ) -> RadrootsProjectionFuture<'a>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsProjectionRunReport {
+    pub projection_id: String,
+    pub projection_version: u32,
+    pub rebuilt: bool,
+    pub batches: u32,
+    pub events_applied: u64,
+    pub last_event_seq: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsProjectionLag {
+    pub projection_id: String,
+    pub projection_version: Option<u32>,
+    pub last_event_seq: i64,
+    pub head_event_seq: i64,
+    pub pending_events: u64,
+    pub cursor_updated_at_ms: Option<i64>,
+}
+
+impl RadrootsProjectionLag {
+    pub fn is_caught_up(&self) -> bool {
+        self.pending_events == 0
+    }
+}
+
+#[derive(Clone)]
+pub struct RadrootsProjectionRunner {
+    store: RadrootsEventStore,
+    batch_size: u32,
+    max_batches: Option<u32>,
+}
+
+impl RadrootsProjectionRunner {
+    pub fn new(store: RadrootsEventStore) -> Self {
+        Self {
+            store,
+            batch_size: RADROOTS_PROJECTION_DEFAULT_BATCH_SIZE,
+            max_batches: None,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_max_batches(mut self, max_batches: u32) -> Self {
+        self.max_batches = Some(max_batches);
+        self
+    }
+
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    pub async fn run<P>(
+        &self,
+        projection: &P,
+        now_ms: i64,
+    ) -> Result<RadrootsProjectionRunReport, RadrootsEventStoreError>
+    where
+        P: RadrootsProjection + ?Sized,
+    {
+        let cursor = read_projection_cursor(self.store.pool(), projection.projection_id()).await?;
+        let rebuilt = cursor
+            .as_ref()
+            .is_some_and(|cursor| cursor.projection_version != projection.projection_version());
+        if rebuilt {
+            self.reset_projection(projection, now_ms).await?;
+        }
+        self.drain(projection, now_ms, rebuilt).await
+    }
+
+    pub async fn rebuild<P>(
+        &self,
+        projection: &P,
+        now_ms: i64,
+    ) -> Result<RadrootsProjectionRunReport, RadrootsEventStoreError>
+    where
+        P: RadrootsProjection + ?Sized,
+    {
+        self.reset_projection(projection, now_ms).await?;
+        self.drain(projection, now_ms, true).await
+    }
+
+    pub async fn run_batch<P>(
+        &self,
+        projection: &P,
+        now_ms: i64,
+    ) -> Result<u64, RadrootsEventStoreError>
+    where
+        P: RadrootsProjection + ?Sized,
+    {
+        let mut tx = self.store.pool().begin().await?;
+        let cursor = read_projection_cursor(&mut *tx, projection.projection_id()).await?;
+        let last_event_seq = cursor
+            .as_ref()
+            .map(|cursor| cursor.last_event_seq)
+            .unwrap_or(0);
+        let events = projectable_events_after(
+            &mut *tx,
+            last_event_seq,
+            Some(unix_from_ms(now_ms)),
+            self.batch_size,
+        )
+        .await?;
+        let (Some(first), Some(last)) = (events.first(), events.last()) else {
+            tx.rollback().await?;
+            return Ok(0);
+        };
+        let batch = RadrootsProjectionBatch {
+            first_event_seq: first.seq,
+            last_event_seq: last.seq,
+            events,
+            applied_at_ms: now_ms,
+        };
+        projection.apply_batch(&mut tx, &batch).await?;
+        write_projection_cursor(
+            &mut *tx,
+            &RadrootsProjectionCursor {
+                projection_id: projection.projection_id().to_owned(),
+                projection_version: projection.projection_version(),
+                last_event_seq: batch.last_event_seq,
+                updated_at_ms: now_ms,
+            },
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(batch.events.len() as u64)
+    }
+
+    pub async fn lag(
+        &self,
+        projection_id: &str,
+        now_ms: i64,
+    ) -> Result<RadrootsProjectionLag, RadrootsEventStoreError> {
+        let cursor = read_projection_cursor(self.store.pool(), projection_id).await?;
+        let last_event_seq = cursor
+            .as_ref()
+            .map(|cursor| cursor.last_event_seq)
+            .unwrap_or(0);
+        let row = sqlx::query(
+            "SELECT COALESCE((SELECT MAX(seq) FROM nostr_event), 0) AS head_event_seq, (SELECT COUNT(*) FROM nostr_event WHERE projection_eligible = 1 AND deleted_by_event_id IS NULL AND (expires_at IS NULL OR expires_at > ?) AND seq > ?) AS pending_events",
+        )
+        .bind(i64::from(unix_from_ms(now_ms)))
+        .bind(last_event_seq)
+        .fetch_one(self.store.pool())
+        .await?;
+        let pending_events: i64 = row.try_get("pending_events")?;
+        Ok(RadrootsProjectionLag {
+            projection_id: projection_id.to_owned(),
+            projection_version: cursor.as_ref().map(|cursor| cursor.projection_version),
+            last_event_seq,
+            head_event_seq: row.try_get("head_event_seq")?,
+            pending_events: u64::try_from(pending_events).map_err(|_| {
+                RadrootsEventStoreError::IntegerRange {
+                    field: "pending_events",
+                    value: pending_events,
+                }
+            })?,
+            cursor_updated_at_ms: cursor.map(|cursor| cursor.updated_at_ms),
+        })
+    }
+
+    async fn drain<P>(
+        &self,
+        projection: &P,
+        now_ms: i64,
+        rebuilt: bool,
+    ) -> Result<RadrootsProjectionRunReport, RadrootsEventStoreError>
+    where
+        P: RadrootsProjection + ?Sized,
+    {
+        let mut batches = 0_u32;
+        let mut events_applied = 0_u64;
+        while self
+            .max_batches
+            .is_none_or(|max_batches| batches < max_batches)
+        {
+            let applied = self.run_batch(projection, now_ms).await?;
+            if applied == 0 {
+                break;
+            }
+            batches += 1;
+            events_applied += applied;
+        }
+        let last_event_seq = read_projection_cursor(self.store.pool(), projection.projection_id())
+            .await?
+            .map(|cursor| cursor.last_event_seq)
+            .unwrap_or(0);
+        Ok(RadrootsProjectionRunReport {
+            projection_id: projection.projection_id().to_owned(),
+            projection_version: projection.projection_version(),
+            rebuilt,
+            batches,
+            events_applied,
+            last_event_seq,
+        })
+    }
+
+    async fn reset_projection<P>(
+        &self,
+        projection: &P,
+        now_ms: i64,
+    ) -> Result<(), RadrootsEventStoreError>
+    where
+        P: RadrootsProjection + ?Sized,
+    {
+        let mut tx = self.store.pool().begin().await?;
+        projection.reset(&mut tx).await?;
+        write_projection_cursor(
+            &mut *tx,
+            &RadrootsProjectionCursor {
+                projection_id: projection.projection_id().to_owned(),
+                projection_version: projection.projection_version(),
+                last_event_seq: 0,
+                updated_at_ms: now_ms,
+            },
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+fn unix_from_ms(now_ms: i64) -> u32 {
+    u32::try_from(now_ms.max(0) / 1_000).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::RadrootsEventIngest;
+    use radroots_events::RadrootsNostrEvent;
+    use radroots_events::kinds::KIND_POST;
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+
+    const NOW_MS: i64 = 100_000;
+
+    struct SeenProjection {
+        version: u32,
+        fail: bool,
+    }
+
+    impl RadrootsProjection for SeenProjection {
+        fn projection_id(&self) -> &str {
+            "seen"
+        }
+
+        fn projection_version(&self) -> u32 {
+            self.version
+        }
+
+        fn apply_batch<'a>(
+            &'a self,
+            tx: &'a mut RadrootsProjectionTransaction<'static>,
+            batch: &'a RadrootsProjectionBatch,
+        ) -> RadrootsProjectionFuture<'a> {
+            Box::pin(async move {
+                for event in &batch.events {
+                    sqlx::query("INSERT INTO seen_projection(event_id, seq) VALUES (?, ?)")
+                        .bind(event.event_id.as_str())
+                        .bind(event.seq)
+                        .execute(&mut **tx)
+                        .await?;
+                }
+                if self.fail {
+                    return Err(RadrootsEventStoreError::MissingEvent("forced".to_owned()));
+                }
+                Ok(())
+            })
+        }
+
+        fn reset<'a>(
+            &'a self,
+            tx: &'a mut RadrootsProjectionTransaction<'static>,
+        ) -> RadrootsProjectionFuture<'a> {
+            Box::pin(async move {
+                sqlx::query("DELETE FROM seen_projection")
+                    .execute(&mut **tx)
+                    .await?;
+                Ok(())
+            })
+        }
+    }
+
+    fn signed_post(created_at: u32, content: &str) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(KIND_POST, content, Vec::<Vec<String>>::new())
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    async fn store_with_posts(count: u32) -> RadrootsEventStore {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        sqlx::query("CREATE TABLE seen_projection(event_id TEXT PRIMARY KEY NOT NULL, seq INTEGER NOT NULL)")
+            .execute(store.pool())
+            .await
+            .expect("projection table");
+        for index in 0..count {
+            store
+                .ingest_event(RadrootsEventIngest::new(
+                    signed_post(10 + index, format!("post {index}").as_str()),
+                    1_000,
+                ))
+                .await
+                .expect("ingest");
+        }
+        store
+    }
+
+    async fn seen_count(store: &RadrootsEventStore) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM seen_projection")
+            .fetch_one(store.pool())
+            .await
+            .expect("count")
+    }
+
+    #[tokio::test]
+    async fn runner_applies_batches_and_advances_cursor() {
+        let store = store_with_posts(5).await;
+        let runner = RadrootsProjectionRunner::new(store.clone()).with_batch_size(2);
+        let projection = SeenProjection {
+            version: 1,
+            fail: false,
+        };
+
+        let before = runner.lag("seen", NOW_MS).await.expect("lag");
+        assert_eq!(before.pending_events, 5);
+        assert_eq!(before.projection_version, None);
+
+        let report = runner.run(&projection, NOW_MS).await.expect("run");
+        assert_eq!(report.batches, 3);
+        assert_eq!(report.events_applied, 5);
+        assert!(!report.rebuilt);
+        assert_eq!(seen_count(&store).await, 5);
+
+        let lag = runner.lag("seen", NOW_MS).await.expect("lag");
+        assert!(lag.is_caught_up());
+        assert_eq!(lag.last_event_seq, lag.head_event_seq);
+        assert_eq!(lag.projection_version, Some(1));
+        assert_eq!(lag.cursor_updated_at_ms, Some(NOW_MS));
+
+        let again = runner.run(&projection, NOW_MS).await.expect("rerun");
+        assert_eq!(again.events_applied, 0);
+        assert_eq!(again.last_event_seq, report.last_event_seq);
+    }
+
+    #[tokio::test]
+    async fn runner_rolls_back_failed_batches() {
+        let store = store_with_posts(3).await;
+        let runner = RadrootsProjectionRunner::new(store.clone());
+        let projection = SeenProjection {
+            version: 1,
+            fail: true,
+        };
+
+        assert!(runner.run(&projection, NOW_MS).await.is_err());
+        assert_eq!(seen_count(&store).await, 0);
+        assert!(
+            store
+                .get_projection_cursor("seen")
+                .await
+                .expect("cursor")
+                .is_none()
+        );
+        assert_eq!(
+            runner
+                .lag("seen", NOW_MS)
+                .await
+                .expect("lag")
+                .pending_events,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn runner_rebuilds_on_version_change_and_on_request() {
+        let store = store_with_posts(3).await;
+        let runner = RadrootsProjectionRunner::new(store.clone()).with_max_batches(1);
+        let v1 = SeenProjection {
+            version: 1,
+            fail: false,
+        };
+        let v2 = SeenProjection {
+            version: 2,
+            fail: false,
+        };
+
+        let partial = RadrootsProjectionRunner::new(store.clone())
+            .with_batch_size(2)
+            .with_max_batches(1)
+            .run(&v1, NOW_MS)
+            .await
+            .expect("partial run");
+        assert_eq!(partial.events_applied, 2);
+
+        let upgraded = runner.run(&v2, NOW_MS + 1).await.expect("upgrade");
+        assert!(upgraded.rebuilt);
+        assert_eq!(upgraded.events_applied, 3);
+        assert_eq!(seen_count(&store).await, 3);
+
+        let rebuilt = runner.rebuild(&v2, NOW_MS + 2).await.expect("rebuild");
+        assert!(rebuilt.rebuilt);
+        assert_eq!(rebuilt.events_applied, 3);
+        assert_eq!(seen_count(&store).await, 3);
+        let cursor = store
+            .get_projection_cursor("seen")
+            .await
+            .expect("cursor")
+            .expect("stored cursor");
+        assert_eq!(cursor.projection_version, 2);
+        assert_eq!(cursor.updated_at_ms, NOW_MS + 2);
+    }
+}
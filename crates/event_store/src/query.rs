@@ -0,0 +1,199 @@
+use crate::RadrootsEventStoreError;
+use crate::model::RadrootsStoredEvent;
+use crate::store::{RadrootsEventStore, stored_event_from_row};
+use radroots_events::RadrootsNostrEvent;
+use sqlx::QueryBuilder;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventQuery {
+    pub ids: Vec<String>,
+    pub authors: Vec<String>,
+    pub kinds: Vec<u32>,
+    pub tags: BTreeMap<String, Vec<String>>,
+    pub since: Option<u32>,
+    pub until: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+impl RadrootsEventQuery {
+    pub fn matches(&self, event: &RadrootsNostrEvent) -> bool {
+        (self.ids.is_empty() || self.ids.contains(&event.id))
+            && (self.authors.is_empty() || self.authors.contains(&event.author))
+            && (self.kinds.is_empty() || self.kinds.contains(&event.kind))
+            && self.since.is_none_or(|since| event.created_at >= since)
+            && self.until.is_none_or(|until| event.created_at <= until)
+            && self.tags.iter().all(|(name, values)| {
+                event.tags.iter().any(|tag| {
+                    tag.first() == Some(name)
+                        && tag.get(1).is_some_and(|value| values.contains(value))
+                })
+            })
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn query_events(
+        &self,
+        query: &RadrootsEventQuery,
+        now_unix: u32,
+    ) -> Result<Vec<RadrootsStoredEvent>, RadrootsEventStoreError> {
+        if query.limit == Some(0) {
+            return Ok(Vec::new());
+        }
+        let mut builder = QueryBuilder::new(
+            "SELECT e.seq, e.event_id, e.pubkey, e.created_at, e.kind, e.tags_json, e.content, e.sig, e.raw_json, e.verification_status, e.contract_status, e.contract_id, e.event_class, e.projection_eligible, e.expires_at, e.inserted_at_ms, e.updated_at_ms FROM nostr_event e WHERE e.verification_status = 'verified' AND e.deleted_by_event_id IS NULL AND (e.expires_at IS NULL OR e.expires_at > ",
+        );
+        builder.push_bind(i64::from(now_unix));
+        builder.push(
+            ") AND (e.event_class IS NULL OR e.event_class NOT IN ('replaceable', 'addressable') OR EXISTS (SELECT 1 FROM nostr_event_head h WHERE h.event_id = e.event_id))",
+        );
+        if !query.ids.is_empty() {
+            builder.push(" AND e.event_id IN (");
+            let mut ids = builder.separated(", ");
+            for id in &query.ids {
+                ids.push_bind(id.as_str());
+            }
+            builder.push(")");
+        }
+        if !query.authors.is_empty() {
+            builder.push(" AND e.pubkey IN (");
+            let mut authors = builder.separated(", ");
+            for author in &query.authors {
+                authors.push_bind(author.as_str());
+            }
+            builder.push(")");
+        }
+        if !query.kinds.is_empty() {
+            builder.push(" AND e.kind IN (");
+            let mut kinds = builder.separated(", ");
+            for kind in &query.kinds {
+                kinds.push_bind(i64::from(*kind));
+            }
+            builder.push(")");
+        }
+        for (name, values) in &query.tags {
+            builder
+                .push(" AND EXISTS (SELECT 1 FROM nostr_event_tag t WHERE t.event_id = e.event_id AND t.tag_name = ")
+                .push_bind(name.as_str())
+                .push(" AND t.tag_value IN (");
+            let mut tag_values = builder.separated(", ");
+            for value in values {
+                tag_values.push_bind(value.as_str());
+            }
+            builder.push("))");
+        }
+        if let Some(since) = query.since {
+            builder
+                .push(" AND e.created_at >= ")
+                .push_bind(i64::from(since));
+        }
+        if let Some(until) = query.until {
+            builder
+                .push(" AND e.created_at <= ")
+                .push_bind(i64::from(until));
+        }
+        builder.push(" ORDER BY e.created_at DESC, e.event_id ASC");
+        if let Some(limit) = query.limit {
+            builder.push(" LIMIT ").push_bind(i64::from(limit));
+        }
+        builder
+            .build()
+            .fetch_all(self.pool())
+            .await?
+            .into_iter()
+            .map(stored_event_from_row)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadrootsEventIngest;
+    use radroots_events::kinds::{KIND_POST, KIND_PROFILE};
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+
+    fn signed_event(
+        kind: u32,
+        created_at: u32,
+        content: &str,
+        tags: Vec<Vec<String>>,
+    ) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(kind, content, tags)
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    #[tokio::test]
+    async fn query_filters_by_kind_tag_window_and_current_head() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let soil = vec![vec!["t".to_owned(), "soil".to_owned()]];
+        let events = [
+            signed_event(KIND_PROFILE, 10, "{\"name\":\"old\"}", Vec::new()),
+            signed_event(KIND_PROFILE, 20, "{\"name\":\"new\"}", Vec::new()),
+            signed_event(KIND_POST, 11, "first", soil.clone()),
+            signed_event(KIND_POST, 12, "second", Vec::new()),
+            signed_event(KIND_POST, 13, "third", soil),
+        ];
+        for event in &events {
+            store
+                .ingest_event(RadrootsEventIngest::new(event.clone(), 1_000))
+                .await
+                .expect("ingest");
+        }
+
+        let profiles = store
+            .query_events(
+                &RadrootsEventQuery {
+                    kinds: vec![KIND_PROFILE],
+                    ..RadrootsEventQuery::default()
+                },
+                100,
+            )
+            .await
+            .expect("profiles");
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].event_id, events[1].id);
+
+        let tagged = RadrootsEventQuery {
+            kinds: vec![KIND_POST],
+            tags: BTreeMap::from([("t".to_owned(), vec!["soil".to_owned()])]),
+            ..RadrootsEventQuery::default()
+        };
+        let posts = store.query_events(&tagged, 100).await.expect("posts");
+        assert_eq!(
+            posts
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![events[4].id.as_str(), events[2].id.as_str()]
+        );
+        assert!(tagged.matches(&events[2]));
+        assert!(!tagged.matches(&events[3]));
+
+        let windowed = store
+            .query_events(
+                &RadrootsEventQuery {
+                    since: Some(11),
+                    until: Some(12),
+                    limit: Some(1),
+                    ..RadrootsEventQuery::default()
+                },
+                100,
+            )
+            .await
+            .expect("window");
+        assert_eq!(windowed.len(), 1);
+        assert_eq!(windowed[0].event_id, events[3].id);
+    }
+}
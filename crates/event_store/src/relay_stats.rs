@@ -0,0 +1,136 @@
+use crate::RadrootsEventStoreError;
+use crate::store::RadrootsEventStore;
+use sqlx::Row;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayDeliveryBucket {
+    pub relay_url: String,
+    pub bucket_start_ms: i64,
+    pub events_seen: u64,
+    pub unique_events: u64,
+    pub delivery_lag_ms_sum: i64,
+    pub last_seen_at_ms: i64,
+}
+
+impl RadrootsRelayDeliveryBucket {
+    pub fn mean_delivery_lag_ms(&self) -> Option<i64> {
+        let events_seen = i64::try_from(self.events_seen).ok()?;
+        (events_seen > 0).then(|| self.delivery_lag_ms_sum / events_seen)
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn relay_delivery_buckets(
+        &self,
+        since_ms: i64,
+        bucket_ms: i64,
+    ) -> Result<Vec<RadrootsRelayDeliveryBucket>, RadrootsEventStoreError> {
+        let bucket_ms = bucket_ms.max(1);
+        let rows = sqlx::query(
+            "WITH seen AS (SELECT event_id, relay_url, MIN(first_seen_at_ms) AS first_seen_at_ms, MAX(last_seen_at_ms) AS last_seen_at_ms FROM relay_event_seen WHERE observation_type IN ('fetch', 'subscription') AND last_seen_at_ms >= ? GROUP BY event_id, relay_url), relay_counts AS (SELECT event_id, COUNT(DISTINCT relay_url) AS relay_count FROM relay_event_seen WHERE observation_type IN ('fetch', 'subscription') GROUP BY event_id) SELECT seen.relay_url AS relay_url, (seen.first_seen_at_ms / ?) * ? AS bucket_start_ms, COUNT(*) AS events_seen, SUM(CASE WHEN relay_counts.relay_count = 1 THEN 1 ELSE 0 END) AS unique_events, SUM(MAX(seen.first_seen_at_ms - e.created_at * 1000, 0)) AS delivery_lag_ms_sum, MAX(seen.last_seen_at_ms) AS last_seen_at_ms FROM seen JOIN nostr_event e ON e.event_id = seen.event_id JOIN relay_counts ON relay_counts.event_id = seen.event_id GROUP BY seen.relay_url, bucket_start_ms ORDER BY seen.relay_url, bucket_start_ms",
+        )
+        .bind(since_ms)
+        .bind(bucket_ms)
+        .bind(bucket_ms)
+        .fetch_all(self.pool())
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(RadrootsRelayDeliveryBucket {
+                    relay_url: row.try_get("relay_url")?,
+                    bucket_start_ms: row.try_get("bucket_start_ms")?,
+                    events_seen: count_from_i64("events_seen", row.try_get("events_seen")?)?,
+                    unique_events: count_from_i64("unique_events", row.try_get("unique_events")?)?,
+                    delivery_lag_ms_sum: row.try_get("delivery_lag_ms_sum")?,
+                    last_seen_at_ms: row.try_get("last_seen_at_ms")?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn count_from_i64(field: &'static str, value: i64) -> Result<u64, RadrootsEventStoreError> {
+    u64::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadrootsEventIngest;
+    use crate::model::{RadrootsRelayObservation, RadrootsRelayObservationType};
+    use radroots_events::RadrootsNostrEvent;
+    use radroots_events::kinds::KIND_POST;
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+
+    fn signed_post(created_at: u32, content: &str) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(KIND_POST, content, Vec::<Vec<String>>::new())
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    async fn observe(
+        store: &RadrootsEventStore,
+        event: &RadrootsNostrEvent,
+        relay_url: &str,
+        observed_at_ms: i64,
+    ) {
+        store
+            .ingest_event(
+                RadrootsEventIngest::new(event.clone(), observed_at_ms).with_observation(
+                    RadrootsRelayObservation::new(
+                        relay_url,
+                        RadrootsRelayObservationType::Fetch,
+                        observed_at_ms,
+                    ),
+                ),
+            )
+            .await
+            .expect("ingest");
+    }
+
+    #[tokio::test]
+    async fn delivery_buckets_report_lag_and_unique_events_per_relay() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let shared = signed_post(100, "shared");
+        let only_a = signed_post(101, "only a");
+        observe(&store, &shared, "wss://a.example.com", 100_500).await;
+        observe(&store, &shared, "wss://b.example.com", 102_000).await;
+        observe(&store, &only_a, "wss://a.example.com", 101_250).await;
+
+        let buckets = store
+            .relay_delivery_buckets(0, 60_000)
+            .await
+            .expect("buckets");
+        assert_eq!(buckets.len(), 2);
+        let relay_a = &buckets[0];
+        assert_eq!(relay_a.relay_url, "wss://a.example.com");
+        assert_eq!(relay_a.bucket_start_ms, 60_000);
+        assert_eq!(relay_a.events_seen, 2);
+        assert_eq!(relay_a.unique_events, 1);
+        assert_eq!(relay_a.delivery_lag_ms_sum, 750);
+        assert_eq!(relay_a.mean_delivery_lag_ms(), Some(375));
+        assert_eq!(relay_a.last_seen_at_ms, 101_250);
+        let relay_b = &buckets[1];
+        assert_eq!(relay_b.events_seen, 1);
+        assert_eq!(relay_b.unique_events, 0);
+        assert_eq!(relay_b.delivery_lag_ms_sum, 2_000);
+
+        assert!(
+            store
+                .relay_delivery_buckets(101_500, 60_000)
+                .await
+                .expect("windowed")
+                .iter()
+                .all(|bucket| bucket.relay_url == "wss://b.example.com")
+        );
+    }
+}
@@ -0,0 +1,579 @@
+use crate::RadrootsEventStoreError;
+use crate::store::{RadrootsEventStore, u32_from_i64, u64_from_i64};
+use radroots_events::kinds::KIND_DELETION;
+use radroots_events::tags::TAG_D;
+use sqlx::Row;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadrootsEventKindMaxAge {
+    pub kind: u32,
+    pub max_age_secs: u32,
+}
+
+impl RadrootsEventKindMaxAge {
+    pub fn new(kind: u32, max_age_secs: u32) -> Self {
+        Self { kind, max_age_secs }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventRetentionPolicy {
+    pub versions_per_head: Option<u32>,
+    pub max_age_by_kind: Vec<RadrootsEventKindMaxAge>,
+    pub max_observations: Option<u64>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl RadrootsEventRetentionPolicy {
+    pub fn with_versions_per_head(mut self, versions: u32) -> Self {
+        self.versions_per_head = Some(versions);
+        self
+    }
+
+    pub fn with_max_age(mut self, kind: u32, max_age_secs: u32) -> Self {
+        self.max_age_by_kind.retain(|limit| limit.kind != kind);
+        self.max_age_by_kind
+            .push(RadrootsEventKindMaxAge::new(kind, max_age_secs));
+        self
+    }
+
+    pub fn with_max_observations(mut self, max_observations: u64) -> Self {
+        self.max_observations = Some(max_observations);
+        self
+    }
+
+    pub fn with_max_total_bytes(mut self, max_total_bytes: u64) -> Self {
+        self.max_total_bytes = Some(max_total_bytes);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RadrootsEventRetentionReason {
+    SupersededVersion,
+    KindMaxAge,
+    ByteBudget,
+}
+
+impl RadrootsEventRetentionReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SupersededVersion => "superseded_version",
+            Self::KindMaxAge => "kind_max_age",
+            Self::ByteBudget => "byte_budget",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsEventRetentionRemoval {
+    pub seq: i64,
+    pub event_id: String,
+    pub kind: u32,
+    pub bytes: u64,
+    pub reason: RadrootsEventRetentionReason,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsEventRetentionReport {
+    pub dry_run: bool,
+    pub cursor_floor_seq: Option<i64>,
+    pub removed_events: Vec<RadrootsEventRetentionRemoval>,
+    pub protected_events: u64,
+    pub removed_observations: u64,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub over_byte_budget: bool,
+}
+
+impl RadrootsEventRetentionReport {
+    pub fn bytes_reclaimed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    pub fn removed_event_ids(&self) -> Vec<&str> {
+        self.removed_events
+            .iter()
+            .map(|removal| removal.event_id.as_str())
+            .collect()
+    }
+}
+
+impl RadrootsEventStore {
+    pub async fn apply_retention(
+        &self,
+        policy: &RadrootsEventRetentionPolicy,
+        now_unix: u32,
+    ) -> Result<RadrootsEventRetentionReport, RadrootsEventStoreError> {
+        run_retention(self, policy, now_unix, false).await
+    }
+
+    pub async fn retention_dry_run(
+        &self,
+        policy: &RadrootsEventRetentionPolicy,
+        now_unix: u32,
+    ) -> Result<RadrootsEventRetentionReport, RadrootsEventStoreError> {
+        run_retention(self, policy, now_unix, true).await
+    }
+}
+
+struct RetentionEvent {
+    seq: i64,
+    event_id: String,
+    kind: u32,
+    pubkey: String,
+    created_at: u32,
+    event_class: Option<String>,
+    d_tag: Option<String>,
+    bytes: u64,
+    is_head: bool,
+}
+
+async fn run_retention(
+    store: &RadrootsEventStore,
+    policy: &RadrootsEventRetentionPolicy,
+    now_unix: u32,
+    dry_run: bool,
+) -> Result<RadrootsEventRetentionReport, RadrootsEventStoreError> {
+    let mut tx = store.pool().begin().await?;
+    let cursor_floor_seq: Option<i64> =
+        sqlx::query_scalar("SELECT MIN(last_event_seq) FROM projection_cursor")
+            .fetch_one(&mut *tx)
+            .await?;
+    let events = sqlx::query(
+        "SELECT e.seq, e.event_id, e.kind, e.pubkey, e.created_at, e.event_class, length(CAST(e.raw_json AS BLOB)) AS bytes, (SELECT t.tag_value FROM nostr_event_tag t WHERE t.event_id = e.event_id AND t.tag_name = ? ORDER BY t.tag_index ASC LIMIT 1) AS d_tag, EXISTS (SELECT 1 FROM nostr_event_head h WHERE h.event_id = e.event_id) AS is_head FROM nostr_event e ORDER BY e.seq ASC",
+    )
+    .bind(TAG_D)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(retention_event_from_row)
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let is_protected =
+        |event: &RetentionEvent| cursor_floor_seq.is_some_and(|floor| event.seq > floor);
+    let mut reasons = BTreeMap::<i64, RadrootsEventRetentionReason>::new();
+    let mut protected = BTreeSet::<i64>::new();
+    let mut mark = |event: &RetentionEvent, reason| {
+        if is_protected(event) {
+            protected.insert(event.seq);
+        } else {
+            reasons.entry(event.seq).or_insert(reason);
+        }
+    };
+
+    if let Some(versions) = policy.versions_per_head {
+        for mut group in head_version_groups(&events).into_values() {
+            group.sort_by(|left, right| {
+                right
+                    .created_at
+                    .cmp(&left.created_at)
+                    .then_with(|| left.event_id.cmp(&right.event_id))
+            });
+            for event in group.into_iter().skip(versions.max(1) as usize) {
+                if !event.is_head {
+                    mark(event, RadrootsEventRetentionReason::SupersededVersion);
+                }
+            }
+        }
+    }
+    for limit in &policy.max_age_by_kind {
+        let cutoff = now_unix.saturating_sub(limit.max_age_secs);
+        for event in events
+            .iter()
+            .filter(|event| event.kind == limit.kind && event.created_at < cutoff)
+        {
+            if event.is_head || event.kind == KIND_DELETION {
+                continue;
+            }
+            mark(event, RadrootsEventRetentionReason::KindMaxAge);
+        }
+    }
+
+    let bytes_before = events.iter().map(|event| event.bytes).sum::<u64>();
+    let mut bytes_after = events
+        .iter()
+        .filter(|event| !reasons.contains_key(&event.seq))
+        .map(|event| event.bytes)
+        .sum::<u64>();
+    if let Some(budget) = policy.max_total_bytes {
+        for event in &events {
+            if bytes_after <= budget {
+                break;
+            }
+            if event.is_head || event.kind == KIND_DELETION || reasons.contains_key(&event.seq) {
+                continue;
+            }
+            if is_protected(event) {
+                protected.insert(event.seq);
+                continue;
+            }
+            reasons.insert(event.seq, RadrootsEventRetentionReason::ByteBudget);
+            bytes_after -= event.bytes;
+        }
+    }
+
+    let removed_events = events
+        .iter()
+        .filter_map(|event| {
+            reasons
+                .get(&event.seq)
+                .map(|reason| RadrootsEventRetentionRemoval {
+                    seq: event.seq,
+                    event_id: event.event_id.clone(),
+                    kind: event.kind,
+                    bytes: event.bytes,
+                    reason: *reason,
+                })
+        })
+        .collect::<Vec<_>>();
+    for removal in &removed_events {
+        sqlx::query("DELETE FROM nostr_event WHERE seq = ?")
+            .bind(removal.seq)
+            .execute(&mut *tx)
+            .await?;
+    }
+    let removed_observations = match policy.max_observations {
+        Some(max_observations) => {
+            let excess = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM relay_event_seen")
+                .fetch_one(&mut *tx)
+                .await?
+                .saturating_sub(i64::try_from(max_observations).unwrap_or(i64::MAX));
+            if excess > 0 {
+                sqlx::query(
+                    "DELETE FROM relay_event_seen WHERE rowid IN (SELECT rowid FROM relay_event_seen ORDER BY last_seen_at_ms ASC, event_id ASC, relay_url ASC, observation_type ASC LIMIT ?)",
+                )
+                .bind(excess)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected()
+            } else {
+                0
+            }
+        }
+        None => 0,
+    };
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+    Ok(RadrootsEventRetentionReport {
+        dry_run,
+        cursor_floor_seq,
+        removed_events,
+        protected_events: protected.len() as u64,
+        removed_observations,
+        bytes_before,
+        bytes_after,
+        over_byte_budget: policy
+            .max_total_bytes
+            .is_some_and(|budget| bytes_after > budget),
+    })
+}
+
+fn head_version_groups(
+    events: &[RetentionEvent],
+) -> BTreeMap<(&str, u32, &str, Option<&str>), Vec<&RetentionEvent>> {
+    let mut groups = BTreeMap::<_, Vec<&RetentionEvent>>::new();
+    for event in events {
+        let d_tag = match event.event_class.as_deref() {
+            Some("replaceable") => None,
+            Some("addressable") => match event.d_tag.as_deref() {
+                Some(d_tag) => Some(d_tag),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let class = event.event_class.as_deref().unwrap_or_default();
+        groups
+            .entry((class, event.kind, event.pubkey.as_str(), d_tag))
+            .or_default()
+            .push(event);
+    }
+    groups
+}
+
+fn retention_event_from_row(
+    row: sqlx::sqlite::SqliteRow,
+) -> Result<RetentionEvent, RadrootsEventStoreError> {
+    Ok(RetentionEvent {
+        seq: row.try_get("seq")?,
+        event_id: row.try_get("event_id")?,
+        kind: u32_from_i64("kind", row.try_get("kind")?)?,
+        pubkey: row.try_get("pubkey")?,
+        created_at: u32_from_i64("created_at", row.try_get("created_at")?)?,
+        event_class: row.try_get("event_class")?,
+        d_tag: row.try_get("d_tag")?,
+        bytes: u64_from_i64("bytes", row.try_get("bytes")?)?,
+        is_head: row.try_get::<i64, _>("is_head")? != 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        RadrootsEventIngest, RadrootsProjectionCursor, RadrootsRelayObservation,
+        RadrootsRelayObservationType,
+    };
+    use radroots_events::RadrootsNostrEvent;
+    use radroots_events::kinds::{KIND_POST, KIND_PROFILE};
+    use radroots_nostr::prelude::{
+        RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
+        radroots_event_from_nostr, radroots_nostr_build_event,
+    };
+    use radroots_test_fixtures::FIXTURE_ALICE_SECRET_KEY_HEX;
+
+    fn signed_event(kind: u32, created_at: u32, content: &str) -> RadrootsNostrEvent {
+        signed_event_with_tags(kind, created_at, Vec::new(), content)
+    }
+
+    fn signed_event_with_tags(
+        kind: u32,
+        created_at: u32,
+        tags: Vec<Vec<String>>,
+        content: &str,
+    ) -> RadrootsNostrEvent {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key");
+        let raw_event = radroots_nostr_build_event(kind, content, tags)
+            .expect("builder")
+            .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+            .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+            .expect("signed event");
+        radroots_event_from_nostr(&raw_event)
+    }
+
+    async fn ingest(store: &RadrootsEventStore, event: &RadrootsNostrEvent) -> i64 {
+        store
+            .ingest_event(RadrootsEventIngest::new(event.clone(), 1_000))
+            .await
+            .expect("ingest")
+            .seq
+    }
+
+    async fn event_count(store: &RadrootsEventStore) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM nostr_event")
+            .fetch_one(store.pool())
+            .await
+            .expect("count")
+    }
+
+    async fn profile_versions(store: &RadrootsEventStore) -> Vec<RadrootsNostrEvent> {
+        let mut versions = Vec::new();
+        for created_at in [10, 20, 30] {
+            let event = signed_event(
+                KIND_PROFILE,
+                created_at,
+                format!("{{\"v\":{created_at}}}").as_str(),
+            );
+            ingest(store, &event).await;
+            versions.push(event);
+        }
+        versions
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_superseded_versions_without_removing_them() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let versions = profile_versions(&store).await;
+        let policy = RadrootsEventRetentionPolicy::default().with_versions_per_head(1);
+
+        let report = store
+            .retention_dry_run(&policy, 100)
+            .await
+            .expect("dry run");
+        assert!(report.dry_run);
+        assert_eq!(
+            report.removed_event_ids(),
+            vec![versions[0].id.as_str(), versions[1].id.as_str()]
+        );
+        assert!(
+            report
+                .removed_events
+                .iter()
+                .all(|removal| removal.reason == RadrootsEventRetentionReason::SupersededVersion)
+        );
+        assert!(report.bytes_reclaimed() > 0);
+        assert_eq!(event_count(&store).await, 3);
+
+        let applied = store.apply_retention(&policy, 100).await.expect("apply");
+        assert_eq!(applied.removed_events, report.removed_events);
+        assert_eq!(event_count(&store).await, 1);
+        assert!(
+            store
+                .get_event(versions[2].id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn retention_keeps_events_projections_have_not_read() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let versions = profile_versions(&store).await;
+        let first_seq = store
+            .get_event(versions[0].id.as_str())
+            .await
+            .expect("get")
+            .expect("stored")
+            .seq;
+        store
+            .update_projection_cursor(&RadrootsProjectionCursor {
+                projection_id: "profiles".to_owned(),
+                projection_version: 1,
+                last_event_seq: first_seq,
+                updated_at_ms: 1_000,
+            })
+            .await
+            .expect("cursor");
+
+        let report = store
+            .apply_retention(
+                &RadrootsEventRetentionPolicy::default().with_versions_per_head(1),
+                100,
+            )
+            .await
+            .expect("apply");
+
+        assert_eq!(report.cursor_floor_seq, Some(first_seq));
+        assert_eq!(report.removed_event_ids(), vec![versions[0].id.as_str()]);
+        assert_eq!(report.protected_events, 1);
+        assert_eq!(event_count(&store).await, 2);
+    }
+
+    #[tokio::test]
+    async fn retention_applies_kind_age_limits_and_observation_caps() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let old_post = signed_event(KIND_POST, 10, "old");
+        let new_post = signed_event(KIND_POST, 1_000, "new");
+        for (index, event) in [&old_post, &new_post].into_iter().enumerate() {
+            for relay in ["wss://a.example", "wss://b.example"] {
+                store
+                    .ingest_event(
+                        RadrootsEventIngest::new(event.clone(), 1_000).with_observation(
+                            RadrootsRelayObservation::new(
+                                relay,
+                                RadrootsRelayObservationType::Fetch,
+                                1_000 + index as i64,
+                            ),
+                        ),
+                    )
+                    .await
+                    .expect("ingest");
+            }
+        }
+        let policy = RadrootsEventRetentionPolicy::default()
+            .with_max_age(KIND_POST, 100)
+            .with_max_observations(1);
+
+        let report = store.apply_retention(&policy, 1_050).await.expect("apply");
+
+        assert_eq!(report.removed_event_ids(), vec![old_post.id.as_str()]);
+        assert_eq!(
+            report.removed_events[0].reason,
+            RadrootsEventRetentionReason::KindMaxAge
+        );
+        assert_eq!(report.removed_observations, 1);
+        let observations = store
+            .observations_for_event(new_post.id.as_str())
+            .await
+            .expect("observations");
+        assert_eq!(observations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn age_limits_keep_deletion_events_so_tombstones_still_reject_reingest() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let post = signed_event(KIND_POST, 10, "typo");
+        ingest(&store, &post).await;
+        let deletion = signed_event_with_tags(
+            KIND_DELETION,
+            20,
+            vec![vec!["e".to_owned(), post.id.clone()]],
+            "",
+        );
+        ingest(&store, &deletion).await;
+        let policy = RadrootsEventRetentionPolicy::default()
+            .with_max_age(KIND_DELETION, 100)
+            .with_max_age(KIND_POST, 100);
+
+        let report = store.apply_retention(&policy, 10_000).await.expect("apply");
+
+        assert!(!report.removed_event_ids().contains(&deletion.id.as_str()));
+        assert!(
+            store
+                .get_event(deletion.id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+        assert_eq!(
+            store
+                .tombstones_for_deletion(deletion.id.as_str())
+                .await
+                .expect("tombstones")
+                .len(),
+            1
+        );
+        let reingest = store
+            .ingest_event(RadrootsEventIngest::new(post.clone(), 11_000))
+            .await
+            .expect("reingest");
+        assert!(!reingest.inserted);
+    }
+
+    #[tokio::test]
+    async fn retention_evicts_oldest_non_head_events_to_meet_byte_budget() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let versions = profile_versions(&store).await;
+        let posts = [
+            signed_event(KIND_POST, 40, "first"),
+            signed_event(KIND_POST, 50, "second"),
+        ];
+        for post in &posts {
+            ingest(&store, post).await;
+        }
+        let before = store
+            .retention_dry_run(&RadrootsEventRetentionPolicy::default(), 100)
+            .await
+            .expect("dry run");
+        assert!(before.removed_events.is_empty());
+        let post_bytes = before.bytes_before / 5;
+
+        let report = store
+            .apply_retention(
+                &RadrootsEventRetentionPolicy::default()
+                    .with_max_total_bytes(before.bytes_before - post_bytes),
+                100,
+            )
+            .await
+            .expect("apply");
+
+        assert_eq!(report.removed_event_ids(), vec![versions[0].id.as_str()]);
+        assert_eq!(
+            report.removed_events[0].reason,
+            RadrootsEventRetentionReason::ByteBudget
+        );
+        assert!(!report.over_byte_budget);
+
+        let tight = store
+            .apply_retention(
+                &RadrootsEventRetentionPolicy::default().with_max_total_bytes(0),
+                100,
+            )
+            .await
+            .expect("apply");
+        assert!(tight.over_byte_budget);
+        assert!(
+            store
+                .get_event(versions[2].id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+    }
+}
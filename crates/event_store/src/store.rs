@@ -2,9 +2,10 @@ use crate::RadrootsEventStoreError;
 use crate::migrations::{EVENT_STORE_MIGRATION_DOWN, EVENT_STORE_MIGRATION_UP};
 use crate::model::{
     RadrootsEventContractStatus, RadrootsEventHeadStoreDecision, RadrootsEventIngest,
-    RadrootsEventIngestReceipt, RadrootsEventVerificationStatus, RadrootsProjectionCursor,
-    RadrootsRelayObservation, RadrootsStoredEvent, RadrootsStoredEventHead, RadrootsStoredEventTag,
-    StoredEventClass, tag_semantic_name, tag_value_type_name,
+    RadrootsEventIngestReceipt, RadrootsEventTombstone, RadrootsEventTombstoneTarget,
+    RadrootsEventVerificationStatus, RadrootsProjectionCursor, RadrootsRelayObservation,
+    RadrootsStoredEvent, RadrootsStoredEventHead, RadrootsStoredEventTag, StoredEventClass,
+    tag_semantic_name, tag_value_type_name,
 };
 use radroots_events::RadrootsNostrEvent;
 use radroots_events::contract::{
@@ -16,6 +17,9 @@ use radroots_events::event_head::{
     select_event_head,
 };
 use radroots_events::ids::{RadrootsEventId, RadrootsEventSignature, RadrootsPublicKey};
+use radroots_events::kinds::KIND_DELETION;
+use radroots_events::tags::TAG_EXPIRATION;
+use radroots_events_codec::deletion::decode::deletion_from_tags;
 use radroots_nostr::prelude::{RadrootsNostrEventVerification, radroots_nostr_verify_event};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use sqlx::{Row, SqlitePool};
@@ -85,7 +89,35 @@ impl RadrootsEventStore {
             .map(Ok)
             .unwrap_or_else(|| serde_json::to_string(&ingest.event))?;
         let tags_json = serde_json::to_string(&ingest.event.tags)?;
+        let expires_at = event_expiration(&ingest.event);
         let mut tx = self.pool.begin().await?;
+        let rejected = if is_tombstoned(&mut tx, &ingest.event).await? {
+            Some(RadrootsEventHeadStoreDecision::Deleted)
+        } else if expires_at.is_some_and(|expires_at| is_expired(expires_at, ingest.observed_at_ms))
+        {
+            Some(RadrootsEventHeadStoreDecision::Expired)
+        } else {
+            None
+        };
+        if let Some(head_decision) = rejected {
+            let seq = existing_event_seq(&mut tx, ingest.event.id.as_str()).await?;
+            if let (Some(_), Some(observation)) = (seq, ingest.relay_observation.as_ref()) {
+                upsert_observation(&mut tx, ingest.event.id.as_str(), observation).await?;
+            }
+            tx.commit().await?;
+            return Ok(RadrootsEventIngestReceipt {
+                seq: seq.unwrap_or(0),
+                event_id: ingest.event.id,
+                inserted: false,
+                verification_status,
+                contract_status: classification.contract_status,
+                contract_id: classification
+                    .contract
+                    .map(|contract| contract.id.to_owned()),
+                projection_eligible: false,
+                head_decision,
+            });
+        }
         let insert = insert_raw_event(
             &mut tx,
             &ingest,
@@ -93,6 +125,7 @@ impl RadrootsEventStore {
             verification_status,
             raw_json.as_str(),
             tags_json.as_str(),
+            expires_at,
         )
         .await?;
         let inserted = insert.inserted;
@@ -125,6 +158,13 @@ impl RadrootsEventStore {
             projection_eligible = false;
         }
 
+        if inserted
+            && ingest.event.kind == KIND_DELETION
+            && verification_status == RadrootsEventVerificationStatus::Verified
+        {
+            apply_deletion(&mut tx, &ingest.event, ingest.observed_at_ms).await?;
+        }
+
         if let Some(observation) = ingest.relay_observation.as_ref() {
             upsert_observation(&mut tx, ingest.event.id.as_str(), observation).await?;
         }
@@ -149,13 +189,15 @@ impl RadrootsEventStore {
         &self,
         event_id: &str,
     ) -> Result<Option<RadrootsStoredEvent>, RadrootsEventStoreError> {
-        let row = sqlx::query(
-            "SELECT seq, event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, inserted_at_ms, updated_at_ms FROM nostr_event WHERE event_id = ?",
-        )
-        .bind(event_id)
-        .fetch_optional(&self.pool)
-        .await?;
-        row.map(stored_event_from_row).transpose()
+        fetch_event(&self.pool, event_id, None).await
+    }
+
+    pub async fn get_event_at(
+        &self,
+        event_id: &str,
+        now_unix: u32,
+    ) -> Result<Option<RadrootsStoredEvent>, RadrootsEventStoreError> {
+        fetch_event(&self.pool, event_id, Some(now_unix)).await
     }
 
     pub async fn tags_for_event(
@@ -216,33 +258,31 @@ impl RadrootsEventStore {
         row.map(stored_head_from_row).transpose()
     }
 
+    pub async fn tombstones_for_deletion(
+        &self,
+        deletion_event_id: &str,
+    ) -> Result<Vec<RadrootsEventTombstone>, RadrootsEventStoreError> {
+        let rows = sqlx::query(
+            "SELECT deletion_event_id, target_type, target, pubkey, deleted_at, recorded_at_ms FROM nostr_event_tombstone WHERE deletion_event_id = ? ORDER BY target_type, target",
+        )
+        .bind(deletion_event_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter().map(tombstone_from_row).collect()
+    }
+
     pub async fn get_projection_cursor(
         &self,
         projection_id: &str,
     ) -> Result<Option<RadrootsProjectionCursor>, RadrootsEventStoreError> {
-        let row = sqlx::query(
-            "SELECT projection_id, projection_version, last_event_seq, updated_at_ms FROM projection_cursor WHERE projection_id = ?",
-        )
-        .bind(projection_id)
-        .fetch_optional(&self.pool)
-        .await?;
-        row.map(projection_cursor_from_row).transpose()
+        read_projection_cursor(&self.pool, projection_id).await
     }
 
     pub async fn update_projection_cursor(
         &self,
         cursor: &RadrootsProjectionCursor,
     ) -> Result<(), RadrootsEventStoreError> {
-        sqlx::query(
-            "INSERT INTO projection_cursor(projection_id, projection_version, last_event_seq, updated_at_ms) VALUES (?, ?, ?, ?) ON CONFLICT(projection_id) DO UPDATE SET projection_version = excluded.projection_version, last_event_seq = excluded.last_event_seq, updated_at_ms = excluded.updated_at_ms",
-        )
-        .bind(cursor.projection_id.as_str())
-        .bind(i64::from(cursor.projection_version))
-        .bind(cursor.last_event_seq)
-        .bind(cursor.updated_at_ms)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
+        write_projection_cursor(&self.pool, cursor).await
     }
 
     pub async fn events_since_cursor(
@@ -255,14 +295,33 @@ impl RadrootsEventStore {
             .as_ref()
             .map(|cursor| cursor.last_event_seq)
             .unwrap_or(0);
-        let rows = sqlx::query(
-            "SELECT seq, event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, inserted_at_ms, updated_at_ms FROM nostr_event WHERE projection_eligible = 1 AND seq > ? ORDER BY seq ASC LIMIT ?",
-        )
-        .bind(last_event_seq)
-        .bind(i64::from(limit))
-        .fetch_all(&self.pool)
-        .await?;
-        rows.into_iter().map(stored_event_from_row).collect()
+        projectable_events_after(&self.pool, last_event_seq, None, limit).await
+    }
+
+    pub async fn events_since_cursor_at(
+        &self,
+        projection_id: &str,
+        limit: u32,
+        now_unix: u32,
+    ) -> Result<Vec<RadrootsStoredEvent>, RadrootsEventStoreError> {
+        let cursor = self.get_projection_cursor(projection_id).await?;
+        let last_event_seq = cursor
+            .as_ref()
+            .map(|cursor| cursor.last_event_seq)
+            .unwrap_or(0);
+        projectable_events_after(&self.pool, last_event_seq, Some(now_unix), limit).await
+    }
+
+    pub async fn sweep_expired_events(
+        &self,
+        now_unix: u32,
+    ) -> Result<u64, RadrootsEventStoreError> {
+        let result =
+            sqlx::query("DELETE FROM nostr_event WHERE expires_at IS NOT NULL AND expires_at <= ?")
+                .bind(i64::from(now_unix))
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
     }
 }
 
@@ -385,6 +444,7 @@ async fn insert_raw_event(
     verification_status: RadrootsEventVerificationStatus,
     raw_json: &str,
     tags_json: &str,
+    expires_at: Option<u64>,
 ) -> Result<InsertRawEventResult, RadrootsEventStoreError> {
     let event = &ingest.event;
     let contract_id = classification.contract.map(|contract| contract.id);
@@ -393,7 +453,7 @@ async fn insert_raw_event(
         .map(|contract| StoredEventClass::from_event_class(contract.class).as_str());
     let projection_eligible = classification.base_projection_eligible(verification_status);
     let result = sqlx::query(
-        "INSERT OR IGNORE INTO nostr_event(event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, inserted_at_ms, updated_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT OR IGNORE INTO nostr_event(event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, expires_at, inserted_at_ms, updated_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(event.id.as_str())
     .bind(event.author.as_str())
@@ -408,6 +468,7 @@ async fn insert_raw_event(
     .bind(contract_id)
     .bind(event_class)
     .bind(bool_i64(projection_eligible))
+    .bind(expires_at.and_then(|expires_at| i64::try_from(expires_at).ok()))
     .bind(ingest.observed_at_ms)
     .bind(ingest.observed_at_ms)
     .execute(&mut **tx)
@@ -428,6 +489,157 @@ async fn event_seq(
     row.try_get("seq").map_err(Into::into)
 }
 
+async fn existing_event_seq(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    event_id: &str,
+) -> Result<Option<i64>, RadrootsEventStoreError> {
+    let row = sqlx::query("SELECT seq FROM nostr_event WHERE event_id = ?")
+        .bind(event_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+    row.map(|row| row.try_get("seq").map_err(Into::into))
+        .transpose()
+}
+
+fn event_expiration(event: &RadrootsNostrEvent) -> Option<u64> {
+    event
+        .tags
+        .iter()
+        .find(|tag| tag.first().map(String::as_str) == Some(TAG_EXPIRATION))
+        .and_then(|tag| tag.get(1))
+        .and_then(|value| value.parse::<u64>().ok())
+}
+
+fn is_expired(expires_at: u64, observed_at_ms: i64) -> bool {
+    u64::try_from(observed_at_ms)
+        .map(|observed_at_ms| expires_at.saturating_mul(1000) <= observed_at_ms)
+        .unwrap_or(false)
+}
+
+async fn is_tombstoned(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    event: &RadrootsNostrEvent,
+) -> Result<bool, RadrootsEventStoreError> {
+    if event.kind == KIND_DELETION {
+        return Ok(false);
+    }
+    let row = sqlx::query(
+        "SELECT 1 FROM nostr_event_tombstone WHERE pubkey = ? AND ((target_type = 'event' AND target = ?) OR (target_type = 'coordinate' AND target = ? AND deleted_at >= ?)) LIMIT 1",
+    )
+    .bind(event.author.as_str())
+    .bind(event.id.as_str())
+    .bind(event_coordinate(event))
+    .bind(i64::from(event.created_at))
+    .fetch_optional(&mut **tx)
+    .await?;
+    Ok(row.is_some())
+}
+
+async fn apply_deletion(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    deletion: &RadrootsNostrEvent,
+    recorded_at_ms: i64,
+) -> Result<(), RadrootsEventStoreError> {
+    let Ok(request) = deletion_from_tags(deletion.kind, &deletion.tags, &deletion.content) else {
+        return Ok(());
+    };
+    for event_id in &request.event_ids {
+        insert_tombstone(
+            tx,
+            deletion,
+            RadrootsEventTombstoneTarget::Event,
+            event_id,
+            recorded_at_ms,
+        )
+        .await?;
+        sqlx::query(
+            "UPDATE nostr_event SET deleted_by_event_id = ?, updated_at_ms = ? WHERE event_id = ? AND pubkey = ? AND kind != ? AND deleted_by_event_id IS NULL",
+        )
+        .bind(deletion.id.as_str())
+        .bind(recorded_at_ms)
+        .bind(event_id.as_str())
+        .bind(deletion.author.as_str())
+        .bind(i64::from(KIND_DELETION))
+        .execute(&mut **tx)
+        .await?;
+    }
+    for address in &request.addresses {
+        let mut parts = address.splitn(3, ':');
+        let (Some(kind), Some(pubkey), Some(d_tag)) = (
+            parts.next().and_then(|kind| kind.parse::<u32>().ok()),
+            parts.next(),
+            parts.next(),
+        ) else {
+            continue;
+        };
+        if pubkey != deletion.author || kind == KIND_DELETION {
+            continue;
+        }
+        insert_tombstone(
+            tx,
+            deletion,
+            RadrootsEventTombstoneTarget::Coordinate,
+            address,
+            recorded_at_ms,
+        )
+        .await?;
+        sqlx::query(
+            "UPDATE nostr_event SET deleted_by_event_id = ?, updated_at_ms = ? WHERE kind = ? AND pubkey = ? AND created_at <= ? AND deleted_by_event_id IS NULL AND COALESCE((SELECT tag_value FROM nostr_event_tag WHERE nostr_event_tag.event_id = nostr_event.event_id AND tag_name = 'd' ORDER BY tag_index LIMIT 1), '') = ?",
+        )
+        .bind(deletion.id.as_str())
+        .bind(recorded_at_ms)
+        .bind(i64::from(kind))
+        .bind(pubkey)
+        .bind(i64::from(deletion.created_at))
+        .bind(d_tag)
+        .execute(&mut **tx)
+        .await?;
+    }
+    sqlx::query(
+        "DELETE FROM nostr_event_head WHERE event_id IN (SELECT event_id FROM nostr_event WHERE deleted_by_event_id = ?)",
+    )
+    .bind(deletion.id.as_str())
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+async fn insert_tombstone(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    deletion: &RadrootsNostrEvent,
+    target_type: RadrootsEventTombstoneTarget,
+    target: &str,
+    recorded_at_ms: i64,
+) -> Result<(), RadrootsEventStoreError> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO nostr_event_tombstone(deletion_event_id, target_type, target, pubkey, deleted_at, recorded_at_ms) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(deletion.id.as_str())
+    .bind(target_type.as_str())
+    .bind(target)
+    .bind(deletion.author.as_str())
+    .bind(i64::from(deletion.created_at))
+    .bind(recorded_at_ms)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn event_coordinate(event: &RadrootsNostrEvent) -> Option<String> {
+    let d_tag = match event.kind {
+        0 | 3 | 10_000..=19_999 => "",
+        30_000..=39_999 => event
+            .tags
+            .iter()
+            .find(|tag| tag.first().map(String::as_str) == Some("d"))
+            .and_then(|tag| tag.get(1))
+            .map(String::as_str)
+            .unwrap_or(""),
+        _ => return None,
+    };
+    Some(format!("{}:{}:{}", event.kind, event.author, d_tag))
+}
+
 async fn insert_tags(
     tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
     event: &RadrootsNostrEvent,
@@ -620,7 +832,84 @@ async fn upsert_head(
     Ok(())
 }
 
-fn stored_event_from_row(
+pub(crate) async fn read_projection_cursor<'e, E>(
+    executor: E,
+    projection_id: &str,
+) -> Result<Option<RadrootsProjectionCursor>, RadrootsEventStoreError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query(
+        "SELECT projection_id, projection_version, last_event_seq, updated_at_ms FROM projection_cursor WHERE projection_id = ?",
+    )
+    .bind(projection_id)
+    .fetch_optional(executor)
+    .await?;
+    row.map(projection_cursor_from_row).transpose()
+}
+
+pub(crate) async fn write_projection_cursor<'e, E>(
+    executor: E,
+    cursor: &RadrootsProjectionCursor,
+) -> Result<(), RadrootsEventStoreError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO projection_cursor(projection_id, projection_version, last_event_seq, updated_at_ms) VALUES (?, ?, ?, ?) ON CONFLICT(projection_id) DO UPDATE SET projection_version = excluded.projection_version, last_event_seq = excluded.last_event_seq, updated_at_ms = excluded.updated_at_ms",
+    )
+    .bind(cursor.projection_id.as_str())
+    .bind(i64::from(cursor.projection_version))
+    .bind(cursor.last_event_seq)
+    .bind(cursor.updated_at_ms)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn projectable_events_after<'e, E>(
+    executor: E,
+    last_event_seq: i64,
+    now_unix: Option<u32>,
+    limit: u32,
+) -> Result<Vec<RadrootsStoredEvent>, RadrootsEventStoreError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now_unix = now_unix.map(i64::from);
+    let rows = sqlx::query(
+        "SELECT seq, event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, expires_at, inserted_at_ms, updated_at_ms FROM nostr_event WHERE projection_eligible = 1 AND deleted_by_event_id IS NULL AND (? IS NULL OR expires_at IS NULL OR expires_at > ?) AND seq > ? ORDER BY seq ASC LIMIT ?",
+    )
+    .bind(now_unix)
+    .bind(now_unix)
+    .bind(last_event_seq)
+    .bind(i64::from(limit))
+    .fetch_all(executor)
+    .await?;
+    rows.into_iter().map(stored_event_from_row).collect()
+}
+
+async fn fetch_event<'e, E>(
+    executor: E,
+    event_id: &str,
+    now_unix: Option<u32>,
+) -> Result<Option<RadrootsStoredEvent>, RadrootsEventStoreError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let now_unix = now_unix.map(i64::from);
+    let row = sqlx::query(
+        "SELECT seq, event_id, pubkey, created_at, kind, tags_json, content, sig, raw_json, verification_status, contract_status, contract_id, event_class, projection_eligible, expires_at, inserted_at_ms, updated_at_ms FROM nostr_event WHERE event_id = ? AND deleted_by_event_id IS NULL AND (? IS NULL OR expires_at IS NULL OR expires_at > ?)",
+    )
+    .bind(event_id)
+    .bind(now_unix)
+    .bind(now_unix)
+    .fetch_optional(executor)
+    .await?;
+    row.map(stored_event_from_row).transpose()
+}
+
+pub(crate) fn stored_event_from_row(
     row: sqlx::sqlite::SqliteRow,
 ) -> Result<RadrootsStoredEvent, RadrootsEventStoreError> {
     let kind = u32_from_i64("kind", row.try_get("kind")?)?;
@@ -634,6 +923,10 @@ fn stored_event_from_row(
         .map(|value| StoredEventClass::parse(value.as_str()))
         .transpose()?;
     let projection_eligible = row.try_get::<i64, _>("projection_eligible")? != 0;
+    let expires_at = row
+        .try_get::<Option<i64>, _>("expires_at")?
+        .map(|value| u64_from_i64("expires_at", value))
+        .transpose()?;
     Ok(RadrootsStoredEvent {
         seq: row.try_get("seq")?,
         event_id: row.try_get("event_id")?,
@@ -649,6 +942,7 @@ fn stored_event_from_row(
         contract_id: row.try_get("contract_id")?,
         event_class,
         projection_eligible,
+        expires_at,
         inserted_at_ms: row.try_get("inserted_at_ms")?,
         updated_at_ms: row.try_get("updated_at_ms")?,
     })
@@ -683,6 +977,19 @@ fn stored_head_from_row(
     })
 }
 
+fn tombstone_from_row(
+    row: sqlx::sqlite::SqliteRow,
+) -> Result<RadrootsEventTombstone, RadrootsEventStoreError> {
+    Ok(RadrootsEventTombstone {
+        deletion_event_id: row.try_get("deletion_event_id")?,
+        target_type: RadrootsEventTombstoneTarget::parse(row.try_get("target_type")?)?,
+        target: row.try_get("target")?,
+        pubkey: row.try_get("pubkey")?,
+        deleted_at: u32_from_i64("deleted_at", row.try_get("deleted_at")?)?,
+        recorded_at_ms: row.try_get("recorded_at_ms")?,
+    })
+}
+
 fn projection_cursor_from_row(
     row: sqlx::sqlite::SqliteRow,
 ) -> Result<RadrootsProjectionCursor, RadrootsEventStoreError> {
@@ -708,10 +1015,20 @@ fn relay_observation_from_row(
     })
 }
 
-fn u32_from_i64(field: &'static str, value: i64) -> Result<u32, RadrootsEventStoreError> {
+pub(crate) fn u32_from_i64(
+    field: &'static str,
+    value: i64,
+) -> Result<u32, RadrootsEventStoreError> {
     u32::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
 }
 
+pub(crate) fn u64_from_i64(
+    field: &'static str,
+    value: i64,
+) -> Result<u64, RadrootsEventStoreError> {
+    u64::try_from(value).map_err(|_| RadrootsEventStoreError::IntegerRange { field, value })
+}
+
 fn bool_i64(value: bool) -> i64 {
     if value { 1 } else { 0 }
 }
@@ -720,7 +1037,9 @@ fn bool_i64(value: bool) -> i64 {
 mod tests {
     use super::*;
     use radroots_events::event_head::event_head_candidate_for_event;
-    use radroots_events::kinds::{KIND_LISTING, KIND_ORDER_REQUEST, KIND_POST, KIND_PROFILE};
+    use radroots_events::kinds::{
+        KIND_DELETION, KIND_LISTING, KIND_ORDER_REQUEST, KIND_POST, KIND_PROFILE,
+    };
     use radroots_nostr::prelude::{
         RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp,
         radroots_event_from_nostr, radroots_nostr_build_event,
@@ -730,6 +1049,8 @@ mod tests {
         "10c5304d6c9ae3a1a16f7860f1cc8f5e3a76225a2663b3a989a0d775919b7df5";
     const FIXTURE_ALICE_PUBLIC_KEY_HEX: &str =
         "585591529da0bab31b3b1b1f986611cf5f435dca84f978c89ee8a40cca7103df";
+    const FIXTURE_BOB_SECRET_KEY_HEX: &str =
+        "3f8d1e0b7c6a5f4e3d2c1b0a99887766554433221100ffeeddccbbaa99887766";
 
     fn fixture_keys() -> RadrootsNostrKeys {
         let secret_key =
@@ -741,16 +1062,32 @@ mod tests {
         core::iter::repeat_n(character, 64).collect()
     }
 
+    fn other_keys() -> RadrootsNostrKeys {
+        let secret_key =
+            RadrootsNostrSecretKey::from_hex(FIXTURE_BOB_SECRET_KEY_HEX).expect("secret key");
+        RadrootsNostrKeys::new(secret_key)
+    }
+
     fn signed_event(
         kind: u32,
         created_at: u32,
         tags: Vec<Vec<String>>,
         content: &str,
+    ) -> RadrootsNostrEvent {
+        signed_event_with_keys(&fixture_keys(), kind, created_at, tags, content)
+    }
+
+    fn signed_event_with_keys(
+        keys: &RadrootsNostrKeys,
+        kind: u32,
+        created_at: u32,
+        tags: Vec<Vec<String>>,
+        content: &str,
     ) -> RadrootsNostrEvent {
         let raw_event = radroots_nostr_build_event(kind, content, tags)
             .expect("builder")
             .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
-            .sign_with_keys(&fixture_keys())
+            .sign_with_keys(keys)
             .expect("signed event");
         radroots_event_from_nostr(&raw_event)
     }
@@ -1370,4 +1707,332 @@ mod tests {
         assert_eq!(replay[0].seq, 5_001);
         assert_eq!(replay[4_999].seq, 10_000);
     }
+
+    #[tokio::test]
+    async fn deletion_hides_event_ids_and_rejects_reingest() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let post = signed_event(KIND_POST, 20, Vec::new(), "hello");
+        let kept = signed_event(KIND_POST, 21, Vec::new(), "kept");
+        for event in [&post, &kept] {
+            store
+                .ingest_event(RadrootsEventIngest::new(event.clone(), 3_000))
+                .await
+                .expect("ingest");
+        }
+        let deletion = signed_event(
+            KIND_DELETION,
+            22,
+            vec![
+                vec!["e".to_owned(), post.id.clone()],
+                vec!["k".to_owned(), KIND_POST.to_string()],
+            ],
+            "typo",
+        );
+
+        let receipt = store
+            .ingest_event(RadrootsEventIngest::new(deletion.clone(), 3_100))
+            .await
+            .expect("deletion");
+        let reingest = store
+            .ingest_event(RadrootsEventIngest::new(post.clone(), 3_200))
+            .await
+            .expect("reingest");
+        let tombstones = store
+            .tombstones_for_deletion(deletion.id.as_str())
+            .await
+            .expect("tombstones");
+        let replay = store
+            .events_since_cursor("social", 10)
+            .await
+            .expect("events");
+
+        assert!(receipt.inserted);
+        assert!(
+            store
+                .get_event(post.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(
+            store
+                .get_event(kept.id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+        assert!(!reingest.inserted);
+        assert!(!reingest.projection_eligible);
+        assert_eq!(
+            reingest.head_decision,
+            RadrootsEventHeadStoreDecision::Deleted
+        );
+        assert_eq!(tombstones.len(), 1);
+        assert_eq!(
+            tombstones[0].target_type,
+            RadrootsEventTombstoneTarget::Event
+        );
+        assert_eq!(tombstones[0].target, post.id);
+        assert_eq!(tombstones[0].deleted_at, 22);
+        assert_eq!(
+            replay
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![kept.id.as_str(), deletion.id.as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn deletion_by_another_author_is_ignored() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let post = signed_event(KIND_POST, 30, Vec::new(), "mine");
+        store
+            .ingest_event(RadrootsEventIngest::new(post.clone(), 4_000))
+            .await
+            .expect("ingest");
+        let listing_address = format!("{KIND_LISTING}:{FIXTURE_ALICE_PUBLIC_KEY_HEX}:listing-1");
+        let deletion = signed_event_with_keys(
+            &other_keys(),
+            KIND_DELETION,
+            31,
+            vec![
+                vec!["e".to_owned(), post.id.clone()],
+                vec!["a".to_owned(), listing_address],
+            ],
+            "",
+        );
+        store
+            .ingest_event(RadrootsEventIngest::new(deletion.clone(), 4_100))
+            .await
+            .expect("deletion");
+        let listing = signed_event(KIND_LISTING, 29, listing_tags("listing-1"), "{}");
+        let receipt = store
+            .ingest_event(RadrootsEventIngest::new(listing, 4_200))
+            .await
+            .expect("listing");
+
+        assert!(
+            store
+                .get_event(post.id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+        assert_eq!(
+            receipt.head_decision,
+            RadrootsEventHeadStoreDecision::Applied
+        );
+        assert_eq!(
+            store
+                .tombstones_for_deletion(deletion.id.as_str())
+                .await
+                .expect("tombstones")
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn coordinate_deletion_hides_versions_up_to_deletion_time() {
+        let store = RadrootsEventStore::open_memory().await.expect("open");
+        let first = signed_event(KIND_LISTING, 40, listing_tags("listing-1"), "{}");
+        let second = signed_event(KIND_LISTING, 41, listing_tags("listing-1"), "{}");
+        let other = signed_event(KIND_LISTING, 41, listing_tags("listing-2"), "{}");
+        for event in [&first, &second, &other] {
+            store
+                .ingest_event(RadrootsEventIngest::new(event.clone(), 5_000))
+                .await
+                .expect("ingest");
+        }
+        let coordinate = head_coordinate_for_event(&second);
+        let deletion = signed_event(
+            KIND_DELETION,
+            42,
+            vec![vec![
+                "a".to_owned(),
+                format!("{KIND_LISTING}:{FIXTURE_ALICE_PUBLIC_KEY_HEX}:listing-1"),
+            ]],
+            "",
+        );
+        store
+            .ingest_event(RadrootsEventIngest::new(deletion, 5_100))
+            .await
+            .expect("deletion");
+
+        assert!(
+            store
+                .get_event(first.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(
+            store
+                .get_event(second.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(
+            store
+                .get_event(other.id.as_str())
+                .await
+                .expect("get")
+                .is_some()
+        );
+        assert!(store.event_head(&coordinate).await.expect("head").is_none());
+
+        let stale = signed_event(
+            KIND_LISTING,
+            42,
+            listing_tags("listing-1"),
+            "{\"stale\":true}",
+        );
+        let stale_receipt = store
+            .ingest_event(RadrootsEventIngest::new(stale.clone(), 5_200))
+            .await
+            .expect("stale");
+        let fresh = signed_event(KIND_LISTING, 43, listing_tags("listing-1"), "{}");
+        let fresh_receipt = store
+            .ingest_event(RadrootsEventIngest::new(fresh.clone(), 5_300))
+            .await
+            .expect("fresh");
+        let head = store
+            .event_head(&coordinate)
+            .await
+            .expect("head")
+            .expect("stored head");
+
+        assert_eq!(stale_receipt.seq, 0);
+        assert_eq!(
+            stale_receipt.head_decision,
+            RadrootsEventHeadStoreDecision::Deleted
+        );
+        assert!(
+            store
+                .get_event(stale.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(fresh_receipt.inserted);
+        assert_eq!(
+            fresh_receipt.head_decision,
+            RadrootsEventHeadStoreDecision::Applied
+        );
+        assert_eq!(head.event_id, fresh.id);
+    }
+
+    #[tokio::test]
+    async fn expired_events_are_hidden_from_queries_and_swept() {
+        let store = RadrootsEventStore::open_memory().await.expect("store");
+        let mut tags = listing_tags("listing-1");
+        tags.push(vec![TAG_EXPIRATION.to_owned(), "50".to_owned()]);
+        let listing = signed_event(KIND_LISTING, 20, tags, "{}");
+        let receipt = store
+            .ingest_event(RadrootsEventIngest::new(listing.clone(), 3_000))
+            .await
+            .expect("ingest");
+        let coordinate = head_coordinate_for_event(&listing);
+
+        assert!(receipt.inserted);
+        assert_eq!(
+            store
+                .get_event_at(listing.id.as_str(), 49)
+                .await
+                .expect("get")
+                .and_then(|event| event.expires_at),
+            Some(50)
+        );
+        assert!(
+            store
+                .get_event_at(listing.id.as_str(), 50)
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert_eq!(
+            store
+                .events_since_cursor_at("market", 10, 49)
+                .await
+                .expect("events")
+                .len(),
+            1
+        );
+        assert!(
+            store
+                .events_since_cursor_at("market", 10, 50)
+                .await
+                .expect("events")
+                .is_empty()
+        );
+
+        assert_eq!(store.sweep_expired_events(49).await.expect("sweep"), 0);
+        assert_eq!(store.sweep_expired_events(50).await.expect("sweep"), 1);
+        assert!(
+            store
+                .get_event(listing.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(store.event_head(&coordinate).await.expect("head").is_none());
+        assert!(
+            store
+                .tags_for_event(listing.id.as_str())
+                .await
+                .expect("tags")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn ingest_skips_events_expired_at_observation() {
+        let store = RadrootsEventStore::open_memory().await.expect("store");
+        let post = signed_event(
+            KIND_POST,
+            1,
+            vec![vec![TAG_EXPIRATION.to_owned(), "3".to_owned()]],
+            "gone",
+        );
+        let unparsable = signed_event(
+            KIND_POST,
+            2,
+            vec![vec![TAG_EXPIRATION.to_owned(), "soon".to_owned()]],
+            "kept",
+        );
+
+        let receipt = store
+            .ingest_event(RadrootsEventIngest::new(post.clone(), 3_000))
+            .await
+            .expect("ingest");
+        let kept = store
+            .ingest_event(RadrootsEventIngest::new(unparsable.clone(), 3_000))
+            .await
+            .expect("ingest");
+
+        assert!(!receipt.inserted);
+        assert_eq!(receipt.seq, 0);
+        assert_eq!(
+            receipt.head_decision,
+            RadrootsEventHeadStoreDecision::Expired
+        );
+        assert!(
+            store
+                .get_event(post.id.as_str())
+                .await
+                .expect("get")
+                .is_none()
+        );
+        assert!(kept.inserted);
+        assert_eq!(
+            store
+                .get_event(unparsable.id.as_str())
+                .await
+                .expect("get")
+                .and_then(|event| event.expires_at),
+            None
+        );
+    }
 }
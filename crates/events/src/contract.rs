@@ -105,6 +105,7 @@ pub enum RadrootsTagSemantic {
     Category,
     Counterparty,
     EventPointer,
+    Expiration,
     GroupId,
     Identifier,
     Image,
@@ -250,6 +251,13 @@ const TAG_A_OPTIONAL: RadrootsTagContract = tag(
     RadrootsTagValueType::AddressableCoordinate,
     true,
 );
+const TAG_A_MANY: RadrootsTagContract = tag(
+    "a",
+    RadrootsTagCardinality::OptionalMany,
+    RadrootsTagSemantic::AddressableCoordinate,
+    RadrootsTagValueType::AddressableCoordinate,
+    true,
+);
 const TAG_E_ROOT: RadrootsTagContract = tag(
     "e",
     RadrootsTagCardinality::RequiredOne,
@@ -278,6 +286,13 @@ const TAG_KIND: RadrootsTagContract = tag(
     RadrootsTagValueType::Kind,
     true,
 );
+const TAG_KIND_MANY: RadrootsTagContract = tag(
+    "k",
+    RadrootsTagCardinality::OptionalMany,
+    RadrootsTagSemantic::Kind,
+    RadrootsTagValueType::Kind,
+    true,
+);
 const TAG_RELAY: RadrootsTagContract = tag(
     "relay",
     RadrootsTagCardinality::OptionalMany,
@@ -313,6 +328,13 @@ const TAG_PUBLISHED_AT: RadrootsTagContract = tag(
     RadrootsTagValueType::UnixTimestamp,
     false,
 );
+const TAG_EXPIRATION: RadrootsTagContract = tag(
+    "expiration",
+    RadrootsTagCardinality::OptionalOne,
+    RadrootsTagSemantic::Expiration,
+    RadrootsTagValueType::UnixTimestamp,
+    false,
+);
 const TAG_LOCATION: RadrootsTagContract = tag(
     "location",
     RadrootsTagCardinality::OptionalMany,
@@ -388,6 +410,7 @@ const NO_TAGS: &[RadrootsTagContract] = &[];
 const D_TAGS: &[RadrootsTagContract] = &[TAG_D];
 const P_TAGS: &[RadrootsTagContract] = &[TAG_P_MANY];
 const EVENT_POINTER_TAGS: &[RadrootsTagContract] = &[TAG_E_MANY, TAG_P_MANY, TAG_KIND];
+const DELETION_TAGS: &[RadrootsTagContract] = &[TAG_E_MANY, TAG_A_MANY, TAG_KIND_MANY];
 const LIST_TAGS: &[RadrootsTagContract] = &[TAG_E_MANY, TAG_A_OPTIONAL, TAG_P_MANY, TAG_RELAY];
 const LIST_SET_TAGS: &[RadrootsTagContract] = &[TAG_D, TAG_E_MANY, TAG_A_OPTIONAL, TAG_P_MANY];
 const PROFILE_TAGS: &[RadrootsTagContract] = &[TAG_P_MANY];
@@ -403,6 +426,7 @@ const LISTING_TAGS: &[RadrootsTagContract] = &[
     TAG_TITLE,
     TAG_SUMMARY,
     TAG_PUBLISHED_AT,
+    TAG_EXPIRATION,
     TAG_LOCATION,
     TAG_PRICE,
     TAG_STATUS,
@@ -425,6 +449,11 @@ const TRADE_VALIDATION_RECEIPT_TAGS: &[RadrootsTagContract] =
     &[TAG_E_ROOT, TAG_A_OPTIONAL, TAG_SERVICE_OUTPUT];
 
 const SOCIAL_REDUCERS: &[RadrootsReducer] = &[RadrootsReducer::SocialProjection];
+const DELETION_REDUCERS: &[RadrootsReducer] = &[
+    RadrootsReducer::SocialProjection,
+    RadrootsReducer::ListingProjection,
+    RadrootsReducer::MarketProjection,
+];
 const PROFILE_REDUCERS: &[RadrootsReducer] = &[RadrootsReducer::ProfileProjection];
 const FARM_OPS_REDUCERS: &[RadrootsReducer] = &[RadrootsReducer::FarmOpsProjection];
 const GROUP_REDUCERS: &[RadrootsReducer] = &[RadrootsReducer::GroupProjection];
@@ -612,6 +641,14 @@ static ALL_KIND_CONTRACTS: &[RadrootsKindContract] = &[
         RadrootsNostrStandard::Nip01,
         ["radroots.social.follow_list.v1"]
     ),
+    kind_contract!(
+        KIND_DELETION,
+        "KIND_DELETION",
+        "Event Deletion Request",
+        RadrootsEventClass::Regular,
+        RadrootsNostrStandard::Nip09,
+        ["radroots.social.deletion.v1"]
+    ),
     kind_contract!(
         KIND_REPOST,
         "KIND_REPOST",
@@ -1357,6 +1394,19 @@ static ALL_EVENT_CONTRACTS: &[RadrootsEventContract] = &[
         P_TAGS,
         PROFILE_REDUCERS
     ),
+    event_contract!(
+        "radroots.social.deletion.v1",
+        KIND_DELETION,
+        "Event Deletion Request",
+        "RadrootsDeletion",
+        RadrootsEventClass::Regular,
+        RadrootsEventPrivacy::Public,
+        RadrootsActorRole::Any,
+        RadrootsContentSchema::PlainText,
+        RadrootsEventDiscriminator::KindOnly,
+        DELETION_TAGS,
+        DELETION_REDUCERS
+    ),
     event_contract!(
         "radroots.social.repost.v1",
         KIND_REPOST,
@@ -2700,6 +2750,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deletion_contract_indexes_event_and_address_targets() {
+        let contract = event_contract("radroots.social.deletion.v1").expect("deletion");
+        assert_eq!(contract.kind, KIND_DELETION);
+        for name in ["e", "a", "k"] {
+            let tag = contract
+                .tags
+                .iter()
+                .find(|tag| tag.name == name)
+                .expect("deletion tag");
+            assert_eq!(tag.cardinality, RadrootsTagCardinality::OptionalMany);
+            assert!(tag.relay_indexed);
+        }
+    }
+
     #[test]
     fn order_request_listing_event_contract_is_event_pointer() {
         let contract = event_contract("radroots.order.request.v1").expect("order request");
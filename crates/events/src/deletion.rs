@@ -0,0 +1,53 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsDeletion {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub event_ids: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub addresses: Vec<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub kinds: Vec<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub reason: Option<String>,
+}
+
+impl RadrootsDeletion {
+    pub fn is_empty(&self) -> bool {
+        self.event_ids.is_empty() && self.addresses.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deletion_model_requires_at_least_one_target() {
+        let mut deletion = RadrootsDeletion {
+            reason: Some("posted by mistake".to_string()),
+            ..RadrootsDeletion::default()
+        };
+        assert!(deletion.is_empty());
+
+        deletion
+            .addresses
+            .push(format!("30402:{}:listing-1", "a".repeat(64)));
+        deletion.kinds.push(30402);
+        assert!(!deletion.is_empty());
+    }
+}
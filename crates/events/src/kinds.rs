@@ -1,6 +1,7 @@
 pub const KIND_PROFILE: u32 = 0;
 pub const KIND_POST: u32 = 1;
 pub const KIND_FOLLOW: u32 = 3;
+pub const KIND_DELETION: u32 = 5;
 pub const KIND_REPOST: u32 = 6;
 pub const KIND_REACTION: u32 = 7;
 pub const KIND_SEAL: u32 = 13;
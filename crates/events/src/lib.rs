@@ -13,6 +13,7 @@ pub mod calendar;
 pub mod comment;
 pub mod contract;
 pub mod coop;
+pub mod deletion;
 pub mod document;
 pub mod draft;
 pub mod event_head;
@@ -61,6 +61,11 @@ pub struct RadrootsListing {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub published_at: Option<u64>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub expiration: Option<u64>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub farm: RadrootsFarmRef,
     pub product: RadrootsListingProduct,
@@ -153,6 +158,7 @@ mod tests {
         let listing = super::RadrootsListing {
             d_tag: "listing-draft".parse().unwrap(),
             published_at: Some(1_700_000_000),
+            expiration: None,
             farm: RadrootsFarmRef::default(),
             product: super::RadrootsListingProduct {
                 key: "lettuce".to_string(),
@@ -2354,6 +2354,10 @@ mod tests {
             RadrootsTradeValidationListingError::MissingDeliveryMethod.to_string(),
             "missing listing delivery method"
         );
+        assert_eq!(
+            RadrootsTradeValidationListingError::InvalidExpiration.to_string(),
+            "listing expiration is not after publication"
+        );
     }
 
     #[test]
@@ -40,6 +40,7 @@ pub const TAG_RELAY: &str = "relay";
 pub const TAG_CHALLENGE: &str = "challenge";
 pub const TAG_TITLE: &str = "title";
 pub const TAG_PUBLISHED_AT: &str = "published_at";
+pub const TAG_EXPIRATION: &str = "expiration";
 pub const TAG_START: &str = "start";
 pub const TAG_END: &str = "end";
 pub const TAG_START_TZID: &str = "start_tzid";
@@ -109,6 +110,7 @@ mod tests {
     fn exposes_social_event_tag_keys() {
         assert_eq!(TAG_TITLE, "title");
         assert_eq!(TAG_PUBLISHED_AT, "published_at");
+        assert_eq!(TAG_EXPIRATION, "expiration");
         assert_eq!(TAG_START, "start");
         assert_eq!(TAG_END, "end");
         assert_eq!(TAG_START_TZID, "start_tzid");
@@ -33,6 +33,7 @@ pub enum RadrootsTradeValidationListingError {
     MissingAvailability,
     MissingLocation,
     MissingDeliveryMethod,
+    InvalidExpiration,
 }
 
 impl core::fmt::Display for RadrootsTradeValidationListingError {
@@ -63,6 +64,7 @@ impl core::fmt::Display for RadrootsTradeValidationListingError {
             Self::MissingAvailability => write!(f, "missing listing availability"),
             Self::MissingLocation => write!(f, "missing listing location"),
             Self::MissingDeliveryMethod => write!(f, "missing listing delivery method"),
+            Self::InvalidExpiration => write!(f, "listing expiration is not after publication"),
         }
     }
 }
@@ -0,0 +1,121 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use radroots_events::{
+    RadrootsNostrEvent,
+    deletion::RadrootsDeletion,
+    kinds::KIND_DELETION,
+    tags::{TAG_A, TAG_E, TAG_K},
+};
+
+use crate::error::EventParseError;
+use crate::field_helpers::{is_lowercase_hex_64, tag_values, validate_lowercase_hex_64_tag};
+use crate::parsed::{RadrootsParsedData, RadrootsParsedEvent};
+
+pub fn deletion_from_tags(
+    kind: u32,
+    tags: &[Vec<String>],
+    content: &str,
+) -> Result<RadrootsDeletion, EventParseError> {
+    if kind != KIND_DELETION {
+        return Err(EventParseError::InvalidKind {
+            expected: "5",
+            got: kind,
+        });
+    }
+    let event_ids = tag_values(tags, TAG_E)?;
+    for id in &event_ids {
+        validate_lowercase_hex_64_tag(id, TAG_E)?;
+    }
+    let addresses = tag_values(tags, TAG_A)?;
+    for address in &addresses {
+        validate_deletion_address(address)?;
+    }
+    if event_ids.is_empty() && addresses.is_empty() {
+        return Err(EventParseError::MissingTag(TAG_E));
+    }
+    let kinds = tag_values(tags, TAG_K)?
+        .iter()
+        .map(|value| {
+            value
+                .parse::<u32>()
+                .map_err(|err| EventParseError::InvalidNumber(TAG_K, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(RadrootsDeletion {
+        event_ids,
+        addresses,
+        kinds,
+        reason: if content.is_empty() {
+            None
+        } else {
+            Some(content.to_string())
+        },
+    })
+}
+
+pub fn data_from_event(
+    id: String,
+    author: String,
+    published_at: u32,
+    kind: u32,
+    content: String,
+    tags: Vec<Vec<String>>,
+) -> Result<RadrootsParsedData<RadrootsDeletion>, EventParseError> {
+    let deletion = deletion_from_tags(kind, &tags, &content)?;
+    Ok(RadrootsParsedData::new(
+        id,
+        author,
+        published_at,
+        kind,
+        deletion,
+    ))
+}
+
+pub fn parsed_from_event(
+    id: String,
+    author: String,
+    published_at: u32,
+    kind: u32,
+    content: String,
+    tags: Vec<Vec<String>>,
+    sig: String,
+) -> Result<RadrootsParsedEvent<RadrootsDeletion>, EventParseError> {
+    let data = data_from_event(
+        id.clone(),
+        author.clone(),
+        published_at,
+        kind,
+        content.clone(),
+        tags.clone(),
+    )?;
+    Ok(RadrootsParsedEvent {
+        event: RadrootsNostrEvent {
+            id,
+            author,
+            created_at: published_at,
+            kind,
+            content,
+            tags,
+            sig,
+        },
+        data,
+    })
+}
+
+pub(crate) fn validate_deletion_address(address: &str) -> Result<(), EventParseError> {
+    let mut parts = address.splitn(3, ':');
+    parts
+        .next()
+        .ok_or(EventParseError::InvalidTag(TAG_A))?
+        .parse::<u32>()
+        .map_err(|err| EventParseError::InvalidNumber(TAG_A, err))?;
+    let pubkey = parts.next().ok_or(EventParseError::InvalidTag(TAG_A))?;
+    if !is_lowercase_hex_64(pubkey) || parts.next().is_none() {
+        return Err(EventParseError::InvalidTag(TAG_A));
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use radroots_events::{
+    deletion::RadrootsDeletion,
+    kinds::KIND_DELETION,
+    tags::{TAG_A, TAG_E, TAG_K},
+};
+
+use crate::deletion::decode::validate_deletion_address;
+use crate::error::EventEncodeError;
+use crate::field_helpers::{push_tag, validate_lowercase_hex_64};
+use crate::wire::WireEventParts;
+
+pub fn deletion_build_tags(
+    deletion: &RadrootsDeletion,
+) -> Result<Vec<Vec<String>>, EventEncodeError> {
+    if deletion.is_empty() {
+        return Err(EventEncodeError::EmptyRequiredField("event_ids"));
+    }
+    let mut tags = Vec::new();
+    for id in &deletion.event_ids {
+        validate_lowercase_hex_64(id, "event_ids")?;
+        push_tag(&mut tags, TAG_E, id.clone());
+    }
+    for address in &deletion.addresses {
+        validate_deletion_address(address)
+            .map_err(|_| EventEncodeError::InvalidField("addresses"))?;
+        push_tag(&mut tags, TAG_A, address.clone());
+    }
+    for kind in &deletion.kinds {
+        push_tag(&mut tags, TAG_K, kind.to_string());
+    }
+    Ok(tags)
+}
+
+pub fn to_wire_parts(deletion: &RadrootsDeletion) -> Result<WireEventParts, EventEncodeError> {
+    to_wire_parts_with_kind(deletion, KIND_DELETION)
+}
+
+pub fn to_wire_parts_with_kind(
+    deletion: &RadrootsDeletion,
+    kind: u32,
+) -> Result<WireEventParts, EventEncodeError> {
+    if kind != KIND_DELETION {
+        return Err(EventEncodeError::InvalidKind(kind));
+    }
+    Ok(WireEventParts {
+        kind,
+        content: deletion.reason.clone().unwrap_or_default(),
+        tags: deletion_build_tags(deletion)?,
+    })
+}
@@ -22,7 +22,7 @@ use radroots_events::{
     },
     plot::RadrootsPlotRef,
     resource_area::RadrootsResourceAreaRef,
-    tags::{TAG_D, TAG_PUBLISHED_AT},
+    tags::{TAG_D, TAG_EXPIRATION, TAG_PUBLISHED_AT},
 };
 
 use crate::d_tag::validate_d_tag_tag;
@@ -261,6 +261,7 @@ pub fn listing_from_event_parts(
     let mut images: Vec<RadrootsListingImage> = Vec::new();
     let mut geohash: Option<String> = None;
     let mut published_at: Option<u64> = None;
+    let mut expiration: Option<u64> = None;
 
     let has_structured_location = tags
         .iter()
@@ -278,6 +279,9 @@ pub fn listing_from_event_parts(
             TAG_PUBLISHED_AT => {
                 published_at = Some(parse_u64_tag_value(tag.get(1), TAG_PUBLISHED_AT)?);
             }
+            TAG_EXPIRATION => {
+                expiration = Some(parse_u64_tag_value(tag.get(1), TAG_EXPIRATION)?);
+            }
             "process" => set_optional(&mut product.process, tag.get(1)),
             "lot" => set_optional(&mut product.lot, tag.get(1)),
             "location" => {
@@ -500,6 +504,7 @@ pub fn listing_from_event_parts(
     Ok(RadrootsListing {
         d_tag,
         published_at,
+        expiration,
         farm: farm_ref,
         product,
         primary_bin_id,
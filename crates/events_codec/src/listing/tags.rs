@@ -22,7 +22,7 @@ use radroots_events::listing::{
 };
 use radroots_events::plot::RadrootsPlotRef;
 use radroots_events::resource_area::RadrootsResourceAreaRef;
-use radroots_events::tags::{TAG_D, TAG_PUBLISHED_AT};
+use radroots_events::tags::{TAG_D, TAG_EXPIRATION, TAG_PUBLISHED_AT};
 
 use crate::d_tag::validate_d_tag;
 use crate::error::EventEncodeError;
@@ -136,6 +136,9 @@ pub fn listing_tags_with_options(
     if let Some(published_at) = listing.published_at {
         tags.push(vec![TAG_PUBLISHED_AT.to_string(), published_at.to_string()]);
     }
+    if let Some(expiration) = listing.expiration {
+        tags.push(vec![TAG_EXPIRATION.to_string(), expiration.to_string()]);
+    }
     if let Some(process) = product.process.as_deref() {
         push_tag_value(&mut tags, "process", process);
     }
@@ -693,6 +696,7 @@ mod tests {
         RadrootsListing {
             d_tag: d_tag(TEST_D_TAG),
             published_at: None,
+            expiration: None,
             farm: RadrootsFarmRef {
                 pubkey: TEST_PUBKEY_HEX.to_string(),
                 d_tag: TEST_FARM_D_TAG.to_string(),
@@ -0,0 +1,142 @@
+use radroots_events::{
+    deletion::RadrootsDeletion,
+    kinds::{KIND_DELETION, KIND_LISTING, KIND_POST},
+    tags::{TAG_A, TAG_E, TAG_K},
+};
+use radroots_events_codec::{
+    deletion::{
+        decode::{deletion_from_tags, parsed_from_event},
+        encode::{deletion_build_tags, to_wire_parts, to_wire_parts_with_kind},
+    },
+    error::{EventEncodeError, EventParseError},
+};
+
+const EVENT_ID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+const AUTHOR: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+fn listing_address() -> String {
+    format!("{KIND_LISTING}:{AUTHOR}:AAAAAAAAAAAAAAAAAAAAAA")
+}
+
+fn sample_deletion() -> RadrootsDeletion {
+    RadrootsDeletion {
+        event_ids: vec![EVENT_ID.to_string()],
+        addresses: vec![listing_address()],
+        kinds: vec![KIND_POST, KIND_LISTING],
+        reason: Some("sold out for the season".to_string()),
+    }
+}
+
+#[test]
+fn deletion_roundtrips_through_wire_parts() {
+    let deletion = sample_deletion();
+    let parts = to_wire_parts(&deletion).expect("wire parts");
+
+    assert_eq!(parts.kind, KIND_DELETION);
+    assert_eq!(parts.content, "sold out for the season");
+    assert_eq!(
+        parts.tags,
+        vec![
+            vec![TAG_E.to_string(), EVENT_ID.to_string()],
+            vec![TAG_A.to_string(), listing_address()],
+            vec![TAG_K.to_string(), KIND_POST.to_string()],
+            vec![TAG_K.to_string(), KIND_LISTING.to_string()],
+        ]
+    );
+
+    let decoded = deletion_from_tags(parts.kind, &parts.tags, &parts.content).expect("decode");
+    assert_eq!(decoded, deletion);
+}
+
+#[test]
+fn deletion_parsed_event_keeps_envelope() {
+    let parts = to_wire_parts(&sample_deletion()).expect("wire parts");
+    let parsed = parsed_from_event(
+        EVENT_ID.to_string(),
+        AUTHOR.to_string(),
+        1_700_000_000,
+        parts.kind,
+        String::new(),
+        parts.tags,
+        "sig".to_string(),
+    )
+    .expect("parsed");
+
+    assert_eq!(parsed.event.kind, KIND_DELETION);
+    assert_eq!(parsed.data.data.reason, None);
+    assert_eq!(parsed.data.data.event_ids, vec![EVENT_ID.to_string()]);
+}
+
+#[test]
+fn deletion_decode_rejects_invalid_shapes() {
+    assert!(matches!(
+        deletion_from_tags(KIND_POST, &[], ""),
+        Err(EventParseError::InvalidKind { got: KIND_POST, .. })
+    ));
+    assert!(matches!(
+        deletion_from_tags(
+            KIND_DELETION,
+            &[vec![TAG_K.to_string(), "1".to_string()]],
+            ""
+        ),
+        Err(EventParseError::MissingTag(TAG_E))
+    ));
+    assert!(matches!(
+        deletion_from_tags(
+            KIND_DELETION,
+            &[vec![TAG_E.to_string(), "ABC".to_string()]],
+            ""
+        ),
+        Err(EventParseError::InvalidTag(TAG_E))
+    ));
+    assert!(matches!(
+        deletion_from_tags(
+            KIND_DELETION,
+            &[vec![TAG_A.to_string(), format!("30402:{AUTHOR}")]],
+            ""
+        ),
+        Err(EventParseError::InvalidTag(TAG_A))
+    ));
+    assert!(matches!(
+        deletion_from_tags(
+            KIND_DELETION,
+            &[
+                vec![TAG_E.to_string(), EVENT_ID.to_string()],
+                vec![TAG_K.to_string(), "post".to_string()],
+            ],
+            ""
+        ),
+        Err(EventParseError::InvalidNumber(TAG_K, _))
+    ));
+}
+
+#[test]
+fn deletion_decode_accepts_replaceable_coordinates() {
+    let tags = vec![vec![TAG_A.to_string(), format!("0:{AUTHOR}:")]];
+    let deletion = deletion_from_tags(KIND_DELETION, &tags, "").expect("decode");
+    assert_eq!(deletion.addresses, vec![format!("0:{AUTHOR}:")]);
+}
+
+#[test]
+fn deletion_encode_rejects_invalid_targets() {
+    assert!(matches!(
+        deletion_build_tags(&RadrootsDeletion::default()),
+        Err(EventEncodeError::EmptyRequiredField("event_ids"))
+    ));
+    let mut bad_id = sample_deletion();
+    bad_id.event_ids = vec!["nope".to_string()];
+    assert!(matches!(
+        deletion_build_tags(&bad_id),
+        Err(EventEncodeError::InvalidField("event_ids"))
+    ));
+    let mut bad_address = sample_deletion();
+    bad_address.addresses = vec!["listing".to_string()];
+    assert!(matches!(
+        deletion_build_tags(&bad_address),
+        Err(EventEncodeError::InvalidField("addresses"))
+    ));
+    assert!(matches!(
+        to_wire_parts_with_kind(&sample_deletion(), KIND_POST),
+        Err(EventEncodeError::InvalidKind(KIND_POST))
+    ));
+}
@@ -168,6 +168,7 @@ fn sample_listing() -> RadrootsListing {
     RadrootsListing {
         d_tag: listing_d_tag(VALID_DOC_D_TAG),
         published_at: None,
+        expiration: None,
         farm: RadrootsFarmRef {
             pubkey: VALID_PUBKEY.to_string(),
             d_tag: VALID_FARM_D_TAG.to_string(),
@@ -5,7 +5,7 @@ use radroots_core::{
     RadrootsCoreDiscountThreshold, RadrootsCoreDiscountValue, RadrootsCoreMoney,
     RadrootsCoreQuantity, RadrootsCoreQuantityPrice, RadrootsCoreUnit,
 };
-use radroots_events::tags::{TAG_D, TAG_PUBLISHED_AT};
+use radroots_events::tags::{TAG_D, TAG_EXPIRATION, TAG_PUBLISHED_AT};
 use radroots_events::{
     farm::RadrootsFarmRef,
     ids::{RadrootsDTag, RadrootsInventoryBinId},
@@ -45,6 +45,7 @@ fn sample_listing(d_tag: &str) -> RadrootsListing {
     RadrootsListing {
         d_tag: listing_d_tag(d_tag),
         published_at: None,
+        expiration: None,
         farm: RadrootsFarmRef {
             pubkey: "farm_pubkey".to_string(),
             d_tag: "AAAAAAAAAAAAAAAAAAAAAA".to_string(),
@@ -91,6 +92,7 @@ fn sample_listing_full(d_tag: &str) -> RadrootsListing {
     RadrootsListing {
         d_tag: listing_d_tag(d_tag),
         published_at: None,
+        expiration: None,
         farm: RadrootsFarmRef {
             pubkey: "farm_pubkey".to_string(),
             d_tag: "AAAAAAAAAAAAAAAAAAAAAA".to_string(),
@@ -263,6 +265,29 @@ fn listing_roundtrips_published_at_for_active_and_rejects_bad_value() {
     assert!(matches!(err, EventParseError::InvalidTag(TAG_PUBLISHED_AT)));
 }
 
+#[test]
+fn listing_roundtrips_expiration_and_rejects_bad_value() {
+    let mut listing = sample_listing("AAAAAAAAAAAAAAAAAAAAAg");
+    listing.expiration = Some(1_782_777_600);
+    let parts = to_wire_parts_with_kind(&listing, KIND_LISTING).unwrap();
+    assert!(parts.tags.iter().any(|tag| {
+        tag.first().map(|value| value.as_str()) == Some(TAG_EXPIRATION)
+            && tag.get(1).map(|value| value.as_str()) == Some("1782777600")
+    }));
+
+    let decoded = listing_from_event(parts.kind, &parts.tags, &parts.content).unwrap();
+    assert_eq!(decoded.expiration, Some(1_782_777_600));
+
+    let mut tags = parts.tags;
+    let expiration = tags
+        .iter_mut()
+        .find(|tag| tag.first().map(|value| value.as_str()) == Some(TAG_EXPIRATION))
+        .expect("expiration tag");
+    expiration[1] = "bad".to_string();
+    let err = listing_from_event(KIND_LISTING, &tags, "# Widget").unwrap_err();
+    assert!(matches!(err, EventParseError::InvalidTag(TAG_EXPIRATION)));
+}
+
 #[test]
 fn to_wire_parts_rejects_non_listing_kind() {
     let err =
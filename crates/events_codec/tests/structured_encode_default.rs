@@ -98,6 +98,7 @@ fn sample_listing(d_tag: &str) -> RadrootsListing {
     RadrootsListing {
         d_tag: listing_d_tag(d_tag),
         published_at: None,
+        expiration: None,
         farm: RadrootsFarmRef {
             pubkey: TEST_PUBKEY_HEX.to_string(),
             d_tag: "AAAAAAAAAAAAAAAAAAAAAA".to_string(),
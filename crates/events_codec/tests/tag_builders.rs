@@ -124,6 +124,7 @@ fn sample_listing() -> RadrootsListing {
     RadrootsListing {
         d_tag: d_tag("AAAAAAAAAAAAAAAAAAAAAg"),
         published_at: None,
+        expiration: None,
         farm: RadrootsFarmRef {
             pubkey: TEST_NPUB.to_string(),
             d_tag: "AAAAAAAAAAAAAAAAAAAAAA".to_string(),
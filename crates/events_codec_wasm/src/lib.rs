@@ -438,6 +438,7 @@ mod tests {
         RadrootsListing {
             d_tag: "AAAAAAAAAAAAAAAAAAAAAg".parse().expect("listing d tag"),
             published_at: None,
+            expiration: None,
             farm: RadrootsFarmRef {
                 pubkey: "farm_pubkey".to_string(),
                 d_tag: "AAAAAAAAAAAAAAAAAAAAAA".to_string(),
@@ -8,4 +8,6 @@ pub enum GeocoderError {
     Io(#[from] std::io::Error),
     #[error("country center not found for {country_id}")]
     CountryCenterNotFound { country_id: String },
+    #[error("search index has not been built for this dataset")]
+    SearchIndexMissing,
 }
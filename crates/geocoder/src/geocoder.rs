@@ -1,7 +1,9 @@
 use crate::error::GeocoderError;
 use crate::model::{
     GeocoderCountryListResult, GeocoderPoint, GeocoderReverseOptions, GeocoderReverseResult,
+    GeocoderSearchOptions, GeocoderSearchResult,
 };
+use crate::search;
 use rusqlite::{Connection, named_params};
 use std::io::Write;
 use std::path::Path;
@@ -73,6 +75,22 @@ impl Geocoder {
         collect_mapped_rows(&mut stmt, params, map_reverse_row)
     }
 
+    pub fn search(
+        &self,
+        query: &str,
+        options: Option<GeocoderSearchOptions>,
+    ) -> Result<Vec<GeocoderSearchResult>, GeocoderError> {
+        search::search(&self.conn, query, &options.unwrap_or_default())
+    }
+
+    pub fn build_search_index(&self) -> Result<(), GeocoderError> {
+        search::build_search_index(&self.conn)
+    }
+
+    pub fn has_search_index(&self) -> Result<bool, GeocoderError> {
+        search::search_index_exists(&self.conn)
+    }
+
     pub fn country(&self, country_id: &str) -> Result<Vec<GeocoderReverseResult>, GeocoderError> {
         let mut stmt = self.conn.prepare(
             r#"
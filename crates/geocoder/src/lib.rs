@@ -4,9 +4,11 @@
 mod error;
 mod geocoder;
 mod model;
+mod search;
 
 pub use error::GeocoderError;
 pub use geocoder::Geocoder;
 pub use model::{
     GeocoderCountryListResult, GeocoderPoint, GeocoderReverseOptions, GeocoderReverseResult,
+    GeocoderSearchOptions, GeocoderSearchResult,
 };
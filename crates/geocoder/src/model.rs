@@ -40,3 +40,37 @@ pub struct GeocoderCountryListResult {
     pub lat: f64,
     pub lng: f64,
 }
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeocoderSearchOptions {
+    pub limit: usize,
+    pub country_id: Option<String>,
+    pub admin1_id: Option<i64>,
+    pub bias: Option<GeocoderPoint>,
+}
+
+impl Default for GeocoderSearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 10,
+            country_id: None,
+            admin1_id: None,
+            bias: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeocoderSearchResult {
+    pub id: i64,
+    pub name: String,
+    pub admin1_id: Option<i64>,
+    pub admin1_name: Option<String>,
+    pub country_id: String,
+    pub country_name: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub population: i64,
+    pub distance_km: Option<f64>,
+    pub score: f64,
+}
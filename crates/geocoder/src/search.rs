@@ -0,0 +1,313 @@
+use crate::error::GeocoderError;
+use crate::model::{GeocoderPoint, GeocoderSearchOptions, GeocoderSearchResult};
+use rusqlite::{Connection, named_params};
+use std::collections::BTreeSet;
+
+const SEARCH_CANDIDATE_LIMIT: i64 = 200;
+const BIASED_SEARCH_CANDIDATE_LIMIT: i64 = 2_000;
+const MIN_QUERY_COVERAGE: f64 = 0.5;
+const EXACT_MATCH_BONUS: f64 = 0.5;
+const PREFIX_MATCH_BONUS: f64 = 0.25;
+const POPULATION_WEIGHT: f64 = 0.15;
+const POPULATION_CEILING: f64 = 10_000_000.0;
+const PROXIMITY_WEIGHT: f64 = 0.15;
+const PROXIMITY_SCALE_KM: f64 = 50.0;
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+pub(crate) fn build_search_index(conn: &Connection) -> Result<(), GeocoderError> {
+    let has_population = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('geonames') WHERE name = 'population'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )? > 0;
+    let population = if has_population {
+        "CAST(COALESCE(population, 0) AS INTEGER)"
+    } else {
+        "0"
+    };
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        r#"
+        DROP TABLE IF EXISTS geonames_search;
+        CREATE VIRTUAL TABLE geonames_search USING fts5(
+          search_name,
+          feature_id UNINDEXED,
+          population UNINDEXED,
+          tokenize = 'trigram remove_diacritics 1'
+        );
+        "#,
+    )?;
+    {
+        let mut select = tx.prepare(&format!(
+            "SELECT id, name, {population} AS population FROM geonames"
+        ))?;
+        let mut insert = tx.prepare(
+            "INSERT INTO geonames_search (search_name, feature_id, population) VALUES (?1, ?2, ?3)",
+        )?;
+        let mut rows = select.query([])?;
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get("id")?;
+            let name: Option<String> = row.get("name")?;
+            let population: i64 = row.get("population")?;
+            let search_name = fold_search_text(name.as_deref().unwrap_or_default());
+            if !search_name.is_empty() {
+                insert.execute((search_name, id, population))?;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub(crate) fn search(
+    conn: &Connection,
+    query: &str,
+    options: &GeocoderSearchOptions,
+) -> Result<Vec<GeocoderSearchResult>, GeocoderError> {
+    let query = fold_search_text(query);
+    if query.is_empty() || options.limit == 0 {
+        return Ok(Vec::new());
+    }
+    if !search_index_exists(conn)? {
+        return Err(GeocoderError::SearchIndexMissing);
+    }
+
+    let query_trigrams = trigrams(&query);
+    let (filter, order, pattern) = if query_trigrams.is_empty() {
+        (
+            "s.search_name LIKE :pattern",
+            "s.population DESC",
+            format!("{query}%"),
+        )
+    } else {
+        (
+            "geonames_search MATCH :pattern",
+            "s.rank, s.population DESC",
+            query_trigrams
+                .iter()
+                .map(|trigram| format!("\"{trigram}\""))
+                .collect::<Vec<_>>()
+                .join(" OR "),
+        )
+    };
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT
+          g.id,
+          g.name,
+          g.admin1_id,
+          g.admin1_name,
+          g.country_id,
+          g.country_name,
+          g.latitude,
+          g.longitude,
+          s.search_name,
+          s.population
+        FROM geonames_search AS s
+        JOIN geonames AS g
+          ON g.id = s.feature_id
+        WHERE {filter}
+          AND (:country_id IS NULL OR g.country_id = :country_id)
+          AND (:admin1_id IS NULL OR g.admin1_id = :admin1_id)
+        ORDER BY {order}
+        LIMIT :candidate_limit
+        "#,
+    ))?;
+    let params = named_params! {
+        ":pattern": pattern,
+        ":country_id": options.country_id,
+        ":admin1_id": options.admin1_id,
+        ":candidate_limit": if options.bias.is_some() {
+            BIASED_SEARCH_CANDIDATE_LIMIT
+        } else {
+            SEARCH_CANDIDATE_LIMIT
+        },
+    };
+    let rows = stmt.query_map(params, |row| {
+        Ok((
+            GeocoderSearchResult {
+                id: row.get("id")?,
+                name: row.get("name")?,
+                admin1_id: row.get("admin1_id")?,
+                admin1_name: row.get("admin1_name")?,
+                country_id: row.get("country_id")?,
+                country_name: row.get("country_name")?,
+                latitude: row.get("latitude")?,
+                longitude: row.get("longitude")?,
+                population: row.get("population")?,
+                distance_km: None,
+                score: 0.0,
+            },
+            row.get::<_, String>("search_name")?,
+        ))
+    })?;
+
+    let mut results = Vec::new();
+    for row in rows {
+        let (mut result, search_name) = row?;
+        let Some(text_score) = text_score(&query, &query_trigrams, &search_name) else {
+            continue;
+        };
+        let distance_km = options
+            .bias
+            .map(|bias| distance_km(bias, result.latitude, result.longitude));
+        result.score = text_score
+            + POPULATION_WEIGHT * population_score(result.population)
+            + distance_km.map_or(0.0, |km| PROXIMITY_WEIGHT / (1.0 + km / PROXIMITY_SCALE_KM));
+        result.distance_km = distance_km;
+        results.push(result);
+    }
+    results.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then(right.population.cmp(&left.population))
+            .then(left.id.cmp(&right.id))
+    });
+    results.truncate(options.limit);
+    Ok(results)
+}
+
+pub(crate) fn search_index_exists(conn: &Connection) -> Result<bool, GeocoderError> {
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name = 'geonames_search'",
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(count > 0)
+}
+
+fn text_score(query: &str, query_trigrams: &BTreeSet<String>, name: &str) -> Option<f64> {
+    let mut score = if query_trigrams.is_empty() {
+        1.0
+    } else {
+        let name_trigrams = trigrams(name);
+        let shared = query_trigrams.intersection(&name_trigrams).count() as f64;
+        let coverage = shared / query_trigrams.len() as f64;
+        if coverage < MIN_QUERY_COVERAGE {
+            return None;
+        }
+        let dice = 2.0 * shared / (query_trigrams.len() + name_trigrams.len()) as f64;
+        (coverage + dice) / 2.0
+    };
+    if name == query {
+        score += EXACT_MATCH_BONUS;
+    } else if name.starts_with(query) {
+        score += PREFIX_MATCH_BONUS;
+    }
+    Some(score)
+}
+
+fn population_score(population: i64) -> f64 {
+    let population = population.max(0) as f64;
+    ((1.0 + population).ln() / (1.0 + POPULATION_CEILING).ln()).min(1.0)
+}
+
+fn distance_km(bias: GeocoderPoint, latitude: f64, longitude: f64) -> f64 {
+    let d_lat = (latitude - bias.lat).to_radians();
+    let d_lng = (longitude - bias.lng).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + bias.lat.to_radians().cos() * latitude.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+fn trigrams(value: &str) -> BTreeSet<String> {
+    let chars = value.chars().collect::<Vec<_>>();
+    chars
+        .windows(3)
+        .map(|window| window.iter().collect::<String>())
+        .collect()
+}
+
+pub(crate) fn fold_search_text(value: &str) -> String {
+    let mut folded = String::with_capacity(value.len());
+    let mut separated = false;
+    for ch in value.chars().flat_map(char::to_lowercase) {
+        let base = match ch {
+            '\u{0300}'..='\u{036f}' => continue,
+            'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+            'æ' => "ae",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'ð' | 'ď' | 'đ' => "d",
+            'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'ĥ' | 'ħ' => "h",
+            'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'ĵ' => "j",
+            'ķ' => "k",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+            'œ' => "oe",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'ß' => "ss",
+            'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+            'þ' => "th",
+            'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'ŵ' => "w",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            _ if ch.is_alphanumeric() => {
+                if separated && !folded.is_empty() {
+                    folded.push(' ');
+                }
+                separated = false;
+                folded.push(ch);
+                continue;
+            }
+            _ => {
+                separated = true;
+                continue;
+            }
+        };
+        if separated && !folded.is_empty() {
+            folded.push(' ');
+        }
+        separated = false;
+        folded.push_str(base);
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_search_text_strips_accents_case_and_punctuation() {
+        assert_eq!(
+            fold_search_text("  Sainte-Anne-de-Bellevue "),
+            "sainte anne de bellevue"
+        );
+        assert_eq!(fold_search_text("Montréal"), "montreal");
+        assert_eq!(fold_search_text("Montre\u{0301}al"), "montreal");
+        assert_eq!(fold_search_text("Łódź, Straße"), "lodz strasse");
+        assert_eq!(fold_search_text("--"), "");
+    }
+
+    #[test]
+    fn text_score_tolerates_prefixes_and_typos() {
+        let query = fold_search_text("Paso Robels");
+        let score = text_score(&query, &trigrams(&query), "paso robles").expect("typo match");
+        assert!(score > 0.5 && score < 1.0, "{score}");
+
+        let prefix = text_score("ste anne", &trigrams("ste anne"), "ste anne de bellevue")
+            .expect("prefix match");
+        let exact = text_score("ste anne", &trigrams("ste anne"), "ste anne").expect("exact");
+        assert!(exact > prefix && prefix > score);
+
+        assert!(text_score("paso robles", &trigrams("paso robles"), "portland").is_none());
+    }
+
+    #[test]
+    fn distance_km_uses_great_circle_distance() {
+        let paris = GeocoderPoint {
+            lat: 48.8566,
+            lng: 2.3522,
+        };
+        let london = distance_km(paris, 51.5074, -0.1278);
+        assert!((london - 343.5).abs() < 1.0, "{london}");
+        assert_eq!(distance_km(paris, paris.lat, paris.lng), 0.0);
+    }
+}
@@ -1,5 +1,6 @@
 use radroots_geocoder::{
     Geocoder, GeocoderCountryListResult, GeocoderError, GeocoderPoint, GeocoderReverseOptions,
+    GeocoderSearchOptions,
 };
 use rusqlite::Connection;
 use std::fs;
@@ -265,6 +266,211 @@ fn country_list_propagates_aggregate_row_mapping_errors() {
     assert_sqlite_error_contains(err, "Invalid column type");
 }
 
+#[test]
+fn search_matches_accent_insensitive_prefixes_and_typos() {
+    let geocoder = open_places_geocoder();
+
+    for (query, expected) in [
+        ("Ste-Anne-de-Bellevue", "Sainte-Anne-de-Bellevue"),
+        ("sainte anne de bellevue", "Sainte-Anne-de-Bellevue"),
+        ("montreal", "Montréal"),
+        ("Paso Robels", "Paso Robles"),
+        ("paso rob", "Paso Robles"),
+        ("mo", "Montréal"),
+    ] {
+        let results = geocoder.search(query, None).expect("search query");
+        assert_eq!(
+            results.first().map(|result| result.name.as_str()),
+            Some(expected),
+            "{query}"
+        );
+    }
+
+    let results = geocoder
+        .search("Sainte-Anne-de-Bellevue", None)
+        .expect("search query");
+    assert_eq!(results[0].id, 10);
+    assert_eq!(results[0].admin1_id, Some(10));
+    assert_eq!(results[0].admin1_name.as_deref(), Some("Quebec"));
+    assert_eq!(results[0].country_id, "CA");
+    assert_eq!(results[0].country_name.as_deref(), Some("Canada"));
+    assert_eq!(results[0].population, 5_000);
+    assert!(approx_eq(results[0].latitude, 45.4039));
+    assert!(approx_eq(results[0].longitude, -73.9525));
+    assert!(results[0].distance_km.is_none());
+
+    assert!(
+        geocoder
+            .search(" - ", None)
+            .expect("blank query")
+            .is_empty()
+    );
+    assert!(
+        geocoder
+            .search("zzzzzz", None)
+            .expect("unmatched query")
+            .is_empty()
+    );
+}
+
+#[test]
+fn search_ranks_by_population_and_bias_and_applies_filters() {
+    let geocoder = open_places_geocoder();
+
+    let results = geocoder.search("springfield", None).expect("search query");
+    let ids = results.iter().map(|result| result.id).collect::<Vec<_>>();
+    assert_eq!(ids, vec![21, 20]);
+
+    let results = geocoder
+        .search(
+            "springfield",
+            Some(GeocoderSearchOptions {
+                bias: Some(GeocoderPoint {
+                    lat: 39.8,
+                    lng: -89.6,
+                }),
+                ..GeocoderSearchOptions::default()
+            }),
+        )
+        .expect("biased search query");
+    assert_eq!(results[0].id, 20);
+    assert!(results[0].distance_km.expect("distance") < 10.0);
+
+    let results = geocoder
+        .search(
+            "springfield",
+            Some(GeocoderSearchOptions {
+                limit: 5,
+                country_id: Some("US".to_owned()),
+                admin1_id: Some(17),
+                bias: None,
+            }),
+        )
+        .expect("filtered search query");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].admin1_name.as_deref(), Some("Illinois"));
+
+    let results = geocoder
+        .search(
+            "springfield",
+            Some(GeocoderSearchOptions {
+                country_id: Some("CA".to_owned()),
+                ..GeocoderSearchOptions::default()
+            }),
+        )
+        .expect("country filtered search query");
+    assert!(results.is_empty());
+
+    let results = geocoder
+        .search(
+            "springfield",
+            Some(GeocoderSearchOptions {
+                limit: 1,
+                ..GeocoderSearchOptions::default()
+            }),
+        )
+        .expect("limited search query");
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn search_keeps_populous_and_nearby_matches_past_the_candidate_window() {
+    let temp = NamedTempFile::new().expect("temp db");
+    let path = temp.into_temp_path();
+    seed_places_database(path.to_str().expect("utf-8 temp path"));
+    let conn = Connection::open(&path).expect("open places database");
+    for id in 1_000..1_300 {
+        insert_feature(&conn, id, "Springfield", "US", 6, 0.0, 0.0);
+        conn.execute("UPDATE features SET population = 0 WHERE id = ?1", [id])
+            .expect("set population");
+    }
+    insert_feature(&conn, 2_000, "Springfield", "US", 6, -27.67, 152.91);
+    conn.execute("UPDATE features SET population = 0 WHERE id = 2000", [])
+        .expect("set population");
+    let geocoder = Geocoder::open_path(&path).expect("open geocoder");
+    geocoder.build_search_index().expect("build search index");
+
+    let results = geocoder.search("springfield", None).expect("search query");
+    let ids = results
+        .iter()
+        .take(2)
+        .map(|result| result.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![21, 20]);
+
+    let results = geocoder
+        .search(
+            "springfield",
+            Some(GeocoderSearchOptions {
+                bias: Some(GeocoderPoint {
+                    lat: -27.67,
+                    lng: 152.91,
+                }),
+                ..GeocoderSearchOptions::default()
+            }),
+        )
+        .expect("biased search query");
+    assert_eq!(results[0].id, 2_000);
+}
+
+#[test]
+fn search_requires_a_built_index_and_rebuilds_on_request() {
+    let path = build_fixture_database();
+    let geocoder = Geocoder::open_path(&path).expect("open geocoder");
+    assert!(!geocoder.has_search_index().expect("check search index"));
+    let err = geocoder
+        .search("san fran", None)
+        .expect_err("search should fail without an index");
+    assert!(matches!(err, GeocoderError::SearchIndexMissing));
+
+    geocoder.build_search_index().expect("build search index");
+    assert!(geocoder.has_search_index().expect("check search index"));
+    let results = geocoder.search("san fran", None).expect("search query");
+    assert_eq!(results[0].name, "San Francisco");
+    assert_eq!(results[0].population, 0);
+
+    let conn = Connection::open(&path).expect("open fixture database");
+    let indexed: i64 = conn
+        .query_row("SELECT COUNT(*) FROM geonames_search", [], |row| row.get(0))
+        .expect("count indexed names");
+    assert_eq!(indexed, 4);
+    insert_feature(&conn, 5, "San José", "US", 6, 37.3382, -121.8863);
+    assert!(
+        geocoder
+            .search("san jose", None)
+            .expect("stale search query")
+            .is_empty()
+    );
+
+    geocoder.build_search_index().expect("rebuild search index");
+    let results = geocoder.search("san jose", None).expect("search query");
+    assert_eq!(results[0].id, 5);
+}
+
+#[test]
+fn search_reports_missing_schema_errors() {
+    let geocoder = Geocoder::open_bytes(&[]).expect("open empty geocoder");
+    let err = geocoder
+        .search("paso robles", None)
+        .expect_err("search should fail without an index");
+    assert!(matches!(err, GeocoderError::SearchIndexMissing));
+    let err = geocoder
+        .build_search_index()
+        .expect_err("index build should fail without schema");
+    assert_sqlite_error_contains(err, "no such");
+}
+
+fn open_places_geocoder() -> Geocoder {
+    let temp = NamedTempFile::new().expect("temp db");
+    let path = temp.into_temp_path();
+    seed_places_database(path.to_str().expect("utf-8 temp path"));
+    Geocoder::open_path(&path)
+        .and_then(|geocoder| geocoder.build_search_index())
+        .expect("build places search index");
+    let bytes = fs::read(&path).expect("read places database bytes");
+    Geocoder::open_bytes(&bytes).expect("open places geocoder")
+}
+
 fn open_fixture_geocoder() -> Geocoder {
     let path = build_fixture_database();
     Geocoder::open_path(&path).expect("open geocoder")
@@ -326,6 +532,65 @@ fn seed_fixture_database(path: &str) {
     insert_feature(&conn, 4, "Sao Paulo", "BR", 27, -23.5505, -46.6333);
 }
 
+fn seed_places_database(path: &str) {
+    let conn = Connection::open(path).expect("open places database");
+    seed_schema(&conn);
+    conn.execute_batch(
+        r#"
+        ALTER TABLE features ADD COLUMN population INTEGER;
+        DROP VIEW geonames;
+        CREATE VIEW geonames AS
+          SELECT
+            features.id,
+            features.name,
+            admin1.id AS admin1_id,
+            admin1.name AS admin1_name,
+            countries.id AS country_id,
+            countries.name AS country_name,
+            coordinates.latitude AS latitude,
+            coordinates.longitude AS longitude,
+            features.population AS population
+          FROM features
+            LEFT JOIN countries ON features.country_id = countries.id
+            LEFT JOIN admin1 ON features.country_id = admin1.country_id AND features.admin1_id = admin1.id
+            JOIN coordinates ON features.id = coordinates.feature_id;
+        "#,
+    )
+    .expect("add population to places schema");
+
+    insert_country(&conn, "CA", "Canada");
+    insert_country(&conn, "US", "United States");
+
+    insert_admin1(&conn, "CA", 10, "Quebec");
+    insert_admin1(&conn, "US", 6, "California");
+    insert_admin1(&conn, "US", 17, "Illinois");
+    insert_admin1(&conn, "US", 25, "Massachusetts");
+
+    for (id, name, country_id, admin1_id, latitude, longitude, population) in [
+        (
+            10,
+            "Sainte-Anne-de-Bellevue",
+            "CA",
+            10,
+            45.4039,
+            -73.9525,
+            5_000,
+        ),
+        (11, "Montréal", "CA", 10, 45.5088, -73.5878, 1_762_949),
+        (12, "Mont-Royal", "CA", 10, 45.5167, -73.6500, 20_000),
+        (13, "Paso Robles", "US", 6, 35.6266, -120.6910, 31_000),
+        (20, "Springfield", "US", 17, 39.8017, -89.6437, 114_000),
+        (21, "Springfield", "US", 25, 42.1015, -72.5898, 155_000),
+    ] {
+        insert_feature(&conn, id, name, country_id, admin1_id, latitude, longitude);
+        conn.execute(
+            "UPDATE features SET population = ?1 WHERE id = ?2",
+            (population, id),
+        )
+        .expect("set population");
+    }
+}
+
 fn seed_high_latitude_database(path: &str) {
     let conn = Connection::open(path).expect("open fixture database");
     seed_schema(&conn);
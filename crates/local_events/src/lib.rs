@@ -2,15 +2,23 @@
 
 mod error;
 mod migrations;
+mod model_conflict;
 mod models;
 mod order_work;
 mod relay_delivery;
 mod relay_set;
 mod relay_url;
+mod seller_order_work;
 mod store;
 
 pub use error::LocalEventsError;
 pub use migrations::{MIGRATIONS, run_all_down, run_all_up};
+pub use model_conflict::{
+    LocalConflictChoice, LocalConflictResolution, LocalEditModel, LocalFieldConflict,
+    LocalModelEdit, LocalModelEditOutcome, LocalModelHead, LocalModelMerge,
+    MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND, MODEL_EDIT_LOCAL_WORK_RECORD_KIND,
+    model_conflict_resolution_record_id, three_way_merge_model,
+};
 pub use models::{
     LocalEventRecord, LocalEventRecordInput, LocalEventRecordUpdate, LocalEventsCursor,
     LocalRecordFamily, LocalRecordStatus, PublishOutboxStatus, SourceRuntime,
@@ -27,4 +35,10 @@ pub use order_work::{
 pub use relay_delivery::{RelayDeliveryEvidence, RelayDeliveryFailure, RelayDeliveryState};
 pub use relay_set::{CANONICAL_RELAY_SET_FINGERPRINT_VERSION, canonical_relay_set_fingerprint};
 pub use relay_url::{RelayUrlValidationError, normalize_relay_url, normalize_relay_urls};
+pub use seller_order_work::{
+    SELLER_ORDER_LOCAL_WORK_RECORD_KIND, SellerOrderAction, SellerOrderLocalWorkValidation,
+    SellerOrderProjection, SellerOrderWork, SellerOrderWorkReadiness,
+    seller_order_local_work_payload, seller_order_local_work_record_id,
+    seller_order_work_projection_issues, validate_seller_order_local_work_payload,
+};
 pub use store::LocalEventsStore;
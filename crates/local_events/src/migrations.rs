@@ -20,6 +20,11 @@ pub static MIGRATIONS: &[Migration] = &[
         up_sql: include_str!("../migrations/0002_network_source_runtime.up.sql"),
         down_sql: include_str!("../migrations/0002_network_source_runtime.down.sql"),
     },
+    Migration {
+        name: "0003_handed_off_status",
+        up_sql: include_str!("../migrations/0003_handed_off_status.up.sql"),
+        down_sql: include_str!("../migrations/0003_handed_off_status.down.sql"),
+    },
 ];
 
 pub fn run_all_up<E>(executor: &E) -> Result<(), SqlError>
@@ -0,0 +1,645 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use radroots_events::kinds::{KIND_FARM, KIND_LISTING, KIND_PROFILE};
+use radroots_events::tags::TAG_D;
+use radroots_sql_core::SqlExecutor;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::models::{signed_event_tag, validate_non_empty, validate_pending_signed_event};
+use crate::{
+    LocalEventRecord, LocalEventRecordInput, LocalEventRecordUpdate, LocalEventsError,
+    LocalEventsStore, LocalRecordFamily, LocalRecordStatus, PublishOutboxStatus, SourceRuntime,
+};
+
+pub const MODEL_EDIT_LOCAL_WORK_RECORD_KIND: &str = "model_edit_v1";
+pub const MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND: &str = "model_conflict_v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalEditModel {
+    Listing,
+    Farm,
+    Profile,
+}
+
+impl LocalEditModel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Listing => "listing",
+            Self::Farm => "farm",
+            Self::Profile => "profile",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, LocalEventsError> {
+        match value {
+            "listing" => Ok(Self::Listing),
+            "farm" => Ok(Self::Farm),
+            "profile" => Ok(Self::Profile),
+            other => Err(LocalEventsError::InvalidRecord(format!(
+                "unknown edit model `{other}`"
+            ))),
+        }
+    }
+
+    pub fn event_kind(self) -> u32 {
+        match self {
+            Self::Listing => KIND_LISTING,
+            Self::Farm => KIND_FARM,
+            Self::Profile => KIND_PROFILE,
+        }
+    }
+
+    fn identity_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Listing => &["d_tag", "farm"],
+            Self::Farm => &["d_tag"],
+            Self::Profile => &[],
+        }
+    }
+
+    fn nested_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Listing => &["product"],
+            Self::Farm | Self::Profile => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalModelHead {
+    pub event_id: Option<String>,
+    pub document: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalModelEdit {
+    pub record_id: String,
+    pub model: LocalEditModel,
+    pub entity_key: String,
+    pub base_event_id: Option<String>,
+    pub base: Value,
+    pub document: Value,
+    pub source_runtime: SourceRuntime,
+    pub created_at_ms: i64,
+    pub owner_account_id: Option<String>,
+    pub owner_pubkey: Option<String>,
+    pub farm_id: Option<String>,
+    pub listing_addr: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalFieldConflict {
+    pub path: String,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalModelMerge {
+    pub merged: Value,
+    pub conflicts: Vec<LocalFieldConflict>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalModelEditOutcome {
+    Saved(LocalEventRecord),
+    Merged(LocalEventRecord),
+    Conflict(LocalEventRecord),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "choice", content = "value", rename_all = "snake_case")]
+pub enum LocalConflictChoice {
+    Ours,
+    Theirs,
+    Base,
+    Value(Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalConflictResolution {
+    pub conflict_record_id: String,
+    pub choices: BTreeMap<String, LocalConflictChoice>,
+    pub source_runtime: SourceRuntime,
+    pub resolved_at_ms: i64,
+}
+
+pub fn model_conflict_resolution_record_id(
+    conflict_record_id: &str,
+) -> Result<String, LocalEventsError> {
+    validate_non_empty("conflict_record_id", conflict_record_id)?;
+    Ok(format!("{conflict_record_id}:resolution"))
+}
+
+pub fn three_way_merge_model(
+    model: LocalEditModel,
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+) -> Result<LocalModelMerge, LocalEventsError> {
+    let base = document_object("base", base)?;
+    let ours = document_object("document", ours)?;
+    let theirs = document_object("head document", theirs)?;
+    for field in model.identity_fields() {
+        let ours_value = field_value(ours, field);
+        let base_value = field_value(base, field);
+        if ours_value != field_value(theirs, field)
+            || base_value.is_some_and(|base_value| Some(base_value) != ours_value)
+        {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "{} edits must not change `{field}`",
+                model.as_str()
+            )));
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    let merged = merge_objects(
+        "",
+        base,
+        ours,
+        theirs,
+        model.nested_fields(),
+        &mut conflicts,
+    );
+    Ok(LocalModelMerge {
+        merged: Value::Object(merged),
+        conflicts,
+    })
+}
+
+impl<E: SqlExecutor> LocalEventsStore<E> {
+    pub fn record_model_edit(
+        &self,
+        edit: &LocalModelEdit,
+        head: &LocalModelHead,
+        inserted_at_ms: i64,
+    ) -> Result<LocalModelEditOutcome, LocalEventsError> {
+        self.append_model_edit(edit, head, inserted_at_ms, None)
+    }
+
+    fn append_model_edit(
+        &self,
+        edit: &LocalModelEdit,
+        head: &LocalModelHead,
+        inserted_at_ms: i64,
+        resolves_record_id: Option<&str>,
+    ) -> Result<LocalModelEditOutcome, LocalEventsError> {
+        validate_non_empty("record_id", &edit.record_id)?;
+        validate_non_empty("entity_key", &edit.entity_key)?;
+        let append = |mut payload: Value, status| {
+            if let Some(resolves_record_id) = resolves_record_id {
+                payload["resolves_record_id"] = json!(resolves_record_id);
+            }
+            self.append_record(&edit_record_input(
+                edit,
+                &edit.record_id,
+                payload,
+                status,
+                inserted_at_ms,
+            ))
+        };
+        if edit.base_event_id == head.event_id {
+            three_way_merge_model(edit.model, &edit.base, &edit.document, &edit.document)?;
+            let record = append(
+                json!({
+                    "record_kind": MODEL_EDIT_LOCAL_WORK_RECORD_KIND,
+                    "model": edit.model,
+                    "entity_key": edit.entity_key,
+                    "base_event_id": edit.base_event_id,
+                    "base": edit.base,
+                    "document": edit.document,
+                }),
+                LocalRecordStatus::LocalSaved,
+            )?;
+            return Ok(LocalModelEditOutcome::Saved(record));
+        }
+
+        let merge = three_way_merge_model(edit.model, &edit.base, &edit.document, &head.document)?;
+        if merge.conflicts.is_empty() {
+            let record = append(
+                json!({
+                    "record_kind": MODEL_EDIT_LOCAL_WORK_RECORD_KIND,
+                    "model": edit.model,
+                    "entity_key": edit.entity_key,
+                    "base_event_id": head.event_id,
+                    "base": head.document,
+                    "document": merge.merged,
+                    "merged_from_base_event_id": edit.base_event_id,
+                }),
+                LocalRecordStatus::LocalSaved,
+            )?;
+            return Ok(LocalModelEditOutcome::Merged(record));
+        }
+
+        let record = append(
+            json!({
+                "record_kind": MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND,
+                "model": edit.model,
+                "entity_key": edit.entity_key,
+                "base_event_id": edit.base_event_id,
+                "head_event_id": head.event_id,
+                "base": edit.base,
+                "ours": edit.document,
+                "theirs": head.document,
+                "merged": merge.merged,
+                "conflicts": merge.conflicts,
+            }),
+            LocalRecordStatus::Conflict,
+        )?;
+        Ok(LocalModelEditOutcome::Conflict(record))
+    }
+
+    pub fn resolve_model_conflict(
+        &self,
+        resolution: &LocalConflictResolution,
+        head: &LocalModelHead,
+    ) -> Result<LocalModelEditOutcome, LocalEventsError> {
+        let record = self
+            .get_record(&resolution.conflict_record_id)?
+            .ok_or_else(|| {
+                LocalEventsError::InvalidRecord(format!(
+                    "conflict record `{}` does not exist",
+                    resolution.conflict_record_id
+                ))
+            })?;
+        let conflict: StoredModelConflict = match (&record.status, &record.local_work_json) {
+            (LocalRecordStatus::Conflict, Some(payload))
+                if payload.get("record_kind").and_then(Value::as_str)
+                    == Some(MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND) =>
+            {
+                serde_json::from_value(payload.clone())?
+            }
+            _ => {
+                return Err(LocalEventsError::InvalidRecord(format!(
+                    "record `{}` is not a model conflict",
+                    record.record_id
+                )));
+            }
+        };
+        let resolution_record_id =
+            model_conflict_resolution_record_id(&resolution.conflict_record_id)?;
+        if self.get_record(&resolution_record_id)?.is_some() {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "conflict record `{}` is already resolved",
+                record.record_id
+            )));
+        }
+
+        let conflict_paths = conflict
+            .conflicts
+            .iter()
+            .map(|field| field.path.as_str())
+            .collect::<BTreeSet<_>>();
+        if let Some(path) = resolution
+            .choices
+            .keys()
+            .find(|path| !conflict_paths.contains(path.as_str()))
+        {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "`{path}` is not a conflicting field"
+            )));
+        }
+        let mut document = conflict.merged;
+        for field in &conflict.conflicts {
+            let chosen = match resolution.choices.get(&field.path) {
+                Some(LocalConflictChoice::Ours) => field.ours.clone(),
+                Some(LocalConflictChoice::Theirs) => field.theirs.clone(),
+                Some(LocalConflictChoice::Base) => field.base.clone(),
+                Some(LocalConflictChoice::Value(value)) => Some(value.clone()),
+                None => {
+                    return Err(LocalEventsError::InvalidRecord(format!(
+                        "conflicting field `{}` has no resolution",
+                        field.path
+                    )));
+                }
+            };
+            set_path(&mut document, &field.path, chosen)?;
+        }
+
+        let edit = LocalModelEdit {
+            record_id: resolution_record_id,
+            model: conflict.model,
+            entity_key: conflict.entity_key,
+            base_event_id: conflict.head_event_id,
+            base: conflict.theirs,
+            document,
+            source_runtime: resolution.source_runtime,
+            created_at_ms: resolution.resolved_at_ms,
+            owner_account_id: record.owner_account_id.clone(),
+            owner_pubkey: record.owner_pubkey.clone(),
+            farm_id: record.farm_id.clone(),
+            listing_addr: record.listing_addr.clone(),
+        };
+        self.append_model_edit(
+            &edit,
+            head,
+            resolution.resolved_at_ms,
+            Some(&record.record_id),
+        )
+    }
+}
+
+impl<E: SqlExecutor> LocalEventsStore<E> {
+    pub fn hand_off_model_edit(
+        &self,
+        record_id: &str,
+        signed: &LocalEventRecordInput,
+        handed_off_at_ms: i64,
+    ) -> Result<LocalEventRecord, LocalEventsError> {
+        let (record, edit) = self.saved_model_edit(record_id)?;
+        validate_pending_signed_event(signed, "model edit")?;
+        if signed.event_kind != Some(i64::from(edit.model.event_kind())) {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "signed {} edit must be a kind {} event",
+                edit.model.as_str(),
+                edit.model.event_kind()
+            )));
+        }
+        if let Some(owner_pubkey) = record.owner_pubkey.as_deref()
+            && signed.event_pubkey.as_deref() != Some(owner_pubkey)
+        {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "signed {} edit must be authored by the owner",
+                edit.model.as_str()
+            )));
+        }
+        if let Some(d_tag) = edit.document.get("d_tag").and_then(Value::as_str)
+            && signed_event_tag(signed, TAG_D) != Some(d_tag)
+        {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "signed {} edit must carry the `{d_tag}` d tag",
+                edit.model.as_str()
+            )));
+        }
+        let signed = self.append_record(signed)?;
+        self.update_outbox(&LocalEventRecordUpdate {
+            record_id: record.record_id,
+            status: LocalRecordStatus::HandedOff,
+            outbox_status: PublishOutboxStatus::None,
+            relay_set_fingerprint: None,
+            relay_delivery_json: None,
+            updated_at_ms: handed_off_at_ms,
+        })?;
+        Ok(signed)
+    }
+
+    fn saved_model_edit(
+        &self,
+        record_id: &str,
+    ) -> Result<(LocalEventRecord, StoredModelEdit), LocalEventsError> {
+        let record = self.get_record(record_id)?.ok_or_else(|| {
+            LocalEventsError::InvalidRecord(format!("model edit `{record_id}` does not exist"))
+        })?;
+        let edit: StoredModelEdit = match &record.local_work_json {
+            Some(payload)
+                if payload.get("record_kind").and_then(Value::as_str)
+                    == Some(MODEL_EDIT_LOCAL_WORK_RECORD_KIND) =>
+            {
+                serde_json::from_value(payload.clone())?
+            }
+            _ => {
+                return Err(LocalEventsError::InvalidRecord(format!(
+                    "record `{record_id}` is not a model edit"
+                )));
+            }
+        };
+        if record.status != LocalRecordStatus::LocalSaved {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "model edit `{record_id}` is {}",
+                record.status.as_str()
+            )));
+        }
+        Ok((record, edit))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StoredModelEdit {
+    model: LocalEditModel,
+    document: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct StoredModelConflict {
+    model: LocalEditModel,
+    entity_key: String,
+    head_event_id: Option<String>,
+    theirs: Value,
+    merged: Value,
+    conflicts: Vec<LocalFieldConflict>,
+}
+
+fn edit_record_input(
+    edit: &LocalModelEdit,
+    record_id: &str,
+    payload: Value,
+    status: LocalRecordStatus,
+    inserted_at_ms: i64,
+) -> LocalEventRecordInput {
+    LocalEventRecordInput {
+        record_id: record_id.to_owned(),
+        family: LocalRecordFamily::LocalWork,
+        status,
+        source_runtime: edit.source_runtime,
+        created_at_ms: edit.created_at_ms,
+        inserted_at_ms,
+        owner_account_id: edit.owner_account_id.clone(),
+        owner_pubkey: edit.owner_pubkey.clone(),
+        farm_id: edit.farm_id.clone(),
+        listing_addr: edit.listing_addr.clone(),
+        local_work_json: Some(payload),
+        event_id: None,
+        event_kind: None,
+        event_pubkey: None,
+        event_created_at: None,
+        event_tags_json: None,
+        event_content: None,
+        event_sig: None,
+        raw_event_json: None,
+        outbox_status: PublishOutboxStatus::None,
+        relay_set_fingerprint: None,
+        relay_delivery_json: None,
+    }
+}
+
+fn document_object<'a>(
+    field: &str,
+    value: &'a Value,
+) -> Result<&'a Map<String, Value>, LocalEventsError> {
+    value
+        .as_object()
+        .ok_or_else(|| LocalEventsError::InvalidRecord(format!("{field} must be a JSON object")))
+}
+
+fn field_value<'a>(object: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    object.get(key).filter(|value| !value.is_null())
+}
+
+fn merge_objects(
+    prefix: &str,
+    base: &Map<String, Value>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    nested_fields: &[&str],
+    conflicts: &mut Vec<LocalFieldConflict>,
+) -> Map<String, Value> {
+    let keys = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect::<BTreeSet<_>>();
+    let mut merged = Map::new();
+    for key in keys {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let base_value = field_value(base, key);
+        let ours_value = field_value(ours, key);
+        let theirs_value = field_value(theirs, key);
+        let value = match (ours_value, theirs_value) {
+            (Some(Value::Object(ours_object)), Some(Value::Object(theirs_object)))
+                if nested_fields.contains(&key.as_str())
+                    && base_value.is_none_or(Value::is_object) =>
+            {
+                let empty = Map::new();
+                let base_object = base_value.and_then(Value::as_object).unwrap_or(&empty);
+                Some(Value::Object(merge_objects(
+                    &path,
+                    base_object,
+                    ours_object,
+                    theirs_object,
+                    &[],
+                    conflicts,
+                )))
+            }
+            _ if ours_value == theirs_value || theirs_value == base_value => ours_value.cloned(),
+            _ if ours_value == base_value => theirs_value.cloned(),
+            _ => {
+                conflicts.push(LocalFieldConflict {
+                    path,
+                    base: base_value.cloned(),
+                    ours: ours_value.cloned(),
+                    theirs: theirs_value.cloned(),
+                });
+                ours_value.cloned()
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value);
+        }
+    }
+    merged
+}
+
+fn set_path(
+    document: &mut Value,
+    path: &str,
+    value: Option<Value>,
+) -> Result<(), LocalEventsError> {
+    let mut target = document;
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        let object = target.as_object_mut().ok_or_else(|| {
+            LocalEventsError::InvalidRecord(format!("conflict path `{path}` is not an object"))
+        })?;
+        if segments.peek().is_none() {
+            match value {
+                Some(value) => {
+                    object.insert(segment.to_owned(), value);
+                }
+                None => {
+                    object.remove(segment);
+                }
+            }
+            return Ok(());
+        }
+        target = object
+            .entry(segment.to_owned())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_way_merge_combines_disjoint_field_edits() {
+        let base = json!({"name": "Hill Farm", "about": "eggs", "website": null});
+        let ours = json!({"name": "Hill Farm", "about": "eggs and honey"});
+        let theirs =
+            json!({"name": "Hill Top Farm", "about": "eggs", "website": "https://hill.example"});
+
+        let merge =
+            three_way_merge_model(LocalEditModel::Profile, &base, &ours, &theirs).expect("merge");
+
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            merge.merged,
+            json!({
+                "name": "Hill Top Farm",
+                "about": "eggs and honey",
+                "website": "https://hill.example"
+            })
+        );
+    }
+
+    #[test]
+    fn three_way_merge_descends_into_listing_product_only() {
+        let base = json!({
+            "d_tag": "eggs",
+            "product": {"title": "Eggs", "summary": "dozen"},
+            "location": {"primary": "north"}
+        });
+        let ours = json!({
+            "d_tag": "eggs",
+            "product": {"title": "Free range eggs", "summary": "dozen"},
+            "location": {"primary": "south"}
+        });
+        let theirs = json!({
+            "d_tag": "eggs",
+            "product": {"title": "Eggs", "summary": "half dozen"},
+            "location": {"primary": "east"}
+        });
+
+        let merge =
+            three_way_merge_model(LocalEditModel::Listing, &base, &ours, &theirs).expect("merge");
+
+        assert_eq!(
+            merge.merged["product"],
+            json!({"title": "Free range eggs", "summary": "half dozen"})
+        );
+        assert_eq!(
+            merge.conflicts,
+            vec![LocalFieldConflict {
+                path: "location".to_owned(),
+                base: Some(json!({"primary": "north"})),
+                ours: Some(json!({"primary": "south"})),
+                theirs: Some(json!({"primary": "east"})),
+            }]
+        );
+    }
+
+    #[test]
+    fn three_way_merge_rejects_identity_changes_and_non_objects() {
+        let base = json!({"d_tag": "farm-a", "name": "A"});
+        let moved = json!({"d_tag": "farm-b", "name": "A"});
+        assert!(three_way_merge_model(LocalEditModel::Farm, &base, &base, &moved).is_err());
+        assert!(three_way_merge_model(LocalEditModel::Farm, &base, &json!([]), &base).is_err());
+        assert!(LocalEditModel::parse("coop").is_err());
+        for model in [
+            LocalEditModel::Listing,
+            LocalEditModel::Farm,
+            LocalEditModel::Profile,
+        ] {
+            assert_eq!(LocalEditModel::parse(model.as_str()).expect("model"), model);
+        }
+    }
+}
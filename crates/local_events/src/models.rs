@@ -40,6 +40,7 @@ pub enum LocalRecordStatus {
     Published,
     Failed,
     Conflict,
+    HandedOff,
 }
 
 impl LocalRecordStatus {
@@ -51,6 +52,7 @@ impl LocalRecordStatus {
             Self::Published => "published",
             Self::Failed => "failed",
             Self::Conflict => "conflict",
+            Self::HandedOff => "handed_off",
         }
     }
 
@@ -62,6 +64,7 @@ impl LocalRecordStatus {
             "published" => Ok(Self::Published),
             "failed" => Ok(Self::Failed),
             "conflict" => Ok(Self::Conflict),
+            "handed_off" => Ok(Self::HandedOff),
             other => Err(LocalEventsError::InvalidRecord(format!(
                 "unknown record status `{other}`"
             ))),
@@ -268,6 +271,36 @@ pub(crate) fn validate_non_empty(field: &str, value: &str) -> Result<(), LocalEv
     Ok(())
 }
 
+pub(crate) fn validate_pending_signed_event(
+    signed: &LocalEventRecordInput,
+    work: &str,
+) -> Result<(), LocalEventsError> {
+    if signed.family != LocalRecordFamily::SignedEvent
+        || signed.status != LocalRecordStatus::PendingPublish
+        || signed.outbox_status != PublishOutboxStatus::Pending
+    {
+        return Err(LocalEventsError::InvalidRecord(format!(
+            "{work} must be handed off as a pending signed event"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn signed_event_tag<'a>(
+    signed: &'a LocalEventRecordInput,
+    name: &str,
+) -> Option<&'a str> {
+    signed
+        .event_tags_json
+        .as_ref()?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_array)
+        .find(|tag| tag.first().and_then(Value::as_str) == Some(name))
+        .and_then(|tag| tag.get(1))
+        .and_then(Value::as_str)
+}
+
 fn validate_required(field: &str, value: Option<&str>) -> Result<(), LocalEventsError> {
     match value {
         Some(value) => validate_non_empty(field, value),
@@ -303,6 +336,7 @@ mod tests {
             (LocalRecordStatus::Published, "published"),
             (LocalRecordStatus::Failed, "failed"),
             (LocalRecordStatus::Conflict, "conflict"),
+            (LocalRecordStatus::HandedOff, "handed_off"),
         ] {
             assert_eq!(variant.as_str(), value);
             assert_eq!(
@@ -342,7 +342,7 @@ fn validate_money(payload: &Value, path: &[&str], currency: &str) -> Result<(),
     Ok(())
 }
 
-fn validate_string_field(
+pub(crate) fn validate_string_field(
     payload: &Value,
     path: &[&str],
     expected: &str,
@@ -359,7 +359,7 @@ fn validate_string_field(
     Ok(())
 }
 
-fn validate_required_string<'a>(
+pub(crate) fn validate_required_string<'a>(
     payload: &'a Value,
     path: &[&str],
 ) -> Result<&'a str, LocalEventsError> {
@@ -394,14 +394,17 @@ fn validate_positive_i64(payload: &Value, path: &[&str]) -> Result<(), LocalEven
     }
 }
 
-fn validate_positive_u64(payload: &Value, path: &[&str]) -> Result<u64, LocalEventsError> {
+pub(crate) fn validate_positive_u64(
+    payload: &Value,
+    path: &[&str],
+) -> Result<u64, LocalEventsError> {
     match value_at(payload, path).and_then(Value::as_u64) {
         Some(value) if value > 0 => Ok(value),
         _ => Err(invalid_field(&path.join("."), "must be positive")),
     }
 }
 
-fn validate_currency(field: &str, value: &str) -> Result<(), LocalEventsError> {
+pub(crate) fn validate_currency(field: &str, value: &str) -> Result<(), LocalEventsError> {
     if value.len() != 3 || !value.bytes().all(|byte| byte.is_ascii_uppercase()) {
         return Err(invalid_field(
             field,
@@ -411,7 +414,7 @@ fn validate_currency(field: &str, value: &str) -> Result<(), LocalEventsError> {
     Ok(())
 }
 
-fn required_array<'a>(
+pub(crate) fn required_array<'a>(
     payload: &'a Value,
     path: &[&str],
 ) -> Result<&'a Vec<Value>, LocalEventsError> {
@@ -437,7 +440,7 @@ fn support_issues(payload: &Value) -> Result<Vec<String>, LocalEventsError> {
     Ok(parsed)
 }
 
-fn value_at<'a>(payload: &'a Value, path: &[&str]) -> Option<&'a Value> {
+pub(crate) fn value_at<'a>(payload: &'a Value, path: &[&str]) -> Option<&'a Value> {
     let mut current = payload;
     for part in path {
         current = current.get(*part)?;
@@ -445,15 +448,15 @@ fn value_at<'a>(payload: &'a Value, path: &[&str]) -> Option<&'a Value> {
     Some(current)
 }
 
-fn missing_field(path: &[&str]) -> LocalEventsError {
+pub(crate) fn missing_field(path: &[&str]) -> LocalEventsError {
     invalid_field(&path.join("."), "is required")
 }
 
-fn invalid_field(field: &str, requirement: &str) -> LocalEventsError {
+pub(crate) fn invalid_field(field: &str, requirement: &str) -> LocalEventsError {
     LocalEventsError::InvalidRecord(format!("local order field `{field}` {requirement}"))
 }
 
-fn invalid_field_at(field: String, requirement: &str) -> LocalEventsError {
+pub(crate) fn invalid_field_at(field: String, requirement: &str) -> LocalEventsError {
     LocalEventsError::InvalidRecord(format!("local order field `{field}` {requirement}"))
 }
 
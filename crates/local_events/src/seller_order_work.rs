@@ -0,0 +1,591 @@
+use radroots_events::kinds::{
+    KIND_ORDER_DECISION, KIND_ORDER_FULFILLMENT_UPDATE, KIND_ORDER_REVISION_PROPOSAL,
+    KIND_ORDER_SETTLEMENT_DECISION,
+};
+use radroots_events::tags::{TAG_A, TAG_D, TAG_E_PREV, TAG_E_ROOT, TAG_P};
+use radroots_sql_core::SqlExecutor;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::models::{signed_event_tag, validate_non_empty, validate_pending_signed_event};
+use crate::order_work::{
+    invalid_field, invalid_field_at, missing_field, required_array, validate_currency,
+    validate_positive_u64, validate_required_string, validate_string_field, value_at,
+};
+use crate::{
+    LocalEventRecord, LocalEventRecordInput, LocalEventRecordUpdate, LocalEventsError,
+    LocalEventsStore, LocalRecordFamily, LocalRecordStatus, PublishOutboxStatus, SourceRuntime,
+};
+
+pub const SELLER_ORDER_LOCAL_WORK_RECORD_KIND: &str = "seller_order_work_v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SellerOrderAction {
+    Decision,
+    RevisionProposal,
+    Fulfillment,
+    Settlement,
+}
+
+impl SellerOrderAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Decision => "decision",
+            Self::RevisionProposal => "revision_proposal",
+            Self::Fulfillment => "fulfillment",
+            Self::Settlement => "settlement",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, LocalEventsError> {
+        match value {
+            "decision" => Ok(Self::Decision),
+            "revision_proposal" => Ok(Self::RevisionProposal),
+            "fulfillment" => Ok(Self::Fulfillment),
+            "settlement" => Ok(Self::Settlement),
+            other => Err(LocalEventsError::InvalidRecord(format!(
+                "unknown seller order action `{other}`"
+            ))),
+        }
+    }
+
+    pub fn event_kind(self) -> u32 {
+        match self {
+            Self::Decision => KIND_ORDER_DECISION,
+            Self::RevisionProposal => KIND_ORDER_REVISION_PROPOSAL,
+            Self::Fulfillment => KIND_ORDER_FULFILLMENT_UPDATE,
+            Self::Settlement => KIND_ORDER_SETTLEMENT_DECISION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SellerOrderProjection {
+    pub order_id: String,
+    pub status: String,
+    pub listing_addr: Option<String>,
+    pub buyer_pubkey: Option<String>,
+    pub seller_pubkey: Option<String>,
+    pub request_event_id: Option<String>,
+    pub agreement_event_id: Option<String>,
+    pub fulfillment_status: Option<String>,
+    pub last_event_id: Option<String>,
+    pub payment_state: String,
+    pub settlement_state: String,
+    pub payment_event_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SellerOrderWork {
+    pub action: SellerOrderAction,
+    pub base_event_id: String,
+    pub event: Value,
+    pub source_runtime: SourceRuntime,
+    pub created_at_ms: i64,
+    pub owner_account_id: Option<String>,
+    pub farm_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SellerOrderLocalWorkValidation {
+    pub order_id: String,
+    pub action: SellerOrderAction,
+    pub base_event_id: String,
+    pub listing_addr: String,
+    pub buyer_pubkey: String,
+    pub seller_pubkey: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SellerOrderWorkReadiness {
+    Ready(LocalEventRecord),
+    Conflict {
+        record: LocalEventRecord,
+        issues: Vec<String>,
+    },
+}
+
+pub fn seller_order_local_work_record_id(
+    order_id: &str,
+    action: SellerOrderAction,
+    base_event_id: &str,
+) -> Result<String, LocalEventsError> {
+    let order_id = order_id.trim();
+    let base_event_id = base_event_id.trim();
+    validate_non_empty("order_id", order_id)?;
+    validate_non_empty("base_event_id", base_event_id)?;
+    Ok(format!(
+        "app:local_work:seller_order:{order_id}:{}:{base_event_id}",
+        action.as_str()
+    ))
+}
+
+pub fn seller_order_local_work_payload(work: &SellerOrderWork) -> Value {
+    json!({
+        "record_kind": SELLER_ORDER_LOCAL_WORK_RECORD_KIND,
+        "action": work.action,
+        "order_id": value_at(&work.event, &["order_id"]),
+        "base_event_id": work.base_event_id,
+        "event": work.event,
+    })
+}
+
+pub fn validate_seller_order_local_work_payload(
+    payload: &Value,
+) -> Result<SellerOrderLocalWorkValidation, LocalEventsError> {
+    validate_string_field(
+        payload,
+        &["record_kind"],
+        SELLER_ORDER_LOCAL_WORK_RECORD_KIND,
+    )?;
+    let action = SellerOrderAction::parse(validate_required_string(payload, &["action"])?)?;
+    let base_event_id = validate_required_string(payload, &["base_event_id"])?;
+    let order_id = validate_required_string(payload, &["order_id"])?;
+    if validate_required_string(payload, &["event", "order_id"])? != order_id {
+        return Err(invalid_field("event.order_id", "must match order_id"));
+    }
+    let listing_addr = validate_required_string(payload, &["event", "listing_addr"])?;
+    let buyer_pubkey = validate_required_string(payload, &["event", "buyer_pubkey"])?;
+    let seller_pubkey = validate_required_string(payload, &["event", "seller_pubkey"])?;
+
+    match action {
+        SellerOrderAction::Decision => validate_decision_event(payload)?,
+        SellerOrderAction::RevisionProposal => validate_revision_proposal_event(payload)?,
+        SellerOrderAction::Fulfillment => validate_fulfillment_event(payload)?,
+        SellerOrderAction::Settlement => validate_settlement_event(payload)?,
+    }
+
+    Ok(SellerOrderLocalWorkValidation {
+        order_id: order_id.to_owned(),
+        action,
+        base_event_id: base_event_id.to_owned(),
+        listing_addr: listing_addr.to_owned(),
+        buyer_pubkey: buyer_pubkey.to_owned(),
+        seller_pubkey: seller_pubkey.to_owned(),
+    })
+}
+
+pub fn seller_order_work_projection_issues(
+    payload: &Value,
+    projection: &SellerOrderProjection,
+) -> Result<Vec<String>, LocalEventsError> {
+    let work = validate_seller_order_local_work_payload(payload)?;
+    let mut issues = Vec::new();
+    if projection.order_id != work.order_id {
+        issues.push("order_id_mismatch".to_owned());
+    }
+    if projection.seller_pubkey.as_deref() != Some(work.seller_pubkey.as_str()) {
+        issues.push("seller_pubkey_mismatch".to_owned());
+    }
+    if projection.buyer_pubkey.as_deref() != Some(work.buyer_pubkey.as_str()) {
+        issues.push("buyer_pubkey_mismatch".to_owned());
+    }
+    if projection.listing_addr.as_deref() != Some(work.listing_addr.as_str()) {
+        issues.push("listing_addr_mismatch".to_owned());
+    }
+    if projection.last_event_id.as_deref() != Some(work.base_event_id.as_str()) {
+        issues.push("base_event_stale".to_owned());
+    }
+
+    match work.action {
+        SellerOrderAction::Decision => {
+            if projection.status != "requested" {
+                issues.push("order_not_requested".to_owned());
+            }
+        }
+        SellerOrderAction::RevisionProposal => {
+            if projection.status != "accepted" {
+                issues.push("order_not_accepted".to_owned());
+            }
+            if value_at(payload, &["event", "root_event_id"]).and_then(Value::as_str)
+                != projection.request_event_id.as_deref()
+            {
+                issues.push("root_event_mismatch".to_owned());
+            }
+            if value_at(payload, &["event", "prev_event_id"]).and_then(Value::as_str)
+                != Some(work.base_event_id.as_str())
+            {
+                issues.push("previous_event_mismatch".to_owned());
+            }
+        }
+        SellerOrderAction::Fulfillment => {
+            if projection.status != "accepted" {
+                issues.push("order_not_accepted".to_owned());
+            }
+            let current = projection.fulfillment_status.as_deref();
+            if matches!(current, Some("delivered" | "seller_cancelled")) {
+                issues.push("fulfillment_terminal".to_owned());
+            }
+            if value_at(payload, &["event", "status"]).and_then(Value::as_str) == current {
+                issues.push("fulfillment_unchanged".to_owned());
+            }
+        }
+        SellerOrderAction::Settlement => {
+            if projection.payment_state != "recorded" {
+                issues.push("payment_not_recorded".to_owned());
+            }
+            if projection.settlement_state != "pending" {
+                issues.push("settlement_not_pending".to_owned());
+            }
+            for (field, expected, issue) in [
+                (
+                    "payment_event_id",
+                    projection.payment_event_id.as_deref(),
+                    "payment_event_mismatch",
+                ),
+                (
+                    "agreement_event_id",
+                    projection.agreement_event_id.as_deref(),
+                    "agreement_event_mismatch",
+                ),
+                (
+                    "root_event_id",
+                    projection.request_event_id.as_deref(),
+                    "root_event_mismatch",
+                ),
+                (
+                    "previous_event_id",
+                    Some(work.base_event_id.as_str()),
+                    "previous_event_mismatch",
+                ),
+            ] {
+                if value_at(payload, &["event", field]).and_then(Value::as_str) != expected {
+                    issues.push(issue.to_owned());
+                }
+            }
+        }
+    }
+    Ok(issues)
+}
+
+impl<E: SqlExecutor> LocalEventsStore<E> {
+    pub fn queue_seller_order_work(
+        &self,
+        work: &SellerOrderWork,
+        projection: &SellerOrderProjection,
+        inserted_at_ms: i64,
+    ) -> Result<LocalEventRecord, LocalEventsError> {
+        let payload = seller_order_local_work_payload(work);
+        let validation = validate_seller_order_local_work_payload(&payload)?;
+        let issues = seller_order_work_projection_issues(&payload, projection)?;
+        if !issues.is_empty() {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "seller order work does not apply to the current order: {}",
+                issues.join(", ")
+            )));
+        }
+        self.append_record(&LocalEventRecordInput {
+            record_id: seller_order_local_work_record_id(
+                &validation.order_id,
+                validation.action,
+                &validation.base_event_id,
+            )?,
+            family: LocalRecordFamily::LocalWork,
+            status: LocalRecordStatus::LocalSaved,
+            source_runtime: work.source_runtime,
+            created_at_ms: work.created_at_ms,
+            inserted_at_ms,
+            owner_account_id: work.owner_account_id.clone(),
+            owner_pubkey: Some(validation.seller_pubkey),
+            farm_id: work.farm_id.clone(),
+            listing_addr: Some(validation.listing_addr),
+            local_work_json: Some(payload),
+            event_id: None,
+            event_kind: None,
+            event_pubkey: None,
+            event_created_at: None,
+            event_tags_json: None,
+            event_content: None,
+            event_sig: None,
+            raw_event_json: None,
+            outbox_status: PublishOutboxStatus::None,
+            relay_set_fingerprint: None,
+            relay_delivery_json: None,
+        })
+    }
+
+    pub fn prepare_seller_order_work_for_signing(
+        &self,
+        record_id: &str,
+        projection: &SellerOrderProjection,
+        updated_at_ms: i64,
+    ) -> Result<SellerOrderWorkReadiness, LocalEventsError> {
+        let (record, payload) = self.saved_seller_order_work(record_id)?;
+        let issues = seller_order_work_projection_issues(&payload, projection)?;
+        if issues.is_empty() {
+            return Ok(SellerOrderWorkReadiness::Ready(record));
+        }
+        let record = self.update_outbox(&LocalEventRecordUpdate {
+            record_id: record.record_id,
+            status: LocalRecordStatus::Conflict,
+            outbox_status: PublishOutboxStatus::None,
+            relay_set_fingerprint: None,
+            relay_delivery_json: None,
+            updated_at_ms,
+        })?;
+        Ok(SellerOrderWorkReadiness::Conflict { record, issues })
+    }
+
+    pub fn hand_off_seller_order_work(
+        &self,
+        record_id: &str,
+        signed: &LocalEventRecordInput,
+        handed_off_at_ms: i64,
+    ) -> Result<LocalEventRecord, LocalEventsError> {
+        let (record, payload) = self.saved_seller_order_work(record_id)?;
+        let validation = validate_seller_order_local_work_payload(&payload)?;
+        validate_pending_signed_event(signed, "seller order work")?;
+        if signed.event_pubkey.as_deref() != Some(validation.seller_pubkey.as_str()) {
+            return Err(LocalEventsError::InvalidRecord(
+                "signed seller order event must be authored by the seller".to_owned(),
+            ));
+        }
+        let issues = signed_seller_order_event_issues(signed, &validation, &payload);
+        if !issues.is_empty() {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "signed seller order event does not match the queued work: {}",
+                issues.join(", ")
+            )));
+        }
+        let signed = self.append_record(signed)?;
+        self.update_outbox(&LocalEventRecordUpdate {
+            record_id: record.record_id,
+            status: LocalRecordStatus::HandedOff,
+            outbox_status: PublishOutboxStatus::None,
+            relay_set_fingerprint: None,
+            relay_delivery_json: None,
+            updated_at_ms: handed_off_at_ms,
+        })?;
+        Ok(signed)
+    }
+
+    fn saved_seller_order_work(
+        &self,
+        record_id: &str,
+    ) -> Result<(LocalEventRecord, Value), LocalEventsError> {
+        let record = self.get_record(record_id)?.ok_or_else(|| {
+            LocalEventsError::InvalidRecord(format!(
+                "seller order work `{record_id}` does not exist"
+            ))
+        })?;
+        let payload = match &record.local_work_json {
+            Some(payload)
+                if payload.get("record_kind").and_then(Value::as_str)
+                    == Some(SELLER_ORDER_LOCAL_WORK_RECORD_KIND) =>
+            {
+                payload.clone()
+            }
+            _ => {
+                return Err(LocalEventsError::InvalidRecord(format!(
+                    "record `{record_id}` is not seller order work"
+                )));
+            }
+        };
+        if record.status != LocalRecordStatus::LocalSaved {
+            return Err(LocalEventsError::InvalidRecord(format!(
+                "seller order work `{record_id}` is {}",
+                record.status.as_str()
+            )));
+        }
+        Ok((record, payload))
+    }
+}
+
+fn signed_seller_order_event_issues(
+    signed: &LocalEventRecordInput,
+    validation: &SellerOrderLocalWorkValidation,
+    payload: &Value,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    if signed.event_kind != Some(i64::from(validation.action.event_kind())) {
+        issues.push("kind_mismatch".to_owned());
+    }
+    let root_event_id = value_at(payload, &["event", "root_event_id"]).and_then(Value::as_str);
+    for (tag, expected, issue) in [
+        (
+            TAG_D,
+            Some(validation.order_id.as_str()),
+            "order_id_mismatch",
+        ),
+        (
+            TAG_A,
+            Some(validation.listing_addr.as_str()),
+            "listing_addr_mismatch",
+        ),
+        (
+            TAG_P,
+            Some(validation.buyer_pubkey.as_str()),
+            "buyer_pubkey_mismatch",
+        ),
+        (
+            TAG_E_PREV,
+            Some(validation.base_event_id.as_str()),
+            "previous_event_mismatch",
+        ),
+    ] {
+        if signed_event_tag(signed, tag) != expected {
+            issues.push(issue.to_owned());
+        }
+    }
+    match (signed_event_tag(signed, TAG_E_ROOT), root_event_id) {
+        (None, _) => issues.push("root_event_missing".to_owned()),
+        (Some(signed_root), Some(root)) if signed_root != root => {
+            issues.push("root_event_mismatch".to_owned());
+        }
+        _ => {}
+    }
+    issues
+}
+
+fn validate_decision_event(payload: &Value) -> Result<(), LocalEventsError> {
+    match validate_required_string(payload, &["event", "decision", "decision"])? {
+        "accepted" => {
+            let commitments =
+                required_array(payload, &["event", "decision", "inventory_commitments"])?;
+            if commitments.is_empty() {
+                return Err(invalid_field(
+                    "event.decision.inventory_commitments",
+                    "must contain at least one commitment",
+                ));
+            }
+            for (index, commitment) in commitments.iter().enumerate() {
+                validate_required_string(commitment, &["bin_id"]).map_err(|_| {
+                    invalid_field_at(
+                        format!("event.decision.inventory_commitments[{index}].bin_id"),
+                        "is required",
+                    )
+                })?;
+                validate_positive_u64(commitment, &["bin_count"]).map_err(|_| {
+                    invalid_field_at(
+                        format!("event.decision.inventory_commitments[{index}].bin_count"),
+                        "must be positive",
+                    )
+                })?;
+            }
+            Ok(())
+        }
+        "declined" => {
+            validate_required_string(payload, &["event", "decision", "reason"])?;
+            Ok(())
+        }
+        _ => Err(invalid_field(
+            "event.decision.decision",
+            "must be accepted or declined",
+        )),
+    }
+}
+
+fn validate_revision_proposal_event(payload: &Value) -> Result<(), LocalEventsError> {
+    validate_required_string(payload, &["event", "revision_id"])?;
+    validate_required_string(payload, &["event", "root_event_id"])?;
+    validate_required_string(payload, &["event", "prev_event_id"])?;
+    validate_required_string(payload, &["event", "reason"])?;
+    let items = required_array(payload, &["event", "items"])?;
+    if items.is_empty() {
+        return Err(invalid_field(
+            "event.items",
+            "must contain at least one item",
+        ));
+    }
+    for (index, item) in items.iter().enumerate() {
+        validate_required_string(item, &["bin_id"])
+            .map_err(|_| invalid_field_at(format!("event.items[{index}].bin_id"), "is required"))?;
+        validate_positive_u64(item, &["bin_count"]).map_err(|_| {
+            invalid_field_at(
+                format!("event.items[{index}].bin_count"),
+                "must be positive",
+            )
+        })?;
+    }
+    if !value_at(payload, &["event", "economics"]).is_some_and(Value::is_object) {
+        return Err(invalid_field("event.economics", "must be an object"));
+    }
+    let currency = validate_required_string(payload, &["event", "economics", "currency"])?;
+    validate_currency("event.economics.currency", currency)
+}
+
+fn validate_fulfillment_event(payload: &Value) -> Result<(), LocalEventsError> {
+    match validate_required_string(payload, &["event", "status"])? {
+        "preparing" | "ready_for_pickup" | "out_for_delivery" | "delivered"
+        | "seller_cancelled" => Ok(()),
+        _ => Err(invalid_field(
+            "event.status",
+            "must be a publishable fulfillment status",
+        )),
+    }
+}
+
+fn validate_settlement_event(payload: &Value) -> Result<(), LocalEventsError> {
+    for field in [
+        "root_event_id",
+        "previous_event_id",
+        "agreement_event_id",
+        "payment_event_id",
+        "quote_id",
+        "economics_digest",
+        "amount",
+    ] {
+        validate_required_string(payload, &["event", field])?;
+    }
+    validate_positive_u64(payload, &["event", "quote_version"])?;
+    let currency = validate_required_string(payload, &["event", "currency"])?;
+    validate_currency("event.currency", currency)?;
+    let reason = value_at(payload, &["event", "reason"]).filter(|reason| !reason.is_null());
+    match validate_required_string(payload, &["event", "decision"])? {
+        "accepted" => {
+            if reason.is_some() {
+                return Err(invalid_field(
+                    "event.reason",
+                    "must be absent for accepted settlements",
+                ));
+            }
+        }
+        "rejected" => {
+            if reason.is_none() {
+                return Err(missing_field(&["event", "reason"]));
+            }
+            validate_required_string(payload, &["event", "reason"])?;
+        }
+        _ => {
+            return Err(invalid_field(
+                "event.decision",
+                "must be accepted or rejected",
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seller_order_action_strings_round_trip() {
+        for action in [
+            SellerOrderAction::Decision,
+            SellerOrderAction::RevisionProposal,
+            SellerOrderAction::Fulfillment,
+            SellerOrderAction::Settlement,
+        ] {
+            assert_eq!(
+                SellerOrderAction::parse(action.as_str()).expect("action"),
+                action
+            );
+        }
+        assert!(SellerOrderAction::parse("payment").is_err());
+    }
+
+    #[test]
+    fn seller_order_record_ids_are_keyed_by_base_event() {
+        assert_eq!(
+            seller_order_local_work_record_id(" ord-a ", SellerOrderAction::Fulfillment, "ev-1")
+                .expect("record id"),
+            "app:local_work:seller_order:ord-a:fulfillment:ev-1"
+        );
+        assert!(
+            seller_order_local_work_record_id("ord-a", SellerOrderAction::Decision, " ").is_err()
+        );
+    }
+}
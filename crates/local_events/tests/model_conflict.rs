@@ -0,0 +1,374 @@
+use std::collections::BTreeMap;
+
+use radroots_local_events::{
+    LocalConflictChoice, LocalConflictResolution, LocalEditModel, LocalEventRecordInput,
+    LocalEventsStore, LocalModelEdit, LocalModelEditOutcome, LocalModelHead, LocalRecordFamily,
+    LocalRecordStatus, MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND, MODEL_EDIT_LOCAL_WORK_RECORD_KIND,
+    PublishOutboxStatus, SourceRuntime, model_conflict_resolution_record_id,
+};
+use radroots_sql_core::SqliteExecutor;
+use serde_json::{Value, json};
+
+fn store() -> LocalEventsStore<SqliteExecutor> {
+    let executor = SqliteExecutor::open_memory().expect("open memory sqlite");
+    let store = LocalEventsStore::new(executor);
+    store.migrate_up().expect("migrate local events");
+    store
+}
+
+fn listing(title: &str, summary: &str, price: &str) -> Value {
+    json!({
+        "d_tag": "eggs",
+        "farm": {"pubkey": "seller-pubkey", "d_tag": "farm-a"},
+        "product": {"key": "eggs", "title": title, "summary": summary},
+        "bins": [{"bin_id": "dozen", "price": price}]
+    })
+}
+
+fn listing_edit(record_id: &str, base_event_id: &str, document: Value) -> LocalModelEdit {
+    LocalModelEdit {
+        record_id: record_id.to_owned(),
+        model: LocalEditModel::Listing,
+        entity_key: "30402:seller-pubkey:eggs".to_owned(),
+        base_event_id: Some(base_event_id.to_owned()),
+        base: listing("Eggs", "dozen", "6"),
+        document,
+        source_runtime: SourceRuntime::App,
+        created_at_ms: 1000,
+        owner_account_id: Some("seller-account".to_owned()),
+        owner_pubkey: Some("seller-pubkey".to_owned()),
+        farm_id: Some("farm-a".to_owned()),
+        listing_addr: Some("30402:seller-pubkey:eggs".to_owned()),
+    }
+}
+
+#[test]
+fn model_edits_on_current_head_are_saved_without_merge() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-base".to_owned()),
+        document: listing("Eggs", "dozen", "6"),
+    };
+
+    let outcome = store
+        .record_model_edit(
+            &listing_edit("edit-a", "event-base", listing("Eggs", "dozen", "7")),
+            &head,
+            1001,
+        )
+        .expect("record edit");
+
+    let LocalModelEditOutcome::Saved(record) = outcome else {
+        panic!("expected saved edit, got {outcome:?}");
+    };
+    assert_eq!(record.family, LocalRecordFamily::LocalWork);
+    assert_eq!(record.status, LocalRecordStatus::LocalSaved);
+    let payload = record.local_work_json.expect("payload");
+    assert_eq!(payload["record_kind"], MODEL_EDIT_LOCAL_WORK_RECORD_KIND);
+    assert_eq!(payload["base_event_id"], "event-base");
+    assert_eq!(payload["document"]["bins"][0]["price"], "7");
+}
+
+#[test]
+fn stale_model_edits_merge_disjoint_fields_onto_the_head() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-device-a".to_owned()),
+        document: listing("Eggs", "dozen", "7"),
+    };
+
+    let outcome = store
+        .record_model_edit(
+            &listing_edit(
+                "edit-device-b",
+                "event-base",
+                listing("Eggs", "a dozen pasture raised eggs", "6"),
+            ),
+            &head,
+            1002,
+        )
+        .expect("record edit");
+
+    let LocalModelEditOutcome::Merged(record) = outcome else {
+        panic!("expected merged edit, got {outcome:?}");
+    };
+    assert_eq!(record.status, LocalRecordStatus::LocalSaved);
+    let payload = record.local_work_json.expect("payload");
+    assert_eq!(payload["base_event_id"], "event-device-a");
+    assert_eq!(payload["merged_from_base_event_id"], "event-base");
+    assert_eq!(
+        payload["document"],
+        listing("Eggs", "a dozen pasture raised eggs", "7")
+    );
+}
+
+#[test]
+fn conflicting_model_edits_are_recorded_and_resolved_into_new_work() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-device-a".to_owned()),
+        document: listing("Brown eggs", "dozen", "7"),
+    };
+
+    let outcome = store
+        .record_model_edit(
+            &listing_edit(
+                "edit-device-b",
+                "event-base",
+                listing("Pasture eggs", "a dozen", "8"),
+            ),
+            &head,
+            1003,
+        )
+        .expect("record edit");
+
+    let LocalModelEditOutcome::Conflict(record) = outcome else {
+        panic!("expected conflict, got {outcome:?}");
+    };
+    assert_eq!(record.status, LocalRecordStatus::Conflict);
+    let payload = record.local_work_json.clone().expect("payload");
+    assert_eq!(
+        payload["record_kind"],
+        MODEL_CONFLICT_LOCAL_WORK_RECORD_KIND
+    );
+    assert_eq!(payload["ours"], listing("Pasture eggs", "a dozen", "8"));
+    assert_eq!(payload["theirs"], listing("Brown eggs", "dozen", "7"));
+    let paths = payload["conflicts"]
+        .as_array()
+        .expect("conflicts")
+        .iter()
+        .map(|conflict| conflict["path"].as_str().expect("path"))
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["bins", "product.title"]);
+    assert_eq!(payload["merged"]["product"]["summary"], "a dozen");
+
+    let mut choices = BTreeMap::from([("bins".to_owned(), LocalConflictChoice::Theirs)]);
+    let mut resolution = LocalConflictResolution {
+        conflict_record_id: record.record_id.clone(),
+        choices: choices.clone(),
+        source_runtime: SourceRuntime::App,
+        resolved_at_ms: 2000,
+    };
+    let err = store
+        .resolve_model_conflict(&resolution, &head)
+        .expect_err("unresolved field");
+    assert!(err.to_string().contains("product.title"), "{err}");
+
+    choices.insert(
+        "product.title".to_owned(),
+        LocalConflictChoice::Value(json!("Pasture brown eggs")),
+    );
+    resolution.choices = choices;
+    let moved_head = LocalModelHead {
+        event_id: Some("event-device-c".to_owned()),
+        document: listing("Brown eggs", "dozen", "9"),
+    };
+    let outcome = store
+        .resolve_model_conflict(&resolution, &moved_head)
+        .expect("resolve conflict");
+
+    let LocalModelEditOutcome::Merged(resolved) = outcome else {
+        panic!("expected resolution merged onto the moved head, got {outcome:?}");
+    };
+    assert_eq!(
+        resolved.record_id,
+        model_conflict_resolution_record_id("edit-device-b").expect("resolution id")
+    );
+    assert_eq!(resolved.status, LocalRecordStatus::LocalSaved);
+    assert_eq!(resolved.listing_addr, record.listing_addr);
+    let payload = resolved.local_work_json.expect("payload");
+    assert_eq!(payload["record_kind"], MODEL_EDIT_LOCAL_WORK_RECORD_KIND);
+    assert_eq!(payload["base_event_id"], "event-device-c");
+    assert_eq!(payload["merged_from_base_event_id"], "event-device-a");
+    assert_eq!(payload["resolves_record_id"], "edit-device-b");
+    assert_eq!(
+        payload["document"],
+        listing("Pasture brown eggs", "a dozen", "9")
+    );
+
+    let err = store
+        .resolve_model_conflict(&resolution, &moved_head)
+        .expect_err("already resolved");
+    assert!(err.to_string().contains("already resolved"), "{err}");
+}
+
+#[test]
+fn model_edit_resolution_reconflicts_when_the_head_touches_the_same_field() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-device-a".to_owned()),
+        document: listing("Brown eggs", "dozen", "6"),
+    };
+    store
+        .record_model_edit(
+            &listing_edit("edit-b", "event-base", listing("White eggs", "dozen", "6")),
+            &head,
+            1001,
+        )
+        .expect("record conflict");
+    let resolution = LocalConflictResolution {
+        conflict_record_id: "edit-b".to_owned(),
+        choices: BTreeMap::from([("product.title".to_owned(), LocalConflictChoice::Ours)]),
+        source_runtime: SourceRuntime::App,
+        resolved_at_ms: 2000,
+    };
+    let outcome = store
+        .resolve_model_conflict(
+            &resolution,
+            &LocalModelHead {
+                event_id: Some("event-device-c".to_owned()),
+                document: listing("Speckled eggs", "dozen", "6"),
+            },
+        )
+        .expect("resolve conflict");
+
+    let LocalModelEditOutcome::Conflict(record) = outcome else {
+        panic!("expected a new conflict, got {outcome:?}");
+    };
+    let payload = record.local_work_json.expect("payload");
+    assert_eq!(payload["head_event_id"], "event-device-c");
+    assert_eq!(payload["resolves_record_id"], "edit-b");
+    assert_eq!(payload["ours"]["product"]["title"], "White eggs");
+}
+
+fn signed_listing(event_id: &str, kind: i64, pubkey: &str, d_tag: &str) -> LocalEventRecordInput {
+    LocalEventRecordInput {
+        record_id: format!("signed:{event_id}"),
+        family: LocalRecordFamily::SignedEvent,
+        status: LocalRecordStatus::PendingPublish,
+        source_runtime: SourceRuntime::App,
+        created_at_ms: 3000,
+        inserted_at_ms: 3001,
+        owner_account_id: Some("seller-account".to_owned()),
+        owner_pubkey: Some(pubkey.to_owned()),
+        farm_id: Some("farm-a".to_owned()),
+        listing_addr: Some(format!("{kind}:{pubkey}:{d_tag}")),
+        local_work_json: None,
+        event_id: Some(event_id.to_owned()),
+        event_kind: Some(kind),
+        event_pubkey: Some(pubkey.to_owned()),
+        event_created_at: Some(3),
+        event_tags_json: Some(json!([["d", d_tag], ["a", "30340:seller-pubkey:farm-a"]])),
+        event_content: Some("Eggs".to_owned()),
+        event_sig: Some("sig".to_owned()),
+        raw_event_json: Some(json!({"id": event_id})),
+        outbox_status: PublishOutboxStatus::Pending,
+        relay_set_fingerprint: None,
+        relay_delivery_json: None,
+    }
+}
+
+#[test]
+fn saved_model_edits_are_handed_off_as_signed_listing_events() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-base".to_owned()),
+        document: listing("Eggs", "dozen", "6"),
+    };
+    store
+        .record_model_edit(
+            &listing_edit("edit-a", "event-base", listing("Eggs", "dozen", "7")),
+            &head,
+            1001,
+        )
+        .expect("record edit");
+
+    for (signed, expected) in [
+        (
+            signed_listing("event-edit", 30340, "seller-pubkey", "eggs"),
+            "kind 30402",
+        ),
+        (
+            signed_listing("event-edit", 30402, "buyer-pubkey", "eggs"),
+            "authored by the owner",
+        ),
+        (
+            signed_listing("event-edit", 30402, "seller-pubkey", "duck-eggs"),
+            "`eggs` d tag",
+        ),
+    ] {
+        let err = store
+            .hand_off_model_edit("edit-a", &signed, 3002)
+            .expect_err("mismatched signed event");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    let signed = store
+        .hand_off_model_edit(
+            "edit-a",
+            &signed_listing("event-edit", 30402, "seller-pubkey", "eggs"),
+            3002,
+        )
+        .expect("hand off");
+    assert_eq!(signed.family, LocalRecordFamily::SignedEvent);
+    assert_eq!(signed.outbox_status, PublishOutboxStatus::Pending);
+    let work = store.get_record("edit-a").expect("get edit").expect("edit");
+    assert_eq!(work.status, LocalRecordStatus::HandedOff);
+    assert_eq!(work.updated_at_ms, 3002);
+    assert!(
+        store
+            .hand_off_model_edit(
+                "edit-a",
+                &signed_listing("event-edit-2", 30402, "seller-pubkey", "eggs"),
+                3003,
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn model_conflict_resolution_rejects_non_conflict_records_and_unknown_paths() {
+    let store = store();
+    let head = LocalModelHead {
+        event_id: Some("event-base".to_owned()),
+        document: listing("Eggs", "dozen", "6"),
+    };
+    store
+        .record_model_edit(
+            &listing_edit("edit-a", "event-base", listing("Eggs", "dozen", "7")),
+            &head,
+            1001,
+        )
+        .expect("record edit");
+
+    let resolution = LocalConflictResolution {
+        conflict_record_id: "edit-a".to_owned(),
+        choices: BTreeMap::new(),
+        source_runtime: SourceRuntime::App,
+        resolved_at_ms: 2000,
+    };
+    assert!(store.resolve_model_conflict(&resolution, &head).is_err());
+
+    let mut moved = listing("Eggs", "dozen", "7");
+    moved["d_tag"] = json!("duck-eggs");
+    assert!(
+        store
+            .record_model_edit(&listing_edit("edit-b", "event-base", moved), &head, 1002)
+            .is_err()
+    );
+
+    let stale_head = LocalModelHead {
+        event_id: Some("event-device-a".to_owned()),
+        document: listing("Brown eggs", "dozen", "6"),
+    };
+    store
+        .record_model_edit(
+            &listing_edit("edit-c", "event-base", listing("White eggs", "dozen", "6")),
+            &stale_head,
+            1003,
+        )
+        .expect("record conflict");
+    let resolution = LocalConflictResolution {
+        conflict_record_id: "edit-c".to_owned(),
+        choices: BTreeMap::from([
+            ("product.title".to_owned(), LocalConflictChoice::Ours),
+            ("product.summary".to_owned(), LocalConflictChoice::Base),
+        ]),
+        source_runtime: SourceRuntime::App,
+        resolved_at_ms: 2000,
+    };
+    let err = store
+        .resolve_model_conflict(&resolution, &stale_head)
+        .expect_err("unknown path");
+    assert!(err.to_string().contains("product.summary"), "{err}");
+}
@@ -0,0 +1,424 @@
+use radroots_local_events::{
+    LocalEventRecordInput, LocalEventsStore, LocalRecordFamily, LocalRecordStatus,
+    PublishOutboxStatus, SELLER_ORDER_LOCAL_WORK_RECORD_KIND, SellerOrderAction,
+    SellerOrderProjection, SellerOrderWork, SellerOrderWorkReadiness, SourceRuntime,
+    seller_order_local_work_payload, seller_order_work_projection_issues,
+    validate_seller_order_local_work_payload,
+};
+use radroots_sql_core::SqliteExecutor;
+use serde_json::{Value, json};
+
+const LISTING_ADDR: &str = "30402:seller-pubkey:eggs";
+
+fn store() -> LocalEventsStore<SqliteExecutor> {
+    let executor = SqliteExecutor::open_memory().expect("open memory sqlite");
+    let store = LocalEventsStore::new(executor);
+    store.migrate_up().expect("migrate local events");
+    store
+}
+
+fn projection(status: &str, last_event_id: &str) -> SellerOrderProjection {
+    SellerOrderProjection {
+        order_id: "ord-1".to_owned(),
+        status: status.to_owned(),
+        listing_addr: Some(LISTING_ADDR.to_owned()),
+        buyer_pubkey: Some("buyer-pubkey".to_owned()),
+        seller_pubkey: Some("seller-pubkey".to_owned()),
+        request_event_id: Some("ev-request".to_owned()),
+        agreement_event_id: None,
+        fulfillment_status: None,
+        last_event_id: Some(last_event_id.to_owned()),
+        payment_state: "not_recorded".to_owned(),
+        settlement_state: "not_required".to_owned(),
+        payment_event_id: None,
+    }
+}
+
+fn event(fields: Value) -> Value {
+    let mut event = json!({
+        "order_id": "ord-1",
+        "listing_addr": LISTING_ADDR,
+        "buyer_pubkey": "buyer-pubkey",
+        "seller_pubkey": "seller-pubkey",
+    });
+    for (key, value) in fields.as_object().expect("event fields") {
+        event[key] = value.clone();
+    }
+    event
+}
+
+fn work(action: SellerOrderAction, base_event_id: &str, fields: Value) -> SellerOrderWork {
+    SellerOrderWork {
+        action,
+        base_event_id: base_event_id.to_owned(),
+        event: event(fields),
+        source_runtime: SourceRuntime::App,
+        created_at_ms: 1000,
+        owner_account_id: Some("seller-account".to_owned()),
+        farm_id: Some("farm-a".to_owned()),
+    }
+}
+
+fn accept_decision() -> SellerOrderWork {
+    work(
+        SellerOrderAction::Decision,
+        "ev-request",
+        json!({"decision": {
+            "decision": "accepted",
+            "inventory_commitments": [{"bin_id": "dozen", "bin_count": 2}]
+        }}),
+    )
+}
+
+fn settlement_fields() -> Value {
+    json!({
+        "root_event_id": "ev-request",
+        "previous_event_id": "ev-payment",
+        "agreement_event_id": "ev-decision",
+        "payment_event_id": "ev-payment",
+        "quote_id": "quote-1",
+        "quote_version": 1,
+        "economics_digest": "digest-1",
+        "amount": "12",
+        "currency": "USD",
+        "decision": "accepted"
+    })
+}
+
+fn signed(work: &SellerOrderWork, event_id: &str, pubkey: &str) -> LocalEventRecordInput {
+    let root_event_id = work.event["root_event_id"]
+        .as_str()
+        .unwrap_or("ev-request")
+        .to_owned();
+    let content = json!({
+        "version": 1,
+        "type": work.action.as_str(),
+        "listing_addr": LISTING_ADDR,
+        "order_id": "ord-1",
+        "payload": work.event,
+    });
+    LocalEventRecordInput {
+        record_id: format!("signed:{event_id}"),
+        family: LocalRecordFamily::SignedEvent,
+        status: LocalRecordStatus::PendingPublish,
+        source_runtime: SourceRuntime::App,
+        created_at_ms: 2000,
+        inserted_at_ms: 2001,
+        owner_account_id: Some("seller-account".to_owned()),
+        owner_pubkey: Some(pubkey.to_owned()),
+        farm_id: Some("farm-a".to_owned()),
+        listing_addr: Some(LISTING_ADDR.to_owned()),
+        local_work_json: None,
+        event_id: Some(event_id.to_owned()),
+        event_kind: Some(i64::from(work.action.event_kind())),
+        event_pubkey: Some(pubkey.to_owned()),
+        event_created_at: Some(2),
+        event_tags_json: Some(json!([
+            ["p", "buyer-pubkey"],
+            ["a", LISTING_ADDR],
+            ["d", "ord-1"],
+            ["e_root", root_event_id],
+            ["e_prev", work.base_event_id]
+        ])),
+        event_content: Some(content.to_string()),
+        event_sig: Some("sig".to_owned()),
+        raw_event_json: Some(json!({"id": event_id})),
+        outbox_status: PublishOutboxStatus::Pending,
+        relay_set_fingerprint: None,
+        relay_delivery_json: None,
+    }
+}
+
+#[test]
+fn seller_order_payloads_validate_every_seller_action() {
+    let cases = [
+        accept_decision(),
+        work(
+            SellerOrderAction::Decision,
+            "ev-request",
+            json!({"decision": {"decision": "declined", "reason": "sold out"}}),
+        ),
+        work(
+            SellerOrderAction::RevisionProposal,
+            "ev-decision",
+            json!({
+                "revision_id": "rev-1",
+                "root_event_id": "ev-request",
+                "prev_event_id": "ev-decision",
+                "reason": "one dozen short",
+                "items": [{"bin_id": "dozen", "bin_count": 1}],
+                "economics": {"currency": "USD"}
+            }),
+        ),
+        work(
+            SellerOrderAction::Fulfillment,
+            "ev-decision",
+            json!({"status": "ready_for_pickup"}),
+        ),
+        work(
+            SellerOrderAction::Settlement,
+            "ev-payment",
+            settlement_fields(),
+        ),
+    ];
+    for case in cases {
+        let validation =
+            validate_seller_order_local_work_payload(&seller_order_local_work_payload(&case))
+                .expect("valid payload");
+        assert_eq!(validation.action, case.action);
+        assert_eq!(validation.order_id, "ord-1");
+        assert_eq!(validation.seller_pubkey, "seller-pubkey");
+    }
+
+    for (action, fields, expected) in [
+        (
+            SellerOrderAction::Decision,
+            json!({"decision": {"decision": "accepted", "inventory_commitments": []}}),
+            "inventory_commitments",
+        ),
+        (
+            SellerOrderAction::Fulfillment,
+            json!({"status": "accepted_not_fulfilled"}),
+            "event.status",
+        ),
+        (
+            SellerOrderAction::Settlement,
+            json!({"decision": "rejected"}),
+            "event.root_event_id",
+        ),
+    ] {
+        let err = validate_seller_order_local_work_payload(&seller_order_local_work_payload(
+            &work(action, "ev-request", fields),
+        ))
+        .expect_err("invalid payload");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    let mut rejected = settlement_fields();
+    rejected["decision"] = json!("rejected");
+    let err = validate_seller_order_local_work_payload(&seller_order_local_work_payload(&work(
+        SellerOrderAction::Settlement,
+        "ev-payment",
+        rejected,
+    )))
+    .expect_err("missing reason");
+    assert!(err.to_string().contains("event.reason"), "{err}");
+}
+
+#[test]
+fn seller_order_work_is_checked_against_the_projection() {
+    let decision = seller_order_local_work_payload(&accept_decision());
+    assert!(
+        seller_order_work_projection_issues(&decision, &projection("requested", "ev-request"))
+            .expect("issues")
+            .is_empty()
+    );
+    assert_eq!(
+        seller_order_work_projection_issues(&decision, &projection("accepted", "ev-decision"))
+            .expect("issues"),
+        vec!["base_event_stale", "order_not_requested"]
+    );
+
+    let mut delivered = projection("accepted", "ev-fulfillment");
+    delivered.fulfillment_status = Some("delivered".to_owned());
+    let fulfillment = seller_order_local_work_payload(&work(
+        SellerOrderAction::Fulfillment,
+        "ev-fulfillment",
+        json!({"status": "delivered"}),
+    ));
+    assert_eq!(
+        seller_order_work_projection_issues(&fulfillment, &delivered).expect("issues"),
+        vec!["fulfillment_terminal", "fulfillment_unchanged"]
+    );
+
+    let mut paid = projection("accepted", "ev-payment");
+    paid.agreement_event_id = Some("ev-decision".to_owned());
+    paid.payment_state = "recorded".to_owned();
+    paid.settlement_state = "pending".to_owned();
+    paid.payment_event_id = Some("ev-payment".to_owned());
+    let settlement = seller_order_local_work_payload(&work(
+        SellerOrderAction::Settlement,
+        "ev-payment",
+        settlement_fields(),
+    ));
+    assert!(
+        seller_order_work_projection_issues(&settlement, &paid)
+            .expect("issues")
+            .is_empty()
+    );
+    paid.seller_pubkey = Some("other-seller".to_owned());
+    paid.payment_event_id = Some("ev-other-payment".to_owned());
+    assert_eq!(
+        seller_order_work_projection_issues(&settlement, &paid).expect("issues"),
+        vec!["seller_pubkey_mismatch", "payment_event_mismatch"]
+    );
+}
+
+#[test]
+fn queued_seller_work_is_handed_off_to_the_outbox_after_signing() {
+    let store = store();
+    let requested = projection("requested", "ev-request");
+    let record = store
+        .queue_seller_order_work(&accept_decision(), &requested, 1001)
+        .expect("queue work");
+
+    assert_eq!(
+        record.record_id,
+        "app:local_work:seller_order:ord-1:decision:ev-request"
+    );
+    assert_eq!(record.family, LocalRecordFamily::LocalWork);
+    assert_eq!(record.status, LocalRecordStatus::LocalSaved);
+    assert_eq!(record.owner_pubkey.as_deref(), Some("seller-pubkey"));
+    assert_eq!(record.listing_addr.as_deref(), Some(LISTING_ADDR));
+    let payload = record.local_work_json.clone().expect("payload");
+    assert_eq!(payload["record_kind"], SELLER_ORDER_LOCAL_WORK_RECORD_KIND);
+    assert_eq!(payload["action"], "decision");
+
+    let SellerOrderWorkReadiness::Ready(ready) = store
+        .prepare_seller_order_work_for_signing(&record.record_id, &requested, 1500)
+        .expect("prepare")
+    else {
+        panic!("expected ready work");
+    };
+    assert_eq!(ready.record_id, record.record_id);
+
+    let decision = accept_decision();
+    let err = store
+        .hand_off_seller_order_work(
+            &record.record_id,
+            &signed(&decision, "ev-decision", "buyer-pubkey"),
+            2002,
+        )
+        .expect_err("wrong author");
+    assert!(err.to_string().contains("authored by the seller"), "{err}");
+
+    let mut fulfillment_kind = signed(&decision, "ev-decision", "seller-pubkey");
+    fulfillment_kind.event_kind = Some(i64::from(SellerOrderAction::Fulfillment.event_kind()));
+    let mut other_order = signed(&decision, "ev-decision", "seller-pubkey");
+    other_order.event_tags_json = Some(json!([
+        ["p", "buyer-pubkey"],
+        ["a", LISTING_ADDR],
+        ["d", "ord-2"],
+        ["e_root", "ev-request"],
+        ["e_prev", "ev-other"]
+    ]));
+    for (mismatched, expected) in [
+        (fulfillment_kind, "kind_mismatch"),
+        (other_order, "order_id_mismatch, previous_event_mismatch"),
+    ] {
+        let err = store
+            .hand_off_seller_order_work(&record.record_id, &mismatched, 2002)
+            .expect_err("mismatched signed event");
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    let signed_record = store
+        .hand_off_seller_order_work(
+            &record.record_id,
+            &signed(&decision, "ev-decision", "seller-pubkey"),
+            2002,
+        )
+        .expect("hand off");
+    assert_eq!(signed_record.family, LocalRecordFamily::SignedEvent);
+    assert_eq!(signed_record.event_kind, Some(3423));
+    assert_eq!(signed_record.outbox_status, PublishOutboxStatus::Pending);
+    let work = store
+        .get_record(&record.record_id)
+        .expect("get work")
+        .expect("work");
+    assert_eq!(work.status, LocalRecordStatus::HandedOff);
+    assert_eq!(work.updated_at_ms, 2002);
+
+    assert!(
+        store
+            .hand_off_seller_order_work(
+                &record.record_id,
+                &signed(&decision, "ev-decision", "seller-pubkey"),
+                2003
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn signed_settlements_must_chain_to_the_order_root() {
+    let store = store();
+    let mut paid = projection("accepted", "ev-payment");
+    paid.agreement_event_id = Some("ev-decision".to_owned());
+    paid.payment_state = "recorded".to_owned();
+    paid.settlement_state = "pending".to_owned();
+    paid.payment_event_id = Some("ev-payment".to_owned());
+    let settlement = work(
+        SellerOrderAction::Settlement,
+        "ev-payment",
+        settlement_fields(),
+    );
+    let record = store
+        .queue_seller_order_work(&settlement, &paid, 1001)
+        .expect("queue settlement");
+
+    let mut detached = signed(&settlement, "ev-settlement", "seller-pubkey");
+    detached.event_tags_json = Some(json!([
+        ["p", "buyer-pubkey"],
+        ["a", LISTING_ADDR],
+        ["d", "ord-1"],
+        ["e_root", "ev-other-request"],
+        ["e_prev", "ev-payment"]
+    ]));
+    let err = store
+        .hand_off_seller_order_work(&record.record_id, &detached, 2002)
+        .expect_err("wrong root");
+    assert!(err.to_string().contains("root_event_mismatch"), "{err}");
+
+    let signed_record = store
+        .hand_off_seller_order_work(
+            &record.record_id,
+            &signed(&settlement, "ev-settlement", "seller-pubkey"),
+            2002,
+        )
+        .expect("hand off settlement");
+    assert_eq!(signed_record.event_kind, Some(3436));
+}
+
+#[test]
+fn stale_seller_work_becomes_a_conflict_before_signing() {
+    let store = store();
+    assert!(
+        store
+            .queue_seller_order_work(
+                &accept_decision(),
+                &projection("cancelled", "ev-cancel"),
+                1001
+            )
+            .is_err()
+    );
+
+    let record = store
+        .queue_seller_order_work(
+            &accept_decision(),
+            &projection("requested", "ev-request"),
+            1001,
+        )
+        .expect("queue work");
+    let readiness = store
+        .prepare_seller_order_work_for_signing(
+            &record.record_id,
+            &projection("cancelled", "ev-cancel"),
+            1500,
+        )
+        .expect("prepare");
+    let SellerOrderWorkReadiness::Conflict { record, issues } = readiness else {
+        panic!("expected conflict, got {readiness:?}");
+    };
+    assert_eq!(record.status, LocalRecordStatus::Conflict);
+    assert_eq!(issues, vec!["base_event_stale", "order_not_requested"]);
+
+    let err = store
+        .hand_off_seller_order_work(
+            &record.record_id,
+            &signed(&accept_decision(), "ev-decision", "seller-pubkey"),
+            2002,
+        )
+        .expect_err("conflicted work");
+    assert!(err.to_string().contains("is conflict"), "{err}");
+}
@@ -0,0 +1,82 @@
+#![forbid(unsafe_code)]
+
+use crate::info::RadrootsLocalRelayInfo;
+use crate::policy::RadrootsLocalRelayWritePolicy;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const RADROOTS_LOCAL_RELAY_DEFAULT_MAX_SUBSCRIPTIONS: usize = 32;
+pub const RADROOTS_LOCAL_RELAY_DEFAULT_MAX_LIMIT: u32 = 500;
+pub const RADROOTS_LOCAL_RELAY_DEFAULT_MAX_MESSAGE_LENGTH: usize = 256 * 1024;
+
+pub type RadrootsLocalRelayClock = fn() -> i64;
+
+#[derive(Clone, Debug)]
+pub struct RadrootsLocalRelayConfig {
+    pub info: RadrootsLocalRelayInfo,
+    pub write_policy: RadrootsLocalRelayWritePolicy,
+    pub max_subscriptions: usize,
+    pub max_limit: u32,
+    pub max_message_length: usize,
+    pub clock: RadrootsLocalRelayClock,
+}
+
+impl Default for RadrootsLocalRelayConfig {
+    fn default() -> Self {
+        Self {
+            info: RadrootsLocalRelayInfo::default(),
+            write_policy: RadrootsLocalRelayWritePolicy::default(),
+            max_subscriptions: RADROOTS_LOCAL_RELAY_DEFAULT_MAX_SUBSCRIPTIONS,
+            max_limit: RADROOTS_LOCAL_RELAY_DEFAULT_MAX_LIMIT,
+            max_message_length: RADROOTS_LOCAL_RELAY_DEFAULT_MAX_MESSAGE_LENGTH,
+            clock: system_clock_ms,
+        }
+    }
+}
+
+impl RadrootsLocalRelayConfig {
+    pub fn with_info(mut self, info: RadrootsLocalRelayInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    pub fn with_write_policy(mut self, write_policy: RadrootsLocalRelayWritePolicy) -> Self {
+        self.write_policy = write_policy;
+        self
+    }
+
+    pub fn with_max_subscriptions(mut self, max_subscriptions: usize) -> Self {
+        self.max_subscriptions = max_subscriptions.max(1);
+        self
+    }
+
+    pub fn with_max_limit(mut self, max_limit: u32) -> Self {
+        self.max_limit = max_limit.max(1);
+        self
+    }
+
+    pub fn with_max_message_length(mut self, max_message_length: usize) -> Self {
+        self.max_message_length = max_message_length;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: RadrootsLocalRelayClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn relay_info(&self) -> RadrootsLocalRelayInfo {
+        let mut info = self.info.clone();
+        info.limitation.max_message_length = Some(self.max_message_length);
+        info.limitation.max_subscriptions = Some(self.max_subscriptions);
+        info.limitation.max_limit = Some(self.max_limit);
+        info.limitation.restricted_writes = true;
+        info
+    }
+}
+
+fn system_clock_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+        .unwrap_or(0)
+}
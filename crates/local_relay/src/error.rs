@@ -0,0 +1,33 @@
+#![forbid(unsafe_code)]
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RadrootsLocalRelayError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Event store error: {0}")]
+    EventStore(#[from] radroots_event_store::RadrootsEventStoreError),
+
+    #[error("Nostr event JSON error: {0}")]
+    NostrEventJson(String),
+
+    #[error("Websocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+
+    #[error("HTTP request head exceeds {0} bytes")]
+    RequestHeadTooLarge(usize),
+
+    #[error("Relay task failed: {0}")]
+    Task(String),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for RadrootsLocalRelayError {
+    fn from(error: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(error))
+    }
+}
@@ -0,0 +1,111 @@
+#![forbid(unsafe_code)]
+
+use crate::RadrootsLocalRelayError;
+use crate::config::RadrootsLocalRelayConfig;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+const NIP11_CONTENT_TYPE: &str = "application/nostr+json";
+
+pub(crate) struct RequestHead {
+    pub(crate) bytes: Vec<u8>,
+    method: String,
+    headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    fn parse(bytes: Vec<u8>) -> Self {
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        let mut lines = text.split("\r\n");
+        let method = lines
+            .next()
+            .and_then(|line| line.split_whitespace().next())
+            .unwrap_or_default()
+            .to_owned();
+        let headers = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+            .collect();
+        Self {
+            bytes,
+            method,
+            headers,
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn is_websocket_upgrade(&self) -> bool {
+        self.header("upgrade")
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+    }
+
+    fn accepts_relay_info(&self) -> bool {
+        self.header("accept")
+            .is_some_and(|value| value.contains(NIP11_CONTENT_TYPE))
+    }
+}
+
+pub(crate) async fn read_request_head(
+    stream: &mut TcpStream,
+) -> Result<RequestHead, RadrootsLocalRelayError> {
+    let mut bytes = Vec::with_capacity(1024);
+    let mut chunk = [0_u8; 1024];
+    loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&chunk[..read]);
+        if bytes.windows(4).any(|window| window == b"\r\n\r\n") {
+            break;
+        }
+        if bytes.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(RadrootsLocalRelayError::RequestHeadTooLarge(
+                MAX_REQUEST_HEAD_BYTES,
+            ));
+        }
+    }
+    Ok(RequestHead::parse(bytes))
+}
+
+pub(crate) async fn write_http_response(
+    stream: &mut TcpStream,
+    head: &RequestHead,
+    config: &RadrootsLocalRelayConfig,
+) -> Result<(), RadrootsLocalRelayError> {
+    let (status, content_type, body) = if head.method.eq_ignore_ascii_case("OPTIONS") {
+        ("204 No Content", None, String::new())
+    } else if head.accepts_relay_info() {
+        (
+            "200 OK",
+            Some(NIP11_CONTENT_TYPE),
+            serde_json::to_string(&config.relay_info())?,
+        )
+    } else {
+        (
+            "200 OK",
+            Some("text/plain; charset=utf-8"),
+            "Use a Nostr client to connect to this relay.\n".to_owned(),
+        )
+    };
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Headers: *\r\nAccess-Control-Allow-Methods: GET, OPTIONS\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    if let Some(content_type) = content_type {
+        response.push_str(format!("Content-Type: {content_type}\r\n").as_str());
+    }
+    response.push_str("\r\n");
+    response.push_str(body.as_str());
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
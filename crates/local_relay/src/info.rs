@@ -0,0 +1,74 @@
+#![forbid(unsafe_code)]
+
+use serde::{Deserialize, Serialize};
+
+pub const RADROOTS_LOCAL_RELAY_SUPPORTED_NIPS: &[u32] = &[1, 9, 11, 40];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsLocalRelayLimitation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_message_length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_subscriptions: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_limit: Option<u32>,
+    #[serde(default)]
+    pub auth_required: bool,
+    #[serde(default)]
+    pub payment_required: bool,
+    #[serde(default)]
+    pub restricted_writes: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsLocalRelayInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    pub supported_nips: Vec<u32>,
+    pub software: String,
+    pub version: String,
+    #[serde(default)]
+    pub limitation: RadrootsLocalRelayLimitation,
+}
+
+impl Default for RadrootsLocalRelayInfo {
+    fn default() -> Self {
+        Self {
+            name: "radroots local relay".to_owned(),
+            description: None,
+            pubkey: None,
+            contact: None,
+            supported_nips: RADROOTS_LOCAL_RELAY_SUPPORTED_NIPS.to_vec(),
+            software: env!("CARGO_PKG_NAME").to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            limitation: RadrootsLocalRelayLimitation::default(),
+        }
+    }
+}
+
+impl RadrootsLocalRelayInfo {
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_pubkey(mut self, pubkey: impl Into<String>) -> Self {
+        self.pubkey = Some(pubkey.into());
+        self
+    }
+
+    pub fn with_contact(mut self, contact: impl Into<String>) -> Self {
+        self.contact = Some(contact.into());
+        self
+    }
+}
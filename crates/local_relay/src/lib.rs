@@ -0,0 +1,22 @@
+#![forbid(unsafe_code)]
+
+mod config;
+mod error;
+mod http;
+mod info;
+mod policy;
+mod server;
+mod session;
+mod stream;
+
+pub use config::{
+    RADROOTS_LOCAL_RELAY_DEFAULT_MAX_LIMIT, RADROOTS_LOCAL_RELAY_DEFAULT_MAX_MESSAGE_LENGTH,
+    RADROOTS_LOCAL_RELAY_DEFAULT_MAX_SUBSCRIPTIONS, RadrootsLocalRelayClock,
+    RadrootsLocalRelayConfig,
+};
+pub use error::RadrootsLocalRelayError;
+pub use info::{
+    RADROOTS_LOCAL_RELAY_SUPPORTED_NIPS, RadrootsLocalRelayInfo, RadrootsLocalRelayLimitation,
+};
+pub use policy::{RadrootsLocalRelayWritePolicy, RadrootsLocalRelayWriteRejection};
+pub use server::{RadrootsLocalRelay, RadrootsLocalRelayHandle};
@@ -0,0 +1,102 @@
+#![forbid(unsafe_code)]
+
+use radroots_event_store::RadrootsEventStore;
+use radroots_local_relay::{
+    RadrootsLocalRelay, RadrootsLocalRelayConfig, RadrootsLocalRelayInfo,
+    RadrootsLocalRelayWritePolicy,
+};
+use std::env;
+use std::process::ExitCode;
+
+const DEFAULT_BIND: &str = "127.0.0.1:7777";
+
+fn usage() {
+    eprintln!("usage:");
+    eprintln!(
+        "  radroots_local_relay [--bind <addr>] [--db <path>] [--name <name>] [--allow-pubkey <hex>]..."
+    );
+}
+
+struct Args {
+    bind: String,
+    db: Option<String>,
+    name: Option<String>,
+    allowed_pubkeys: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut parsed = Args {
+        bind: DEFAULT_BIND.to_owned(),
+        db: None,
+        name: None,
+        allowed_pubkeys: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {flag}"))
+        };
+        match flag.as_str() {
+            "--bind" => parsed.bind = value()?,
+            "--db" => parsed.db = Some(value()?),
+            "--name" => parsed.name = Some(value()?),
+            "--allow-pubkey" => parsed.allowed_pubkeys.push(value()?),
+            other => return Err(format!("unknown argument {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let store = match args.db.as_deref() {
+        Some(path) => RadrootsEventStore::open_file(path).await,
+        None => RadrootsEventStore::open_memory().await,
+    }
+    .map_err(|error| error.to_string())?;
+    let mut info = RadrootsLocalRelayInfo::default();
+    if let Some(name) = args.name {
+        info = info.with_name(name);
+    }
+    let write_policy = args.allowed_pubkeys.into_iter().fold(
+        RadrootsLocalRelayWritePolicy::radroots_kinds(),
+        |policy, pubkey| policy.with_allowed_pubkey(pubkey),
+    );
+    let config = RadrootsLocalRelayConfig::default()
+        .with_info(info)
+        .with_write_policy(write_policy);
+    let relay = RadrootsLocalRelay::bind(args.bind.as_str(), store, config)
+        .await
+        .map_err(|error| error.to_string())?;
+    eprintln!(
+        "radroots_local_relay listening on {}",
+        relay.url().map_err(|error| error.to_string())?
+    );
+    relay
+        .serve(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let parsed = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprintln!("{error}");
+            usage();
+            return ExitCode::from(2);
+        }
+    };
+    match run(parsed).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#![forbid(unsafe_code)]
+
+use radroots_events::RadrootsNostrEvent;
+use radroots_events::contract::kind_contract;
+use std::collections::BTreeSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsLocalRelayWriteRejection {
+    UnsupportedKind(u32),
+    PubkeyNotAllowed(String),
+}
+
+impl RadrootsLocalRelayWriteRejection {
+    pub fn message(&self) -> String {
+        match self {
+            Self::UnsupportedKind(kind) => {
+                format!("blocked: kind {kind} is not a radroots event kind")
+            }
+            Self::PubkeyNotAllowed(_) => "restricted: pubkey is not on the allowlist".to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsLocalRelayWritePolicy {
+    allowed_pubkeys: Option<BTreeSet<String>>,
+    extra_kinds: BTreeSet<u32>,
+}
+
+impl RadrootsLocalRelayWritePolicy {
+    pub fn radroots_kinds() -> Self {
+        Self::default()
+    }
+
+    pub fn with_allowed_pubkey(mut self, pubkey: impl Into<String>) -> Self {
+        self.allowed_pubkeys
+            .get_or_insert_with(BTreeSet::new)
+            .insert(pubkey.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn with_extra_kind(mut self, kind: u32) -> Self {
+        self.extra_kinds.insert(kind);
+        self
+    }
+
+    pub fn allowed_pubkeys(&self) -> Option<&BTreeSet<String>> {
+        self.allowed_pubkeys.as_ref()
+    }
+
+    pub fn check(
+        &self,
+        event: &RadrootsNostrEvent,
+    ) -> Result<(), RadrootsLocalRelayWriteRejection> {
+        if kind_contract(event.kind).is_none() && !self.extra_kinds.contains(&event.kind) {
+            return Err(RadrootsLocalRelayWriteRejection::UnsupportedKind(
+                event.kind,
+            ));
+        }
+        if self
+            .allowed_pubkeys
+            .as_ref()
+            .is_some_and(|allowed| !allowed.contains(&event.author.to_ascii_lowercase()))
+        {
+            return Err(RadrootsLocalRelayWriteRejection::PubkeyNotAllowed(
+                event.author.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
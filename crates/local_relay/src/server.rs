@@ -0,0 +1,147 @@
+#![forbid(unsafe_code)]
+
+use crate::RadrootsLocalRelayError;
+use crate::config::RadrootsLocalRelayConfig;
+use crate::http::{RequestHead, read_request_head, write_http_response};
+use crate::session::{BroadcastEvent, run_session};
+use crate::stream::PrefixedStream;
+use radroots_event_store::RadrootsEventStore;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+
+const BROADCAST_CAPACITY: usize = 1024;
+
+pub(crate) struct RelayState {
+    pub(crate) store: RadrootsEventStore,
+    pub(crate) config: RadrootsLocalRelayConfig,
+    pub(crate) events: broadcast::Sender<Arc<BroadcastEvent>>,
+}
+
+pub struct RadrootsLocalRelay {
+    listener: TcpListener,
+    state: Arc<RelayState>,
+}
+
+impl RadrootsLocalRelay {
+    pub async fn bind(
+        addr: impl ToSocketAddrs,
+        store: RadrootsEventStore,
+        config: RadrootsLocalRelayConfig,
+    ) -> Result<Self, RadrootsLocalRelayError> {
+        let listener = TcpListener::bind(addr).await?;
+        let (events, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Ok(Self {
+            listener,
+            state: Arc::new(RelayState {
+                store,
+                config,
+                events,
+            }),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, RadrootsLocalRelayError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn url(&self) -> Result<String, RadrootsLocalRelayError> {
+        Ok(format!("ws://{}", self.local_addr()?))
+    }
+
+    pub fn event_store(&self) -> &RadrootsEventStore {
+        &self.state.store
+    }
+
+    pub async fn serve<F>(self, shutdown: F) -> Result<(), RadrootsLocalRelayError>
+    where
+        F: Future<Output = ()> + Send,
+    {
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                () = &mut shutdown => return Ok(()),
+                accepted = self.listener.accept() => {
+                    let (stream, _) = accepted?;
+                    let state = Arc::clone(&self.state);
+                    tokio::spawn(async move {
+                        let _ = handle_connection(stream, state).await;
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn spawn(self) -> Result<RadrootsLocalRelayHandle, RadrootsLocalRelayError> {
+        let url = self.url()?;
+        let store = self.state.store.clone();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(self.serve(async move {
+            let _ = shutdown_rx.await;
+        }));
+        Ok(RadrootsLocalRelayHandle {
+            url,
+            store,
+            shutdown_tx: Some(shutdown_tx),
+            task,
+        })
+    }
+}
+
+pub struct RadrootsLocalRelayHandle {
+    url: String,
+    store: RadrootsEventStore,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    task: JoinHandle<Result<(), RadrootsLocalRelayError>>,
+}
+
+impl RadrootsLocalRelayHandle {
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn event_store(&self) -> &RadrootsEventStore {
+        &self.store
+    }
+
+    pub async fn shutdown(mut self) -> Result<(), RadrootsLocalRelayError> {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+        (&mut self.task)
+            .await
+            .map_err(|error| RadrootsLocalRelayError::Task(error.to_string()))?
+    }
+}
+
+impl Drop for RadrootsLocalRelayHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    state: Arc<RelayState>,
+) -> Result<(), RadrootsLocalRelayError> {
+    let head = read_request_head(&mut stream).await?;
+    if !head.is_websocket_upgrade() {
+        return write_http_response(&mut stream, &head, &state.config).await;
+    }
+    let RequestHead { bytes, .. } = head;
+    let config = WebSocketConfig::default()
+        .max_message_size(Some(state.config.max_message_length))
+        .max_frame_size(Some(state.config.max_message_length));
+    let websocket = tokio_tungstenite::accept_async_with_config(
+        PrefixedStream::new(bytes, stream),
+        Some(config),
+    )
+    .await?;
+    run_session(websocket, state).await
+}
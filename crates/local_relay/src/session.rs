@@ -0,0 +1,253 @@
+#![forbid(unsafe_code)]
+
+use crate::RadrootsLocalRelayError;
+use crate::server::RelayState;
+use futures::{SinkExt, StreamExt};
+use nostr::{ClientMessage, Event, Filter, JsonUtil, RelayMessage, SubscriptionId};
+use radroots_event_store::{
+    RadrootsEventHeadStoreDecision, RadrootsEventIngest, RadrootsEventQuery, RadrootsStoredEvent,
+};
+use radroots_events::RadrootsNostrEvent;
+use radroots_nostr::prelude::radroots_event_from_nostr;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::Message;
+
+pub(crate) struct BroadcastEvent {
+    event: Event,
+    radroots: RadrootsNostrEvent,
+}
+
+type Subscriptions = BTreeMap<SubscriptionId, Vec<RadrootsEventQuery>>;
+
+pub(crate) async fn run_session<S>(
+    websocket: WebSocketStream<S>,
+    state: Arc<RelayState>,
+) -> Result<(), RadrootsLocalRelayError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut sink, mut stream) = websocket.split();
+    let mut live = state.events.subscribe();
+    let mut subscriptions = Subscriptions::new();
+    loop {
+        tokio::select! {
+            frame = stream.next() => {
+                let text = match frame {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => return Err(error.into()),
+                };
+                for reply in handle_client_text(&state, &mut subscriptions, text.as_str()).await {
+                    sink.send(Message::Text(reply.as_json().into())).await?;
+                }
+            }
+            broadcast = live.recv() => {
+                let broadcast = match broadcast {
+                    Ok(broadcast) => broadcast,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return Ok(()),
+                };
+                for (subscription_id, queries) in &subscriptions {
+                    if queries.iter().any(|query| query.matches(&broadcast.radroots)) {
+                        let message =
+                            RelayMessage::event(subscription_id.clone(), broadcast.event.clone());
+                        sink.send(Message::Text(message.as_json().into())).await?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn handle_client_text(
+    state: &RelayState,
+    subscriptions: &mut Subscriptions,
+    text: &str,
+) -> Vec<RelayMessage<'static>> {
+    let message = match ClientMessage::from_json(text) {
+        Ok(message) => message,
+        Err(error) => {
+            return vec![RelayMessage::notice(format!(
+                "error: could not parse client message: {error}"
+            ))];
+        }
+    };
+    match message {
+        ClientMessage::Event(event) => vec![handle_event(state, event.into_owned()).await],
+        ClientMessage::Req {
+            subscription_id,
+            filters,
+        } => {
+            handle_req(
+                state,
+                subscriptions,
+                subscription_id.into_owned(),
+                filters.into_iter().map(|filter| filter.into_owned()),
+            )
+            .await
+        }
+        ClientMessage::Close(subscription_id) => {
+            subscriptions.remove(subscription_id.as_ref());
+            Vec::new()
+        }
+        ClientMessage::Count { .. } => vec![RelayMessage::notice("unsupported: COUNT")],
+        ClientMessage::Auth(_) => vec![RelayMessage::notice("unsupported: AUTH")],
+        ClientMessage::NegOpen { .. }
+        | ClientMessage::NegMsg { .. }
+        | ClientMessage::NegClose { .. } => {
+            vec![RelayMessage::notice("unsupported: negentropy")]
+        }
+    }
+}
+
+async fn handle_event(state: &RelayState, event: Event) -> RelayMessage<'static> {
+    let event_id = event.id;
+    if let Err(error) = event.verify() {
+        return RelayMessage::ok(event_id, false, format!("invalid: {error}"));
+    }
+    let radroots = radroots_event_from_nostr(&event);
+    if let Err(rejection) = state.config.write_policy.check(&radroots) {
+        return RelayMessage::ok(event_id, false, rejection.message());
+    }
+    let ingest = RadrootsEventIngest::new(radroots.clone(), (state.config.clock)())
+        .with_raw_json(event.as_json());
+    let receipt = match state.store.ingest_event(ingest).await {
+        Ok(receipt) => receipt,
+        Err(error) => {
+            return RelayMessage::ok(event_id, false, format!("error: {error}"));
+        }
+    };
+    match receipt.head_decision {
+        RadrootsEventHeadStoreDecision::Deleted => {
+            RelayMessage::ok(event_id, false, "blocked: event has been deleted")
+        }
+        RadrootsEventHeadStoreDecision::Expired => {
+            RelayMessage::ok(event_id, false, "invalid: event has expired")
+        }
+        _ if !receipt.inserted => {
+            RelayMessage::ok(event_id, true, "duplicate: already have this event")
+        }
+        _ => {
+            let _ = state
+                .events
+                .send(Arc::new(BroadcastEvent { event, radroots }));
+            RelayMessage::ok(event_id, true, "")
+        }
+    }
+}
+
+async fn handle_req(
+    state: &RelayState,
+    subscriptions: &mut Subscriptions,
+    subscription_id: SubscriptionId,
+    filters: impl Iterator<Item = Filter>,
+) -> Vec<RelayMessage<'static>> {
+    if subscriptions.len() >= state.config.max_subscriptions
+        && !subscriptions.contains_key(&subscription_id)
+    {
+        return vec![RelayMessage::closed(
+            subscription_id,
+            "error: too many subscriptions",
+        )];
+    }
+    let mut queries = Vec::new();
+    for filter in filters {
+        if filter.search.is_some() {
+            return vec![RelayMessage::closed(
+                subscription_id,
+                "unsupported: search is not supported",
+            )];
+        }
+        queries.push(query_from_filter(&filter, state.config.max_limit));
+    }
+    let now_unix = u32::try_from((state.config.clock)().max(0) / 1_000).unwrap_or(u32::MAX);
+    let mut seen = BTreeSet::new();
+    let mut stored = Vec::new();
+    for query in &queries {
+        match state.store.query_events(query, now_unix).await {
+            Ok(events) => stored.extend(
+                events
+                    .into_iter()
+                    .filter(|event| seen.insert(event.event_id.clone())),
+            ),
+            Err(error) => {
+                return vec![RelayMessage::closed(
+                    subscription_id,
+                    format!("error: {error}"),
+                )];
+            }
+        }
+    }
+    stored.sort_by(|left, right| {
+        right
+            .created_at
+            .cmp(&left.created_at)
+            .then_with(|| left.event_id.cmp(&right.event_id))
+    });
+    let mut replies = Vec::with_capacity(stored.len() + 1);
+    for event in &stored {
+        match nostr_event_from_stored(event) {
+            Ok(event) => replies.push(RelayMessage::event(subscription_id.clone(), event)),
+            Err(error) => {
+                return vec![RelayMessage::closed(
+                    subscription_id,
+                    format!("error: {error}"),
+                )];
+            }
+        }
+    }
+    replies.push(RelayMessage::eose(subscription_id.clone()));
+    subscriptions.insert(subscription_id, queries);
+    replies
+}
+
+pub(crate) fn query_from_filter(filter: &Filter, max_limit: u32) -> RadrootsEventQuery {
+    let timestamp =
+        |timestamp: nostr::Timestamp| u32::try_from(timestamp.as_secs()).unwrap_or(u32::MAX);
+    RadrootsEventQuery {
+        ids: filter.ids.iter().flatten().map(|id| id.to_hex()).collect(),
+        authors: filter
+            .authors
+            .iter()
+            .flatten()
+            .map(|author| author.to_hex())
+            .collect(),
+        kinds: filter
+            .kinds
+            .iter()
+            .flatten()
+            .map(|kind| u32::from(kind.as_u16()))
+            .collect(),
+        tags: filter
+            .generic_tags
+            .iter()
+            .map(|(tag, values)| (tag.to_string(), values.iter().cloned().collect()))
+            .collect(),
+        since: filter.since.map(timestamp),
+        until: filter.until.map(timestamp),
+        limit: Some(
+            filter
+                .limit
+                .map(|limit| u32::try_from(limit).unwrap_or(u32::MAX).min(max_limit))
+                .unwrap_or(max_limit),
+        ),
+    }
+}
+
+fn nostr_event_from_stored(stored: &RadrootsStoredEvent) -> Result<Event, RadrootsLocalRelayError> {
+    let tags = serde_json::from_str::<serde_json::Value>(stored.tags_json.as_str())?;
+    Ok(serde_json::from_value(serde_json::json!({
+        "id": stored.event_id,
+        "pubkey": stored.pubkey,
+        "created_at": stored.created_at,
+        "kind": stored.kind,
+        "tags": tags,
+        "content": stored.content,
+        "sig": stored.sig,
+    }))?)
+}
@@ -0,0 +1,219 @@
+use futures::{SinkExt, StreamExt};
+use nostr::{Event, JsonUtil, RelayMessage};
+use radroots_event_store::RadrootsEventStore;
+use radroots_events::kinds::KIND_POST;
+use radroots_local_relay::{
+    RadrootsLocalRelay, RadrootsLocalRelayConfig, RadrootsLocalRelayHandle, RadrootsLocalRelayInfo,
+    RadrootsLocalRelayWritePolicy,
+};
+use radroots_nostr::prelude::{
+    RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp, radroots_nostr_build_event,
+};
+use radroots_test_fixtures::{
+    FIXTURE_ALICE_PUBLIC_KEY_HEX, FIXTURE_ALICE_SECRET_KEY_HEX, FIXTURE_BOB_SECRET_KEY_HEX,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+fn fixed_clock() -> i64 {
+    1_700_000_100_000
+}
+
+fn signed_event(secret_key_hex: &str, kind: u32, created_at: u64, content: &str) -> Event {
+    let secret_key = RadrootsNostrSecretKey::from_hex(secret_key_hex).expect("secret key");
+    radroots_nostr_build_event(kind, content, vec![vec!["t".to_owned(), "soil".to_owned()]])
+        .expect("builder")
+        .custom_created_at(RadrootsNostrTimestamp::from_secs(created_at))
+        .sign_with_keys(&RadrootsNostrKeys::new(secret_key))
+        .expect("signed event")
+}
+
+async fn spawn_relay(write_policy: RadrootsLocalRelayWritePolicy) -> RadrootsLocalRelayHandle {
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let config = RadrootsLocalRelayConfig::default()
+        .with_info(RadrootsLocalRelayInfo::default().with_name("farm stand"))
+        .with_write_policy(write_policy)
+        .with_clock(fixed_clock);
+    RadrootsLocalRelay::bind("127.0.0.1:0", store, config)
+        .await
+        .expect("bind")
+        .spawn()
+        .expect("spawn")
+}
+
+async fn connect(relay: &RadrootsLocalRelayHandle) -> Client {
+    tokio_tungstenite::connect_async(relay.url())
+        .await
+        .expect("connect")
+        .0
+}
+
+async fn send(client: &mut Client, frame: String) {
+    client
+        .send(Message::Text(frame.into()))
+        .await
+        .expect("send");
+}
+
+async fn receive(client: &mut Client) -> RelayMessage<'static> {
+    loop {
+        let frame = client.next().await.expect("frame").expect("message");
+        if let Message::Text(text) = frame {
+            return RelayMessage::from_json(text.as_str()).expect("relay message");
+        }
+    }
+}
+
+fn publish_frame(event: &Event) -> String {
+    format!(r#"["EVENT",{}]"#, event.as_json())
+}
+
+fn assert_ok(message: RelayMessage<'static>, event: &Event, accepted: bool, prefix: &str) {
+    let RelayMessage::Ok {
+        event_id,
+        status,
+        message,
+    } = message
+    else {
+        panic!("expected OK, got {message:?}");
+    };
+    assert_eq!(event_id, event.id);
+    assert_eq!(status, accepted);
+    assert!(message.starts_with(prefix), "unexpected message {message}");
+}
+
+#[tokio::test]
+async fn relay_stores_events_and_serves_stored_and_live_subscriptions() {
+    let relay = spawn_relay(RadrootsLocalRelayWritePolicy::radroots_kinds()).await;
+    let first = signed_event(
+        FIXTURE_ALICE_SECRET_KEY_HEX,
+        KIND_POST,
+        1_700_000_000,
+        "first",
+    );
+    let second = signed_event(
+        FIXTURE_ALICE_SECRET_KEY_HEX,
+        KIND_POST,
+        1_700_000_010,
+        "second",
+    );
+
+    let mut publisher = connect(&relay).await;
+    send(&mut publisher, publish_frame(&first)).await;
+    assert_ok(receive(&mut publisher).await, &first, true, "");
+    send(&mut publisher, publish_frame(&first)).await;
+    assert_ok(receive(&mut publisher).await, &first, true, "duplicate:");
+
+    let mut subscriber = connect(&relay).await;
+    send(
+        &mut subscriber,
+        r##"["REQ","posts",{"kinds":[1],"#t":["soil"]}]"##.to_owned(),
+    )
+    .await;
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::Event { ref event, .. } if event.id == first.id
+    ));
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::EndOfStoredEvents(ref id) if id.as_str() == "posts"
+    ));
+
+    send(&mut publisher, publish_frame(&second)).await;
+    assert_ok(receive(&mut publisher).await, &second, true, "");
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::Event { ref subscription_id, ref event }
+            if subscription_id.as_str() == "posts" && event.id == second.id
+    ));
+
+    send(&mut subscriber, r#"["CLOSE","posts"]"#.to_owned()).await;
+    send(
+        &mut subscriber,
+        r#"["REQ","recent",{"kinds":[1],"limit":1}]"#.to_owned(),
+    )
+    .await;
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::Event { ref event, .. } if event.id == second.id
+    ));
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::EndOfStoredEvents(_)
+    ));
+    send(&mut subscriber, "not json".to_owned()).await;
+    assert!(matches!(
+        receive(&mut subscriber).await,
+        RelayMessage::Notice(_)
+    ));
+
+    assert!(
+        relay
+            .event_store()
+            .get_event(second.id.to_hex().as_str())
+            .await
+            .expect("lookup")
+            .is_some()
+    );
+    relay.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test]
+async fn relay_rejects_invalid_signatures_foreign_kinds_and_unlisted_pubkeys() {
+    let relay = spawn_relay(
+        RadrootsLocalRelayWritePolicy::radroots_kinds()
+            .with_allowed_pubkey(FIXTURE_ALICE_PUBLIC_KEY_HEX),
+    )
+    .await;
+    let mut client = connect(&relay).await;
+
+    let trusted = signed_event(FIXTURE_ALICE_SECRET_KEY_HEX, KIND_POST, 1_700_000_000, "ok");
+    let mut tampered = serde_json::to_value(&trusted).expect("event json");
+    tampered["content"] = serde_json::Value::String("tampered".to_owned());
+    send(&mut client, format!(r#"["EVENT",{tampered}]"#)).await;
+    assert_ok(receive(&mut client).await, &trusted, false, "invalid:");
+
+    let foreign = signed_event(FIXTURE_ALICE_SECRET_KEY_HEX, 999, 1_700_000_000, "foreign");
+    send(&mut client, publish_frame(&foreign)).await;
+    assert_ok(receive(&mut client).await, &foreign, false, "blocked:");
+
+    let unlisted = signed_event(FIXTURE_BOB_SECRET_KEY_HEX, KIND_POST, 1_700_000_000, "bob");
+    send(&mut client, publish_frame(&unlisted)).await;
+    assert_ok(receive(&mut client).await, &unlisted, false, "restricted:");
+
+    send(&mut client, publish_frame(&trusted)).await;
+    assert_ok(receive(&mut client).await, &trusted, true, "");
+    relay.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test]
+async fn relay_serves_nip11_information_document() {
+    let relay = spawn_relay(RadrootsLocalRelayWritePolicy::radroots_kinds()).await;
+    let addr = relay.url().trim_start_matches("ws://").to_owned();
+    let mut stream = TcpStream::connect(addr.as_str()).await.expect("connect");
+    stream
+        .write_all(
+            format!("GET / HTTP/1.1\r\nHost: {addr}\r\nAccept: application/nostr+json\r\n\r\n")
+                .as_bytes(),
+        )
+        .await
+        .expect("request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("response");
+    let (head, body) = response.split_once("\r\n\r\n").expect("http response");
+    assert!(head.starts_with("HTTP/1.1 200 OK"));
+    assert!(head.contains("Content-Type: application/nostr+json"));
+    assert!(head.contains("Access-Control-Allow-Origin: *"));
+    let info = serde_json::from_str::<RadrootsLocalRelayInfo>(body).expect("nip11");
+    assert_eq!(info.name, "farm stand");
+    assert!(info.supported_nips.contains(&11));
+    assert!(info.limitation.restricted_writes);
+    relay.shutdown().await.expect("shutdown");
+}
@@ -109,8 +109,8 @@ pub mod prelude {
     #[cfg(feature = "client")]
     pub use crate::types::{
         RadrootsNostrMonitor, RadrootsNostrMonitorNotification, RadrootsNostrOutput,
-        RadrootsNostrRelay, RadrootsNostrRelayPoolNotification, RadrootsNostrRelayStatus,
-        RadrootsNostrSubscribeAutoCloseOptions,
+        RadrootsNostrRelay, RadrootsNostrRelayNotification, RadrootsNostrRelayPoolNotification,
+        RadrootsNostrRelayStatus, RadrootsNostrSubscribeAutoCloseOptions,
     };
     pub use crate::util::radroots_nostr_npub_string;
 
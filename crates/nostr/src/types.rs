@@ -35,6 +35,9 @@ pub type RadrootsNostrOutput<T> = nostr_sdk::prelude::Output<T>;
 #[cfg(feature = "client")]
 pub type RadrootsNostrRelay = nostr_sdk::Relay;
 
+#[cfg(feature = "client")]
+pub type RadrootsNostrRelayNotification = nostr_sdk::pool::RelayNotification;
+
 #[cfg(feature = "client")]
 pub type RadrootsNostrRelayPoolNotification = nostr_sdk::RelayPoolNotification;
 
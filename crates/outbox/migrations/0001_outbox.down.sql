@@ -1,3 +1,6 @@
+DROP TABLE outbox_dead_letter_audit;
+DROP TABLE outbox_event_relay_attempt;
 DROP TABLE outbox_event_relay_status;
 DROP TABLE outbox_event;
+DROP TABLE outbox_operation_prerequisite;
 DROP TABLE outbox_operation;
@@ -5,6 +5,7 @@ CREATE TABLE outbox_operation (
   idempotency_key TEXT,
   idempotency_digest TEXT NOT NULL,
   status TEXT NOT NULL CHECK (status IN ('queued', 'complete', 'failed_terminal', 'cancelled')),
+  publish_at_ms INTEGER,
   created_at_ms INTEGER NOT NULL,
   updated_at_ms INTEGER NOT NULL
 );
@@ -16,6 +17,16 @@ WHERE idempotency_key IS NOT NULL;
 CREATE INDEX outbox_operation_status_idx
 ON outbox_operation(status, created_at_ms, operation_id);
 
+CREATE TABLE outbox_operation_prerequisite (
+  operation_id INTEGER NOT NULL REFERENCES outbox_operation(operation_id) ON DELETE CASCADE,
+  prerequisite_operation_id INTEGER NOT NULL REFERENCES outbox_operation(operation_id) ON DELETE CASCADE,
+  PRIMARY KEY (operation_id, prerequisite_operation_id),
+  CHECK (operation_id != prerequisite_operation_id)
+);
+
+CREATE INDEX outbox_operation_prerequisite_reverse_idx
+ON outbox_operation_prerequisite(prerequisite_operation_id, operation_id);
+
 CREATE TABLE outbox_event (
   outbox_event_id INTEGER PRIMARY KEY AUTOINCREMENT,
   operation_id INTEGER NOT NULL REFERENCES outbox_operation(operation_id) ON DELETE CASCADE,
@@ -58,3 +69,30 @@ CREATE TABLE outbox_event_relay_status (
 
 CREATE INDEX outbox_event_relay_status_idx
 ON outbox_event_relay_status(status, relay_url, outbox_event_id);
+
+CREATE TABLE outbox_event_relay_attempt (
+  attempt_id INTEGER PRIMARY KEY AUTOINCREMENT,
+  outbox_event_id INTEGER NOT NULL REFERENCES outbox_event(outbox_event_id) ON DELETE CASCADE,
+  relay_url TEXT NOT NULL,
+  attempt_number INTEGER NOT NULL,
+  status TEXT NOT NULL CHECK (status IN ('accepted', 'failed_retryable', 'failed_terminal')),
+  error TEXT,
+  attempted_at_ms INTEGER NOT NULL
+);
+
+CREATE INDEX outbox_event_relay_attempt_event_idx
+ON outbox_event_relay_attempt(outbox_event_id, relay_url, attempt_id);
+
+CREATE TABLE outbox_dead_letter_audit (
+  audit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+  outbox_event_id INTEGER NOT NULL,
+  operation_id INTEGER NOT NULL,
+  event_id TEXT NOT NULL,
+  operation_kind TEXT NOT NULL,
+  expected_pubkey TEXT NOT NULL,
+  last_error TEXT,
+  relays_json TEXT NOT NULL,
+  actor TEXT NOT NULL,
+  reason TEXT NOT NULL,
+  purged_at_ms INTEGER NOT NULL
+);
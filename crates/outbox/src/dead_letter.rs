@@ -0,0 +1,624 @@
+#![forbid(unsafe_code)]
+
+use crate::RadrootsOutboxError;
+use crate::model::{
+    RadrootsOutboxDeadLetter, RadrootsOutboxDeadLetterAudit, RadrootsOutboxEventRecord,
+    RadrootsOutboxEventState, RadrootsOutboxOperationStatus, RadrootsOutboxRelayAttemptRecord,
+    RadrootsOutboxRelayStatus,
+};
+use crate::store::{
+    RadrootsOutbox, canonical_relays, event_by_id_tx, fail_operations_with_failed_prerequisites_tx,
+    operation_from_row, prerequisite_failure_error, relay_status_from_row,
+};
+use sqlx::{Row, Sqlite, Transaction};
+
+impl RadrootsOutbox {
+    pub async fn list_dead_letters(
+        &self,
+        after_outbox_event_id: Option<i64>,
+        limit: u32,
+    ) -> Result<Vec<RadrootsOutboxDeadLetter>, RadrootsOutboxError> {
+        let rows = sqlx::query(
+            "SELECT outbox_event_id FROM outbox_event WHERE state = ? AND outbox_event_id > ? ORDER BY outbox_event_id LIMIT ?",
+        )
+        .bind(RadrootsOutboxEventState::FailedTerminal.as_str())
+        .bind(after_outbox_event_id.unwrap_or(0))
+        .bind(i64::from(limit))
+        .fetch_all(self.pool())
+        .await?;
+        let mut tx = self.pool().begin().await?;
+        let mut dead_letters = Vec::with_capacity(rows.len());
+        for row in rows {
+            dead_letters.push(dead_letter_tx(&mut tx, row.try_get("outbox_event_id")?).await?);
+        }
+        tx.commit().await?;
+        Ok(dead_letters)
+    }
+
+    pub async fn dead_letter(
+        &self,
+        outbox_event_id: i64,
+    ) -> Result<RadrootsOutboxDeadLetter, RadrootsOutboxError> {
+        let mut tx = self.pool().begin().await?;
+        ensure_dead_letter_tx(&mut tx, outbox_event_id).await?;
+        let dead_letter = dead_letter_tx(&mut tx, outbox_event_id).await?;
+        tx.commit().await?;
+        Ok(dead_letter)
+    }
+
+    pub async fn replace_dead_letter_relays(
+        &self,
+        outbox_event_id: i64,
+        target_relays: Vec<String>,
+        now_ms: i64,
+    ) -> Result<RadrootsOutboxDeadLetter, RadrootsOutboxError> {
+        let target_relays = canonical_relays(target_relays);
+        if target_relays.is_empty() {
+            return Err(RadrootsOutboxError::EmptyTargetRelays(outbox_event_id));
+        }
+        let mut tx = self.pool().begin().await?;
+        ensure_dead_letter_tx(&mut tx, outbox_event_id).await?;
+        let existing = sqlx::query(
+            "SELECT relay_url FROM outbox_event_relay_status WHERE outbox_event_id = ?",
+        )
+        .bind(outbox_event_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|row| row.try_get::<String, _>("relay_url"))
+        .collect::<Result<Vec<_>, _>>()?;
+        for relay_url in existing
+            .iter()
+            .filter(|relay| !target_relays.contains(relay))
+        {
+            sqlx::query(
+                "DELETE FROM outbox_event_relay_status WHERE outbox_event_id = ? AND relay_url = ?",
+            )
+            .bind(outbox_event_id)
+            .bind(relay_url.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        for relay_url in target_relays
+            .iter()
+            .filter(|relay| !existing.contains(relay))
+        {
+            sqlx::query(
+                "INSERT INTO outbox_event_relay_status(outbox_event_id, relay_url, status, attempt_count) VALUES (?, ?, ?, 0)",
+            )
+            .bind(outbox_event_id)
+            .bind(relay_url.as_str())
+            .bind(RadrootsOutboxRelayStatus::Pending.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE outbox_event SET accepted_quorum = MIN(accepted_quorum, ?), updated_at_ms = ? WHERE outbox_event_id = ?",
+        )
+        .bind(target_relays.len() as i64)
+        .bind(now_ms)
+        .bind(outbox_event_id)
+        .execute(&mut *tx)
+        .await?;
+        let dead_letter = dead_letter_tx(&mut tx, outbox_event_id).await?;
+        tx.commit().await?;
+        Ok(dead_letter)
+    }
+
+    pub async fn requeue_dead_letter(
+        &self,
+        outbox_event_id: i64,
+        now_ms: i64,
+    ) -> Result<RadrootsOutboxEventRecord, RadrootsOutboxError> {
+        let mut tx = self.pool().begin().await?;
+        ensure_dead_letter_tx(&mut tx, outbox_event_id).await?;
+        sqlx::query(
+            "UPDATE outbox_event_relay_status SET status = ?, attempt_count = 0, last_error = NULL WHERE outbox_event_id = ? AND status != ?",
+        )
+        .bind(RadrootsOutboxRelayStatus::Pending.as_str())
+        .bind(outbox_event_id)
+        .bind(RadrootsOutboxRelayStatus::Accepted.as_str())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE outbox_event SET state = CASE WHEN signed_event_json IS NULL THEN ? ELSE ? END, attempt_count = 0, claim_token = NULL, claim_owner = NULL, claim_expires_at_ms = NULL, next_attempt_after_ms = ?, last_error = NULL, updated_at_ms = ? WHERE outbox_event_id = ?",
+        )
+        .bind(RadrootsOutboxEventState::DraftQueued.as_str())
+        .bind(RadrootsOutboxEventState::Signed.as_str())
+        .bind(now_ms)
+        .bind(now_ms)
+        .bind(outbox_event_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE outbox_operation SET status = ?, updated_at_ms = ? WHERE operation_id = (SELECT operation_id FROM outbox_event WHERE outbox_event_id = ?)",
+        )
+        .bind(RadrootsOutboxOperationStatus::Queued.as_str())
+        .bind(now_ms)
+        .bind(outbox_event_id)
+        .execute(&mut *tx)
+        .await?;
+        let operation_id: i64 =
+            sqlx::query("SELECT operation_id FROM outbox_event WHERE outbox_event_id = ?")
+                .bind(outbox_event_id)
+                .fetch_one(&mut *tx)
+                .await?
+                .try_get("operation_id")?;
+        revive_dependents_tx(&mut tx, operation_id, now_ms).await?;
+        fail_operations_with_failed_prerequisites_tx(&mut tx, now_ms).await?;
+        let event = event_by_id_tx(&mut tx, outbox_event_id).await?;
+        tx.commit().await?;
+        Ok(event)
+    }
+
+    pub async fn purge_dead_letter(
+        &self,
+        outbox_event_id: i64,
+        actor: impl AsRef<str>,
+        reason: impl AsRef<str>,
+        now_ms: i64,
+    ) -> Result<RadrootsOutboxDeadLetterAudit, RadrootsOutboxError> {
+        let mut tx = self.pool().begin().await?;
+        ensure_dead_letter_tx(&mut tx, outbox_event_id).await?;
+        let dead_letter = dead_letter_tx(&mut tx, outbox_event_id).await?;
+        let relays_json = serde_json::to_string(
+            &dead_letter
+                .relays
+                .iter()
+                .map(|relay| {
+                    serde_json::json!({
+                        "relay_url": relay.relay_url,
+                        "status": relay.status.as_str(),
+                        "attempt_count": relay.attempt_count,
+                        "last_error": relay.last_error,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )?;
+        let audit = sqlx::query(
+            "INSERT INTO outbox_dead_letter_audit(outbox_event_id, operation_id, event_id, operation_kind, expected_pubkey, last_error, relays_json, actor, reason, purged_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(outbox_event_id)
+        .bind(dead_letter.operation.operation_id)
+        .bind(dead_letter.event.event_id.as_str())
+        .bind(dead_letter.operation.operation_kind.as_str())
+        .bind(dead_letter.operation.expected_pubkey.as_str())
+        .bind(dead_letter.event.last_error.as_deref())
+        .bind(relays_json.as_str())
+        .bind(actor.as_ref())
+        .bind(reason.as_ref())
+        .bind(now_ms)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM outbox_event WHERE outbox_event_id = ?")
+            .bind(outbox_event_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "DELETE FROM outbox_operation WHERE operation_id = ? AND NOT EXISTS (SELECT 1 FROM outbox_event WHERE operation_id = ?)",
+        )
+        .bind(dead_letter.operation.operation_id)
+        .bind(dead_letter.operation.operation_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(RadrootsOutboxDeadLetterAudit {
+            audit_id: audit.last_insert_rowid(),
+            outbox_event_id,
+            operation_id: dead_letter.operation.operation_id,
+            event_id: dead_letter.event.event_id,
+            operation_kind: dead_letter.operation.operation_kind,
+            expected_pubkey: dead_letter.operation.expected_pubkey,
+            last_error: dead_letter.event.last_error,
+            relays_json,
+            actor: actor.as_ref().to_owned(),
+            reason: reason.as_ref().to_owned(),
+            purged_at_ms: now_ms,
+        })
+    }
+
+    pub async fn dead_letter_audits(
+        &self,
+    ) -> Result<Vec<RadrootsOutboxDeadLetterAudit>, RadrootsOutboxError> {
+        sqlx::query(
+            "SELECT audit_id, outbox_event_id, operation_id, event_id, operation_kind, expected_pubkey, last_error, relays_json, actor, reason, purged_at_ms FROM outbox_dead_letter_audit ORDER BY audit_id",
+        )
+        .fetch_all(self.pool())
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(RadrootsOutboxDeadLetterAudit {
+                audit_id: row.try_get("audit_id")?,
+                outbox_event_id: row.try_get("outbox_event_id")?,
+                operation_id: row.try_get("operation_id")?,
+                event_id: row.try_get("event_id")?,
+                operation_kind: row.try_get("operation_kind")?,
+                expected_pubkey: row.try_get("expected_pubkey")?,
+                last_error: row.try_get("last_error")?,
+                relays_json: row.try_get("relays_json")?,
+                actor: row.try_get("actor")?,
+                reason: row.try_get("reason")?,
+                purged_at_ms: row.try_get("purged_at_ms")?,
+            })
+        })
+        .collect()
+    }
+}
+
+async fn revive_dependents_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    operation_id: i64,
+    now_ms: i64,
+) -> Result<(), RadrootsOutboxError> {
+    let mut pending = vec![operation_id];
+    while let Some(prerequisite_operation_id) = pending.pop() {
+        let error = prerequisite_failure_error(
+            prerequisite_operation_id,
+            RadrootsOutboxOperationStatus::FailedTerminal.as_str(),
+        );
+        let rows = sqlx::query(
+            "SELECT DISTINCT e.operation_id FROM outbox_event e JOIN outbox_operation o ON o.operation_id = e.operation_id WHERE o.status = ? AND e.state = ? AND e.last_error = ? ORDER BY e.operation_id",
+        )
+        .bind(RadrootsOutboxOperationStatus::FailedTerminal.as_str())
+        .bind(RadrootsOutboxEventState::FailedTerminal.as_str())
+        .bind(error.as_str())
+        .fetch_all(&mut **tx)
+        .await?;
+        for row in rows {
+            let dependent_operation_id: i64 = row.try_get("operation_id")?;
+            sqlx::query(
+                "UPDATE outbox_event SET state = CASE WHEN signed_event_json IS NULL THEN ? ELSE ? END, claim_token = NULL, claim_owner = NULL, claim_expires_at_ms = NULL, next_attempt_after_ms = ?, last_error = NULL, updated_at_ms = ? WHERE operation_id = ? AND state = ? AND last_error = ?",
+            )
+            .bind(RadrootsOutboxEventState::DraftQueued.as_str())
+            .bind(RadrootsOutboxEventState::Signed.as_str())
+            .bind(now_ms)
+            .bind(now_ms)
+            .bind(dependent_operation_id)
+            .bind(RadrootsOutboxEventState::FailedTerminal.as_str())
+            .bind(error.as_str())
+            .execute(&mut **tx)
+            .await?;
+            sqlx::query(
+                "UPDATE outbox_operation SET status = ?, updated_at_ms = ? WHERE operation_id = ?",
+            )
+            .bind(RadrootsOutboxOperationStatus::Queued.as_str())
+            .bind(now_ms)
+            .bind(dependent_operation_id)
+            .execute(&mut **tx)
+            .await?;
+            pending.push(dependent_operation_id);
+        }
+    }
+    Ok(())
+}
+
+async fn ensure_dead_letter_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    outbox_event_id: i64,
+) -> Result<(), RadrootsOutboxError> {
+    let state = sqlx::query("SELECT state FROM outbox_event WHERE outbox_event_id = ?")
+        .bind(outbox_event_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or(RadrootsOutboxError::EventNotFound(outbox_event_id))?
+        .try_get::<String, _>("state")?;
+    let state = RadrootsOutboxEventState::parse(state.as_str())?;
+    if state != RadrootsOutboxEventState::FailedTerminal {
+        return Err(RadrootsOutboxError::NotDeadLetter {
+            outbox_event_id,
+            state: state.as_str(),
+        });
+    }
+    Ok(())
+}
+
+async fn dead_letter_tx(
+    tx: &mut Transaction<'_, Sqlite>,
+    outbox_event_id: i64,
+) -> Result<RadrootsOutboxDeadLetter, RadrootsOutboxError> {
+    let event = event_by_id_tx(tx, outbox_event_id).await?;
+    let operation = sqlx::query(
+        "SELECT operation_id, operation_kind, expected_pubkey, idempotency_key, idempotency_digest, status, publish_at_ms, created_at_ms, updated_at_ms FROM outbox_operation WHERE operation_id = ?",
+    )
+    .bind(event.operation_id)
+    .fetch_one(&mut **tx)
+    .await?;
+    let relays = sqlx::query(
+        "SELECT outbox_event_id, relay_url, status, attempt_count, last_attempt_at_ms, acknowledged_at_ms, last_error FROM outbox_event_relay_status WHERE outbox_event_id = ? ORDER BY relay_url",
+    )
+    .bind(outbox_event_id)
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(relay_status_from_row)
+    .collect::<Result<Vec<_>, _>>()?;
+    let relay_attempts = sqlx::query(
+        "SELECT attempt_id, outbox_event_id, relay_url, attempt_number, status, error, attempted_at_ms FROM outbox_event_relay_attempt WHERE outbox_event_id = ? ORDER BY relay_url, attempt_id",
+    )
+    .bind(outbox_event_id)
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(RadrootsOutboxRelayAttemptRecord {
+            attempt_id: row.try_get("attempt_id")?,
+            outbox_event_id: row.try_get("outbox_event_id")?,
+            relay_url: row.try_get("relay_url")?,
+            attempt_number: row.try_get("attempt_number")?,
+            status: RadrootsOutboxRelayStatus::parse(row.try_get::<String, _>("status")?.as_str())?,
+            error: row.try_get("error")?,
+            attempted_at_ms: row.try_get("attempted_at_ms")?,
+        })
+    })
+    .collect::<Result<Vec<_>, RadrootsOutboxError>>()?;
+    Ok(RadrootsOutboxDeadLetter {
+        operation: operation_from_row(operation)?,
+        event,
+        relays,
+        relay_attempts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RadrootsOutboxOperationInput;
+    use radroots_events::draft::RadrootsFrozenEventDraft;
+    use radroots_events::kinds::KIND_POST;
+    use radroots_nostr::prelude::{RadrootsNostrKeys, RadrootsNostrSecretKey};
+
+    const FIXTURE_ALICE_SECRET_KEY_HEX: &str =
+        "10c5304d6c9ae3a1a16f7860f1cc8f5e3a76225a2663b3a989a0d775919b7df5";
+    const FIXTURE_ALICE_PUBLIC_KEY_HEX: &str =
+        "585591529da0bab31b3b1b1f986611cf5f435dca84f978c89ee8a40cca7103df";
+    const RELAY_PRIMARY_WSS: &str = "wss://relay.example.com";
+    const RELAY_SECONDARY_WSS: &str = "wss://relay-2.example.com";
+    const RELAY_TERTIARY_WSS: &str = "wss://relay-3.example.com";
+
+    fn operation_input() -> RadrootsOutboxOperationInput {
+        let draft = RadrootsFrozenEventDraft::new(
+            "radroots.social.post.v1",
+            KIND_POST,
+            1_700_000_000,
+            Vec::new(),
+            "market day",
+            FIXTURE_ALICE_PUBLIC_KEY_HEX,
+        )
+        .expect("post draft");
+        RadrootsOutboxOperationInput::new(
+            "publish_post",
+            draft,
+            vec![RELAY_PRIMARY_WSS.to_owned(), RELAY_SECONDARY_WSS.to_owned()],
+            1_000,
+        )
+    }
+
+    fn fixture_keys() -> RadrootsNostrKeys {
+        RadrootsNostrKeys::new(
+            RadrootsNostrSecretKey::from_hex(FIXTURE_ALICE_SECRET_KEY_HEX).expect("secret key"),
+        )
+    }
+
+    #[tokio::test]
+    async fn dead_letters_expose_history_and_support_requeue_relay_edits_and_purge() {
+        let outbox = RadrootsOutbox::open_memory().await.expect("open");
+        let receipt = outbox
+            .enqueue_operation(operation_input())
+            .await
+            .expect("enqueue");
+        let outbox_event_id = receipt.outbox_event_id;
+
+        let claimed = outbox
+            .claim_next_ready_event("worker-a", "claim-1", 2_000, 1_000)
+            .await
+            .expect("claim")
+            .expect("claimed event");
+        outbox
+            .sign_claimed_event(&claimed, &fixture_keys(), 1_010)
+            .await
+            .expect("sign");
+        outbox
+            .mark_relay_failed_retryable(
+                outbox_event_id,
+                "claim-1",
+                RELAY_PRIMARY_WSS,
+                "timeout",
+                1_100,
+            )
+            .await
+            .expect("primary retryable");
+        outbox
+            .mark_relay_failed_terminal(
+                outbox_event_id,
+                "claim-1",
+                RELAY_SECONDARY_WSS,
+                "blocked: spam",
+                1_100,
+            )
+            .await
+            .expect("secondary terminal");
+        outbox
+            .complete_publish_attempt(
+                outbox_event_id,
+                "claim-1",
+                "retry",
+                "exhausted",
+                1_500,
+                1_200,
+            )
+            .await
+            .expect("first attempt");
+        outbox
+            .claim_next_ready_event("worker-a", "claim-2", 3_000, 1_500)
+            .await
+            .expect("claim")
+            .expect("reclaimed event");
+        outbox
+            .mark_relay_failed_terminal(
+                outbox_event_id,
+                "claim-2",
+                RELAY_PRIMARY_WSS,
+                "rate-limited: slow down",
+                1_600,
+            )
+            .await
+            .expect("primary terminal");
+        assert_eq!(
+            outbox
+                .complete_publish_attempt(
+                    outbox_event_id,
+                    "claim-2",
+                    "retry",
+                    "exhausted",
+                    2_000,
+                    1_700
+                )
+                .await
+                .expect("second attempt"),
+            RadrootsOutboxEventState::FailedTerminal
+        );
+
+        let dead_letters = outbox.list_dead_letters(None, 10).await.expect("list");
+        assert_eq!(dead_letters.len(), 1);
+        let dead_letter = &dead_letters[0];
+        assert_eq!(dead_letter.event.last_error.as_deref(), Some("exhausted"));
+        assert_eq!(
+            dead_letter.operation.status,
+            RadrootsOutboxOperationStatus::FailedTerminal
+        );
+        let primary_history = dead_letter
+            .relay_attempts
+            .iter()
+            .filter(|attempt| attempt.relay_url == RELAY_PRIMARY_WSS)
+            .map(|attempt| {
+                (
+                    attempt.attempt_number,
+                    attempt.status,
+                    attempt.error.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            primary_history,
+            vec![
+                (
+                    1,
+                    RadrootsOutboxRelayStatus::FailedRetryable,
+                    Some("timeout")
+                ),
+                (
+                    2,
+                    RadrootsOutboxRelayStatus::FailedTerminal,
+                    Some("rate-limited: slow down")
+                ),
+            ]
+        );
+        assert!(
+            outbox
+                .list_dead_letters(Some(outbox_event_id), 10)
+                .await
+                .expect("next page")
+                .is_empty()
+        );
+
+        let narrowed = outbox
+            .replace_dead_letter_relays(
+                outbox_event_id,
+                vec![RELAY_SECONDARY_WSS.to_owned()],
+                2_050,
+            )
+            .await
+            .expect("narrow relays");
+        assert_eq!(narrowed.event.accepted_quorum, 1);
+        let edited = outbox
+            .replace_dead_letter_relays(
+                outbox_event_id,
+                vec![
+                    RELAY_SECONDARY_WSS.to_owned(),
+                    RELAY_TERTIARY_WSS.to_owned(),
+                ],
+                2_100,
+            )
+            .await
+            .expect("replace relays");
+        assert_eq!(edited.event.accepted_quorum, 1);
+        assert_eq!(
+            edited
+                .relays
+                .iter()
+                .map(|relay| (relay.relay_url.as_str(), relay.status))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    RELAY_SECONDARY_WSS,
+                    RadrootsOutboxRelayStatus::FailedTerminal
+                ),
+                (RELAY_TERTIARY_WSS, RadrootsOutboxRelayStatus::Pending),
+            ]
+        );
+        assert!(matches!(
+            outbox
+                .replace_dead_letter_relays(outbox_event_id, Vec::new(), 2_100)
+                .await,
+            Err(RadrootsOutboxError::EmptyTargetRelays(_))
+        ));
+
+        let requeued = outbox
+            .requeue_dead_letter(outbox_event_id, 2_200)
+            .await
+            .expect("requeue");
+        assert_eq!(requeued.state, RadrootsOutboxEventState::Signed);
+        assert_eq!(requeued.attempt_count, 0);
+        assert!(
+            outbox
+                .relay_statuses(outbox_event_id)
+                .await
+                .expect("relay statuses")
+                .iter()
+                .all(|relay| relay.status == RadrootsOutboxRelayStatus::Pending
+                    && relay.attempt_count == 0)
+        );
+        assert!(matches!(
+            outbox.dead_letter(outbox_event_id).await,
+            Err(RadrootsOutboxError::NotDeadLetter {
+                state: "signed",
+                ..
+            })
+        ));
+
+        outbox
+            .claim_next_ready_event("worker-b", "claim-3", 4_000, 2_300)
+            .await
+            .expect("claim")
+            .expect("requeued claim");
+        outbox
+            .mark_publish_failed_terminal(outbox_event_id, "claim-3", "operator gave up", 2_400)
+            .await
+            .expect("fail again");
+        let audit = outbox
+            .purge_dead_letter(outbox_event_id, "ops@farm", "listing withdrawn", 2_500)
+            .await
+            .expect("purge");
+        assert_eq!(audit.operation_id, receipt.operation_id);
+        assert_eq!(audit.last_error.as_deref(), Some("operator gave up"));
+        assert!(audit.relays_json.contains(RELAY_TERTIARY_WSS));
+        assert_eq!(
+            outbox.dead_letter_audits().await.expect("audits"),
+            vec![audit]
+        );
+        assert!(
+            outbox
+                .get_event(outbox_event_id)
+                .await
+                .expect("event")
+                .is_none()
+        );
+        assert!(
+            outbox
+                .get_operation(receipt.operation_id)
+                .await
+                .expect("operation")
+                .is_none()
+        );
+    }
+}
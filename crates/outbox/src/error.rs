@@ -28,9 +28,41 @@ pub enum RadrootsOutboxError {
         new_digest: String,
     },
 
+    #[error("Outbox operation not found: {0}")]
+    OperationNotFound(i64),
+
+    #[error("Outbox operation {operation_id} is {status}, not queued")]
+    OperationNotQueued {
+        operation_id: i64,
+        status: &'static str,
+    },
+
+    #[error("Outbox operation {operation_id} is claimed by a worker")]
+    OperationClaimed { operation_id: i64 },
+
+    #[error("Outbox operation {operation_id} has already been accepted by a relay")]
+    OperationPartiallyPublished { operation_id: i64 },
+
+    #[error(
+        "Outbox operation {operation_id} cannot depend on {prerequisite_operation_id}: dependency cycle"
+    )]
+    PrerequisiteCycle {
+        operation_id: i64,
+        prerequisite_operation_id: i64,
+    },
+
     #[error("Outbox event not found: {0}")]
     EventNotFound(i64),
 
+    #[error("Outbox event {outbox_event_id} is {state}, not a dead letter")]
+    NotDeadLetter {
+        outbox_event_id: i64,
+        state: &'static str,
+    },
+
+    #[error("Outbox event {0} must keep at least one target relay")]
+    EmptyTargetRelays(i64),
+
     #[error("Claim token mismatch for outbox event {outbox_event_id}")]
     ClaimTokenMismatch { outbox_event_id: i64 },
 
@@ -1,5 +1,6 @@
 #![forbid(unsafe_code)]
 
+mod dead_letter;
 mod error;
 mod migrations;
 mod model;
@@ -8,9 +9,10 @@ mod store;
 pub use error::RadrootsOutboxError;
 pub use migrations::{OUTBOX_MIGRATION_DOWN, OUTBOX_MIGRATION_UP};
 pub use model::{
-    RadrootsOutboxClaimedEvent, RadrootsOutboxEnqueueReceipt, RadrootsOutboxEnqueueStatus,
-    RadrootsOutboxEventRecord, RadrootsOutboxEventState, RadrootsOutboxEventStoreIngestReceipt,
-    RadrootsOutboxOperationInput, RadrootsOutboxOperationRecord, RadrootsOutboxOperationStatus,
-    RadrootsOutboxRelayStatus, RadrootsOutboxRelayStatusRecord,
+    RadrootsOutboxClaimedEvent, RadrootsOutboxDeadLetter, RadrootsOutboxDeadLetterAudit,
+    RadrootsOutboxEnqueueReceipt, RadrootsOutboxEnqueueStatus, RadrootsOutboxEventRecord,
+    RadrootsOutboxEventState, RadrootsOutboxEventStoreIngestReceipt, RadrootsOutboxOperationInput,
+    RadrootsOutboxOperationRecord, RadrootsOutboxOperationStatus, RadrootsOutboxRelayAttemptRecord,
+    RadrootsOutboxRelayPublishBucket, RadrootsOutboxRelayStatus, RadrootsOutboxRelayStatusRecord,
 };
 pub use store::RadrootsOutbox;
@@ -127,6 +127,8 @@ pub struct RadrootsOutboxOperationInput {
     pub draft: RadrootsFrozenEventDraft,
     pub target_relays: Vec<String>,
     pub idempotency_key: Option<String>,
+    pub publish_at_ms: Option<i64>,
+    pub prerequisite_operation_ids: Vec<i64>,
     pub created_at_ms: i64,
 }
 
@@ -142,6 +144,8 @@ impl RadrootsOutboxOperationInput {
             draft,
             target_relays,
             idempotency_key: None,
+            publish_at_ms: None,
+            prerequisite_operation_ids: Vec::new(),
             created_at_ms,
         }
     }
@@ -150,6 +154,16 @@ impl RadrootsOutboxOperationInput {
         self.idempotency_key = Some(idempotency_key.into());
         self
     }
+
+    pub fn with_publish_at_ms(mut self, publish_at_ms: i64) -> Self {
+        self.publish_at_ms = Some(publish_at_ms);
+        self
+    }
+
+    pub fn with_prerequisite(mut self, operation_id: i64) -> Self {
+        self.prerequisite_operation_ids.push(operation_id);
+        self
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -175,6 +189,7 @@ pub struct RadrootsOutboxOperationRecord {
     pub idempotency_key: Option<String>,
     pub idempotency_digest: String,
     pub status: RadrootsOutboxOperationStatus,
+    pub publish_at_ms: Option<i64>,
     pub created_at_ms: i64,
     pub updated_at_ms: i64,
 }
@@ -214,6 +229,52 @@ pub struct RadrootsOutboxRelayStatusRecord {
     pub last_error: Option<String>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsOutboxRelayPublishBucket {
+    pub relay_url: String,
+    pub bucket_start_ms: i64,
+    pub attempts: i64,
+    pub accepted: i64,
+    pub failed_retryable: i64,
+    pub failed_terminal: i64,
+    pub last_attempt_at_ms: i64,
+    pub last_accepted_at_ms: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsOutboxRelayAttemptRecord {
+    pub attempt_id: i64,
+    pub outbox_event_id: i64,
+    pub relay_url: String,
+    pub attempt_number: i64,
+    pub status: RadrootsOutboxRelayStatus,
+    pub error: Option<String>,
+    pub attempted_at_ms: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsOutboxDeadLetter {
+    pub operation: RadrootsOutboxOperationRecord,
+    pub event: RadrootsOutboxEventRecord,
+    pub relays: Vec<RadrootsOutboxRelayStatusRecord>,
+    pub relay_attempts: Vec<RadrootsOutboxRelayAttemptRecord>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsOutboxDeadLetterAudit {
+    pub audit_id: i64,
+    pub outbox_event_id: i64,
+    pub operation_id: i64,
+    pub event_id: String,
+    pub operation_kind: String,
+    pub expected_pubkey: String,
+    pub last_error: Option<String>,
+    pub relays_json: String,
+    pub actor: String,
+    pub reason: String,
+    pub purged_at_ms: i64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RadrootsOutboxClaimedEvent {
     pub outbox_event_id: i64,
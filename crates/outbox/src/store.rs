@@ -6,7 +6,7 @@ use crate::model::{
     RadrootsOutboxClaimedEvent, RadrootsOutboxEnqueueReceipt, RadrootsOutboxEnqueueStatus,
     RadrootsOutboxEventRecord, RadrootsOutboxEventState, RadrootsOutboxEventStoreIngestReceipt,
     RadrootsOutboxOperationInput, RadrootsOutboxOperationRecord, RadrootsOutboxOperationStatus,
-    RadrootsOutboxRelayStatus, RadrootsOutboxRelayStatusRecord,
+    RadrootsOutboxRelayPublishBucket, RadrootsOutboxRelayStatus, RadrootsOutboxRelayStatusRecord,
 };
 use radroots_event_store::{RadrootsEventIngest, RadrootsEventStore};
 use radroots_events::RadrootsNostrEvent;
@@ -16,6 +16,7 @@ use serde::Serialize;
 use sha2::{Digest, Sha256};
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteQueryResult};
 use sqlx::{Row, SqlitePool};
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -74,11 +75,18 @@ impl RadrootsOutbox {
         input: RadrootsOutboxOperationInput,
     ) -> Result<RadrootsOutboxEnqueueReceipt, RadrootsOutboxError> {
         let target_relays = canonical_relays(input.target_relays);
+        let prerequisite_operation_ids = input
+            .prerequisite_operation_ids
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
         let digest = idempotency_digest(
             input.operation_kind.as_str(),
             input.draft.expected_pubkey.as_str(),
             &input.draft,
             &target_relays,
+            &prerequisite_operation_ids,
         )?;
         let accepted_quorum = target_relays.len() as i64;
         let mut tx = self.pool.begin().await?;
@@ -112,14 +120,19 @@ impl RadrootsOutbox {
             }
         }
 
+        for prerequisite_operation_id in &prerequisite_operation_ids {
+            operation_status_tx(&mut tx, *prerequisite_operation_id).await?;
+        }
+
         let operation = sqlx::query(
-            "INSERT INTO outbox_operation(operation_kind, expected_pubkey, idempotency_key, idempotency_digest, status, created_at_ms, updated_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO outbox_operation(operation_kind, expected_pubkey, idempotency_key, idempotency_digest, status, publish_at_ms, created_at_ms, updated_at_ms) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(input.operation_kind.as_str())
         .bind(input.draft.expected_pubkey.as_str())
         .bind(input.idempotency_key.as_deref())
         .bind(digest.as_str())
         .bind(RadrootsOutboxOperationStatus::Queued.as_str())
+        .bind(input.publish_at_ms)
         .bind(input.created_at_ms)
         .bind(input.created_at_ms)
         .execute(&mut *tx)
@@ -152,6 +165,10 @@ impl RadrootsOutbox {
             .execute(&mut *tx)
             .await?;
         }
+        for prerequisite_operation_id in prerequisite_operation_ids {
+            insert_prerequisite_tx(&mut tx, operation_id, prerequisite_operation_id).await?;
+        }
+        fail_operations_with_failed_prerequisites_tx(&mut tx, input.created_at_ms).await?;
 
         tx.commit().await?;
         Ok(RadrootsOutboxEnqueueReceipt {
@@ -168,7 +185,7 @@ impl RadrootsOutbox {
         operation_id: i64,
     ) -> Result<Option<RadrootsOutboxOperationRecord>, RadrootsOutboxError> {
         let row = sqlx::query(
-            "SELECT operation_id, operation_kind, expected_pubkey, idempotency_key, idempotency_digest, status, created_at_ms, updated_at_ms FROM outbox_operation WHERE operation_id = ?",
+            "SELECT operation_id, operation_kind, expected_pubkey, idempotency_key, idempotency_digest, status, publish_at_ms, created_at_ms, updated_at_ms FROM outbox_operation WHERE operation_id = ?",
         )
         .bind(operation_id)
         .fetch_optional(&self.pool)
@@ -196,6 +213,144 @@ impl RadrootsOutbox {
         relay_statuses_for(&self.pool, outbox_event_id).await
     }
 
+    pub async fn reschedule_operation(
+        &self,
+        operation_id: i64,
+        publish_at_ms: Option<i64>,
+        now_ms: i64,
+    ) -> Result<RadrootsOutboxOperationRecord, RadrootsOutboxError> {
+        let mut tx = self.pool.begin().await?;
+        unpublished_operation_events_tx(&mut tx, operation_id, now_ms).await?;
+        sqlx::query(
+            "UPDATE outbox_operation SET publish_at_ms = ?, updated_at_ms = ? WHERE operation_id = ?",
+        )
+        .bind(publish_at_ms)
+        .bind(now_ms)
+        .bind(operation_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        self.get_operation(operation_id)
+            .await?
+            .ok_or(RadrootsOutboxError::OperationNotFound(operation_id))
+    }
+
+    pub async fn cancel_operation(
+        &self,
+        operation_id: i64,
+        now_ms: i64,
+    ) -> Result<RadrootsOutboxOperationRecord, RadrootsOutboxError> {
+        let mut tx = self.pool.begin().await?;
+        for outbox_event_id in
+            unpublished_operation_events_tx(&mut tx, operation_id, now_ms).await?
+        {
+            sqlx::query(
+                "UPDATE outbox_event SET state = ?, claim_token = NULL, claim_owner = NULL, claim_expires_at_ms = NULL, updated_at_ms = ? WHERE outbox_event_id = ?",
+            )
+            .bind(RadrootsOutboxEventState::Cancelled.as_str())
+            .bind(now_ms)
+            .bind(outbox_event_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE outbox_operation SET status = ?, updated_at_ms = ? WHERE operation_id = ?",
+        )
+        .bind(RadrootsOutboxOperationStatus::Cancelled.as_str())
+        .bind(now_ms)
+        .bind(operation_id)
+        .execute(&mut *tx)
+        .await?;
+        fail_operations_with_failed_prerequisites_tx(&mut tx, now_ms).await?;
+        tx.commit().await?;
+        self.get_operation(operation_id)
+            .await?
+            .ok_or(RadrootsOutboxError::OperationNotFound(operation_id))
+    }
+
+    pub async fn add_operation_prerequisite(
+        &self,
+        operation_id: i64,
+        prerequisite_operation_id: i64,
+        now_ms: i64,
+    ) -> Result<(), RadrootsOutboxError> {
+        let mut tx = self.pool.begin().await?;
+        unpublished_operation_events_tx(&mut tx, operation_id, now_ms).await?;
+        operation_status_tx(&mut tx, prerequisite_operation_id).await?;
+        let cycle = operation_id == prerequisite_operation_id
+            || sqlx::query(
+                "WITH RECURSIVE ancestor(operation_id) AS (SELECT prerequisite_operation_id FROM outbox_operation_prerequisite WHERE operation_id = ? UNION SELECT p.prerequisite_operation_id FROM outbox_operation_prerequisite p JOIN ancestor a ON p.operation_id = a.operation_id) SELECT 1 FROM ancestor WHERE operation_id = ? LIMIT 1",
+            )
+            .bind(prerequisite_operation_id)
+            .bind(operation_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some();
+        if cycle {
+            return Err(RadrootsOutboxError::PrerequisiteCycle {
+                operation_id,
+                prerequisite_operation_id,
+            });
+        }
+        insert_prerequisite_tx(&mut tx, operation_id, prerequisite_operation_id).await?;
+        sqlx::query("UPDATE outbox_operation SET updated_at_ms = ? WHERE operation_id = ?")
+            .bind(now_ms)
+            .bind(operation_id)
+            .execute(&mut *tx)
+            .await?;
+        fail_operations_with_failed_prerequisites_tx(&mut tx, now_ms).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn operation_prerequisites(
+        &self,
+        operation_id: i64,
+    ) -> Result<Vec<i64>, RadrootsOutboxError> {
+        sqlx::query(
+            "SELECT prerequisite_operation_id FROM outbox_operation_prerequisite WHERE operation_id = ? ORDER BY prerequisite_operation_id",
+        )
+        .bind(operation_id)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| row.try_get("prerequisite_operation_id").map_err(Into::into))
+        .collect()
+    }
+
+    pub async fn relay_publish_buckets(
+        &self,
+        since_ms: i64,
+        bucket_ms: i64,
+    ) -> Result<Vec<RadrootsOutboxRelayPublishBucket>, RadrootsOutboxError> {
+        let bucket_ms = bucket_ms.max(1);
+        let rows = sqlx::query(
+            "SELECT relay_url, (last_attempt_at_ms / ?) * ? AS bucket_start_ms, SUM(attempt_count) AS attempts, SUM(CASE WHEN status = ? THEN 1 ELSE 0 END) AS accepted, SUM(CASE WHEN status = ? THEN 1 ELSE 0 END) AS failed_retryable, SUM(CASE WHEN status = ? THEN 1 ELSE 0 END) AS failed_terminal, MAX(last_attempt_at_ms) AS last_attempt_at_ms, MAX(acknowledged_at_ms) AS last_accepted_at_ms FROM outbox_event_relay_status WHERE last_attempt_at_ms IS NOT NULL AND last_attempt_at_ms >= ? GROUP BY relay_url, bucket_start_ms ORDER BY relay_url, bucket_start_ms",
+        )
+        .bind(bucket_ms)
+        .bind(bucket_ms)
+        .bind(RadrootsOutboxRelayStatus::Accepted.as_str())
+        .bind(RadrootsOutboxRelayStatus::FailedRetryable.as_str())
+        .bind(RadrootsOutboxRelayStatus::FailedTerminal.as_str())
+        .bind(since_ms)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(RadrootsOutboxRelayPublishBucket {
+                    relay_url: row.try_get("relay_url")?,
+                    bucket_start_ms: row.try_get("bucket_start_ms")?,
+                    attempts: row.try_get("attempts")?,
+                    accepted: row.try_get("accepted")?,
+                    failed_retryable: row.try_get("failed_retryable")?,
+                    failed_terminal: row.try_get("failed_terminal")?,
+                    last_attempt_at_ms: row.try_get("last_attempt_at_ms")?,
+                    last_accepted_at_ms: row.try_get("last_accepted_at_ms")?,
+                })
+            })
+            .collect()
+    }
+
     pub async fn claim_next_ready_event(
         &self,
         claim_owner: impl AsRef<str>,
@@ -205,10 +360,11 @@ impl RadrootsOutbox {
     ) -> Result<Option<RadrootsOutboxClaimedEvent>, RadrootsOutboxError> {
         let mut tx = self.pool.begin().await?;
         let row = sqlx::query(
-            "SELECT outbox_event_id, state, signed_event_json FROM outbox_event WHERE state IN ('draft_queued', 'sign_retryable', 'signed', 'publish_retryable') AND next_attempt_after_ms <= ? AND (claim_token IS NULL OR claim_expires_at_ms <= ?) ORDER BY created_at_ms, outbox_event_id LIMIT 1",
+            "SELECT outbox_event_id, state, signed_event_json FROM outbox_event WHERE state IN ('draft_queued', 'sign_retryable', 'signed', 'publish_retryable') AND next_attempt_after_ms <= ? AND (claim_token IS NULL OR claim_expires_at_ms <= ?) AND NOT EXISTS (SELECT 1 FROM outbox_operation o WHERE o.operation_id = outbox_event.operation_id AND o.publish_at_ms > ?) AND NOT EXISTS (SELECT 1 FROM outbox_operation_prerequisite p JOIN outbox_operation po ON po.operation_id = p.prerequisite_operation_id WHERE p.operation_id = outbox_event.operation_id AND po.status != 'complete') ORDER BY created_at_ms, outbox_event_id LIMIT 1",
         )
         .bind(now_ms)
         .bind(now_ms)
+        .bind(now_ms)
         .fetch_optional(&mut *tx)
         .await?;
         let Some(row) = row else {
@@ -416,20 +572,23 @@ impl RadrootsOutbox {
         relay_url: &str,
         acknowledged_at_ms: i64,
     ) -> Result<(), RadrootsOutboxError> {
+        let mut tx = self.pool.begin().await?;
+        claimed_event_identity_tx(&mut tx, outbox_event_id, claim_token).await?;
         let changed = sqlx::query(
-            "UPDATE outbox_event_relay_status SET status = ?, attempt_count = attempt_count + 1, last_attempt_at_ms = ?, acknowledged_at_ms = ?, last_error = NULL WHERE outbox_event_id = ? AND relay_url = ? AND EXISTS (SELECT 1 FROM outbox_event WHERE outbox_event_id = ? AND claim_token = ?)",
+            "UPDATE outbox_event_relay_status SET status = ?, attempt_count = attempt_count + 1, last_attempt_at_ms = ?, acknowledged_at_ms = ?, last_error = NULL WHERE outbox_event_id = ? AND relay_url = ?",
         )
         .bind(RadrootsOutboxRelayStatus::Accepted.as_str())
         .bind(acknowledged_at_ms)
         .bind(acknowledged_at_ms)
         .bind(outbox_event_id)
         .bind(relay_url)
-        .bind(outbox_event_id)
-        .bind(claim_token)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        self.ensure_claimed_update(outbox_event_id, claim_token, changed)
-            .await?;
+        if changed.rows_affected() == 0 {
+            return Err(RadrootsOutboxError::ClaimTokenMismatch { outbox_event_id });
+        }
+        record_relay_attempt_tx(&mut tx, outbox_event_id, relay_url, acknowledged_at_ms).await?;
+        tx.commit().await?;
         Ok(())
     }
 
@@ -578,6 +737,7 @@ impl RadrootsOutbox {
             .bind(operation_id)
             .execute(&mut *tx)
             .await?;
+            fail_operations_with_failed_prerequisites_tx(&mut tx, now_ms).await?;
         }
 
         tx.commit().await?;
@@ -698,6 +858,7 @@ impl RadrootsOutbox {
         .bind(row.operation_id)
         .execute(&mut *tx)
         .await?;
+        fail_operations_with_failed_prerequisites_tx(&mut tx, now_ms).await?;
 
         tx.commit().await?;
         Ok(())
@@ -712,20 +873,23 @@ impl RadrootsOutbox {
         error: &str,
         attempted_at_ms: i64,
     ) -> Result<(), RadrootsOutboxError> {
+        let mut tx = self.pool.begin().await?;
+        claimed_event_identity_tx(&mut tx, outbox_event_id, claim_token).await?;
         let changed = sqlx::query(
-            "UPDATE outbox_event_relay_status SET status = ?, attempt_count = attempt_count + 1, last_attempt_at_ms = ?, acknowledged_at_ms = NULL, last_error = ? WHERE outbox_event_id = ? AND relay_url = ? AND EXISTS (SELECT 1 FROM outbox_event WHERE outbox_event_id = ? AND claim_token = ?)",
+            "UPDATE outbox_event_relay_status SET status = ?, attempt_count = attempt_count + 1, last_attempt_at_ms = ?, acknowledged_at_ms = NULL, last_error = ? WHERE outbox_event_id = ? AND relay_url = ?",
         )
         .bind(status.as_str())
         .bind(attempted_at_ms)
         .bind(error)
         .bind(outbox_event_id)
         .bind(relay_url)
-        .bind(outbox_event_id)
-        .bind(claim_token)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        self.ensure_claimed_update(outbox_event_id, claim_token, changed)
-            .await?;
+        if changed.rows_affected() == 0 {
+            return Err(RadrootsOutboxError::ClaimTokenMismatch { outbox_event_id });
+        }
+        record_relay_attempt_tx(&mut tx, outbox_event_id, relay_url, attempted_at_ms).await?;
+        tx.commit().await?;
         Ok(())
     }
 }
@@ -805,7 +969,7 @@ async fn existing_idempotent_operation(
     .transpose()
 }
 
-async fn event_by_id_tx(
+pub(crate) async fn event_by_id_tx(
     tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
     outbox_event_id: i64,
 ) -> Result<RadrootsOutboxEventRecord, RadrootsOutboxError> {
@@ -841,6 +1005,23 @@ async fn claimed_event_identity_tx(
     })
 }
 
+async fn record_relay_attempt_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    outbox_event_id: i64,
+    relay_url: &str,
+    attempted_at_ms: i64,
+) -> Result<(), RadrootsOutboxError> {
+    sqlx::query(
+        "INSERT INTO outbox_event_relay_attempt(outbox_event_id, relay_url, attempt_number, status, error, attempted_at_ms) SELECT outbox_event_id, relay_url, attempt_count, status, last_error, ? FROM outbox_event_relay_status WHERE outbox_event_id = ? AND relay_url = ?",
+    )
+    .bind(attempted_at_ms)
+    .bind(outbox_event_id)
+    .bind(relay_url)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
 async fn relay_urls_for_tx(
     tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
     outbox_event_id: i64,
@@ -856,6 +1037,114 @@ async fn relay_urls_for_tx(
         .collect()
 }
 
+async fn operation_status_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    operation_id: i64,
+) -> Result<RadrootsOutboxOperationStatus, RadrootsOutboxError> {
+    let status = sqlx::query("SELECT status FROM outbox_operation WHERE operation_id = ?")
+        .bind(operation_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or(RadrootsOutboxError::OperationNotFound(operation_id))?
+        .try_get::<String, _>("status")?;
+    RadrootsOutboxOperationStatus::parse(status.as_str())
+}
+
+async fn insert_prerequisite_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    operation_id: i64,
+    prerequisite_operation_id: i64,
+) -> Result<(), RadrootsOutboxError> {
+    sqlx::query(
+        "INSERT OR IGNORE INTO outbox_operation_prerequisite(operation_id, prerequisite_operation_id) VALUES (?, ?)",
+    )
+    .bind(operation_id)
+    .bind(prerequisite_operation_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub(crate) async fn fail_operations_with_failed_prerequisites_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    now_ms: i64,
+) -> Result<(), RadrootsOutboxError> {
+    loop {
+        let rows = sqlx::query(
+            "SELECT p.operation_id, MIN(p.prerequisite_operation_id) AS prerequisite_operation_id, po.status AS prerequisite_status FROM outbox_operation_prerequisite p JOIN outbox_operation o ON o.operation_id = p.operation_id JOIN outbox_operation po ON po.operation_id = p.prerequisite_operation_id WHERE o.status = ? AND po.status IN (?, ?) GROUP BY p.operation_id ORDER BY p.operation_id",
+        )
+        .bind(RadrootsOutboxOperationStatus::Queued.as_str())
+        .bind(RadrootsOutboxOperationStatus::FailedTerminal.as_str())
+        .bind(RadrootsOutboxOperationStatus::Cancelled.as_str())
+        .fetch_all(&mut **tx)
+        .await?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        for row in rows {
+            let operation_id: i64 = row.try_get("operation_id")?;
+            let prerequisite_operation_id: i64 = row.try_get("prerequisite_operation_id")?;
+            let prerequisite_status: String = row.try_get("prerequisite_status")?;
+            let error = prerequisite_failure_error(prerequisite_operation_id, &prerequisite_status);
+            sqlx::query(
+                "UPDATE outbox_event SET state = ?, claim_token = NULL, claim_owner = NULL, claim_expires_at_ms = NULL, last_error = ?, next_attempt_after_ms = ?, updated_at_ms = ? WHERE operation_id = ? AND state NOT IN ('published', 'failed_terminal', 'cancelled')",
+            )
+            .bind(RadrootsOutboxEventState::FailedTerminal.as_str())
+            .bind(error.as_str())
+            .bind(now_ms)
+            .bind(now_ms)
+            .bind(operation_id)
+            .execute(&mut **tx)
+            .await?;
+            sqlx::query(
+                "UPDATE outbox_operation SET status = ?, updated_at_ms = ? WHERE operation_id = ?",
+            )
+            .bind(RadrootsOutboxOperationStatus::FailedTerminal.as_str())
+            .bind(now_ms)
+            .bind(operation_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+}
+
+pub(crate) fn prerequisite_failure_error(prerequisite_operation_id: i64, status: &str) -> String {
+    format!("prerequisite operation {prerequisite_operation_id} is {status}")
+}
+
+async fn unpublished_operation_events_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    operation_id: i64,
+    now_ms: i64,
+) -> Result<Vec<i64>, RadrootsOutboxError> {
+    let status = operation_status_tx(tx, operation_id).await?;
+    if status != RadrootsOutboxOperationStatus::Queued {
+        return Err(RadrootsOutboxError::OperationNotQueued {
+            operation_id,
+            status: status.as_str(),
+        });
+    }
+    let rows = sqlx::query(
+        "SELECT e.outbox_event_id, e.claim_token IS NOT NULL AND e.claim_expires_at_ms > ? AS claimed, EXISTS (SELECT 1 FROM outbox_event_relay_status s WHERE s.outbox_event_id = e.outbox_event_id AND s.status = ?) AS accepted FROM outbox_event e WHERE e.operation_id = ? ORDER BY e.outbox_event_id",
+    )
+    .bind(now_ms)
+    .bind(RadrootsOutboxRelayStatus::Accepted.as_str())
+    .bind(operation_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    let mut outbox_event_ids = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.try_get::<bool, _>("claimed")? {
+            return Err(RadrootsOutboxError::OperationClaimed { operation_id });
+        }
+        if row.try_get::<bool, _>("accepted")? {
+            return Err(RadrootsOutboxError::OperationPartiallyPublished { operation_id });
+        }
+        outbox_event_ids.push(row.try_get("outbox_event_id")?);
+    }
+    Ok(outbox_event_ids)
+}
+
 async fn relay_statuses_for(
     pool: &SqlitePool,
     outbox_event_id: i64,
@@ -869,7 +1158,7 @@ async fn relay_statuses_for(
     rows.into_iter().map(relay_status_from_row).collect()
 }
 
-fn operation_from_row(
+pub(crate) fn operation_from_row(
     row: sqlx::sqlite::SqliteRow,
 ) -> Result<RadrootsOutboxOperationRecord, RadrootsOutboxError> {
     let status =
@@ -881,12 +1170,13 @@ fn operation_from_row(
         idempotency_key: row.try_get("idempotency_key")?,
         idempotency_digest: row.try_get("idempotency_digest")?,
         status,
+        publish_at_ms: row.try_get("publish_at_ms")?,
         created_at_ms: row.try_get("created_at_ms")?,
         updated_at_ms: row.try_get("updated_at_ms")?,
     })
 }
 
-fn event_from_row(
+pub(crate) fn event_from_row(
     row: sqlx::sqlite::SqliteRow,
 ) -> Result<RadrootsOutboxEventRecord, RadrootsOutboxError> {
     let draft: RadrootsFrozenEventDraft =
@@ -920,7 +1210,7 @@ fn event_from_row(
     })
 }
 
-fn relay_status_from_row(
+pub(crate) fn relay_status_from_row(
     row: sqlx::sqlite::SqliteRow,
 ) -> Result<RadrootsOutboxRelayStatusRecord, RadrootsOutboxError> {
     let status = RadrootsOutboxRelayStatus::parse(row.try_get::<String, _>("status")?.as_str())?;
@@ -947,7 +1237,7 @@ fn event_from_signed(signed_event: &RadrootsSignedNostrEvent) -> RadrootsNostrEv
     }
 }
 
-fn canonical_relays(relays: Vec<String>) -> Vec<String> {
+pub(crate) fn canonical_relays(relays: Vec<String>) -> Vec<String> {
     let mut out = Vec::new();
     for relay in relays {
         if !out.iter().any(|existing| existing == &relay) {
@@ -963,6 +1253,8 @@ struct DigestInput<'a> {
     expected_pubkey: &'a str,
     draft: &'a RadrootsFrozenEventDraft,
     target_relays: &'a [String],
+    #[serde(skip_serializing_if = "<[i64]>::is_empty")]
+    prerequisite_operation_ids: &'a [i64],
 }
 
 fn idempotency_digest(
@@ -970,12 +1262,14 @@ fn idempotency_digest(
     expected_pubkey: &str,
     draft: &RadrootsFrozenEventDraft,
     target_relays: &[String],
+    prerequisite_operation_ids: &[i64],
 ) -> Result<String, RadrootsOutboxError> {
     let input = DigestInput {
         operation_kind,
         expected_pubkey,
         draft,
         target_relays,
+        prerequisite_operation_ids,
     };
     let bytes = serde_json::to_vec(&input)?;
     Ok(hex::encode(Sha256::digest(bytes)))
@@ -1632,6 +1926,360 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn scheduled_operations_wait_for_publish_at_and_can_be_rescheduled_or_cancelled() {
+        let outbox = RadrootsOutbox::open_memory().await.expect("open");
+        let scheduled = outbox
+            .enqueue_operation(
+                operation_input(post_draft(FIXTURE_ALICE_PUBLIC_KEY_HEX, "market"), 1_000)
+                    .with_publish_at_ms(10_000),
+            )
+            .await
+            .expect("enqueue scheduled");
+        assert_eq!(
+            outbox
+                .get_operation(scheduled.operation_id)
+                .await
+                .expect("operation")
+                .expect("operation record")
+                .publish_at_ms,
+            Some(10_000)
+        );
+        assert!(
+            outbox
+                .claim_next_ready_event("worker-a", "early", 20_000, 9_999)
+                .await
+                .expect("early claim")
+                .is_none()
+        );
+
+        let rescheduled = outbox
+            .reschedule_operation(scheduled.operation_id, Some(5_000), 2_000)
+            .await
+            .expect("reschedule");
+        assert_eq!(rescheduled.publish_at_ms, Some(5_000));
+        assert_eq!(rescheduled.updated_at_ms, 2_000);
+        let claimed = outbox
+            .claim_next_ready_event("worker-a", "claim-a", 6_000, 5_000)
+            .await
+            .expect("claim")
+            .expect("claimed event");
+        assert!(matches!(
+            outbox
+                .reschedule_operation(scheduled.operation_id, Some(20_000), 5_500)
+                .await,
+            Err(RadrootsOutboxError::OperationClaimed { .. })
+        ));
+
+        assert_eq!(
+            outbox.recover_expired_claims(7_000).await.expect("recover"),
+            1
+        );
+        outbox
+            .reschedule_operation(scheduled.operation_id, Some(20_000), 7_000)
+            .await
+            .expect("reschedule after recovery");
+        assert!(
+            outbox
+                .claim_next_ready_event("worker-b", "claim-b", 30_000, 19_999)
+                .await
+                .expect("claim before publish_at")
+                .is_none()
+        );
+        let reclaimed = outbox
+            .claim_next_ready_event("worker-b", "claim-b", 30_000, 20_000)
+            .await
+            .expect("reclaim")
+            .expect("reclaimed event");
+        assert_eq!(reclaimed.outbox_event_id, claimed.outbox_event_id);
+        outbox
+            .mark_relay_accepted(
+                reclaimed.outbox_event_id,
+                "claim-b",
+                RELAY_PRIMARY_WSS,
+                20_100,
+            )
+            .await
+            .expect("accepted");
+        outbox
+            .mark_publish_retryable(
+                reclaimed.outbox_event_id,
+                "claim-b",
+                "secondary pending",
+                21_000,
+                20_200,
+            )
+            .await
+            .expect("retryable");
+        assert!(matches!(
+            outbox
+                .cancel_operation(scheduled.operation_id, 20_300)
+                .await,
+            Err(RadrootsOutboxError::OperationPartiallyPublished { .. })
+        ));
+
+        let cancelled = outbox
+            .enqueue_operation(
+                operation_input(post_draft(FIXTURE_ALICE_PUBLIC_KEY_HEX, "withdrawn"), 1_100)
+                    .with_publish_at_ms(50_000),
+            )
+            .await
+            .expect("enqueue cancelled");
+        let record = outbox
+            .cancel_operation(cancelled.operation_id, 30_000)
+            .await
+            .expect("cancel");
+        assert_eq!(record.status, RadrootsOutboxOperationStatus::Cancelled);
+        assert_eq!(
+            outbox
+                .get_event(cancelled.outbox_event_id)
+                .await
+                .expect("event")
+                .expect("event record")
+                .state,
+            RadrootsOutboxEventState::Cancelled
+        );
+        assert!(matches!(
+            outbox
+                .reschedule_operation(cancelled.operation_id, None, 30_100)
+                .await,
+            Err(RadrootsOutboxError::OperationNotQueued {
+                status: "cancelled",
+                ..
+            })
+        ));
+        assert!(matches!(
+            outbox.cancel_operation(999, 30_100).await,
+            Err(RadrootsOutboxError::OperationNotFound(999))
+        ));
+    }
+
+    #[tokio::test]
+    async fn prerequisites_gate_claims_reject_cycles_and_propagate_terminal_failures() {
+        let outbox = RadrootsOutbox::open_memory().await.expect("open");
+        let enqueue = |content: &'static str, created_at_ms: i64, prerequisites: Vec<i64>| {
+            let outbox = outbox.clone();
+            async move {
+                let input = prerequisites.into_iter().fold(
+                    operation_input(
+                        post_draft(FIXTURE_ALICE_PUBLIC_KEY_HEX, content),
+                        created_at_ms,
+                    ),
+                    RadrootsOutboxOperationInput::with_prerequisite,
+                );
+                outbox.enqueue_operation(input).await.expect("enqueue")
+            }
+        };
+        let farm = enqueue("farm", 2_000, Vec::new()).await;
+        let listing = enqueue("listing", 1_000, vec![farm.operation_id]).await;
+        assert_eq!(
+            outbox
+                .operation_prerequisites(listing.operation_id)
+                .await
+                .expect("prerequisites"),
+            vec![farm.operation_id]
+        );
+
+        let claimed = outbox
+            .claim_next_ready_event("worker-a", "claim-farm", 10_000, 3_000)
+            .await
+            .expect("claim")
+            .expect("claimed farm");
+        assert_eq!(claimed.operation_id, farm.operation_id);
+        assert!(
+            outbox
+                .claim_next_ready_event("worker-a", "claim-blocked", 10_000, 3_000)
+                .await
+                .expect("blocked claim")
+                .is_none()
+        );
+        for relay_url in [RELAY_PRIMARY_WSS, RELAY_SECONDARY_WSS] {
+            outbox
+                .mark_relay_accepted(farm.outbox_event_id, "claim-farm", relay_url, 3_100)
+                .await
+                .expect("accepted");
+        }
+        outbox
+            .complete_publish_attempt(
+                farm.outbox_event_id,
+                "claim-farm",
+                "retry",
+                "terminal",
+                4_000,
+                3_200,
+            )
+            .await
+            .expect("complete farm");
+        let claimed = outbox
+            .claim_next_ready_event("worker-a", "claim-listing", 10_000, 3_300)
+            .await
+            .expect("claim listing")
+            .expect("claimed listing");
+        assert_eq!(claimed.operation_id, listing.operation_id);
+
+        let payment = enqueue("payment", 5_000, Vec::new()).await;
+        let settlement = enqueue("settlement", 5_100, vec![payment.operation_id]).await;
+        let receipt = enqueue("receipt", 5_200, vec![settlement.operation_id]).await;
+        assert!(matches!(
+            outbox
+                .add_operation_prerequisite(payment.operation_id, receipt.operation_id, 5_300)
+                .await,
+            Err(RadrootsOutboxError::PrerequisiteCycle { .. })
+        ));
+        assert!(matches!(
+            outbox
+                .add_operation_prerequisite(payment.operation_id, payment.operation_id, 5_300)
+                .await,
+            Err(RadrootsOutboxError::PrerequisiteCycle { .. })
+        ));
+
+        let claimed = outbox
+            .claim_next_ready_event("worker-a", "claim-payment", 10_000, 5_400)
+            .await
+            .expect("claim payment")
+            .expect("claimed payment");
+        assert_eq!(claimed.operation_id, payment.operation_id);
+        outbox
+            .mark_publish_failed_terminal(
+                payment.outbox_event_id,
+                "claim-payment",
+                "rejected",
+                5_500,
+            )
+            .await
+            .expect("fail payment");
+        for (dependent, prerequisite) in [(&settlement, &payment), (&receipt, &settlement)] {
+            let operation = outbox
+                .get_operation(dependent.operation_id)
+                .await
+                .expect("operation")
+                .expect("operation record");
+            assert_eq!(
+                operation.status,
+                RadrootsOutboxOperationStatus::FailedTerminal
+            );
+            let event = outbox
+                .get_event(dependent.outbox_event_id)
+                .await
+                .expect("event")
+                .expect("event record");
+            assert_eq!(event.state, RadrootsOutboxEventState::FailedTerminal);
+            assert_eq!(
+                event.last_error,
+                Some(format!(
+                    "prerequisite operation {} is failed_terminal",
+                    prerequisite.operation_id
+                ))
+            );
+        }
+
+        let orphan = enqueue("orphan", 6_000, vec![payment.operation_id]).await;
+        assert_eq!(
+            outbox
+                .get_operation(orphan.operation_id)
+                .await
+                .expect("operation")
+                .expect("operation record")
+                .status,
+            RadrootsOutboxOperationStatus::FailedTerminal
+        );
+        assert!(matches!(
+            outbox
+                .enqueue_operation(
+                    operation_input(post_draft(FIXTURE_ALICE_PUBLIC_KEY_HEX, "missing"), 6_100)
+                        .with_prerequisite(999),
+                )
+                .await,
+            Err(RadrootsOutboxError::OperationNotFound(999))
+        ));
+
+        outbox
+            .requeue_dead_letter(payment.outbox_event_id, 7_000)
+            .await
+            .expect("requeue payment");
+        for dependent in [&settlement, &receipt, &orphan] {
+            assert_eq!(
+                outbox
+                    .get_operation(dependent.operation_id)
+                    .await
+                    .expect("operation")
+                    .expect("operation record")
+                    .status,
+                RadrootsOutboxOperationStatus::Queued
+            );
+            let event = outbox
+                .get_event(dependent.outbox_event_id)
+                .await
+                .expect("event")
+                .expect("event record");
+            assert_eq!(event.state, RadrootsOutboxEventState::DraftQueued);
+            assert_eq!(event.last_error, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn relay_publish_buckets_group_outcomes_by_relay_and_window() {
+        let outbox = RadrootsOutbox::open_memory().await.expect("open");
+        let (receipt, claimed) = enqueue_signed_fixture(&outbox).await;
+        outbox
+            .mark_relay_accepted(
+                receipt.outbox_event_id,
+                claimed.claim_token.as_str(),
+                RELAY_PRIMARY_WSS,
+                61_000,
+            )
+            .await
+            .expect("accepted");
+        outbox
+            .mark_relay_failed_retryable(
+                receipt.outbox_event_id,
+                claimed.claim_token.as_str(),
+                RELAY_SECONDARY_WSS,
+                "timeout",
+                62_000,
+            )
+            .await
+            .expect("retryable");
+
+        let buckets = outbox
+            .relay_publish_buckets(0, 60_000)
+            .await
+            .expect("buckets");
+        assert_eq!(
+            buckets,
+            vec![
+                RadrootsOutboxRelayPublishBucket {
+                    relay_url: RELAY_SECONDARY_WSS.to_owned(),
+                    bucket_start_ms: 60_000,
+                    attempts: 1,
+                    accepted: 0,
+                    failed_retryable: 1,
+                    failed_terminal: 0,
+                    last_attempt_at_ms: 62_000,
+                    last_accepted_at_ms: None,
+                },
+                RadrootsOutboxRelayPublishBucket {
+                    relay_url: RELAY_PRIMARY_WSS.to_owned(),
+                    bucket_start_ms: 60_000,
+                    attempts: 1,
+                    accepted: 1,
+                    failed_retryable: 0,
+                    failed_terminal: 0,
+                    last_attempt_at_ms: 61_000,
+                    last_accepted_at_ms: Some(61_000),
+                },
+            ]
+        );
+        assert!(
+            outbox
+                .relay_publish_buckets(61_500, 60_000)
+                .await
+                .expect("windowed")
+                .iter()
+                .all(|bucket| bucket.relay_url == RELAY_SECONDARY_WSS)
+        );
+    }
+
     #[tokio::test]
     async fn smoke_outbox_claim_cancel_cycles_complete_one_thousand_events() {
         let outbox = RadrootsOutbox::open_memory().await.expect("open");
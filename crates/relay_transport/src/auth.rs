@@ -0,0 +1,891 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    RadrootsRelayFetchAdapter, RadrootsRelayFetchItem, RadrootsRelayFetchMode,
+    RadrootsRelayFetchRequest, RadrootsRelayOutcome, RadrootsRelayOutcomeKind,
+    RadrootsRelayPublishAdapter, RadrootsRelayPublishRelayReceipt, RadrootsRelayPublishRequest,
+    RadrootsRelayTargetSet, RadrootsRelayTransportError,
+};
+use futures::future::BoxFuture;
+use nostr::JsonUtil;
+use radroots_events::draft::RadrootsSignedNostrEvent;
+use radroots_events::kinds::KIND_RELAY_AUTH;
+use radroots_nostr::prelude::{
+    RadrootsNostrKeys, RadrootsNostrTimestamp, radroots_event_from_nostr,
+    radroots_nostr_build_event,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "runtime-tokio")]
+use radroots_nostr::prelude::{
+    RadrootsNostrClient, RadrootsNostrRelay, RadrootsNostrRelayNotification,
+    RadrootsNostrRelayStatus,
+};
+#[cfg(feature = "runtime-tokio")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "runtime-tokio")]
+use std::time::Duration;
+#[cfg(feature = "runtime-tokio")]
+use tokio::sync::broadcast;
+
+pub type RadrootsRelayAuthClock = fn() -> u32;
+
+#[cfg(feature = "runtime-tokio")]
+pub const RADROOTS_RELAY_CONNECTION_DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsRelayClientMessage {
+    Event {
+        raw_json: String,
+    },
+    Req {
+        subscription_id: String,
+        filters: Vec<Value>,
+    },
+    Close {
+        subscription_id: String,
+    },
+    Auth {
+        raw_json: String,
+    },
+}
+
+impl RadrootsRelayClientMessage {
+    pub fn to_json(&self) -> Result<String, RadrootsRelayTransportError> {
+        let value = match self {
+            Self::Event { raw_json } => {
+                serde_json::json!(["EVENT", serde_json::from_str::<Value>(raw_json)?])
+            }
+            Self::Req {
+                subscription_id,
+                filters,
+            } => {
+                let mut frame = vec![
+                    Value::String("REQ".to_owned()),
+                    Value::String(subscription_id.clone()),
+                ];
+                frame.extend(filters.iter().cloned());
+                Value::Array(frame)
+            }
+            Self::Close { subscription_id } => serde_json::json!(["CLOSE", subscription_id]),
+            Self::Auth { raw_json } => {
+                serde_json::json!(["AUTH", serde_json::from_str::<Value>(raw_json)?])
+            }
+        };
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RadrootsRelayTransportError> {
+        let (label, rest) = parse_frame(json)?;
+        match (label.as_str(), rest.as_slice()) {
+            ("EVENT", [event @ Value::Object(_)]) => Ok(Self::Event {
+                raw_json: serde_json::to_string(event)?,
+            }),
+            ("REQ", [Value::String(subscription_id), filters @ ..]) => Ok(Self::Req {
+                subscription_id: subscription_id.clone(),
+                filters: filters.to_vec(),
+            }),
+            ("CLOSE", [Value::String(subscription_id)]) => Ok(Self::Close {
+                subscription_id: subscription_id.clone(),
+            }),
+            ("AUTH", [event @ Value::Object(_)]) => Ok(Self::Auth {
+                raw_json: serde_json::to_string(event)?,
+            }),
+            _ => Err(unexpected_frame(json)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsRelayServerMessage {
+    Ok {
+        event_id: String,
+        accepted: bool,
+        message: String,
+    },
+    Event {
+        subscription_id: String,
+        raw_json: String,
+    },
+    Eose {
+        subscription_id: String,
+    },
+    Closed {
+        subscription_id: String,
+        message: String,
+    },
+    Notice {
+        message: String,
+    },
+    Auth {
+        challenge: String,
+    },
+}
+
+impl RadrootsRelayServerMessage {
+    pub fn to_json(&self) -> Result<String, RadrootsRelayTransportError> {
+        let value = match self {
+            Self::Ok {
+                event_id,
+                accepted,
+                message,
+            } => serde_json::json!(["OK", event_id, accepted, message]),
+            Self::Event {
+                subscription_id,
+                raw_json,
+            } => serde_json::json!([
+                "EVENT",
+                subscription_id,
+                serde_json::from_str::<Value>(raw_json)?
+            ]),
+            Self::Eose { subscription_id } => serde_json::json!(["EOSE", subscription_id]),
+            Self::Closed {
+                subscription_id,
+                message,
+            } => serde_json::json!(["CLOSED", subscription_id, message]),
+            Self::Notice { message } => serde_json::json!(["NOTICE", message]),
+            Self::Auth { challenge } => serde_json::json!(["AUTH", challenge]),
+        };
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RadrootsRelayTransportError> {
+        let (label, rest) = parse_frame(json)?;
+        match (label.as_str(), rest.as_slice()) {
+            (
+                "OK",
+                [
+                    Value::String(event_id),
+                    Value::Bool(accepted),
+                    Value::String(message),
+                ],
+            ) => Ok(Self::Ok {
+                event_id: event_id.clone(),
+                accepted: *accepted,
+                message: message.clone(),
+            }),
+            ("EVENT", [Value::String(subscription_id), event @ Value::Object(_)]) => {
+                Ok(Self::Event {
+                    subscription_id: subscription_id.clone(),
+                    raw_json: serde_json::to_string(event)?,
+                })
+            }
+            ("EOSE", [Value::String(subscription_id)]) => Ok(Self::Eose {
+                subscription_id: subscription_id.clone(),
+            }),
+            ("CLOSED", [Value::String(subscription_id), Value::String(message)]) => {
+                Ok(Self::Closed {
+                    subscription_id: subscription_id.clone(),
+                    message: message.clone(),
+                })
+            }
+            ("NOTICE", [Value::String(message)]) => Ok(Self::Notice {
+                message: message.clone(),
+            }),
+            ("AUTH", [Value::String(challenge)]) => Ok(Self::Auth {
+                challenge: challenge.clone(),
+            }),
+            _ => Err(unexpected_frame(json)),
+        }
+    }
+}
+
+pub trait RadrootsRelayConnection: Send + Sync {
+    fn exchange<'a>(
+        &'a self,
+        relay_url: &'a str,
+        message: RadrootsRelayClientMessage,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayServerMessage>, RadrootsRelayTransportError>>;
+
+    fn connection_id(&self, _relay_url: &str) -> u64 {
+        0
+    }
+}
+
+pub trait RadrootsRelayAuthSigner: Send + Sync {
+    fn sign_relay_auth<'a>(
+        &'a self,
+        relay_url: &'a str,
+        challenge: &'a str,
+        created_at: u32,
+    ) -> BoxFuture<'a, Result<RadrootsSignedNostrEvent, RadrootsRelayTransportError>>;
+}
+
+impl RadrootsRelayAuthSigner for RadrootsNostrKeys {
+    fn sign_relay_auth<'a>(
+        &'a self,
+        relay_url: &'a str,
+        challenge: &'a str,
+        created_at: u32,
+    ) -> BoxFuture<'a, Result<RadrootsSignedNostrEvent, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            let tags = vec![
+                vec!["relay".to_owned(), relay_url.to_owned()],
+                vec!["challenge".to_owned(), challenge.to_owned()],
+            ];
+            let event = radroots_nostr_build_event(KIND_RELAY_AUTH, "", tags)
+                .map_err(|error| RadrootsRelayTransportError::RelayAuth(error.to_string()))?
+                .custom_created_at(RadrootsNostrTimestamp::from_secs(u64::from(created_at)))
+                .sign_with_keys(self)
+                .map_err(|error| RadrootsRelayTransportError::RelayAuth(error.to_string()))?;
+            RadrootsSignedNostrEvent::from_event(radroots_event_from_nostr(&event), event.as_json())
+                .map_err(|error| RadrootsRelayTransportError::RelayAuth(error.to_string()))
+        })
+    }
+}
+
+impl<S> RadrootsRelayAuthSigner for Arc<S>
+where
+    S: RadrootsRelayAuthSigner + ?Sized,
+{
+    fn sign_relay_auth<'a>(
+        &'a self,
+        relay_url: &'a str,
+        challenge: &'a str,
+        created_at: u32,
+    ) -> BoxFuture<'a, Result<RadrootsSignedNostrEvent, RadrootsRelayTransportError>> {
+        (**self).sign_relay_auth(relay_url, challenge, created_at)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct RelayAuthSession {
+    connection_id: u64,
+    challenge: Option<String>,
+    authenticated: bool,
+}
+
+fn relay_session<'s>(
+    sessions: &'s mut BTreeMap<String, RelayAuthSession>,
+    relay_url: &str,
+    connection_id: u64,
+) -> &'s mut RelayAuthSession {
+    let session = sessions.entry(relay_url.to_owned()).or_default();
+    if session.connection_id != connection_id {
+        *session = RelayAuthSession {
+            connection_id,
+            ..RelayAuthSession::default()
+        };
+    }
+    session
+}
+
+pub struct RadrootsRelayAuthenticator<C, S> {
+    connection: Arc<C>,
+    signer: Arc<S>,
+    clock: RadrootsRelayAuthClock,
+    sessions: Arc<Mutex<BTreeMap<String, RelayAuthSession>>>,
+}
+
+impl<C, S> Clone for RadrootsRelayAuthenticator<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            connection: Arc::clone(&self.connection),
+            signer: Arc::clone(&self.signer),
+            clock: self.clock,
+            sessions: Arc::clone(&self.sessions),
+        }
+    }
+}
+
+impl<C, S> RadrootsRelayAuthenticator<C, S>
+where
+    C: RadrootsRelayConnection,
+    S: RadrootsRelayAuthSigner,
+{
+    pub fn new(connection: C, signer: S) -> Self {
+        Self {
+            connection: Arc::new(connection),
+            signer: Arc::new(signer),
+            clock: system_clock_secs,
+            sessions: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: RadrootsRelayAuthClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn connection(&self) -> &C {
+        &self.connection
+    }
+
+    pub fn is_authenticated(&self, relay_url: &str) -> Result<bool, RadrootsRelayTransportError> {
+        let connection_id = self.connection.connection_id(relay_url);
+        Ok(self
+            .sessions
+            .lock()
+            .map_err(|_| lock_poisoned())?
+            .get(relay_url)
+            .is_some_and(|session| session.connection_id == connection_id && session.authenticated))
+    }
+
+    pub async fn exchange(
+        &self,
+        relay_url: &str,
+        message: RadrootsRelayClientMessage,
+    ) -> Result<Vec<RadrootsRelayServerMessage>, RadrootsRelayTransportError> {
+        let replies = self.connection.exchange(relay_url, message).await?;
+        let connection_id = self.connection.connection_id(relay_url);
+        let mut sessions = self.sessions.lock().map_err(|_| lock_poisoned())?;
+        let session = relay_session(&mut sessions, relay_url, connection_id);
+        for reply in &replies {
+            let RadrootsRelayServerMessage::Auth { challenge } = reply else {
+                continue;
+            };
+            if session.challenge.as_deref() != Some(challenge.as_str()) {
+                session.challenge = Some(challenge.clone());
+                session.authenticated = false;
+            }
+        }
+        Ok(replies)
+    }
+
+    pub async fn ensure_authenticated(
+        &self,
+        relay_url: &str,
+    ) -> Result<Option<RadrootsRelayOutcome>, RadrootsRelayTransportError> {
+        let connection_id = self.connection.connection_id(relay_url);
+        let pending = {
+            let mut sessions = self.sessions.lock().map_err(|_| lock_poisoned())?;
+            let session = relay_session(&mut sessions, relay_url, connection_id);
+            session.challenge.is_some() && !session.authenticated
+        };
+        if !pending {
+            return Ok(None);
+        }
+        self.authenticate(relay_url).await.map(Some)
+    }
+
+    pub async fn authenticate(
+        &self,
+        relay_url: &str,
+    ) -> Result<RadrootsRelayOutcome, RadrootsRelayTransportError> {
+        let connection_id = self.connection.connection_id(relay_url);
+        let challenge = {
+            let mut sessions = self.sessions.lock().map_err(|_| lock_poisoned())?;
+            relay_session(&mut sessions, relay_url, connection_id)
+                .challenge
+                .clone()
+        };
+        let Some(challenge) = challenge else {
+            return Ok(RadrootsRelayOutcome::classify(
+                "auth-required: relay did not send an AUTH challenge",
+            ));
+        };
+        let auth_event = self
+            .signer
+            .sign_relay_auth(relay_url, challenge.as_str(), (self.clock)())
+            .await?;
+        let replies = self
+            .exchange(
+                relay_url,
+                RadrootsRelayClientMessage::Auth {
+                    raw_json: auth_event.raw_json,
+                },
+            )
+            .await?;
+        let outcome = ok_outcome(replies.as_slice(), auth_event.id.as_str());
+        if outcome.counts_toward_quorum() {
+            let mut sessions = self.sessions.lock().map_err(|_| lock_poisoned())?;
+            let session = relay_session(&mut sessions, relay_url, connection_id);
+            if session.challenge.as_deref() == Some(challenge.as_str()) {
+                session.authenticated = true;
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+pub struct RadrootsAuthRelayPublishAdapter<C, S> {
+    authenticator: RadrootsRelayAuthenticator<C, S>,
+}
+
+impl<C, S> RadrootsAuthRelayPublishAdapter<C, S>
+where
+    C: RadrootsRelayConnection,
+    S: RadrootsRelayAuthSigner,
+{
+    pub fn new(authenticator: RadrootsRelayAuthenticator<C, S>) -> Self {
+        Self { authenticator }
+    }
+
+    pub fn authenticator(&self) -> &RadrootsRelayAuthenticator<C, S> {
+        &self.authenticator
+    }
+
+    async fn publish_to_relay(
+        &self,
+        relay_url: &str,
+        signed_event: &RadrootsSignedNostrEvent,
+    ) -> Result<RadrootsRelayOutcome, RadrootsRelayTransportError> {
+        if let Some(auth_outcome) = self.authenticator.ensure_authenticated(relay_url).await?
+            && !auth_outcome.counts_toward_quorum()
+        {
+            return Ok(auth_rejected(auth_outcome));
+        }
+        let send = || {
+            self.authenticator.exchange(
+                relay_url,
+                RadrootsRelayClientMessage::Event {
+                    raw_json: signed_event.raw_json.clone(),
+                },
+            )
+        };
+        let outcome = ok_outcome(send().await?.as_slice(), signed_event.id.as_str());
+        if outcome.kind != RadrootsRelayOutcomeKind::AuthRequired {
+            return Ok(outcome);
+        }
+        let auth_outcome = self.authenticator.authenticate(relay_url).await?;
+        if !auth_outcome.counts_toward_quorum() {
+            return Ok(auth_rejected(auth_outcome));
+        }
+        Ok(ok_outcome(
+            send().await?.as_slice(),
+            signed_event.id.as_str(),
+        ))
+    }
+}
+
+impl<C, S> RadrootsRelayPublishAdapter for RadrootsAuthRelayPublishAdapter<C, S>
+where
+    C: RadrootsRelayConnection,
+    S: RadrootsRelayAuthSigner,
+{
+    fn publish<'a>(
+        &'a self,
+        request: RadrootsRelayPublishRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayPublishRelayReceipt>, RadrootsRelayTransportError>>
+    {
+        Box::pin(async move {
+            let mut receipts = Vec::new();
+            for relay in request.targets.relays() {
+                let outcome = self
+                    .publish_to_relay(relay.as_str(), &request.signed_event)
+                    .await
+                    .unwrap_or_else(|error| {
+                        RadrootsRelayOutcome::connection_failed(error.to_string())
+                    });
+                receipts.push(RadrootsRelayPublishRelayReceipt::attempted(
+                    relay.as_str(),
+                    outcome,
+                ));
+            }
+            Ok(receipts)
+        })
+    }
+}
+
+pub struct RadrootsAuthRelayFetchAdapter<C, S> {
+    authenticator: RadrootsRelayAuthenticator<C, S>,
+    targets: RadrootsRelayTargetSet,
+    filters: Vec<Value>,
+    subscription_id: String,
+}
+
+impl<C, S> RadrootsAuthRelayFetchAdapter<C, S>
+where
+    C: RadrootsRelayConnection,
+    S: RadrootsRelayAuthSigner,
+{
+    pub fn new(
+        authenticator: RadrootsRelayAuthenticator<C, S>,
+        targets: RadrootsRelayTargetSet,
+        filters: Vec<Value>,
+    ) -> Self {
+        Self {
+            authenticator,
+            targets,
+            filters,
+            subscription_id: "radroots-fetch".to_owned(),
+        }
+    }
+
+    pub fn with_subscription_id(mut self, subscription_id: impl Into<String>) -> Self {
+        self.subscription_id = subscription_id.into();
+        self
+    }
+
+    pub fn authenticator(&self) -> &RadrootsRelayAuthenticator<C, S> {
+        &self.authenticator
+    }
+
+    async fn fetch_from_relay(
+        &self,
+        relay_url: &str,
+        request: &RadrootsRelayFetchRequest,
+    ) -> Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError> {
+        if let Some(auth_outcome) = self.authenticator.ensure_authenticated(relay_url).await?
+            && !auth_outcome.counts_toward_quorum()
+        {
+            return Ok(vec![RadrootsRelayFetchItem::Closed {
+                relay_url: relay_url.to_owned(),
+                message: auth_rejected(auth_outcome).message.unwrap_or_default(),
+            }]);
+        }
+        let send = || {
+            self.authenticator.exchange(
+                relay_url,
+                RadrootsRelayClientMessage::Req {
+                    subscription_id: self.subscription_id.clone(),
+                    filters: self.filters.clone(),
+                },
+            )
+        };
+        let mut replies = send().await?;
+        let auth_required = replies.iter().any(|reply| {
+            matches!(
+                reply,
+                RadrootsRelayServerMessage::Closed { message, .. }
+                    if RadrootsRelayOutcome::classify(message).kind
+                        == RadrootsRelayOutcomeKind::AuthRequired
+            )
+        });
+        if auth_required {
+            let auth_outcome = self.authenticator.authenticate(relay_url).await?;
+            if !auth_outcome.counts_toward_quorum() {
+                return Ok(vec![RadrootsRelayFetchItem::Closed {
+                    relay_url: relay_url.to_owned(),
+                    message: auth_rejected(auth_outcome).message.unwrap_or_default(),
+                }]);
+            }
+            replies = send().await?;
+        }
+        let closed = replies
+            .iter()
+            .any(|reply| matches!(reply, RadrootsRelayServerMessage::Closed { .. }));
+        if request.mode == RadrootsRelayFetchMode::Fetch && !closed {
+            self.authenticator
+                .exchange(
+                    relay_url,
+                    RadrootsRelayClientMessage::Close {
+                        subscription_id: self.subscription_id.clone(),
+                    },
+                )
+                .await?;
+        }
+        Ok(replies
+            .into_iter()
+            .filter_map(|reply| match reply {
+                RadrootsRelayServerMessage::Event { raw_json, .. } => {
+                    Some(RadrootsRelayFetchItem::Event {
+                        relay_url: relay_url.to_owned(),
+                        raw_json,
+                        observed_at_ms: request.observed_at_ms,
+                    })
+                }
+                RadrootsRelayServerMessage::Eose { .. } => Some(RadrootsRelayFetchItem::Eose {
+                    relay_url: relay_url.to_owned(),
+                }),
+                RadrootsRelayServerMessage::Closed { message, .. } => {
+                    Some(RadrootsRelayFetchItem::Closed {
+                        relay_url: relay_url.to_owned(),
+                        message,
+                    })
+                }
+                RadrootsRelayServerMessage::Notice { message } => {
+                    Some(RadrootsRelayFetchItem::Notice {
+                        relay_url: relay_url.to_owned(),
+                        message,
+                    })
+                }
+                RadrootsRelayServerMessage::Ok { .. } | RadrootsRelayServerMessage::Auth { .. } => {
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
+impl<C, S> RadrootsRelayFetchAdapter for RadrootsAuthRelayFetchAdapter<C, S>
+where
+    C: RadrootsRelayConnection,
+    S: RadrootsRelayAuthSigner,
+{
+    fn fetch<'a>(
+        &'a self,
+        request: RadrootsRelayFetchRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            let mut items = Vec::new();
+            for relay in self.targets.relays() {
+                if request.skips_relay(relay.as_str()) {
+                    continue;
+                }
+                items.extend(self.fetch_from_relay(relay.as_str(), &request).await?);
+            }
+            Ok(items)
+        })
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[derive(Clone)]
+struct ClientRelayHandle {
+    relay: RadrootsNostrRelay,
+    notifications: Arc<tokio::sync::Mutex<broadcast::Receiver<RadrootsNostrRelayNotification>>>,
+    connection: Arc<AtomicU64>,
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl ClientRelayHandle {
+    fn observe(&self, notification: &RadrootsNostrRelayNotification) -> bool {
+        let dropped = matches!(
+            notification,
+            RadrootsNostrRelayNotification::RelayStatus {
+                status: RadrootsNostrRelayStatus::Disconnected
+                    | RadrootsNostrRelayStatus::Terminated
+                    | RadrootsNostrRelayStatus::Banned,
+            }
+        );
+        if dropped {
+            self.connection.fetch_add(1, Ordering::SeqCst);
+        }
+        dropped
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[derive(Clone)]
+pub struct RadrootsNostrClientRelayConnection {
+    client: RadrootsNostrClient,
+    timeout: Duration,
+    relays: Arc<Mutex<BTreeMap<String, ClientRelayHandle>>>,
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl RadrootsNostrClientRelayConnection {
+    pub fn new(client: RadrootsNostrClient) -> Self {
+        Self {
+            client,
+            timeout: RADROOTS_RELAY_CONNECTION_DEFAULT_TIMEOUT,
+            relays: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn client(&self) -> &RadrootsNostrClient {
+        &self.client
+    }
+
+    async fn relay_handle(
+        &self,
+        relay_url: &str,
+    ) -> Result<ClientRelayHandle, RadrootsRelayTransportError> {
+        let cached = self
+            .relays
+            .lock()
+            .map_err(|_| lock_poisoned())?
+            .get(relay_url)
+            .cloned();
+        let handle =
+            match cached {
+                Some(handle) => handle,
+                None => {
+                    self.client.add_relay(relay_url).await.map_err(|error| {
+                        RadrootsRelayTransportError::Transport(error.to_string())
+                    })?;
+                    let relay = self.client.relay(relay_url).await.map_err(|error| {
+                        RadrootsRelayTransportError::Transport(error.to_string())
+                    })?;
+                    let handle = ClientRelayHandle {
+                        notifications: Arc::new(tokio::sync::Mutex::new(relay.notifications())),
+                        relay,
+                        connection: Arc::new(AtomicU64::new(1)),
+                    };
+                    self.relays
+                        .lock()
+                        .map_err(|_| lock_poisoned())?
+                        .entry(relay_url.to_owned())
+                        .or_insert(handle)
+                        .clone()
+                }
+            };
+        if !handle.relay.is_connected() {
+            handle.connection.fetch_add(1, Ordering::SeqCst);
+            handle
+                .relay
+                .try_connect(self.timeout)
+                .await
+                .map_err(|error| RadrootsRelayTransportError::Transport(error.to_string()))?;
+        }
+        Ok(handle)
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl RadrootsRelayConnection for RadrootsNostrClientRelayConnection {
+    fn exchange<'a>(
+        &'a self,
+        relay_url: &'a str,
+        message: RadrootsRelayClientMessage,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayServerMessage>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            let handle = self.relay_handle(relay_url).await?;
+            let mut notifications = handle.notifications.lock().await;
+            let mut replies = Vec::new();
+            while let Ok(notification) = notifications.try_recv() {
+                handle.observe(&notification);
+                if let Some(reply @ RadrootsRelayServerMessage::Auth { .. }) =
+                    server_message(notification)
+                {
+                    replies.push(reply);
+                }
+            }
+
+            let frame = message.to_json()?;
+            let client_message = nostr::ClientMessage::from_json(frame.as_str())
+                .map_err(|error| RadrootsRelayTransportError::Transport(error.to_string()))?;
+            handle
+                .relay
+                .send_msg(client_message)
+                .map_err(|error| RadrootsRelayTransportError::Transport(error.to_string()))?;
+            let awaited = match &message {
+                RadrootsRelayClientMessage::Close { .. } => return Ok(replies),
+                RadrootsRelayClientMessage::Event { raw_json }
+                | RadrootsRelayClientMessage::Auth { raw_json } => {
+                    serde_json::from_str::<Value>(raw_json)?
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .map(str::to_owned)
+                        .unwrap_or_default()
+                }
+                RadrootsRelayClientMessage::Req {
+                    subscription_id, ..
+                } => subscription_id.clone(),
+            };
+            let collected = tokio::time::timeout(self.timeout, async {
+                loop {
+                    let notification = match notifications.recv().await {
+                        Ok(notification) => notification,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => {
+                            return Err(RadrootsRelayTransportError::Transport(format!(
+                                "relay connection closed: {relay_url}"
+                            )));
+                        }
+                    };
+                    if handle.observe(&notification) {
+                        return Err(RadrootsRelayTransportError::Transport(format!(
+                            "relay disconnected: {relay_url}"
+                        )));
+                    }
+                    let Some(reply) = server_message(notification) else {
+                        continue;
+                    };
+                    let done = match &reply {
+                        RadrootsRelayServerMessage::Ok { event_id, .. } => event_id == &awaited,
+                        RadrootsRelayServerMessage::Eose { subscription_id }
+                        | RadrootsRelayServerMessage::Closed {
+                            subscription_id, ..
+                        } => subscription_id == &awaited,
+                        _ => false,
+                    };
+                    replies.push(reply);
+                    if done {
+                        return Ok(());
+                    }
+                }
+            })
+            .await;
+            match collected {
+                Ok(result) => result?,
+                Err(_) if matches!(message, RadrootsRelayClientMessage::Req { .. }) => {}
+                Err(_) => {
+                    return Err(RadrootsRelayTransportError::Transport(format!(
+                        "timeout: relay did not answer {relay_url}"
+                    )));
+                }
+            }
+            Ok(replies)
+        })
+    }
+
+    fn connection_id(&self, relay_url: &str) -> u64 {
+        self.relays
+            .lock()
+            .ok()
+            .and_then(|relays| {
+                relays
+                    .get(relay_url)
+                    .map(|handle| handle.connection.load(Ordering::SeqCst))
+            })
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+fn server_message(
+    notification: RadrootsNostrRelayNotification,
+) -> Option<RadrootsRelayServerMessage> {
+    let RadrootsNostrRelayNotification::Message { message } = notification else {
+        return None;
+    };
+    RadrootsRelayServerMessage::from_json(message.as_json().as_str()).ok()
+}
+
+fn ok_outcome(replies: &[RadrootsRelayServerMessage], event_id: &str) -> RadrootsRelayOutcome {
+    let ok = replies.iter().find_map(|reply| match reply {
+        RadrootsRelayServerMessage::Ok {
+            event_id: ok_event_id,
+            accepted,
+            message,
+        } if ok_event_id == event_id => Some((*accepted, message.as_str())),
+        _ => None,
+    });
+    match ok {
+        Some((true, message)) if message.to_ascii_lowercase().starts_with("duplicate:") => {
+            RadrootsRelayOutcome::duplicate_accepted(message)
+        }
+        Some((true, _)) => RadrootsRelayOutcome::accepted(),
+        Some((false, message)) => RadrootsRelayOutcome::classify(message),
+        None => RadrootsRelayOutcome::classify("error: relay did not return OK"),
+    }
+}
+
+fn auth_rejected(outcome: RadrootsRelayOutcome) -> RadrootsRelayOutcome {
+    let reason = outcome.message.unwrap_or_default();
+    let message = if RadrootsRelayOutcome::classify(reason.as_str()).kind
+        == RadrootsRelayOutcomeKind::AuthRequired
+    {
+        reason
+    } else {
+        format!("auth-required: AUTH rejected: {reason}")
+    };
+    RadrootsRelayOutcome::classify(message)
+}
+
+fn parse_frame(json: &str) -> Result<(String, Vec<Value>), RadrootsRelayTransportError> {
+    let Value::Array(values) = serde_json::from_str::<Value>(json)? else {
+        return Err(unexpected_frame(json));
+    };
+    let mut values = values.into_iter();
+    let Some(Value::String(label)) = values.next() else {
+        return Err(unexpected_frame(json));
+    };
+    Ok((label, values.collect()))
+}
+
+fn unexpected_frame(json: &str) -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport(format!("unexpected relay frame: {json}"))
+}
+
+fn lock_poisoned() -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport("relay auth session lock poisoned".to_owned())
+}
+
+fn system_clock_secs() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| u32::try_from(duration.as_secs()).ok())
+        .unwrap_or(u32::MAX)
+}
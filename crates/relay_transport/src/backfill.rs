@@ -0,0 +1,379 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    RadrootsRelayFetchAdapter, RadrootsRelayFetchItem, RadrootsRelayFetchRequest,
+    RadrootsRelayTransportError, fetch_and_ingest_relay_events,
+};
+use futures::future::BoxFuture;
+use radroots_event_store::{
+    RadrootsEventQuery, RadrootsEventStore, RadrootsRelayBackfillCheckpoint,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+pub const RADROOTS_RELAY_BACKFILL_DEFAULT_PAGE_LIMIT: usize = 500;
+pub const RADROOTS_RELAY_BACKFILL_DEFAULT_INITIAL_WINDOW_SECS: u32 = 24 * 60 * 60;
+pub const RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_WINDOW_SECS: u32 = 30 * 24 * 60 * 60;
+pub const RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_PAGES_PER_RUN: usize = 64;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsRelayBackfillFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<u32>,
+}
+
+impl RadrootsRelayBackfillFilter {
+    pub fn key(&self) -> Result<String, RadrootsRelayTransportError> {
+        let normalized = Self {
+            kinds: sorted(self.kinds.iter().copied()),
+            authors: sorted(self.authors.iter().cloned()),
+            tags: self
+                .tags
+                .iter()
+                .map(|(name, values)| (name.clone(), sorted(values.iter().cloned())))
+                .collect(),
+            since: self.since,
+            until: self.until,
+        };
+        Ok(serde_json::to_string(&normalized)?)
+    }
+
+    pub fn window(&self, since: u32, until: u32) -> Self {
+        Self {
+            since: Some(since),
+            until: Some(until),
+            ..self.clone()
+        }
+    }
+
+    pub fn to_relay_filter(&self, limit: usize) -> Value {
+        let mut filter = serde_json::Map::new();
+        if !self.kinds.is_empty() {
+            filter.insert("kinds".to_owned(), serde_json::json!(self.kinds));
+        }
+        if !self.authors.is_empty() {
+            filter.insert("authors".to_owned(), serde_json::json!(self.authors));
+        }
+        for (name, values) in &self.tags {
+            filter.insert(format!("#{name}"), serde_json::json!(values));
+        }
+        if let Some(since) = self.since {
+            filter.insert("since".to_owned(), serde_json::json!(since));
+        }
+        if let Some(until) = self.until {
+            filter.insert("until".to_owned(), serde_json::json!(until));
+        }
+        filter.insert("limit".to_owned(), serde_json::json!(limit));
+        Value::Object(filter)
+    }
+
+    pub fn to_event_query(&self, limit: usize) -> RadrootsEventQuery {
+        RadrootsEventQuery {
+            authors: self.authors.clone(),
+            kinds: self.kinds.clone(),
+            tags: self.tags.clone(),
+            since: self.since,
+            until: self.until,
+            limit: Some(u32::try_from(limit).unwrap_or(u32::MAX)),
+            ..RadrootsEventQuery::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayBackfillPolicy {
+    pub page_limit: usize,
+    pub initial_window_secs: u32,
+    pub min_window_secs: u32,
+    pub max_window_secs: u32,
+    pub max_pages_per_run: usize,
+}
+
+impl Default for RadrootsRelayBackfillPolicy {
+    fn default() -> Self {
+        Self {
+            page_limit: RADROOTS_RELAY_BACKFILL_DEFAULT_PAGE_LIMIT,
+            initial_window_secs: RADROOTS_RELAY_BACKFILL_DEFAULT_INITIAL_WINDOW_SECS,
+            min_window_secs: 1,
+            max_window_secs: RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_WINDOW_SECS,
+            max_pages_per_run: RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_PAGES_PER_RUN,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayBackfillPage {
+    pub filter: RadrootsRelayBackfillFilter,
+    pub limit: usize,
+    pub observed_at_ms: i64,
+}
+
+pub trait RadrootsRelayBackfillSource: Send + Sync {
+    fn fetch_page<'a>(
+        &'a self,
+        relay_url: &'a str,
+        page: RadrootsRelayBackfillPage,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayBackfillReport {
+    pub pages: usize,
+    pub events_fetched: usize,
+    pub inserted_count: usize,
+    pub duplicate_count: usize,
+    pub truncated_pages: usize,
+    pub checkpoint: RadrootsRelayBackfillCheckpoint,
+}
+
+impl RadrootsRelayBackfillReport {
+    pub fn completed(&self) -> bool {
+        self.checkpoint.completed
+    }
+}
+
+pub async fn backfill_relay_events<S>(
+    source: &S,
+    event_store: &RadrootsEventStore,
+    relay_url: &str,
+    filter: &RadrootsRelayBackfillFilter,
+    policy: &RadrootsRelayBackfillPolicy,
+    now_unix: u32,
+    now_ms: i64,
+) -> Result<RadrootsRelayBackfillReport, RadrootsRelayTransportError>
+where
+    S: RadrootsRelayBackfillSource,
+{
+    let filter_key = filter.key()?;
+    let page_limit = policy.page_limit.max(1);
+    let min_window_secs = policy.min_window_secs.max(1);
+    let max_window_secs = policy.max_window_secs.max(min_window_secs);
+    let checkpoint = event_store
+        .relay_backfill_checkpoint(relay_url, filter_key.as_str())
+        .await?;
+    let mut checkpoint = checkpoint.unwrap_or_else(|| RadrootsRelayBackfillCheckpoint {
+        relay_url: relay_url.to_owned(),
+        filter_key: filter_key.clone(),
+        since_floor: filter.since.unwrap_or(0),
+        until_cursor: filter.until.unwrap_or(now_unix),
+        window_secs: policy
+            .initial_window_secs
+            .clamp(min_window_secs, max_window_secs),
+        pages: 0,
+        events_fetched: 0,
+        truncated_seconds: 0,
+        completed: false,
+        updated_at_ms: now_ms,
+    });
+    let mut report = RadrootsRelayBackfillReport {
+        pages: 0,
+        events_fetched: 0,
+        inserted_count: 0,
+        duplicate_count: 0,
+        truncated_pages: 0,
+        checkpoint: checkpoint.clone(),
+    };
+    if checkpoint.since_floor > checkpoint.until_cursor {
+        checkpoint.completed = true;
+    }
+
+    while !checkpoint.completed && report.pages < policy.max_pages_per_run.max(1) {
+        let until = checkpoint.until_cursor;
+        let start = until
+            .saturating_sub(checkpoint.window_secs.saturating_sub(1))
+            .max(checkpoint.since_floor);
+        let items = source
+            .fetch_page(
+                relay_url,
+                RadrootsRelayBackfillPage {
+                    filter: filter.window(start, until),
+                    limit: page_limit,
+                    observed_at_ms: now_ms,
+                },
+            )
+            .await?;
+        let created_at = items
+            .iter()
+            .filter_map(|item| match item {
+                RadrootsRelayFetchItem::Event { raw_json, .. } => Some(raw_created_at(raw_json)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let max_events = items.len();
+        let receipt = fetch_and_ingest_relay_events(
+            &PreparedFetchItems::new(items),
+            event_store,
+            RadrootsRelayFetchRequest::fetch(now_ms, max_events),
+        )
+        .await?;
+
+        report.pages += 1;
+        report.events_fetched += created_at.len();
+        report.inserted_count += receipt.inserted_count;
+        report.duplicate_count += receipt.duplicate_count;
+        checkpoint.pages = checkpoint.pages.saturating_add(1);
+        checkpoint.events_fetched = checkpoint
+            .events_fetched
+            .saturating_add(u64::try_from(created_at.len()).unwrap_or(u64::MAX));
+
+        let full = created_at.len() >= page_limit;
+        if full {
+            report.truncated_pages += 1;
+        }
+        match created_at.into_iter().flatten().min() {
+            None => {
+                if start <= checkpoint.since_floor {
+                    checkpoint.completed = true;
+                } else {
+                    checkpoint.until_cursor = start - 1;
+                    checkpoint.window_secs = checkpoint
+                        .window_secs
+                        .saturating_mul(2)
+                        .min(max_window_secs);
+                }
+            }
+            Some(oldest) if oldest < until => {
+                checkpoint.until_cursor = oldest.max(checkpoint.since_floor);
+                if full {
+                    checkpoint.window_secs = (checkpoint.window_secs / 2).max(min_window_secs);
+                }
+            }
+            Some(_) => {
+                if full {
+                    checkpoint.truncated_seconds = checkpoint.truncated_seconds.saturating_add(1);
+                }
+                if until <= checkpoint.since_floor {
+                    checkpoint.completed = true;
+                } else {
+                    checkpoint.until_cursor = until - 1;
+                    if full {
+                        checkpoint.window_secs = min_window_secs;
+                    }
+                }
+            }
+        }
+        checkpoint.updated_at_ms = now_ms;
+        event_store
+            .save_relay_backfill_checkpoint(&checkpoint)
+            .await?;
+    }
+    report.checkpoint = checkpoint;
+    Ok(report)
+}
+
+pub struct RadrootsInProcessBackfillPeer {
+    event_store: RadrootsEventStore,
+    result_cap: Option<usize>,
+    pages: Mutex<Vec<RadrootsRelayBackfillPage>>,
+}
+
+impl RadrootsInProcessBackfillPeer {
+    pub fn new(event_store: RadrootsEventStore) -> Self {
+        Self {
+            event_store,
+            result_cap: None,
+            pages: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_result_cap(mut self, result_cap: usize) -> Self {
+        self.result_cap = Some(result_cap);
+        self
+    }
+
+    pub fn requested_pages(
+        &self,
+    ) -> Result<Vec<RadrootsRelayBackfillPage>, RadrootsRelayTransportError> {
+        Ok(self.pages.lock().map_err(|_| lock_poisoned())?.clone())
+    }
+
+    pub fn into_event_store(self) -> RadrootsEventStore {
+        self.event_store
+    }
+}
+
+impl RadrootsRelayBackfillSource for RadrootsInProcessBackfillPeer {
+    fn fetch_page<'a>(
+        &'a self,
+        relay_url: &'a str,
+        page: RadrootsRelayBackfillPage,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            self.pages
+                .lock()
+                .map_err(|_| lock_poisoned())?
+                .push(page.clone());
+            let limit = self
+                .result_cap
+                .map_or(page.limit, |result_cap| result_cap.min(page.limit));
+            let now_unix = u32::try_from(page.observed_at_ms.max(0) / 1_000).unwrap_or(u32::MAX);
+            let events = self
+                .event_store
+                .query_events(&page.filter.to_event_query(limit), now_unix)
+                .await?;
+            let mut items = events
+                .into_iter()
+                .map(|event| RadrootsRelayFetchItem::Event {
+                    relay_url: relay_url.to_owned(),
+                    raw_json: event.raw_json,
+                    observed_at_ms: page.observed_at_ms,
+                })
+                .collect::<Vec<_>>();
+            items.push(RadrootsRelayFetchItem::Eose {
+                relay_url: relay_url.to_owned(),
+            });
+            Ok(items)
+        })
+    }
+}
+
+struct PreparedFetchItems {
+    items: Mutex<Vec<RadrootsRelayFetchItem>>,
+}
+
+impl PreparedFetchItems {
+    fn new(items: Vec<RadrootsRelayFetchItem>) -> Self {
+        Self {
+            items: Mutex::new(items),
+        }
+    }
+}
+
+impl RadrootsRelayFetchAdapter for PreparedFetchItems {
+    fn fetch<'a>(
+        &'a self,
+        _request: RadrootsRelayFetchRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            Ok(std::mem::take(
+                &mut *self.items.lock().map_err(|_| lock_poisoned())?,
+            ))
+        })
+    }
+}
+
+fn raw_created_at(raw_json: &str) -> Option<u32> {
+    serde_json::from_str::<Value>(raw_json)
+        .ok()?
+        .get("created_at")?
+        .as_u64()
+        .and_then(|created_at| u32::try_from(created_at).ok())
+}
+
+fn sorted<T: Ord>(values: impl Iterator<Item = T>) -> Vec<T> {
+    values.collect::<BTreeSet<_>>().into_iter().collect()
+}
+
+fn lock_poisoned() -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport("backfill peer lock poisoned".to_owned())
+}
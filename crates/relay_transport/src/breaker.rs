@@ -0,0 +1,543 @@
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "storage")]
+use crate::{RadrootsRelayFetchAdapter, RadrootsRelayFetchItem, RadrootsRelayFetchRequest};
+use crate::{
+    RadrootsRelayOutcome, RadrootsRelayOutcomeKind, RadrootsRelayPublishAdapter,
+    RadrootsRelayPublishRelayReceipt, RadrootsRelayPublishRequest, RadrootsRelayTargetSet,
+    RadrootsRelayTransportError, RadrootsRelayUrl,
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RadrootsRelayCircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRelayCircuitPolicy {
+    pub failure_threshold: u32,
+    pub cool_down_ms: i64,
+    pub max_cool_down_ms: i64,
+    pub cool_down_jitter_ms: i64,
+    pub bucket_capacity: f64,
+    pub refill_per_sec: f64,
+    pub min_refill_per_sec: f64,
+    pub rate_limited_factor: f64,
+    pub recovery_factor: f64,
+}
+
+impl Default for RadrootsRelayCircuitPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            cool_down_ms: 30_000,
+            max_cool_down_ms: 10 * 60 * 1_000,
+            cool_down_jitter_ms: 5_000,
+            bucket_capacity: 10.0,
+            refill_per_sec: 5.0,
+            min_refill_per_sec: 0.1,
+            rate_limited_factor: 0.5,
+            recovery_factor: 1.25,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RadrootsRelayAdmission {
+    Allowed,
+    Paused { until_ms: i64 },
+    Throttled { retry_at_ms: i64 },
+}
+
+impl RadrootsRelayAdmission {
+    pub fn is_allowed(self) -> bool {
+        matches!(self, Self::Allowed)
+    }
+
+    pub fn outcome(self) -> Option<RadrootsRelayOutcome> {
+        match self {
+            Self::Allowed => None,
+            Self::Paused { until_ms } => Some(RadrootsRelayOutcome::classify(format!(
+                "rate-limited: relay paused by circuit breaker until {until_ms}"
+            ))),
+            Self::Throttled { retry_at_ms } => Some(RadrootsRelayOutcome::classify(format!(
+                "rate-limited: relay request budget exhausted until {retry_at_ms}"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRelayCircuitSnapshot {
+    pub relay_url: String,
+    pub state: RadrootsRelayCircuitState,
+    pub consecutive_failures: u32,
+    pub trips: u32,
+    pub paused_until_ms: Option<i64>,
+    pub tokens: f64,
+    pub refill_per_sec: f64,
+    pub last_outcome: Option<RadrootsRelayOutcomeKind>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayPartition {
+    pub admitted: Vec<RadrootsRelayUrl>,
+    pub held: Vec<(RadrootsRelayUrl, RadrootsRelayAdmission)>,
+}
+
+#[derive(Clone, Debug)]
+struct RelayCircuit {
+    state: RadrootsRelayCircuitState,
+    consecutive_failures: u32,
+    trips: u32,
+    paused_until_ms: Option<i64>,
+    probe_in_flight: bool,
+    tokens: f64,
+    refill_per_sec: f64,
+    refilled_at_ms: i64,
+    last_outcome: Option<RadrootsRelayOutcomeKind>,
+}
+
+impl RelayCircuit {
+    fn new(policy: &RadrootsRelayCircuitPolicy, now_ms: i64) -> Self {
+        Self {
+            state: RadrootsRelayCircuitState::Closed,
+            consecutive_failures: 0,
+            trips: 0,
+            paused_until_ms: None,
+            probe_in_flight: false,
+            tokens: policy.bucket_capacity.max(1.0),
+            refill_per_sec: policy.refill_per_sec.max(policy.min_refill_per_sec),
+            refilled_at_ms: now_ms,
+            last_outcome: None,
+        }
+    }
+
+    fn refill(&mut self, policy: &RadrootsRelayCircuitPolicy, now_ms: i64) {
+        let elapsed_ms = now_ms.saturating_sub(self.refilled_at_ms).max(0);
+        self.tokens = (self.tokens + elapsed_ms as f64 / 1_000.0 * self.refill_per_sec)
+            .min(policy.bucket_capacity.max(1.0));
+        self.refilled_at_ms = self.refilled_at_ms.max(now_ms);
+        if self.state == RadrootsRelayCircuitState::Open
+            && self
+                .paused_until_ms
+                .is_none_or(|until_ms| now_ms >= until_ms)
+        {
+            self.state = RadrootsRelayCircuitState::HalfOpen;
+            self.probe_in_flight = false;
+        }
+    }
+
+    fn admission(&self, now_ms: i64) -> RadrootsRelayAdmission {
+        match self.state {
+            RadrootsRelayCircuitState::Open => RadrootsRelayAdmission::Paused {
+                until_ms: self.paused_until_ms.unwrap_or(now_ms),
+            },
+            RadrootsRelayCircuitState::HalfOpen if self.probe_in_flight => {
+                RadrootsRelayAdmission::Paused { until_ms: now_ms }
+            }
+            _ if self.tokens < 1.0 => {
+                let missing = 1.0 - self.tokens;
+                let wait_ms = (missing / self.refill_per_sec * 1_000.0).ceil() as i64;
+                RadrootsRelayAdmission::Throttled {
+                    retry_at_ms: now_ms.saturating_add(wait_ms.max(1)),
+                }
+            }
+            _ => RadrootsRelayAdmission::Allowed,
+        }
+    }
+
+    fn consume(&mut self) {
+        self.tokens -= 1.0;
+        if self.state == RadrootsRelayCircuitState::HalfOpen {
+            self.probe_in_flight = true;
+        }
+    }
+
+    fn snapshot(&self, relay_url: &str) -> RadrootsRelayCircuitSnapshot {
+        RadrootsRelayCircuitSnapshot {
+            relay_url: relay_url.to_owned(),
+            state: self.state,
+            consecutive_failures: self.consecutive_failures,
+            trips: self.trips,
+            paused_until_ms: match self.state {
+                RadrootsRelayCircuitState::Open => self.paused_until_ms,
+                _ => None,
+            },
+            tokens: self.tokens,
+            refill_per_sec: self.refill_per_sec,
+            last_outcome: self.last_outcome,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RadrootsRelayCircuitBreaker {
+    policy: RadrootsRelayCircuitPolicy,
+    relays: Arc<Mutex<BTreeMap<String, RelayCircuit>>>,
+}
+
+impl Default for RadrootsRelayCircuitBreaker {
+    fn default() -> Self {
+        Self::new(RadrootsRelayCircuitPolicy::default())
+    }
+}
+
+impl RadrootsRelayCircuitBreaker {
+    pub fn new(policy: RadrootsRelayCircuitPolicy) -> Self {
+        Self {
+            policy,
+            relays: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn policy(&self) -> &RadrootsRelayCircuitPolicy {
+        &self.policy
+    }
+
+    pub fn admit(
+        &self,
+        relay_url: &str,
+        now_ms: i64,
+    ) -> Result<RadrootsRelayAdmission, RadrootsRelayTransportError> {
+        let mut relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        let circuit = relays
+            .entry(relay_url.to_owned())
+            .or_insert_with(|| RelayCircuit::new(&self.policy, now_ms));
+        circuit.refill(&self.policy, now_ms);
+        let admission = circuit.admission(now_ms);
+        if admission.is_allowed() {
+            circuit.consume();
+        }
+        Ok(admission)
+    }
+
+    pub fn peek(
+        &self,
+        relay_url: &str,
+        now_ms: i64,
+    ) -> Result<RadrootsRelayAdmission, RadrootsRelayTransportError> {
+        let relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        Ok(relays
+            .get(relay_url)
+            .map_or(RadrootsRelayAdmission::Allowed, |circuit| {
+                let mut circuit = circuit.clone();
+                circuit.refill(&self.policy, now_ms);
+                circuit.admission(now_ms)
+            }))
+    }
+
+    pub fn record(
+        &self,
+        relay_url: &str,
+        outcome: &RadrootsRelayOutcome,
+        now_ms: i64,
+    ) -> Result<RadrootsRelayCircuitSnapshot, RadrootsRelayTransportError> {
+        let mut relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        let circuit = relays
+            .entry(relay_url.to_owned())
+            .or_insert_with(|| RelayCircuit::new(&self.policy, now_ms));
+        circuit.refill(&self.policy, now_ms);
+        circuit.last_outcome = Some(outcome.kind);
+        circuit.probe_in_flight = false;
+        match outcome.kind {
+            RadrootsRelayOutcomeKind::Accepted
+            | RadrootsRelayOutcomeKind::DuplicateAccepted
+            | RadrootsRelayOutcomeKind::Blocked
+            | RadrootsRelayOutcomeKind::Invalid
+            | RadrootsRelayOutcomeKind::Restricted => {
+                circuit.state = RadrootsRelayCircuitState::Closed;
+                circuit.consecutive_failures = 0;
+                circuit.paused_until_ms = None;
+                circuit.refill_per_sec = (circuit.refill_per_sec * self.policy.recovery_factor)
+                    .min(
+                        self.policy
+                            .refill_per_sec
+                            .max(self.policy.min_refill_per_sec),
+                    );
+            }
+            RadrootsRelayOutcomeKind::AuthRequired | RadrootsRelayOutcomeKind::PowRequired => {}
+            RadrootsRelayOutcomeKind::RateLimited => {
+                circuit.tokens = 0.0;
+                circuit.refill_per_sec = (circuit.refill_per_sec * self.policy.rate_limited_factor)
+                    .max(self.policy.min_refill_per_sec);
+                self.fail(relay_url, circuit, now_ms);
+            }
+            RadrootsRelayOutcomeKind::Error
+            | RadrootsRelayOutcomeKind::Timeout
+            | RadrootsRelayOutcomeKind::ConnectionFailed
+            | RadrootsRelayOutcomeKind::Unknown => self.fail(relay_url, circuit, now_ms),
+        }
+        Ok(circuit.snapshot(relay_url))
+    }
+
+    pub fn snapshot(
+        &self,
+        relay_url: &str,
+        now_ms: i64,
+    ) -> Result<Option<RadrootsRelayCircuitSnapshot>, RadrootsRelayTransportError> {
+        let relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        Ok(relays.get(relay_url).map(|circuit| {
+            let mut circuit = circuit.clone();
+            circuit.refill(&self.policy, now_ms);
+            circuit.snapshot(relay_url)
+        }))
+    }
+
+    pub fn snapshots(
+        &self,
+        now_ms: i64,
+    ) -> Result<Vec<RadrootsRelayCircuitSnapshot>, RadrootsRelayTransportError> {
+        let relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        Ok(relays
+            .iter()
+            .map(|(relay_url, circuit)| {
+                let mut circuit = circuit.clone();
+                circuit.refill(&self.policy, now_ms);
+                circuit.snapshot(relay_url)
+            })
+            .collect())
+    }
+
+    pub fn blocked_relays(&self, now_ms: i64) -> Result<Vec<String>, RadrootsRelayTransportError> {
+        let relays = self.relays.lock().map_err(|_| lock_poisoned())?;
+        Ok(relays
+            .iter()
+            .filter(|(_, circuit)| {
+                let mut circuit = (*circuit).clone();
+                circuit.refill(&self.policy, now_ms);
+                !circuit.admission(now_ms).is_allowed()
+            })
+            .map(|(relay_url, _)| relay_url.clone())
+            .collect())
+    }
+
+    pub fn partition(
+        &self,
+        targets: &RadrootsRelayTargetSet,
+        now_ms: i64,
+    ) -> Result<RadrootsRelayPartition, RadrootsRelayTransportError> {
+        let mut admitted = Vec::new();
+        let mut held = Vec::new();
+        for relay in targets.relays() {
+            match self.admit(relay.as_str(), now_ms)? {
+                RadrootsRelayAdmission::Allowed => admitted.push(relay.clone()),
+                admission => held.push((relay.clone(), admission)),
+            }
+        }
+        Ok(RadrootsRelayPartition { admitted, held })
+    }
+
+    fn fail(&self, relay_url: &str, circuit: &mut RelayCircuit, now_ms: i64) {
+        circuit.consecutive_failures = circuit.consecutive_failures.saturating_add(1);
+        let trip = circuit.state == RadrootsRelayCircuitState::HalfOpen
+            || circuit.consecutive_failures >= self.policy.failure_threshold.max(1);
+        if !trip {
+            return;
+        }
+        circuit.trips = circuit.trips.saturating_add(1);
+        let exponent = circuit.trips.saturating_sub(1).min(16);
+        let cool_down_ms = self
+            .policy
+            .cool_down_ms
+            .max(0)
+            .saturating_mul(1_i64 << exponent)
+            .min(self.policy.max_cool_down_ms.max(self.policy.cool_down_ms));
+        let jitter_ms = jitter(relay_url, circuit.trips, self.policy.cool_down_jitter_ms);
+        circuit.state = RadrootsRelayCircuitState::Open;
+        circuit.paused_until_ms = Some(
+            now_ms
+                .saturating_add(cool_down_ms)
+                .saturating_add(jitter_ms),
+        );
+    }
+}
+
+pub struct RadrootsCircuitBreakerPublishAdapter<A> {
+    inner: A,
+    breaker: RadrootsRelayCircuitBreaker,
+}
+
+impl<A> RadrootsCircuitBreakerPublishAdapter<A>
+where
+    A: RadrootsRelayPublishAdapter,
+{
+    pub fn new(inner: A, breaker: RadrootsRelayCircuitBreaker) -> Self {
+        Self { inner, breaker }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn breaker(&self) -> &RadrootsRelayCircuitBreaker {
+        &self.breaker
+    }
+}
+
+impl<A> RadrootsRelayPublishAdapter for RadrootsCircuitBreakerPublishAdapter<A>
+where
+    A: RadrootsRelayPublishAdapter,
+{
+    fn publish<'a>(
+        &'a self,
+        request: RadrootsRelayPublishRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayPublishRelayReceipt>, RadrootsRelayTransportError>>
+    {
+        Box::pin(async move {
+            let now_ms = request.now_ms;
+            let RadrootsRelayPartition { admitted, held } =
+                self.breaker.partition(&request.targets, now_ms)?;
+            let mut receipts = held
+                .into_iter()
+                .filter_map(|(relay, admission)| {
+                    admission.outcome().map(|outcome| {
+                        RadrootsRelayPublishRelayReceipt::skipped(relay.as_str(), outcome)
+                    })
+                })
+                .collect::<Vec<_>>();
+            if admitted.is_empty() {
+                return Ok(receipts);
+            }
+            let inner_request = RadrootsRelayPublishRequest {
+                targets: RadrootsRelayTargetSet::from_urls(admitted)?,
+                ..request
+            };
+            for receipt in self.inner.publish(inner_request).await? {
+                if receipt.attempted {
+                    self.breaker
+                        .record(receipt.relay_url.as_str(), &receipt.outcome, now_ms)?;
+                }
+                receipts.push(receipt);
+            }
+            receipts.sort_by(|left, right| left.relay_url.cmp(&right.relay_url));
+            Ok(receipts)
+        })
+    }
+}
+
+#[cfg(feature = "storage")]
+pub struct RadrootsCircuitBreakerFetchAdapter<A> {
+    inner: A,
+    breaker: RadrootsRelayCircuitBreaker,
+    targets: RadrootsRelayTargetSet,
+}
+
+#[cfg(feature = "storage")]
+impl<A> RadrootsCircuitBreakerFetchAdapter<A>
+where
+    A: RadrootsRelayFetchAdapter,
+{
+    pub fn new(
+        inner: A,
+        breaker: RadrootsRelayCircuitBreaker,
+        targets: RadrootsRelayTargetSet,
+    ) -> Self {
+        Self {
+            inner,
+            breaker,
+            targets,
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn breaker(&self) -> &RadrootsRelayCircuitBreaker {
+        &self.breaker
+    }
+
+    pub fn targets(&self) -> &RadrootsRelayTargetSet {
+        &self.targets
+    }
+}
+
+#[cfg(feature = "storage")]
+impl<A> RadrootsRelayFetchAdapter for RadrootsCircuitBreakerFetchAdapter<A>
+where
+    A: RadrootsRelayFetchAdapter,
+{
+    fn fetch<'a>(
+        &'a self,
+        request: RadrootsRelayFetchRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            let now_ms = request.observed_at_ms;
+            let mut skip_relays = request.skip_relays.clone();
+            let mut admitted = Vec::new();
+            for relay in self.targets.relays() {
+                if request.skips_relay(relay.as_str()) {
+                    continue;
+                }
+                if self.breaker.admit(relay.as_str(), now_ms)?.is_allowed() {
+                    admitted.push(relay.as_str().to_owned());
+                } else {
+                    skip_relays.push(relay.as_str().to_owned());
+                }
+            }
+            if admitted.is_empty() {
+                return Ok(Vec::new());
+            }
+            let items = match self
+                .inner
+                .fetch(request.with_skip_relays(skip_relays))
+                .await
+            {
+                Ok(items) => items,
+                Err(error) => {
+                    let outcome = RadrootsRelayOutcome::connection_failed(error.to_string());
+                    for relay_url in &admitted {
+                        self.breaker.record(relay_url, &outcome, now_ms)?;
+                    }
+                    return Err(error);
+                }
+            };
+            let mut outcomes = BTreeMap::<&str, RadrootsRelayOutcome>::new();
+            for item in &items {
+                match item {
+                    RadrootsRelayFetchItem::Event { relay_url, .. }
+                    | RadrootsRelayFetchItem::Eose { relay_url } => {
+                        outcomes
+                            .entry(relay_url.as_str())
+                            .or_insert_with(RadrootsRelayOutcome::accepted);
+                    }
+                    RadrootsRelayFetchItem::Closed { relay_url, message } => {
+                        outcomes
+                            .insert(relay_url.as_str(), RadrootsRelayOutcome::classify(message));
+                    }
+                    RadrootsRelayFetchItem::Notice { .. } => {}
+                }
+            }
+            for relay_url in &admitted {
+                let outcome = outcomes.remove(relay_url.as_str()).unwrap_or_else(|| {
+                    RadrootsRelayOutcome::timeout("relay did not answer the fetch")
+                });
+                self.breaker.record(relay_url, &outcome, now_ms)?;
+            }
+            Ok(items)
+        })
+    }
+}
+
+fn jitter(relay_url: &str, trips: u32, jitter_ms: i64) -> i64 {
+    if jitter_ms <= 0 {
+        return 0;
+    }
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in relay_url.bytes().chain(trips.to_le_bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    i64::try_from(hash % (jitter_ms as u64 + 1)).unwrap_or(0)
+}
+
+fn lock_poisoned() -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport("relay circuit breaker lock poisoned".to_owned())
+}
@@ -43,6 +43,9 @@ pub enum RadrootsRelayTransportError {
     #[error("Outbox claim {0} does not contain a signed event")]
     MissingSignedOutboxEvent(i64),
 
+    #[error("Relay auth error: {0}")]
+    RelayAuth(String),
+
     #[error("Relay transport error: {0}")]
     Transport(String),
 }
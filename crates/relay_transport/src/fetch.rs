@@ -22,6 +22,7 @@ pub struct RadrootsRelayFetchRequest {
     pub mode: RadrootsRelayFetchMode,
     pub observed_at_ms: i64,
     pub max_events: usize,
+    pub skip_relays: Vec<String>,
 }
 
 impl RadrootsRelayFetchRequest {
@@ -30,6 +31,7 @@ impl RadrootsRelayFetchRequest {
             mode: RadrootsRelayFetchMode::Fetch,
             observed_at_ms,
             max_events,
+            skip_relays: Vec::new(),
         }
     }
 
@@ -38,8 +40,18 @@ impl RadrootsRelayFetchRequest {
             mode: RadrootsRelayFetchMode::Subscription,
             observed_at_ms,
             max_events,
+            skip_relays: Vec::new(),
         }
     }
+
+    pub fn with_skip_relays(mut self, skip_relays: Vec<String>) -> Self {
+        self.skip_relays = skip_relays;
+        self
+    }
+
+    pub fn skips_relay(&self, relay_url: &str) -> bool {
+        self.skip_relays.iter().any(|skipped| skipped == relay_url)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -62,6 +74,17 @@ pub enum RadrootsRelayFetchItem {
     },
 }
 
+impl RadrootsRelayFetchItem {
+    pub fn relay_url(&self) -> &str {
+        match self {
+            Self::Event { relay_url, .. }
+            | Self::Eose { relay_url }
+            | Self::Closed { relay_url, .. }
+            | Self::Notice { relay_url, .. } => relay_url.as_str(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RadrootsRelayFetchOutcomeKind {
     Eose,
@@ -264,7 +287,7 @@ impl RadrootsMockRelayFetchAdapter {
 impl RadrootsRelayFetchAdapter for RadrootsMockRelayFetchAdapter {
     fn fetch<'a>(
         &'a self,
-        _request: RadrootsRelayFetchRequest,
+        request: RadrootsRelayFetchRequest,
     ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
         Box::pin(async move {
             Ok(self
@@ -273,7 +296,10 @@ impl RadrootsRelayFetchAdapter for RadrootsMockRelayFetchAdapter {
                 .map_err(|_| {
                     RadrootsRelayTransportError::Transport("fetch item lock poisoned".to_owned())
                 })?
-                .clone())
+                .iter()
+                .filter(|item| !request.skips_relay(item.relay_url()))
+                .cloned()
+                .collect())
         })
     }
 }
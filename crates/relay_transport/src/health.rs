@@ -0,0 +1,248 @@
+#![forbid(unsafe_code)]
+
+use crate::{RadrootsRelayTargetSet, RadrootsRelayTransportError, RadrootsRelayUrl};
+use radroots_event_store::{RadrootsEventStore, RadrootsRelayDeliveryBucket};
+use radroots_outbox::{RadrootsOutbox, RadrootsOutboxRelayPublishBucket};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const RADROOTS_RELAY_HEALTH_NEUTRAL_SCORE: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRelayHealthWeights {
+    pub acceptance: f64,
+    pub latency: f64,
+    pub freshness: f64,
+    pub uniqueness: f64,
+}
+
+impl Default for RadrootsRelayHealthWeights {
+    fn default() -> Self {
+        Self {
+            acceptance: 0.4,
+            latency: 0.2,
+            freshness: 0.2,
+            uniqueness: 0.2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRelayHealthPolicy {
+    pub window_ms: i64,
+    pub bucket_ms: i64,
+    pub half_life_ms: i64,
+    pub latency_reference_ms: i64,
+    pub freshness_half_life_ms: i64,
+    pub weights: RadrootsRelayHealthWeights,
+}
+
+impl Default for RadrootsRelayHealthPolicy {
+    fn default() -> Self {
+        Self {
+            window_ms: 7 * 24 * 60 * 60 * 1_000,
+            bucket_ms: 60 * 60 * 1_000,
+            half_life_ms: 24 * 60 * 60 * 1_000,
+            latency_reference_ms: 2_000,
+            freshness_half_life_ms: 6 * 60 * 60 * 1_000,
+            weights: RadrootsRelayHealthWeights::default(),
+        }
+    }
+}
+
+impl RadrootsRelayHealthPolicy {
+    fn decay(&self, bucket_start_ms: i64, now_ms: i64) -> f64 {
+        let midpoint_ms = bucket_start_ms
+            .saturating_add(self.bucket_ms / 2)
+            .min(now_ms);
+        half_life_factor(now_ms.saturating_sub(midpoint_ms), self.half_life_ms)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRelayHealthScore {
+    pub relay_url: String,
+    pub acceptance: f64,
+    pub latency: f64,
+    pub freshness: f64,
+    pub uniqueness: f64,
+    pub score: f64,
+    pub last_activity_at_ms: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RadrootsRankedRelaySet {
+    scores: Vec<RadrootsRelayHealthScore>,
+}
+
+#[derive(Default)]
+struct RelayHealthSums {
+    attempts: f64,
+    accepted: f64,
+    events_seen: f64,
+    unique_events: f64,
+    lag_ms: f64,
+    last_activity_at_ms: Option<i64>,
+}
+
+impl RelayHealthSums {
+    fn touch(&mut self, at_ms: Option<i64>) {
+        self.last_activity_at_ms = self.last_activity_at_ms.max(at_ms);
+    }
+
+    fn score(
+        &self,
+        relay_url: String,
+        policy: &RadrootsRelayHealthPolicy,
+        now_ms: i64,
+    ) -> RadrootsRelayHealthScore {
+        let acceptance = (self.accepted + 1.0) / (self.attempts + 2.0);
+        let latency = if self.events_seen > 0.0 {
+            let mean_lag_ms = self.lag_ms / self.events_seen;
+            1.0 / (1.0 + mean_lag_ms / policy.latency_reference_ms.max(1) as f64)
+        } else {
+            RADROOTS_RELAY_HEALTH_NEUTRAL_SCORE
+        };
+        let freshness = self.last_activity_at_ms.map_or(0.0, |at_ms| {
+            half_life_factor(now_ms.saturating_sub(at_ms), policy.freshness_half_life_ms)
+        });
+        let uniqueness = if self.events_seen > 0.0 {
+            self.unique_events / self.events_seen
+        } else {
+            0.0
+        };
+        let weights = policy.weights;
+        let total_weight =
+            weights.acceptance + weights.latency + weights.freshness + weights.uniqueness;
+        let score = if total_weight > 0.0 {
+            (weights.acceptance * acceptance
+                + weights.latency * latency
+                + weights.freshness * freshness
+                + weights.uniqueness * uniqueness)
+                / total_weight
+        } else {
+            RADROOTS_RELAY_HEALTH_NEUTRAL_SCORE
+        };
+        RadrootsRelayHealthScore {
+            relay_url,
+            acceptance,
+            latency,
+            freshness,
+            uniqueness,
+            score,
+            last_activity_at_ms: self.last_activity_at_ms,
+        }
+    }
+}
+
+impl RadrootsRankedRelaySet {
+    pub fn from_history(
+        deliveries: &[RadrootsRelayDeliveryBucket],
+        publishes: &[RadrootsOutboxRelayPublishBucket],
+        policy: &RadrootsRelayHealthPolicy,
+        now_ms: i64,
+    ) -> Self {
+        let mut sums = BTreeMap::<String, RelayHealthSums>::new();
+        for bucket in deliveries {
+            let decay = policy.decay(bucket.bucket_start_ms, now_ms);
+            let relay = sums.entry(bucket.relay_url.clone()).or_default();
+            relay.events_seen += bucket.events_seen as f64 * decay;
+            relay.unique_events += bucket.unique_events as f64 * decay;
+            relay.lag_ms += bucket.delivery_lag_ms_sum as f64 * decay;
+            relay.touch(Some(bucket.last_seen_at_ms));
+        }
+        for bucket in publishes {
+            let decay = policy.decay(bucket.bucket_start_ms, now_ms);
+            let relay = sums.entry(bucket.relay_url.clone()).or_default();
+            relay.attempts += bucket.attempts.max(0) as f64 * decay;
+            relay.accepted += bucket.accepted.max(0) as f64 * decay;
+            relay.touch(bucket.last_accepted_at_ms);
+        }
+        let mut scores = sums
+            .into_iter()
+            .map(|(relay_url, relay)| relay.score(relay_url, policy, now_ms))
+            .collect::<Vec<_>>();
+        scores.sort_by(|left, right| {
+            right
+                .score
+                .total_cmp(&left.score)
+                .then_with(|| left.relay_url.cmp(&right.relay_url))
+        });
+        Self { scores }
+    }
+
+    pub fn scores(&self) -> &[RadrootsRelayHealthScore] {
+        self.scores.as_slice()
+    }
+
+    pub fn score_for(&self, relay_url: &str) -> Option<&RadrootsRelayHealthScore> {
+        self.scores
+            .iter()
+            .find(|score| score.relay_url == relay_url)
+    }
+
+    pub fn rank(&self, candidates: &RadrootsRelayTargetSet) -> Vec<RadrootsRelayUrl> {
+        let mut ranked = candidates.relays().to_vec();
+        ranked.sort_by(|left, right| {
+            self.candidate_score(right)
+                .total_cmp(&self.candidate_score(left))
+                .then_with(|| left.cmp(right))
+        });
+        ranked
+    }
+
+    pub fn select(
+        &self,
+        candidates: &RadrootsRelayTargetSet,
+        count: usize,
+    ) -> Result<RadrootsRelayTargetSet, RadrootsRelayTransportError> {
+        let mut ranked = self.rank(candidates);
+        ranked.truncate(count.max(1));
+        RadrootsRelayTargetSet::from_urls(ranked)
+    }
+
+    pub fn order(&self, relays: &mut [RadrootsRelayUrl]) {
+        relays.sort_by(|left, right| {
+            self.candidate_score(right)
+                .total_cmp(&self.candidate_score(left))
+        });
+    }
+
+    pub(crate) fn candidate_score(&self, relay_url: &RadrootsRelayUrl) -> f64 {
+        self.score_for(relay_url.as_str())
+            .map_or(RADROOTS_RELAY_HEALTH_NEUTRAL_SCORE, |score| score.score)
+    }
+}
+
+pub async fn compute_relay_health(
+    event_store: &RadrootsEventStore,
+    outbox: Option<&RadrootsOutbox>,
+    policy: &RadrootsRelayHealthPolicy,
+    now_ms: i64,
+) -> Result<RadrootsRankedRelaySet, RadrootsRelayTransportError> {
+    let since_ms = now_ms.saturating_sub(policy.window_ms);
+    let deliveries = event_store
+        .relay_delivery_buckets(since_ms, policy.bucket_ms)
+        .await?;
+    let publishes = match outbox {
+        Some(outbox) => {
+            outbox
+                .relay_publish_buckets(since_ms, policy.bucket_ms)
+                .await?
+        }
+        None => Vec::new(),
+    };
+    Ok(RadrootsRankedRelaySet::from_history(
+        deliveries.as_slice(),
+        publishes.as_slice(),
+        policy,
+        now_ms,
+    ))
+}
+
+fn half_life_factor(age_ms: i64, half_life_ms: i64) -> f64 {
+    if half_life_ms <= 0 {
+        return if age_ms <= 0 { 1.0 } else { 0.0 };
+    }
+    0.5_f64.powf(age_ms.max(0) as f64 / half_life_ms as f64)
+}
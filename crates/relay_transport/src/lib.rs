@@ -1,14 +1,51 @@
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "storage")]
+mod auth;
+#[cfg(feature = "storage")]
+mod backfill;
+mod breaker;
 mod error;
 #[cfg(feature = "storage")]
 mod fetch;
 #[cfg(feature = "storage")]
+mod health;
+#[cfg(feature = "storage")]
+mod negentropy;
+#[cfg(feature = "storage")]
 mod outbox;
 mod outcome;
 mod publish;
 mod relay;
+#[cfg(feature = "storage")]
+mod routing;
+#[cfg(feature = "runtime-tokio")]
+mod worker;
 
+#[cfg(feature = "runtime-tokio")]
+pub use auth::{RADROOTS_RELAY_CONNECTION_DEFAULT_TIMEOUT, RadrootsNostrClientRelayConnection};
+#[cfg(feature = "storage")]
+pub use auth::{
+    RadrootsAuthRelayFetchAdapter, RadrootsAuthRelayPublishAdapter, RadrootsRelayAuthClock,
+    RadrootsRelayAuthSigner, RadrootsRelayAuthenticator, RadrootsRelayClientMessage,
+    RadrootsRelayConnection, RadrootsRelayServerMessage,
+};
+#[cfg(feature = "storage")]
+pub use backfill::{
+    RADROOTS_RELAY_BACKFILL_DEFAULT_INITIAL_WINDOW_SECS,
+    RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_PAGES_PER_RUN,
+    RADROOTS_RELAY_BACKFILL_DEFAULT_MAX_WINDOW_SECS, RADROOTS_RELAY_BACKFILL_DEFAULT_PAGE_LIMIT,
+    RadrootsInProcessBackfillPeer, RadrootsRelayBackfillFilter, RadrootsRelayBackfillPage,
+    RadrootsRelayBackfillPolicy, RadrootsRelayBackfillReport, RadrootsRelayBackfillSource,
+    backfill_relay_events,
+};
+#[cfg(feature = "storage")]
+pub use breaker::RadrootsCircuitBreakerFetchAdapter;
+pub use breaker::{
+    RadrootsCircuitBreakerPublishAdapter, RadrootsRelayAdmission, RadrootsRelayCircuitBreaker,
+    RadrootsRelayCircuitPolicy, RadrootsRelayCircuitSnapshot, RadrootsRelayCircuitState,
+    RadrootsRelayPartition,
+};
 pub use error::RadrootsRelayTransportError;
 #[cfg(feature = "storage")]
 pub use fetch::{
@@ -18,6 +55,18 @@ pub use fetch::{
     fetch_and_ingest_relay_events,
 };
 #[cfg(feature = "storage")]
+pub use health::{
+    RADROOTS_RELAY_HEALTH_NEUTRAL_SCORE, RadrootsRankedRelaySet, RadrootsRelayHealthPolicy,
+    RadrootsRelayHealthScore, RadrootsRelayHealthWeights, compute_relay_health,
+};
+#[cfg(feature = "storage")]
+pub use negentropy::{
+    RADROOTS_NEGENTROPY_DEFAULT_ID_BATCH_SIZE, RADROOTS_NEGENTROPY_DEFAULT_MAX_ROUNDS,
+    RadrootsInProcessNegentropyPeer, RadrootsNegentropyClientMessage,
+    RadrootsNegentropyFetchAdapter, RadrootsNegentropyReconcileReport,
+    RadrootsNegentropyRelayMessage, RadrootsRelayNegentropyTransport,
+};
+#[cfg(feature = "storage")]
 pub use outbox::{
     RadrootsOutboxPublishPolicy, RadrootsOutboxPublishReceipt, publish_claimed_outbox_event,
 };
@@ -29,3 +78,16 @@ pub use publish::{
     RadrootsRelayPublishRelayReceipt, RadrootsRelayPublishRequest, publish_signed_event,
 };
 pub use relay::{RadrootsRelayTargetSet, RadrootsRelayUrl, RadrootsRelayUrlPolicy};
+#[cfg(feature = "storage")]
+pub use routing::{
+    RADROOTS_RELAY_ROUTING_DEFAULT_MAX_AUTHORS_PER_REQUEST,
+    RADROOTS_RELAY_ROUTING_DEFAULT_MAX_RELAYS_PER_PUBKEY,
+    RADROOTS_RELAY_ROUTING_DEFAULT_MAX_TOTAL_RELAYS, RadrootsRelayFetchRoute, RadrootsRelayList,
+    RadrootsRelayRouter, RadrootsRelayRoutingPolicy, resolve_relay_lists, resolve_relay_router,
+};
+#[cfg(feature = "runtime-tokio")]
+pub use worker::{
+    RADROOTS_OUTBOX_WORKER_DEFAULT_CLAIM_TTL_MS, RADROOTS_OUTBOX_WORKER_DEFAULT_CONCURRENCY,
+    RADROOTS_OUTBOX_WORKER_DEFAULT_MAX_ATTEMPTS, RadrootsOutboxWorker, RadrootsOutboxWorkerClock,
+    RadrootsOutboxWorkerConfig, RadrootsOutboxWorkerEvent, RadrootsOutboxWorkerReport,
+};
@@ -0,0 +1,486 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    RadrootsRelayFetchAdapter, RadrootsRelayFetchItem, RadrootsRelayFetchRequest,
+    RadrootsRelayTransportError,
+};
+use futures::future::BoxFuture;
+use radroots_event_store::{
+    RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT, RadrootsEventStore, RadrootsNegentropyFilter,
+    RadrootsNegentropySession,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+pub const RADROOTS_NEGENTROPY_DEFAULT_MAX_ROUNDS: u32 = 64;
+pub const RADROOTS_NEGENTROPY_DEFAULT_ID_BATCH_SIZE: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsNegentropyClientMessage {
+    Open {
+        subscription_id: String,
+        filter: RadrootsNegentropyFilter,
+        initial_message: String,
+    },
+    Message {
+        subscription_id: String,
+        message: String,
+    },
+    Close {
+        subscription_id: String,
+    },
+}
+
+impl RadrootsNegentropyClientMessage {
+    pub fn subscription_id(&self) -> &str {
+        match self {
+            Self::Open {
+                subscription_id, ..
+            }
+            | Self::Message {
+                subscription_id, ..
+            }
+            | Self::Close { subscription_id } => subscription_id,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, RadrootsRelayTransportError> {
+        let value = match self {
+            Self::Open {
+                subscription_id,
+                filter,
+                initial_message,
+            } => serde_json::json!(["NEG-OPEN", subscription_id, filter, initial_message]),
+            Self::Message {
+                subscription_id,
+                message,
+            } => serde_json::json!(["NEG-MSG", subscription_id, message]),
+            Self::Close { subscription_id } => serde_json::json!(["NEG-CLOSE", subscription_id]),
+        };
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RadrootsRelayTransportError> {
+        let frame = parse_frame(json)?;
+        match (frame.label.as_str(), frame.rest.as_slice()) {
+            ("NEG-OPEN", [filter, Value::String(initial_message)]) => Ok(Self::Open {
+                subscription_id: frame.subscription_id,
+                filter: serde_json::from_value(filter.clone())?,
+                initial_message: initial_message.clone(),
+            }),
+            ("NEG-MSG", [Value::String(message)]) => Ok(Self::Message {
+                subscription_id: frame.subscription_id,
+                message: message.clone(),
+            }),
+            ("NEG-CLOSE", []) => Ok(Self::Close {
+                subscription_id: frame.subscription_id,
+            }),
+            _ => Err(unexpected_frame(json)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsNegentropyRelayMessage {
+    Message {
+        subscription_id: String,
+        message: String,
+    },
+    Error {
+        subscription_id: String,
+        reason: String,
+    },
+}
+
+impl RadrootsNegentropyRelayMessage {
+    pub fn to_json(&self) -> Result<String, RadrootsRelayTransportError> {
+        let value = match self {
+            Self::Message {
+                subscription_id,
+                message,
+            } => serde_json::json!(["NEG-MSG", subscription_id, message]),
+            Self::Error {
+                subscription_id,
+                reason,
+            } => serde_json::json!(["NEG-ERR", subscription_id, reason]),
+        };
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, RadrootsRelayTransportError> {
+        let frame = parse_frame(json)?;
+        match (frame.label.as_str(), frame.rest.as_slice()) {
+            ("NEG-MSG", [Value::String(message)]) => Ok(Self::Message {
+                subscription_id: frame.subscription_id,
+                message: message.clone(),
+            }),
+            ("NEG-ERR", [Value::String(reason)]) => Ok(Self::Error {
+                subscription_id: frame.subscription_id,
+                reason: reason.clone(),
+            }),
+            _ => Err(unexpected_frame(json)),
+        }
+    }
+}
+
+pub trait RadrootsRelayNegentropyTransport: Send + Sync {
+    fn exchange<'a>(
+        &'a self,
+        relay_url: &'a str,
+        message: RadrootsNegentropyClientMessage,
+    ) -> BoxFuture<'a, Result<Option<RadrootsNegentropyRelayMessage>, RadrootsRelayTransportError>>;
+
+    fn fetch_events<'a>(
+        &'a self,
+        relay_url: &'a str,
+        event_ids: Vec<String>,
+        observed_at_ms: i64,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>>;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsNegentropyReconcileReport {
+    pub relay_url: String,
+    pub rounds: u32,
+    pub local_items: usize,
+    pub have_ids: Vec<String>,
+    pub need_ids: Vec<String>,
+}
+
+pub struct RadrootsNegentropyFetchAdapter<T> {
+    transport: T,
+    event_store: RadrootsEventStore,
+    relay_url: String,
+    filter: RadrootsNegentropyFilter,
+    subscription_id: String,
+    frame_size_limit: u64,
+    max_rounds: u32,
+    id_batch_size: usize,
+}
+
+impl<T> RadrootsNegentropyFetchAdapter<T>
+where
+    T: RadrootsRelayNegentropyTransport,
+{
+    pub fn new(
+        transport: T,
+        event_store: RadrootsEventStore,
+        relay_url: impl Into<String>,
+        filter: RadrootsNegentropyFilter,
+    ) -> Self {
+        Self {
+            transport,
+            event_store,
+            relay_url: relay_url.into(),
+            filter,
+            subscription_id: "radroots-neg".to_owned(),
+            frame_size_limit: RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT,
+            max_rounds: RADROOTS_NEGENTROPY_DEFAULT_MAX_ROUNDS,
+            id_batch_size: RADROOTS_NEGENTROPY_DEFAULT_ID_BATCH_SIZE,
+        }
+    }
+
+    pub fn with_subscription_id(mut self, subscription_id: impl Into<String>) -> Self {
+        self.subscription_id = subscription_id.into();
+        self
+    }
+
+    pub fn with_frame_size_limit(mut self, frame_size_limit: u64) -> Self {
+        self.frame_size_limit = frame_size_limit;
+        self
+    }
+
+    pub fn with_max_rounds(mut self, max_rounds: u32) -> Self {
+        self.max_rounds = max_rounds.max(1);
+        self
+    }
+
+    pub fn with_id_batch_size(mut self, id_batch_size: usize) -> Self {
+        self.id_batch_size = id_batch_size.max(1);
+        self
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub async fn reconcile(
+        &self,
+    ) -> Result<RadrootsNegentropyReconcileReport, RadrootsRelayTransportError> {
+        let mut session = self
+            .event_store
+            .negentropy_session(&self.filter, self.frame_size_limit)
+            .await?;
+        let mut report = RadrootsNegentropyReconcileReport {
+            relay_url: self.relay_url.clone(),
+            local_items: session.item_count(),
+            ..RadrootsNegentropyReconcileReport::default()
+        };
+        let mut outgoing = RadrootsNegentropyClientMessage::Open {
+            subscription_id: self.subscription_id.clone(),
+            filter: self.filter.clone(),
+            initial_message: session.initiate()?,
+        };
+        let result = loop {
+            if report.rounds >= self.max_rounds {
+                break Err(RadrootsRelayTransportError::Transport(format!(
+                    "negentropy reconciliation with {} exceeded {} rounds",
+                    self.relay_url, self.max_rounds
+                )));
+            }
+            report.rounds += 1;
+            let reply = self
+                .transport
+                .exchange(self.relay_url.as_str(), outgoing)
+                .await?;
+            let message = match reply {
+                Some(RadrootsNegentropyRelayMessage::Message { message, .. }) => message,
+                Some(RadrootsNegentropyRelayMessage::Error { reason, .. }) => {
+                    break Err(RadrootsRelayTransportError::Transport(format!(
+                        "negentropy error from {}: {reason}",
+                        self.relay_url
+                    )));
+                }
+                None => {
+                    break Err(RadrootsRelayTransportError::Transport(format!(
+                        "negentropy session with {} ended without a reply",
+                        self.relay_url
+                    )));
+                }
+            };
+            let round = session.reconcile_with_ids(message.as_str())?;
+            report.have_ids.extend(round.have_ids);
+            report.need_ids.extend(round.need_ids);
+            match round.next_message {
+                Some(message) => {
+                    outgoing = RadrootsNegentropyClientMessage::Message {
+                        subscription_id: self.subscription_id.clone(),
+                        message,
+                    };
+                }
+                None => break Ok(()),
+            }
+        };
+        self.transport
+            .exchange(
+                self.relay_url.as_str(),
+                RadrootsNegentropyClientMessage::Close {
+                    subscription_id: self.subscription_id.clone(),
+                },
+            )
+            .await?;
+        result.map(|()| report)
+    }
+}
+
+impl<T> RadrootsRelayFetchAdapter for RadrootsNegentropyFetchAdapter<T>
+where
+    T: RadrootsRelayNegentropyTransport,
+{
+    fn fetch<'a>(
+        &'a self,
+        request: RadrootsRelayFetchRequest,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            if request.skips_relay(self.relay_url.as_str()) {
+                return Ok(Vec::new());
+            }
+            let report = self.reconcile().await?;
+            let mut items = Vec::new();
+            let need_ids = report
+                .need_ids
+                .into_iter()
+                .take(request.max_events)
+                .collect::<Vec<_>>();
+            for chunk in need_ids.chunks(self.id_batch_size) {
+                items.extend(
+                    self.transport
+                        .fetch_events(
+                            self.relay_url.as_str(),
+                            chunk.to_vec(),
+                            request.observed_at_ms,
+                        )
+                        .await?,
+                );
+            }
+            items.push(RadrootsRelayFetchItem::Eose {
+                relay_url: self.relay_url.clone(),
+            });
+            Ok(items)
+        })
+    }
+}
+
+pub struct RadrootsInProcessNegentropyPeer {
+    event_store: RadrootsEventStore,
+    frame_size_limit: u64,
+    sessions: Mutex<BTreeMap<(String, String), RadrootsNegentropySession>>,
+    exchanged: Mutex<Vec<String>>,
+}
+
+impl RadrootsInProcessNegentropyPeer {
+    pub fn new(event_store: RadrootsEventStore) -> Self {
+        Self {
+            event_store,
+            frame_size_limit: RADROOTS_NEGENTROPY_DEFAULT_FRAME_SIZE_LIMIT,
+            sessions: Mutex::new(BTreeMap::new()),
+            exchanged: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_frame_size_limit(mut self, frame_size_limit: u64) -> Self {
+        self.frame_size_limit = frame_size_limit;
+        self
+    }
+
+    pub fn exchanged_frames(&self) -> Result<Vec<String>, RadrootsRelayTransportError> {
+        Ok(self.exchanged.lock().map_err(|_| lock_poisoned())?.clone())
+    }
+
+    fn record(&self, frame: String) -> Result<(), RadrootsRelayTransportError> {
+        self.exchanged
+            .lock()
+            .map_err(|_| lock_poisoned())?
+            .push(frame);
+        Ok(())
+    }
+
+    fn respond(
+        &self,
+        key: (String, String),
+        session: Option<RadrootsNegentropySession>,
+        query: &str,
+    ) -> Result<RadrootsNegentropyRelayMessage, RadrootsRelayTransportError> {
+        let mut sessions = self.sessions.lock().map_err(|_| lock_poisoned())?;
+        if let Some(session) = session {
+            sessions.insert(key.clone(), session);
+        }
+        let subscription_id = key.1.clone();
+        let Some(session) = sessions.get_mut(&key) else {
+            return Ok(RadrootsNegentropyRelayMessage::Error {
+                subscription_id,
+                reason: "closed: unknown negentropy subscription".to_owned(),
+            });
+        };
+        Ok(match session.reconcile(query) {
+            Ok(message) => RadrootsNegentropyRelayMessage::Message {
+                subscription_id,
+                message,
+            },
+            Err(error) => RadrootsNegentropyRelayMessage::Error {
+                subscription_id,
+                reason: format!("error: {error}"),
+            },
+        })
+    }
+}
+
+impl RadrootsRelayNegentropyTransport for RadrootsInProcessNegentropyPeer {
+    fn exchange<'a>(
+        &'a self,
+        relay_url: &'a str,
+        message: RadrootsNegentropyClientMessage,
+    ) -> BoxFuture<'a, Result<Option<RadrootsNegentropyRelayMessage>, RadrootsRelayTransportError>>
+    {
+        Box::pin(async move {
+            self.record(message.to_json()?)?;
+            let key = (relay_url.to_owned(), message.subscription_id().to_owned());
+            let reply = match message {
+                RadrootsNegentropyClientMessage::Open {
+                    filter,
+                    initial_message,
+                    ..
+                } => {
+                    let session = self
+                        .event_store
+                        .negentropy_session(&filter, self.frame_size_limit)
+                        .await?;
+                    Some(self.respond(key, Some(session), initial_message.as_str())?)
+                }
+                RadrootsNegentropyClientMessage::Message { message, .. } => {
+                    Some(self.respond(key, None, message.as_str())?)
+                }
+                RadrootsNegentropyClientMessage::Close { .. } => {
+                    self.sessions
+                        .lock()
+                        .map_err(|_| lock_poisoned())?
+                        .remove(&key);
+                    None
+                }
+            };
+            if let Some(reply) = &reply {
+                self.record(reply.to_json()?)?;
+            }
+            Ok(reply)
+        })
+    }
+
+    fn fetch_events<'a>(
+        &'a self,
+        relay_url: &'a str,
+        event_ids: Vec<String>,
+        observed_at_ms: i64,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayFetchItem>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            let now_unix = u32::try_from(observed_at_ms.max(0) / 1_000).unwrap_or(u32::MAX);
+            let mut items = Vec::with_capacity(event_ids.len());
+            for event_id in event_ids {
+                let Some(stored) = self
+                    .event_store
+                    .get_event_at(event_id.as_str(), now_unix)
+                    .await?
+                else {
+                    continue;
+                };
+                let event = stored.to_nostr_event()?;
+                let raw_json = serde_json::to_string(&serde_json::json!({
+                    "id": event.id,
+                    "pubkey": event.author,
+                    "created_at": event.created_at,
+                    "kind": event.kind,
+                    "tags": event.tags,
+                    "content": event.content,
+                    "sig": event.sig,
+                }))?;
+                items.push(RadrootsRelayFetchItem::Event {
+                    relay_url: relay_url.to_owned(),
+                    raw_json,
+                    observed_at_ms,
+                });
+            }
+            Ok(items)
+        })
+    }
+}
+
+struct NegentropyFrame {
+    label: String,
+    subscription_id: String,
+    rest: Vec<Value>,
+}
+
+fn parse_frame(json: &str) -> Result<NegentropyFrame, RadrootsRelayTransportError> {
+    let Value::Array(values) = serde_json::from_str::<Value>(json)? else {
+        return Err(unexpected_frame(json));
+    };
+    let mut values = values.into_iter();
+    let (Some(Value::String(label)), Some(Value::String(subscription_id))) =
+        (values.next(), values.next())
+    else {
+        return Err(unexpected_frame(json));
+    };
+    Ok(NegentropyFrame {
+        label,
+        subscription_id,
+        rest: values.collect(),
+    })
+}
+
+fn unexpected_frame(json: &str) -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport(format!("unexpected negentropy frame: {json}"))
+}
+
+fn lock_poisoned() -> RadrootsRelayTransportError {
+    RadrootsRelayTransportError::Transport("negentropy peer lock poisoned".to_owned())
+}
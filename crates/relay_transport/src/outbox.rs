@@ -1,9 +1,9 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    RadrootsRelayOutcomeKind, RadrootsRelayPublishAdapter, RadrootsRelayPublishReceipt,
-    RadrootsRelayPublishRequest, RadrootsRelayTargetSet, RadrootsRelayTransportError,
-    RadrootsRelayUrlPolicy, publish_signed_event,
+    RadrootsRankedRelaySet, RadrootsRelayOutcomeKind, RadrootsRelayPublishAdapter,
+    RadrootsRelayPublishReceipt, RadrootsRelayPublishRequest, RadrootsRelayTargetSet,
+    RadrootsRelayTransportError, RadrootsRelayUrlPolicy, publish_signed_event,
 };
 use radroots_event_store::{
     RadrootsEventIngest, RadrootsEventStore, RadrootsRelayObservation, RadrootsRelayObservationType,
@@ -15,12 +15,13 @@ use radroots_outbox::{
     RadrootsOutboxRelayStatus,
 };
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RadrootsOutboxPublishPolicy {
     pub accepted_quorum: Option<usize>,
     pub next_attempt_after_ms: i64,
     pub republish_accepted_relays: bool,
     pub relay_url_policy: RadrootsRelayUrlPolicy,
+    pub relay_health: Option<RadrootsRankedRelaySet>,
 }
 
 impl RadrootsOutboxPublishPolicy {
@@ -30,6 +31,7 @@ impl RadrootsOutboxPublishPolicy {
             next_attempt_after_ms,
             republish_accepted_relays: false,
             relay_url_policy: RadrootsRelayUrlPolicy::Public,
+            relay_health: None,
         }
     }
 
@@ -47,6 +49,11 @@ impl RadrootsOutboxPublishPolicy {
         self.relay_url_policy = policy;
         self
     }
+
+    pub fn with_relay_health(mut self, relay_health: RadrootsRankedRelaySet) -> Self {
+        self.relay_health = Some(relay_health);
+        self
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -117,9 +124,17 @@ where
     }
     let targets = RadrootsRelayTargetSet::new(publishable.relays, policy.relay_url_policy)?;
     let quorum = overall_quorum.saturating_sub(publishable.accepted_count);
-    let request = RadrootsRelayPublishRequest::new(signed_event.clone(), targets, now_ms)
-        .with_accepted_quorum(quorum);
-    let publish = publish_signed_event(adapter, request).await?;
+    let publish = match policy.relay_health.as_ref() {
+        Some(health) if quorum < targets.len() => {
+            publish_healthiest_first(adapter, &signed_event, health, &targets, quorum, now_ms)
+                .await?
+        }
+        _ => {
+            let request = RadrootsRelayPublishRequest::new(signed_event.clone(), targets, now_ms)
+                .with_accepted_quorum(quorum);
+            publish_signed_event(adapter, request).await?
+        }
+    };
 
     for relay in &publish.relays {
         match relay.outcome.kind {
@@ -191,6 +206,44 @@ where
     })
 }
 
+async fn publish_healthiest_first<A>(
+    adapter: &A,
+    signed_event: &RadrootsSignedNostrEvent,
+    health: &RadrootsRankedRelaySet,
+    targets: &RadrootsRelayTargetSet,
+    quorum: usize,
+    now_ms: i64,
+) -> Result<RadrootsRelayPublishReceipt, RadrootsRelayTransportError>
+where
+    A: RadrootsRelayPublishAdapter,
+{
+    let mut ranked = health.rank(targets);
+    let reserve = ranked.split_off(quorum.max(1).min(ranked.len()));
+    let preferred = RadrootsRelayTargetSet::from_urls(ranked)?;
+    let request = RadrootsRelayPublishRequest::new(signed_event.clone(), preferred, now_ms)
+        .with_accepted_quorum(quorum);
+    let mut publish = publish_signed_event(adapter, request).await?;
+    if publish.quorum_met || reserve.is_empty() {
+        return Ok(publish);
+    }
+
+    let remaining = quorum.saturating_sub(publish.accepted_count);
+    let request = RadrootsRelayPublishRequest::new(
+        signed_event.clone(),
+        RadrootsRelayTargetSet::from_urls(reserve)?,
+        now_ms,
+    )
+    .with_accepted_quorum(remaining);
+    let fallback = publish_signed_event(adapter, request).await?;
+    publish.attempted_count += fallback.attempted_count;
+    publish.accepted_count += fallback.accepted_count;
+    publish.retryable_count += fallback.retryable_count;
+    publish.terminal_count += fallback.terminal_count;
+    publish.quorum_met = publish.accepted_count >= quorum;
+    publish.relays.extend(fallback.relays);
+    Ok(publish)
+}
+
 struct PublishableRelays {
     relays: Vec<String>,
     total_target_count: usize,
@@ -10,6 +10,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "runtime-tokio")]
+use crate::{
+    RadrootsAuthRelayPublishAdapter, RadrootsNostrClientRelayConnection, RadrootsRelayAuthSigner,
+    RadrootsRelayAuthenticator,
+};
 #[cfg(feature = "client")]
 use nostr::JsonUtil;
 #[cfg(feature = "client")]
@@ -187,12 +192,82 @@ impl RadrootsRelayPublishAdapter for RadrootsMockRelayPublishAdapter {
 #[derive(Clone)]
 pub struct RadrootsNostrClientPublishAdapter {
     client: RadrootsNostrClient,
+    relay_auth: Option<Arc<dyn RadrootsRelayPublishAdapter>>,
 }
 
 #[cfg(feature = "client")]
 impl RadrootsNostrClientPublishAdapter {
     pub fn new(client: RadrootsNostrClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            relay_auth: None,
+        }
+    }
+
+    #[cfg(feature = "runtime-tokio")]
+    pub fn with_relay_auth<S>(
+        mut self,
+        authenticator: RadrootsRelayAuthenticator<RadrootsNostrClientRelayConnection, S>,
+    ) -> Self
+    where
+        S: RadrootsRelayAuthSigner + 'static,
+    {
+        self.relay_auth = Some(Arc::new(RadrootsAuthRelayPublishAdapter::new(
+            authenticator,
+        )));
+        self
+    }
+
+    async fn retry_auth_required(
+        &self,
+        request: &RadrootsRelayPublishRequest,
+        receipts: &mut [RadrootsRelayPublishRelayReceipt],
+    ) {
+        let Some(relay_auth) = self.relay_auth.as_ref() else {
+            return;
+        };
+        let auth_required = receipts
+            .iter()
+            .filter(|receipt| receipt.outcome.kind == RadrootsRelayOutcomeKind::AuthRequired)
+            .map(|receipt| receipt.relay_url.clone())
+            .collect::<Vec<_>>();
+        if auth_required.is_empty() {
+            return;
+        }
+        let retried = match RadrootsRelayTargetSet::new(
+            auth_required.clone(),
+            RadrootsRelayUrlPolicy::LocalDev,
+        ) {
+            Ok(targets) => {
+                relay_auth
+                    .publish(RadrootsRelayPublishRequest::new(
+                        request.signed_event.clone(),
+                        targets,
+                        request.now_ms,
+                    ))
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        let retried = retried.unwrap_or_else(|error| {
+            auth_required
+                .into_iter()
+                .map(|relay_url| {
+                    RadrootsRelayPublishRelayReceipt::attempted(
+                        relay_url,
+                        RadrootsRelayOutcome::connection_failed(error.to_string()),
+                    )
+                })
+                .collect()
+        });
+        for retry in retried {
+            if let Some(receipt) = receipts
+                .iter_mut()
+                .find(|receipt| receipt.relay_url == retry.relay_url)
+            {
+                *receipt = retry;
+            }
+        }
     }
 }
 
@@ -272,6 +347,7 @@ impl RadrootsRelayPublishAdapter for RadrootsNostrClientPublishAdapter {
                     relay_url, outcome,
                 ));
             }
+            self.retry_auth_required(&request, &mut receipts).await;
             Ok(receipts)
         })
     }
@@ -0,0 +1,361 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    RadrootsRankedRelaySet, RadrootsRelayTargetSet, RadrootsRelayTransportError, RadrootsRelayUrl,
+    RadrootsRelayUrlPolicy,
+};
+use radroots_event_store::{RadrootsEventQuery, RadrootsEventStore};
+use radroots_events::RadrootsNostrEvent;
+use radroots_events::kinds::KIND_LIST_READ_WRITE_RELAYS;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const RADROOTS_RELAY_ROUTING_DEFAULT_MAX_RELAYS_PER_PUBKEY: usize = 3;
+pub const RADROOTS_RELAY_ROUTING_DEFAULT_MAX_AUTHORS_PER_REQUEST: usize = 100;
+pub const RADROOTS_RELAY_ROUTING_DEFAULT_MAX_TOTAL_RELAYS: usize = 12;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsRelayList {
+    pub pubkey: String,
+    pub created_at: u32,
+    pub read: Vec<RadrootsRelayUrl>,
+    pub write: Vec<RadrootsRelayUrl>,
+}
+
+impl RadrootsRelayList {
+    pub fn from_event(event: &RadrootsNostrEvent, policy: RadrootsRelayUrlPolicy) -> Self {
+        let mut read = Vec::new();
+        let mut write = Vec::new();
+        for tag in &event.tags {
+            let (Some("r"), Some(url)) = (tag.first().map(String::as_str), tag.get(1)) else {
+                continue;
+            };
+            let (readable, writable) = match tag.get(2).map(String::as_str) {
+                None | Some("") => (true, true),
+                Some("read") => (true, false),
+                Some("write") => (false, true),
+                Some(_) => continue,
+            };
+            let Ok(relay) = RadrootsRelayUrl::parse(url, policy) else {
+                continue;
+            };
+            if readable && !read.contains(&relay) {
+                read.push(relay.clone());
+            }
+            if writable && !write.contains(&relay) {
+                write.push(relay);
+            }
+        }
+        Self {
+            pubkey: event.author.clone(),
+            created_at: event.created_at,
+            read,
+            write,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadrootsRelayRoutingPolicy {
+    pub max_relays_per_pubkey: usize,
+    pub max_authors_per_request: usize,
+    pub max_total_relays: usize,
+    pub relay_url_policy: RadrootsRelayUrlPolicy,
+}
+
+impl Default for RadrootsRelayRoutingPolicy {
+    fn default() -> Self {
+        Self {
+            max_relays_per_pubkey: RADROOTS_RELAY_ROUTING_DEFAULT_MAX_RELAYS_PER_PUBKEY,
+            max_authors_per_request: RADROOTS_RELAY_ROUTING_DEFAULT_MAX_AUTHORS_PER_REQUEST,
+            max_total_relays: RADROOTS_RELAY_ROUTING_DEFAULT_MAX_TOTAL_RELAYS,
+            relay_url_policy: RadrootsRelayUrlPolicy::Public,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsRelayFetchRoute {
+    pub relay_url: RadrootsRelayUrl,
+    pub authors: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadrootsRelayRouter {
+    fallback: RadrootsRelayTargetSet,
+    policy: RadrootsRelayRoutingPolicy,
+    relay_lists: BTreeMap<String, RadrootsRelayList>,
+    health: RadrootsRankedRelaySet,
+}
+
+impl RadrootsRelayRouter {
+    pub fn new(fallback: RadrootsRelayTargetSet, policy: RadrootsRelayRoutingPolicy) -> Self {
+        Self {
+            fallback,
+            policy,
+            relay_lists: BTreeMap::new(),
+            health: RadrootsRankedRelaySet::default(),
+        }
+    }
+
+    pub fn with_health(mut self, health: RadrootsRankedRelaySet) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_relay_list(mut self, relay_list: RadrootsRelayList) -> Self {
+        let newer = self
+            .relay_lists
+            .get(&relay_list.pubkey)
+            .is_none_or(|current| current.created_at <= relay_list.created_at);
+        if newer {
+            self.relay_lists
+                .insert(relay_list.pubkey.clone(), relay_list);
+        }
+        self
+    }
+
+    pub fn with_relay_lists<I>(self, relay_lists: I) -> Self
+    where
+        I: IntoIterator<Item = RadrootsRelayList>,
+    {
+        relay_lists.into_iter().fold(self, |router, relay_list| {
+            router.with_relay_list(relay_list)
+        })
+    }
+
+    pub fn fallback(&self) -> &RadrootsRelayTargetSet {
+        &self.fallback
+    }
+
+    pub fn policy(&self) -> &RadrootsRelayRoutingPolicy {
+        &self.policy
+    }
+
+    pub fn health(&self) -> &RadrootsRankedRelaySet {
+        &self.health
+    }
+
+    pub fn relay_list(&self, pubkey: &str) -> Option<&RadrootsRelayList> {
+        self.relay_lists.get(pubkey)
+    }
+
+    pub fn write_relays(&self, pubkey: &str) -> Vec<RadrootsRelayUrl> {
+        self.capped(
+            self.relay_list(pubkey)
+                .map(|relay_list| relay_list.write.as_slice()),
+        )
+    }
+
+    pub fn read_relays(&self, pubkey: &str) -> Vec<RadrootsRelayUrl> {
+        self.capped(
+            self.relay_list(pubkey)
+                .map(|relay_list| relay_list.read.as_slice()),
+        )
+    }
+
+    pub fn publish_targets(
+        &self,
+        author: &str,
+        recipients: &[String],
+    ) -> Result<RadrootsRelayTargetSet, RadrootsRelayTransportError> {
+        let recipients = recipients
+            .iter()
+            .map(String::as_str)
+            .collect::<BTreeSet<_>>();
+        let mut parties = vec![self.write_relays(author)];
+        parties.extend(
+            recipients
+                .into_iter()
+                .map(|recipient| self.read_relays(recipient)),
+        );
+        let max_total_relays = self.policy.max_total_relays.max(1);
+        let mut selected = Vec::<RadrootsRelayUrl>::new();
+        let depth = parties.iter().map(Vec::len).max().unwrap_or(0);
+        for rank in 0..depth {
+            for relay in parties.iter().filter_map(|relays| relays.get(rank)) {
+                if selected.len() >= max_total_relays {
+                    break;
+                }
+                if !selected.contains(relay) {
+                    selected.push(relay.clone());
+                }
+            }
+        }
+        RadrootsRelayTargetSet::from_urls(selected)
+    }
+
+    pub fn publish_targets_for_event(
+        &self,
+        event: &RadrootsNostrEvent,
+    ) -> Result<RadrootsRelayTargetSet, RadrootsRelayTransportError> {
+        let recipients = event
+            .tags
+            .iter()
+            .filter(|tag| tag.first().is_some_and(|name| name == "p"))
+            .filter_map(|tag| tag.get(1).cloned())
+            .collect::<Vec<_>>();
+        self.publish_targets(event.author.as_str(), recipients.as_slice())
+    }
+
+    pub fn fetch_routes(&self, authors: &[String]) -> Vec<RadrootsRelayFetchRoute> {
+        let authors = authors.iter().map(String::as_str).collect::<BTreeSet<_>>();
+        let candidates = authors
+            .iter()
+            .map(|author| (*author, self.fetch_candidates(author)))
+            .collect::<Vec<_>>();
+        let mut coverage = BTreeMap::<&RadrootsRelayUrl, usize>::new();
+        for relay in candidates.iter().flat_map(|(_, relays)| relays.iter()) {
+            *coverage.entry(relay).or_default() += 1;
+        }
+
+        let max_relays_per_pubkey = self.policy.max_relays_per_pubkey.max(1);
+        let mut assigned = BTreeMap::<RadrootsRelayUrl, BTreeSet<&str>>::new();
+        for (author, relays) in &candidates {
+            let mut relays = relays.iter().collect::<Vec<_>>();
+            relays.sort_by(|left, right| {
+                let covered = |relay: &RadrootsRelayUrl| coverage.get(relay).copied().unwrap_or(0);
+                covered(right)
+                    .cmp(&covered(left))
+                    .then_with(|| {
+                        self.health
+                            .candidate_score(right)
+                            .total_cmp(&self.health.candidate_score(left))
+                    })
+                    .then_with(|| left.cmp(right))
+            });
+            for relay in relays.into_iter().take(max_relays_per_pubkey) {
+                assigned.entry(relay.clone()).or_default().insert(author);
+            }
+        }
+
+        let max_total_relays = self.policy.max_total_relays.max(1);
+        if assigned.len() > max_total_relays {
+            let mut ranked = assigned.into_iter().collect::<Vec<_>>();
+            ranked.sort_by(|(left_relay, left), (right_relay, right)| {
+                right
+                    .len()
+                    .cmp(&left.len())
+                    .then_with(|| {
+                        self.health
+                            .candidate_score(right_relay)
+                            .total_cmp(&self.health.candidate_score(left_relay))
+                    })
+                    .then_with(|| left_relay.cmp(right_relay))
+            });
+            let fallback = self
+                .ranked_fallback()
+                .into_iter()
+                .take(max_relays_per_pubkey.min(max_total_relays))
+                .collect::<Vec<_>>();
+            let mut kept = max_total_relays;
+            let uncovered = loop {
+                let covered = ranked[..kept]
+                    .iter()
+                    .flat_map(|(_, authors)| authors.iter().copied())
+                    .collect::<BTreeSet<_>>();
+                let uncovered = authors
+                    .iter()
+                    .copied()
+                    .filter(|author| !covered.contains(author))
+                    .collect::<Vec<_>>();
+                let reserved = if uncovered.is_empty() {
+                    0
+                } else {
+                    fallback
+                        .iter()
+                        .filter(|relay| !ranked[..kept].iter().any(|(kept, _)| kept == *relay))
+                        .count()
+                };
+                if kept == 0 || kept + reserved <= max_total_relays {
+                    break uncovered;
+                }
+                kept -= 1;
+            };
+            ranked.truncate(kept);
+            assigned = ranked.into_iter().collect();
+            for author in uncovered {
+                for relay in &fallback {
+                    assigned.entry(relay.clone()).or_default().insert(author);
+                }
+            }
+        }
+
+        let max_authors_per_request = self.policy.max_authors_per_request.max(1);
+        assigned
+            .into_iter()
+            .flat_map(|(relay_url, authors)| {
+                let authors = authors.into_iter().map(str::to_owned).collect::<Vec<_>>();
+                authors
+                    .chunks(max_authors_per_request)
+                    .map(|chunk| RadrootsRelayFetchRoute {
+                        relay_url: relay_url.clone(),
+                        authors: chunk.to_vec(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn fetch_candidates(&self, author: &str) -> Vec<RadrootsRelayUrl> {
+        match self.relay_list(author) {
+            Some(relay_list) if !relay_list.write.is_empty() => relay_list.write.clone(),
+            _ => self.fallback.relays().to_vec(),
+        }
+    }
+
+    fn ranked_fallback(&self) -> Vec<RadrootsRelayUrl> {
+        let mut relays = self.fallback.relays().to_vec();
+        self.health.order(relays.as_mut_slice());
+        relays
+    }
+
+    fn capped(&self, relays: Option<&[RadrootsRelayUrl]>) -> Vec<RadrootsRelayUrl> {
+        let mut relays = match relays {
+            Some(relays) if !relays.is_empty() => relays.to_vec(),
+            _ => self.fallback.relays().to_vec(),
+        };
+        self.health.order(relays.as_mut_slice());
+        relays.truncate(self.policy.max_relays_per_pubkey.max(1));
+        relays
+    }
+}
+
+pub async fn resolve_relay_lists(
+    event_store: &RadrootsEventStore,
+    pubkeys: &[String],
+    policy: RadrootsRelayUrlPolicy,
+    now_unix: u32,
+) -> Result<Vec<RadrootsRelayList>, RadrootsRelayTransportError> {
+    if pubkeys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let query = RadrootsEventQuery {
+        authors: pubkeys.to_vec(),
+        kinds: vec![KIND_LIST_READ_WRITE_RELAYS],
+        ..RadrootsEventQuery::default()
+    };
+    event_store
+        .query_events(&query, now_unix)
+        .await?
+        .iter()
+        .map(|stored| {
+            Ok(RadrootsRelayList::from_event(
+                &stored.to_nostr_event()?,
+                policy,
+            ))
+        })
+        .collect()
+}
+
+pub async fn resolve_relay_router(
+    event_store: &RadrootsEventStore,
+    pubkeys: &[String],
+    fallback: RadrootsRelayTargetSet,
+    policy: RadrootsRelayRoutingPolicy,
+    now_unix: u32,
+) -> Result<RadrootsRelayRouter, RadrootsRelayTransportError> {
+    let relay_lists =
+        resolve_relay_lists(event_store, pubkeys, policy.relay_url_policy, now_unix).await?;
+    Ok(RadrootsRelayRouter::new(fallback, policy).with_relay_lists(relay_lists))
+}
@@ -0,0 +1,537 @@
+#![forbid(unsafe_code)]
+
+use crate::{
+    RadrootsOutboxPublishPolicy, RadrootsRelayHealthPolicy, RadrootsRelayPublishAdapter,
+    RadrootsRelayUrlPolicy, compute_relay_health, publish_claimed_outbox_event,
+};
+use core::future::Future;
+use radroots_event_store::RadrootsEventStore;
+use radroots_nostr::prelude::RadrootsNostrKeys;
+use radroots_outbox::{
+    RadrootsOutbox, RadrootsOutboxClaimedEvent, RadrootsOutboxError, RadrootsOutboxEventState,
+};
+use radroots_runtime::{Backoff, BackoffConfig};
+use std::pin::pin;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, watch};
+
+pub const RADROOTS_OUTBOX_WORKER_DEFAULT_CONCURRENCY: usize = 4;
+pub const RADROOTS_OUTBOX_WORKER_DEFAULT_CLAIM_TTL_MS: i64 = 60_000;
+pub const RADROOTS_OUTBOX_WORKER_DEFAULT_MAX_ATTEMPTS: i64 = 8;
+const RADROOTS_OUTBOX_WORKER_EVENT_CAPACITY: usize = 256;
+
+pub type RadrootsOutboxWorkerClock = fn() -> i64;
+
+#[derive(Clone, Debug)]
+pub struct RadrootsOutboxWorkerConfig {
+    pub worker_id: String,
+    pub concurrency: usize,
+    pub claim_ttl_ms: i64,
+    pub max_attempts: i64,
+    pub retry_backoff: BackoffConfig,
+    pub idle_backoff: BackoffConfig,
+    pub accepted_quorum: Option<usize>,
+    pub relay_url_policy: RadrootsRelayUrlPolicy,
+    pub relay_health: Option<RadrootsRelayHealthPolicy>,
+    pub clock: RadrootsOutboxWorkerClock,
+}
+
+impl Default for RadrootsOutboxWorkerConfig {
+    fn default() -> Self {
+        Self {
+            worker_id: "radroots-outbox-worker".to_owned(),
+            concurrency: RADROOTS_OUTBOX_WORKER_DEFAULT_CONCURRENCY,
+            claim_ttl_ms: RADROOTS_OUTBOX_WORKER_DEFAULT_CLAIM_TTL_MS,
+            max_attempts: RADROOTS_OUTBOX_WORKER_DEFAULT_MAX_ATTEMPTS,
+            retry_backoff: BackoffConfig::default(),
+            idle_backoff: BackoffConfig {
+                base_ms: 250,
+                max_ms: 5_000,
+                factor: 2,
+                jitter_ms: 0,
+            },
+            accepted_quorum: None,
+            relay_url_policy: RadrootsRelayUrlPolicy::Public,
+            relay_health: None,
+            clock: system_clock_ms,
+        }
+    }
+}
+
+impl RadrootsOutboxWorkerConfig {
+    pub fn with_worker_id(mut self, worker_id: impl Into<String>) -> Self {
+        self.worker_id = worker_id.into();
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_claim_ttl_ms(mut self, claim_ttl_ms: i64) -> Self {
+        self.claim_ttl_ms = claim_ttl_ms.max(1);
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: i64) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_retry_backoff(mut self, retry_backoff: BackoffConfig) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    pub fn with_idle_backoff(mut self, idle_backoff: BackoffConfig) -> Self {
+        self.idle_backoff = idle_backoff;
+        self
+    }
+
+    pub fn with_accepted_quorum(mut self, accepted_quorum: usize) -> Self {
+        self.accepted_quorum = Some(accepted_quorum);
+        self
+    }
+
+    pub fn with_relay_url_policy(mut self, relay_url_policy: RadrootsRelayUrlPolicy) -> Self {
+        self.relay_url_policy = relay_url_policy;
+        self
+    }
+
+    pub fn with_relay_health(mut self, relay_health: RadrootsRelayHealthPolicy) -> Self {
+        self.relay_health = Some(relay_health);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: RadrootsOutboxWorkerClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn retry_at_ms(&self, attempt: i64, now_ms: i64) -> i64 {
+        let attempt = u32::try_from(attempt.max(1)).unwrap_or(u32::MAX);
+        let delay_ms = self.retry_backoff.delay_for_attempt(attempt).as_millis();
+        now_ms.saturating_add(i64::try_from(delay_ms).unwrap_or(i64::MAX))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RadrootsOutboxWorkerReport {
+    pub recovered_claims: u64,
+    pub claimed: u64,
+    pub published: u64,
+    pub retried: u64,
+    pub dead_lettered: u64,
+    pub errors: u64,
+}
+
+impl RadrootsOutboxWorkerReport {
+    fn absorb(&mut self, lane: RadrootsOutboxWorkerReport) {
+        self.claimed += lane.claimed;
+        self.published += lane.published;
+        self.retried += lane.retried;
+        self.dead_lettered += lane.dead_lettered;
+        self.errors += lane.errors;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsOutboxWorkerEvent {
+    Started {
+        worker_id: String,
+        recovered_claims: u64,
+    },
+    Claimed {
+        outbox_event_id: i64,
+        attempt: i64,
+    },
+    Signed {
+        outbox_event_id: i64,
+        event_id: String,
+    },
+    Published {
+        outbox_event_id: i64,
+        event_id: String,
+        accepted_count: usize,
+    },
+    RetryScheduled {
+        outbox_event_id: i64,
+        attempt: i64,
+        next_attempt_after_ms: i64,
+        error: String,
+    },
+    DeadLettered {
+        outbox_event_id: i64,
+        attempt: i64,
+        error: String,
+    },
+    Error {
+        outbox_event_id: Option<i64>,
+        error: String,
+    },
+    Stopped(RadrootsOutboxWorkerReport),
+}
+
+pub struct RadrootsOutboxWorker<A> {
+    outbox: RadrootsOutbox,
+    event_store: RadrootsEventStore,
+    adapter: A,
+    keys: RadrootsNostrKeys,
+    config: RadrootsOutboxWorkerConfig,
+    events: broadcast::Sender<RadrootsOutboxWorkerEvent>,
+}
+
+impl<A> RadrootsOutboxWorker<A>
+where
+    A: RadrootsRelayPublishAdapter,
+{
+    pub fn new(
+        outbox: RadrootsOutbox,
+        event_store: RadrootsEventStore,
+        adapter: A,
+        keys: RadrootsNostrKeys,
+        config: RadrootsOutboxWorkerConfig,
+    ) -> Self {
+        let (events, _) = broadcast::channel(RADROOTS_OUTBOX_WORKER_EVENT_CAPACITY);
+        Self {
+            outbox,
+            event_store,
+            adapter,
+            keys,
+            config,
+            events,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<RadrootsOutboxWorkerEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn adapter(&self) -> &A {
+        &self.adapter
+    }
+
+    pub fn config(&self) -> &RadrootsOutboxWorkerConfig {
+        &self.config
+    }
+
+    pub async fn run_until_signal(
+        &self,
+    ) -> Result<RadrootsOutboxWorkerReport, RadrootsOutboxError> {
+        self.run(radroots_runtime::shutdown_signal()).await
+    }
+
+    pub async fn run<F>(
+        &self,
+        shutdown: F,
+    ) -> Result<RadrootsOutboxWorkerReport, RadrootsOutboxError>
+    where
+        F: Future<Output = ()>,
+    {
+        let recovered_claims = self
+            .outbox
+            .recover_expired_claims((self.config.clock)())
+            .await?;
+        self.emit(RadrootsOutboxWorkerEvent::Started {
+            worker_id: self.config.worker_id.clone(),
+            recovered_claims,
+        });
+        let (stop, stopped) = watch::channel(false);
+        let mut lanes = pin!(futures::future::join_all(
+            (0..self.config.concurrency.max(1)).map(|lane| self.run_lane(lane, stopped.clone())),
+        ));
+        let lane_reports = tokio::select! {
+            reports = &mut lanes => reports,
+            () = shutdown => {
+                let _ = stop.send(true);
+                lanes.await
+            }
+        };
+        let mut report = RadrootsOutboxWorkerReport {
+            recovered_claims,
+            ..RadrootsOutboxWorkerReport::default()
+        };
+        for lane in lane_reports {
+            report.absorb(lane);
+        }
+        self.emit(RadrootsOutboxWorkerEvent::Stopped(report.clone()));
+        Ok(report)
+    }
+
+    async fn run_lane(
+        &self,
+        lane: usize,
+        mut stopped: watch::Receiver<bool>,
+    ) -> RadrootsOutboxWorkerReport {
+        let mut report = RadrootsOutboxWorkerReport::default();
+        let mut idle = Backoff::new(self.config.idle_backoff.clone());
+        let claim_owner = format!("{}/{lane}", self.config.worker_id);
+        let mut sequence = 0_u64;
+        while !*stopped.borrow() {
+            sequence += 1;
+            let now_ms = (self.config.clock)();
+            let claim_token = format!("{claim_owner}/{now_ms}/{sequence}");
+            let claimed = self
+                .outbox
+                .claim_next_ready_event(
+                    claim_owner.as_str(),
+                    claim_token.as_str(),
+                    now_ms.saturating_add(self.config.claim_ttl_ms),
+                    now_ms,
+                )
+                .await;
+            match claimed {
+                Ok(Some(claimed)) => {
+                    idle.reset();
+                    self.process(claimed, &mut report).await;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(error) => self.record_error(&mut report, None, error.to_string()),
+            }
+            tokio::select! {
+                () = tokio::time::sleep(idle.next_delay()) => {}
+                _ = stopped.changed() => {}
+            }
+        }
+        report
+    }
+
+    async fn process(
+        &self,
+        mut claimed: RadrootsOutboxClaimedEvent,
+        report: &mut RadrootsOutboxWorkerReport,
+    ) {
+        report.claimed += 1;
+        let outbox_event_id = claimed.outbox_event_id;
+        let attempt = match self.outbox.get_event(outbox_event_id).await {
+            Ok(Some(record)) => record.attempt_count,
+            Ok(None) => {
+                self.record_error(
+                    report,
+                    Some(outbox_event_id),
+                    RadrootsOutboxError::EventNotFound(outbox_event_id).to_string(),
+                );
+                return;
+            }
+            Err(error) => {
+                self.record_error(report, Some(outbox_event_id), error.to_string());
+                return;
+            }
+        };
+        self.emit(RadrootsOutboxWorkerEvent::Claimed {
+            outbox_event_id,
+            attempt,
+        });
+
+        if attempt > self.config.max_attempts {
+            let error = format!("retry budget exhausted after {} attempts", attempt - 1);
+            let finished = self
+                .outbox
+                .mark_publish_failed_terminal(
+                    outbox_event_id,
+                    claimed.claim_token.as_str(),
+                    error.as_str(),
+                    (self.config.clock)(),
+                )
+                .await;
+            match finished {
+                Ok(()) => self.record_dead_letter(report, outbox_event_id, attempt, error),
+                Err(error) => self.record_error(report, Some(outbox_event_id), error.to_string()),
+            }
+            return;
+        }
+
+        if claimed.signed_event.is_none() {
+            let signed = self
+                .outbox
+                .sign_claimed_event(&claimed, &self.keys, (self.config.clock)())
+                .await;
+            match signed {
+                Ok(signed_event) => {
+                    self.emit(RadrootsOutboxWorkerEvent::Signed {
+                        outbox_event_id,
+                        event_id: signed_event.id.clone(),
+                    });
+                    claimed.signed_event = Some(signed_event);
+                }
+                Err(error) => {
+                    let now_ms = (self.config.clock)();
+                    let next_attempt_after_ms = self.config.retry_at_ms(attempt, now_ms);
+                    let error = error.to_string();
+                    let rescheduled = self
+                        .outbox
+                        .mark_sign_retryable(
+                            outbox_event_id,
+                            claimed.claim_token.as_str(),
+                            error.as_str(),
+                            next_attempt_after_ms,
+                            now_ms,
+                        )
+                        .await;
+                    self.record_retry(
+                        report,
+                        outbox_event_id,
+                        attempt,
+                        next_attempt_after_ms,
+                        error,
+                        rescheduled,
+                    );
+                    return;
+                }
+            }
+        }
+
+        let now_ms = (self.config.clock)();
+        let next_attempt_after_ms = self.config.retry_at_ms(attempt, now_ms);
+        let mut policy = RadrootsOutboxPublishPolicy::new(next_attempt_after_ms)
+            .relay_url_policy(self.config.relay_url_policy);
+        if let Some(accepted_quorum) = self.config.accepted_quorum {
+            policy = policy.with_accepted_quorum(accepted_quorum);
+        }
+        if let Some(health_policy) = self.config.relay_health.as_ref() {
+            let health =
+                compute_relay_health(&self.event_store, Some(&self.outbox), health_policy, now_ms)
+                    .await;
+            match health {
+                Ok(health) => policy = policy.with_relay_health(health),
+                Err(error) => self.record_error(report, Some(outbox_event_id), error.to_string()),
+            }
+        }
+        let published = publish_claimed_outbox_event(
+            &self.outbox,
+            &self.event_store,
+            &self.adapter,
+            &claimed,
+            policy,
+            now_ms,
+        )
+        .await;
+        let receipt = match published {
+            Ok(receipt) => receipt,
+            Err(error) => {
+                let error = error.to_string();
+                let rescheduled = self
+                    .outbox
+                    .mark_publish_retryable(
+                        outbox_event_id,
+                        claimed.claim_token.as_str(),
+                        error.as_str(),
+                        next_attempt_after_ms,
+                        now_ms,
+                    )
+                    .await;
+                self.record_retry(
+                    report,
+                    outbox_event_id,
+                    attempt,
+                    next_attempt_after_ms,
+                    error,
+                    rescheduled,
+                );
+                return;
+            }
+        };
+        let record = match self.outbox.get_event(outbox_event_id).await {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                self.record_error(
+                    report,
+                    Some(outbox_event_id),
+                    RadrootsOutboxError::EventNotFound(outbox_event_id).to_string(),
+                );
+                return;
+            }
+            Err(error) => {
+                self.record_error(report, Some(outbox_event_id), error.to_string());
+                return;
+            }
+        };
+        let error = record.last_error.unwrap_or_default();
+        match record.state {
+            RadrootsOutboxEventState::Published => {
+                report.published += 1;
+                self.emit(RadrootsOutboxWorkerEvent::Published {
+                    outbox_event_id,
+                    event_id: receipt.publish.event_id,
+                    accepted_count: receipt.publish.accepted_count,
+                });
+            }
+            RadrootsOutboxEventState::FailedTerminal => {
+                self.record_dead_letter(report, outbox_event_id, attempt, error);
+            }
+            _ => {
+                self.record_retry(
+                    report,
+                    outbox_event_id,
+                    attempt,
+                    record.next_attempt_after_ms,
+                    error,
+                    Ok(()),
+                );
+            }
+        }
+    }
+
+    fn record_retry(
+        &self,
+        report: &mut RadrootsOutboxWorkerReport,
+        outbox_event_id: i64,
+        attempt: i64,
+        next_attempt_after_ms: i64,
+        error: String,
+        rescheduled: Result<(), RadrootsOutboxError>,
+    ) {
+        if let Err(reschedule_error) = rescheduled {
+            self.record_error(report, Some(outbox_event_id), reschedule_error.to_string());
+            return;
+        }
+        report.retried += 1;
+        self.emit(RadrootsOutboxWorkerEvent::RetryScheduled {
+            outbox_event_id,
+            attempt,
+            next_attempt_after_ms,
+            error,
+        });
+    }
+
+    fn record_dead_letter(
+        &self,
+        report: &mut RadrootsOutboxWorkerReport,
+        outbox_event_id: i64,
+        attempt: i64,
+        error: String,
+    ) {
+        report.dead_lettered += 1;
+        self.emit(RadrootsOutboxWorkerEvent::DeadLettered {
+            outbox_event_id,
+            attempt,
+            error,
+        });
+    }
+
+    fn record_error(
+        &self,
+        report: &mut RadrootsOutboxWorkerReport,
+        outbox_event_id: Option<i64>,
+        error: String,
+    ) {
+        report.errors += 1;
+        self.emit(RadrootsOutboxWorkerEvent::Error {
+            outbox_event_id,
+            error,
+        });
+    }
+
+    fn emit(&self, event: RadrootsOutboxWorkerEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+fn system_clock_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+        .unwrap_or(0)
+}
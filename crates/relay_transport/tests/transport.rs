@@ -1,22 +1,46 @@
+use futures::future::BoxFuture;
 use nostr::JsonUtil;
-use radroots_event_store::{RadrootsEventStore, RadrootsEventVerificationStatus};
+use radroots_event_store::{
+    RadrootsEventIngest, RadrootsEventStore, RadrootsEventVerificationStatus,
+    RadrootsNegentropyFilter, RadrootsRelayObservation, RadrootsRelayObservationType,
+};
+use radroots_events::RadrootsNostrEvent;
 use radroots_events::draft::{RadrootsFrozenEventDraft, RadrootsSignedNostrEvent};
-use radroots_events::kinds::KIND_POST;
+use radroots_events::kinds::{KIND_LIST_READ_WRITE_RELAYS, KIND_POST};
+use radroots_local_relay::{RadrootsLocalRelay, RadrootsLocalRelayConfig};
+use radroots_nostr::prelude::RadrootsNostrClient;
 use radroots_nostr::prelude::{
-    RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp, radroots_nostr_build_event,
-    radroots_nostr_sign_frozen_draft,
+    RadrootsNostrKeys, RadrootsNostrSecretKey, RadrootsNostrTimestamp, radroots_event_from_nostr,
+    radroots_nostr_build_event, radroots_nostr_sign_frozen_draft,
 };
 use radroots_outbox::{
     RadrootsOutbox, RadrootsOutboxEventState, RadrootsOutboxOperationInput,
-    RadrootsOutboxOperationStatus, RadrootsOutboxRelayStatus,
+    RadrootsOutboxOperationStatus, RadrootsOutboxRelayPublishBucket, RadrootsOutboxRelayStatus,
 };
 use radroots_relay_transport::{
-    RadrootsMockRelayFetchAdapter, RadrootsMockRelayPublishAdapter, RadrootsOutboxPublishPolicy,
-    RadrootsRelayFetchItem, RadrootsRelayFetchOutcomeKind, RadrootsRelayFetchRequest,
-    RadrootsRelayOutcome, RadrootsRelayOutcomeKind, RadrootsRelayTargetSet, RadrootsRelayUrl,
-    RadrootsRelayUrlPolicy, fetch_and_ingest_relay_events, publish_claimed_outbox_event,
-    publish_signed_event,
+    RadrootsAuthRelayFetchAdapter, RadrootsAuthRelayPublishAdapter,
+    RadrootsCircuitBreakerFetchAdapter, RadrootsCircuitBreakerPublishAdapter,
+    RadrootsInProcessBackfillPeer, RadrootsInProcessNegentropyPeer, RadrootsMockRelayFetchAdapter,
+    RadrootsMockRelayPublishAdapter, RadrootsNegentropyClientMessage,
+    RadrootsNegentropyFetchAdapter, RadrootsNegentropyRelayMessage,
+    RadrootsNostrClientPublishAdapter, RadrootsNostrClientRelayConnection,
+    RadrootsOutboxPublishPolicy, RadrootsOutboxWorker, RadrootsOutboxWorkerConfig,
+    RadrootsOutboxWorkerEvent, RadrootsRankedRelaySet, RadrootsRelayAdmission,
+    RadrootsRelayAuthenticator, RadrootsRelayBackfillFilter, RadrootsRelayBackfillPage,
+    RadrootsRelayBackfillPolicy, RadrootsRelayCircuitBreaker, RadrootsRelayCircuitPolicy,
+    RadrootsRelayCircuitState, RadrootsRelayClientMessage, RadrootsRelayConnection,
+    RadrootsRelayFetchAdapter, RadrootsRelayFetchItem, RadrootsRelayFetchOutcomeKind,
+    RadrootsRelayFetchRequest, RadrootsRelayHealthPolicy, RadrootsRelayOutcome,
+    RadrootsRelayOutcomeKind, RadrootsRelayPublishAdapter, RadrootsRelayPublishRequest,
+    RadrootsRelayRouter, RadrootsRelayRoutingPolicy, RadrootsRelayServerMessage,
+    RadrootsRelayTargetSet, RadrootsRelayTransportError, RadrootsRelayUrl, RadrootsRelayUrlPolicy,
+    backfill_relay_events, compute_relay_health, fetch_and_ingest_relay_events,
+    publish_claimed_outbox_event, publish_signed_event, resolve_relay_lists, resolve_relay_router,
 };
+use radroots_runtime::BackoffConfig;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
 
 const FIXTURE_ALICE_SECRET_KEY_HEX: &str =
     "10c5304d6c9ae3a1a16f7860f1cc8f5e3a76225a2663b3a989a0d775919b7df5";
@@ -156,7 +180,7 @@ fn outcome_prefix_classification_covers_required_kinds() {
 async fn mock_publish_preserves_exact_raw_json_and_counts_outcomes() {
     let signed = signed_post("hello");
     let targets = RadrootsRelayTargetSet::new(
-        vec![RELAY_PRIMARY_WSS, RELAY_SECONDARY_WSS, RELAY_TERTIARY_WSS],
+        vec![RELAY_PRIMARY_WSS, RELAY_TERTIARY_WSS, RELAY_SECONDARY_WSS],
         RadrootsRelayUrlPolicy::Public,
     )
     .expect("targets");
@@ -872,3 +896,1522 @@ async fn smoke_relay_fetch_processes_one_thousand_event_receipts() {
         .expect("replay");
     assert_eq!(replay.len(), 1_000);
 }
+
+#[tokio::test]
+async fn negentropy_fetch_ingests_only_events_missing_locally() {
+    let posts = (0..5)
+        .map(|n| signed_post(format!("negentropy {n}").as_str()))
+        .collect::<Vec<_>>();
+    let remote = RadrootsEventStore::open_memory().await.expect("remote");
+    let local = RadrootsEventStore::open_memory().await.expect("local");
+    for (index, post) in posts.iter().enumerate() {
+        let event = radroots_event_from_nostr(
+            &nostr::Event::from_json(post.raw_json.as_str()).expect("event json"),
+        );
+        remote
+            .ingest_event(RadrootsEventIngest::new(event.clone(), 1_000))
+            .await
+            .expect("remote ingest");
+        if index < 2 {
+            local
+                .ingest_event(RadrootsEventIngest::new(event, 1_000))
+                .await
+                .expect("local ingest");
+        }
+    }
+
+    let filter = RadrootsNegentropyFilter {
+        kinds: vec![KIND_POST],
+        ..RadrootsNegentropyFilter::default()
+    };
+    let adapter = RadrootsNegentropyFetchAdapter::new(
+        RadrootsInProcessNegentropyPeer::new(remote),
+        local.clone(),
+        RELAY_PRIMARY_WSS,
+        filter.clone(),
+    )
+    .with_subscription_id("neg-1");
+    let report = adapter.reconcile().await.expect("reconcile");
+    assert_eq!(report.local_items, 2);
+    assert!(report.have_ids.is_empty());
+    assert_eq!(report.need_ids.len(), 3);
+
+    let receipt = fetch_and_ingest_relay_events(
+        &adapter,
+        &local,
+        RadrootsRelayFetchRequest::fetch(2_000, 10),
+    )
+    .await
+    .expect("negentropy fetch");
+    assert_eq!(receipt.inserted_count, 3);
+    assert_eq!(receipt.duplicate_count, 0);
+    assert_eq!(receipt.eose_count, 1);
+    for post in &posts {
+        assert!(
+            local
+                .get_event(post.id.as_str())
+                .await
+                .expect("lookup")
+                .is_some()
+        );
+    }
+
+    let frames = adapter.transport().exchanged_frames().expect("frames");
+    let open = RadrootsNegentropyClientMessage::from_json(frames[0].as_str()).expect("open");
+    assert!(matches!(
+        open,
+        RadrootsNegentropyClientMessage::Open { ref subscription_id, filter: ref sent, .. }
+            if subscription_id == "neg-1" && sent == &filter
+    ));
+    assert!(matches!(
+        RadrootsNegentropyRelayMessage::from_json(frames[1].as_str()).expect("reply"),
+        RadrootsNegentropyRelayMessage::Message { .. }
+    ));
+    assert!(
+        frames
+            .iter()
+            .any(|frame| frame == r#"["NEG-CLOSE","neg-1"]"#)
+    );
+    assert!(RadrootsNegentropyRelayMessage::from_json(r#"["NEG-ERR","neg-1"]"#).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn client_publish_adapter_reaches_local_relay() {
+    let relay_store = RadrootsEventStore::open_memory()
+        .await
+        .expect("relay store");
+    let relay = RadrootsLocalRelay::bind(
+        "127.0.0.1:0",
+        relay_store,
+        RadrootsLocalRelayConfig::default(),
+    )
+    .await
+    .expect("bind")
+    .spawn()
+    .expect("spawn");
+    let signed = signed_post("farm stand");
+    let targets = RadrootsRelayTargetSet::new(vec![relay.url()], RadrootsRelayUrlPolicy::LocalDev)
+        .expect("targets");
+    let client = RadrootsNostrClient::new_signerless();
+    client
+        .add_write_relay(relay.url())
+        .await
+        .expect("add relay");
+    client
+        .try_connect_relay(relay.url(), std::time::Duration::from_secs(5))
+        .await
+        .expect("connect relay");
+    let adapter = RadrootsNostrClientPublishAdapter::new(client);
+
+    let receipt = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed.clone(), targets, 1_000),
+    )
+    .await
+    .expect("publish");
+    assert!(receipt.quorum_met, "{receipt:?}");
+    assert_eq!(receipt.accepted_count, 1);
+    assert!(
+        relay
+            .event_store()
+            .get_event(signed.id.as_str())
+            .await
+            .expect("lookup")
+            .is_some()
+    );
+    relay.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test]
+async fn client_relay_connection_exchanges_raw_frames_with_local_relay() {
+    let relay = RadrootsLocalRelay::bind(
+        "127.0.0.1:0",
+        RadrootsEventStore::open_memory()
+            .await
+            .expect("relay store"),
+        RadrootsLocalRelayConfig::default(),
+    )
+    .await
+    .expect("bind")
+    .spawn()
+    .expect("spawn");
+    let targets = RadrootsRelayTargetSet::new(vec![relay.url()], RadrootsRelayUrlPolicy::LocalDev)
+        .expect("targets");
+    let client = RadrootsNostrClient::new_signerless();
+    client
+        .add_write_relay(relay.url())
+        .await
+        .expect("add relay");
+    client
+        .try_connect_relay(relay.url(), Duration::from_secs(5))
+        .await
+        .expect("connect relay");
+    let authenticator = RadrootsRelayAuthenticator::new(
+        RadrootsNostrClientRelayConnection::new(client.clone())
+            .with_timeout(Duration::from_secs(5)),
+        fixture_keys(),
+    )
+    .with_clock(auth_clock);
+    let adapter =
+        RadrootsNostrClientPublishAdapter::new(client).with_relay_auth(authenticator.clone());
+    let signed = signed_post("raw frames");
+
+    let receipt = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed.clone(), targets.clone(), 1_000),
+    )
+    .await
+    .expect("publish");
+    assert!(receipt.quorum_met, "{receipt:?}");
+    let replies = authenticator
+        .exchange(
+            relay.url(),
+            RadrootsRelayClientMessage::Event {
+                raw_json: signed.raw_json.clone(),
+            },
+        )
+        .await
+        .expect("event exchange");
+    assert!(replies.iter().any(|reply| matches!(
+        reply,
+        RadrootsRelayServerMessage::Ok { event_id, accepted: true, .. } if event_id == &signed.id
+    )));
+    assert!(authenticator.connection().connection_id(relay.url()) > 0);
+
+    let fetch_adapter = RadrootsAuthRelayFetchAdapter::new(
+        authenticator,
+        targets,
+        vec![serde_json::json!({"kinds": [KIND_POST]})],
+    );
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let fetched = fetch_and_ingest_relay_events(
+        &fetch_adapter,
+        &store,
+        RadrootsRelayFetchRequest::fetch(2_000, 10),
+    )
+    .await
+    .expect("fetch");
+    assert_eq!(fetched.inserted_count, 1);
+    assert_eq!(fetched.eose_count, 1);
+    relay.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test]
+async fn relay_health_ranks_by_history_and_recovering_relays_regain_score() {
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let shared = signed_post("shared");
+    let exclusive = signed_post("exclusive");
+    let created_at_ms = i64::from(shared.created_at) * 1_000;
+    for (event, relay_url, lag_ms) in [
+        (&shared, RELAY_PRIMARY_WSS, 400),
+        (&shared, RELAY_SECONDARY_WSS, 6_000),
+        (&exclusive, RELAY_PRIMARY_WSS, 600),
+    ] {
+        let observed_at_ms = created_at_ms + lag_ms;
+        let raw_event = radroots_event_from_nostr(
+            &nostr::Event::from_json(event.raw_json.as_str()).expect("raw event"),
+        );
+        store
+            .ingest_event(
+                RadrootsEventIngest::new(raw_event, observed_at_ms).with_observation(
+                    RadrootsRelayObservation::new(
+                        relay_url,
+                        RadrootsRelayObservationType::Subscription,
+                        observed_at_ms,
+                    ),
+                ),
+            )
+            .await
+            .expect("ingest");
+    }
+
+    let policy = RadrootsRelayHealthPolicy::default();
+    let now_ms = created_at_ms + 60_000;
+    let health = compute_relay_health(&store, None, &policy, now_ms)
+        .await
+        .expect("health");
+    let primary = health.score_for(RELAY_PRIMARY_WSS).expect("primary score");
+    let secondary = health
+        .score_for(RELAY_SECONDARY_WSS)
+        .expect("secondary score");
+    assert_eq!(primary.uniqueness, 0.5);
+    assert_eq!(secondary.uniqueness, 0.0);
+    assert!(primary.latency > secondary.latency);
+    assert_eq!(health.scores()[0].relay_url, RELAY_PRIMARY_WSS);
+
+    let candidates = RadrootsRelayTargetSet::new(
+        [RELAY_SECONDARY_WSS, RELAY_TERTIARY_WSS, RELAY_PRIMARY_WSS],
+        RadrootsRelayUrlPolicy::Public,
+    )
+    .expect("candidates");
+    assert_eq!(
+        health
+            .rank(&candidates)
+            .iter()
+            .map(RadrootsRelayUrl::as_str)
+            .collect::<Vec<_>>(),
+        vec![RELAY_PRIMARY_WSS, RELAY_TERTIARY_WSS, RELAY_SECONDARY_WSS]
+    );
+    assert_eq!(
+        health
+            .select(&candidates, 1)
+            .expect("selected")
+            .relay_strings(),
+        vec![RELAY_PRIMARY_WSS.to_owned()]
+    );
+
+    let day_ms = 24 * 60 * 60 * 1_000;
+    let outage = RadrootsOutboxRelayPublishBucket {
+        relay_url: RELAY_TERTIARY_WSS.to_owned(),
+        bucket_start_ms: 0,
+        attempts: 12,
+        accepted: 0,
+        failed_retryable: 12,
+        failed_terminal: 0,
+        last_attempt_at_ms: 3_000_000,
+        last_accepted_at_ms: None,
+    };
+    let recovery = RadrootsOutboxRelayPublishBucket {
+        relay_url: RELAY_TERTIARY_WSS.to_owned(),
+        bucket_start_ms: 5 * day_ms,
+        attempts: 4,
+        accepted: 4,
+        failed_retryable: 0,
+        failed_terminal: 0,
+        last_attempt_at_ms: 5 * day_ms + 60_000,
+        last_accepted_at_ms: Some(5 * day_ms + 60_000),
+    };
+    let during_outage = RadrootsRankedRelaySet::from_history(
+        &[],
+        std::slice::from_ref(&outage),
+        &policy,
+        day_ms / 24,
+    );
+    let after_recovery = RadrootsRankedRelaySet::from_history(
+        &[],
+        &[outage, recovery],
+        &policy,
+        5 * day_ms + day_ms / 24,
+    );
+    let outage_score = during_outage
+        .score_for(RELAY_TERTIARY_WSS)
+        .expect("outage score");
+    let recovered_score = after_recovery
+        .score_for(RELAY_TERTIARY_WSS)
+        .expect("recovered score");
+    assert!(outage_score.acceptance < 0.2);
+    assert!(recovered_score.acceptance > 0.7);
+    assert!(recovered_score.score > outage_score.score);
+}
+
+fn publish_history(
+    relay_url: &str,
+    attempts: i64,
+    accepted: i64,
+) -> RadrootsOutboxRelayPublishBucket {
+    RadrootsOutboxRelayPublishBucket {
+        relay_url: relay_url.to_owned(),
+        bucket_start_ms: 0,
+        attempts,
+        accepted,
+        failed_retryable: attempts - accepted,
+        failed_terminal: 0,
+        last_attempt_at_ms: 60_000,
+        last_accepted_at_ms: (accepted > 0).then_some(60_000),
+    }
+}
+
+fn tertiary_preferred_health() -> RadrootsRankedRelaySet {
+    RadrootsRankedRelaySet::from_history(
+        &[],
+        &[
+            publish_history(RELAY_PRIMARY_WSS, 10, 2),
+            publish_history(RELAY_SECONDARY_WSS, 10, 5),
+            publish_history(RELAY_TERTIARY_WSS, 10, 10),
+        ],
+        &RadrootsRelayHealthPolicy::default(),
+        120_000,
+    )
+}
+
+#[test]
+fn relay_router_prefers_healthier_relays_within_caps() {
+    let fallback = RadrootsRelayTargetSet::new(
+        [RELAY_PRIMARY_WSS, RELAY_SECONDARY_WSS, RELAY_TERTIARY_WSS],
+        RadrootsRelayUrlPolicy::Public,
+    )
+    .expect("fallback");
+    let policy = RadrootsRelayRoutingPolicy {
+        max_relays_per_pubkey: 1,
+        ..RadrootsRelayRoutingPolicy::default()
+    };
+    let unranked = RadrootsRelayRouter::new(fallback.clone(), policy);
+    let ranked = unranked.clone().with_health(tertiary_preferred_health());
+    let authors = vec![FIXTURE_ALICE_PUBLIC_KEY_HEX.to_owned()];
+
+    assert_eq!(
+        unranked.write_relays(FIXTURE_ALICE_PUBLIC_KEY_HEX)[0].as_str(),
+        RELAY_SECONDARY_WSS
+    );
+    assert_eq!(
+        ranked.write_relays(FIXTURE_ALICE_PUBLIC_KEY_HEX)[0].as_str(),
+        RELAY_TERTIARY_WSS
+    );
+    assert_eq!(
+        ranked
+            .publish_targets(FIXTURE_ALICE_PUBLIC_KEY_HEX, &[])
+            .expect("publish targets")
+            .relay_strings(),
+        vec![RELAY_TERTIARY_WSS.to_owned()]
+    );
+    let routes = ranked.fetch_routes(authors.as_slice());
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].relay_url.as_str(), RELAY_TERTIARY_WSS);
+}
+
+#[tokio::test]
+async fn outbox_publish_with_health_tries_healthiest_relays_before_the_rest() {
+    let signed = signed_post("healthiest first");
+    let outbox = RadrootsOutbox::open_memory().await.expect("outbox");
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let draft = RadrootsFrozenEventDraft::new(
+        "radroots.social.post.v1",
+        KIND_POST,
+        signed.created_at,
+        signed.tags.clone(),
+        signed.content.clone(),
+        signed.pubkey.as_str(),
+    )
+    .expect("draft");
+    let mut outbox_event_ids = Vec::new();
+    for key in ["healthy-a", "healthy-b"] {
+        let receipt = outbox
+            .enqueue_operation(
+                RadrootsOutboxOperationInput::new(
+                    "publish_post",
+                    draft.clone(),
+                    vec![
+                        RELAY_PRIMARY_WSS.to_owned(),
+                        RELAY_SECONDARY_WSS.to_owned(),
+                        RELAY_TERTIARY_WSS.to_owned(),
+                    ],
+                    1_000,
+                )
+                .with_idempotency_key(key),
+            )
+            .await
+            .expect("enqueue");
+        outbox_event_ids.push(receipt.outbox_event_id);
+    }
+
+    let mut attempted = Vec::new();
+    for (index, adapter) in [
+        RadrootsMockRelayPublishAdapter::new(),
+        RadrootsMockRelayPublishAdapter::new().with_outcome(
+            RELAY_TERTIARY_WSS,
+            RadrootsRelayOutcome::timeout("timeout: no OK"),
+        ),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let offset = index as i64 * 10_000;
+        let claimed = outbox
+            .claim_next_ready_event("signer", "sign", 2_000 + offset, 1_000 + offset)
+            .await
+            .expect("claim")
+            .expect("claim");
+        outbox
+            .sign_claimed_event(&claimed, &fixture_keys(), 1_100 + offset)
+            .await
+            .expect("sign");
+        outbox
+            .recover_expired_claims(2_001 + offset)
+            .await
+            .expect("recover");
+        let publish_claim = outbox
+            .claim_next_ready_event("publisher", "publish", 3_000 + offset, 2_100 + offset)
+            .await
+            .expect("claim")
+            .expect("publish claim");
+        let published = publish_claimed_outbox_event(
+            &outbox,
+            &store,
+            &adapter,
+            &publish_claim,
+            RadrootsOutboxPublishPolicy::new(2_500 + offset)
+                .with_accepted_quorum(1)
+                .with_relay_health(tertiary_preferred_health()),
+            2_200 + offset,
+        )
+        .await
+        .expect("publish");
+        assert!(published.publish.quorum_met);
+        attempted.push(
+            published
+                .publish
+                .relays
+                .iter()
+                .map(|relay| relay.relay_url.clone())
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    assert_eq!(attempted[0], vec![RELAY_TERTIARY_WSS.to_owned()]);
+    assert_eq!(
+        attempted[1],
+        vec![
+            RELAY_TERTIARY_WSS.to_owned(),
+            RELAY_SECONDARY_WSS.to_owned(),
+            RELAY_PRIMARY_WSS.to_owned(),
+        ]
+    );
+    let statuses = outbox
+        .relay_statuses(outbox_event_ids[0])
+        .await
+        .expect("statuses");
+    assert_eq!(
+        statuses
+            .iter()
+            .filter(|status| status.status == RadrootsOutboxRelayStatus::Pending)
+            .count(),
+        2
+    );
+}
+
+static WORKER_CLOCK_MS: AtomicI64 = AtomicI64::new(1_000_000);
+
+fn worker_clock() -> i64 {
+    WORKER_CLOCK_MS.fetch_add(1_000, Ordering::SeqCst)
+}
+
+fn worker_operation(content: &str, relay_url: &str) -> RadrootsOutboxOperationInput {
+    let draft = RadrootsFrozenEventDraft::new(
+        "radroots.social.post.v1",
+        KIND_POST,
+        1_700_000_000,
+        Vec::new(),
+        content,
+        FIXTURE_ALICE_PUBLIC_KEY_HEX,
+    )
+    .expect("draft");
+    RadrootsOutboxOperationInput::new("publish_post", draft, vec![relay_url.to_owned()], 1_000)
+}
+
+#[tokio::test]
+async fn outbox_worker_publishes_retries_dead_letters_and_recovers_claims() {
+    let outbox = RadrootsOutbox::open_memory().await.expect("outbox");
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let stranded = outbox
+        .enqueue_operation(worker_operation("stranded", RELAY_PRIMARY_WSS))
+        .await
+        .expect("enqueue stranded");
+    outbox
+        .claim_next_ready_event("crashed-worker", "stale", 2_000, 1_000)
+        .await
+        .expect("claim")
+        .expect("stale claim");
+    let accepted = outbox
+        .enqueue_operation(worker_operation("accepted", RELAY_PRIMARY_WSS))
+        .await
+        .expect("enqueue accepted");
+    let throttled = outbox
+        .enqueue_operation(worker_operation("throttled", RELAY_SECONDARY_WSS))
+        .await
+        .expect("enqueue throttled");
+
+    let adapter = RadrootsMockRelayPublishAdapter::new().with_outcome(
+        RELAY_SECONDARY_WSS,
+        RadrootsRelayOutcome::classify("rate-limited: slow down"),
+    );
+    let fast_backoff = BackoffConfig {
+        base_ms: 1,
+        max_ms: 4,
+        factor: 2,
+        jitter_ms: 0,
+    };
+    let worker = RadrootsOutboxWorker::new(
+        outbox.clone(),
+        store,
+        adapter,
+        fixture_keys(),
+        RadrootsOutboxWorkerConfig::default()
+            .with_worker_id("market-worker")
+            .with_concurrency(2)
+            .with_max_attempts(2)
+            .with_retry_backoff(fast_backoff.clone())
+            .with_idle_backoff(fast_backoff)
+            .with_clock(worker_clock),
+    );
+    let mut log = worker.subscribe();
+    let mut progress = worker.subscribe();
+    let shutdown = async move {
+        let (mut published, mut dead_lettered) = (0, 0);
+        while published < 2 || dead_lettered < 1 {
+            match progress.recv().await.expect("progress event") {
+                RadrootsOutboxWorkerEvent::Published { .. } => published += 1,
+                RadrootsOutboxWorkerEvent::DeadLettered { .. } => dead_lettered += 1,
+                _ => {}
+            }
+        }
+    };
+    let report = tokio::time::timeout(Duration::from_secs(10), worker.run(shutdown))
+        .await
+        .expect("worker finished")
+        .expect("worker report");
+
+    assert_eq!(report.recovered_claims, 1);
+    assert_eq!(report.published, 2);
+    assert_eq!(report.retried, 2);
+    assert_eq!(report.dead_lettered, 1);
+    assert_eq!(report.errors, 0);
+    for published in [&stranded, &accepted] {
+        assert_eq!(
+            outbox
+                .get_event(published.outbox_event_id)
+                .await
+                .expect("event")
+                .expect("event record")
+                .state,
+            RadrootsOutboxEventState::Published
+        );
+    }
+    let dead_letter = outbox
+        .dead_letter(throttled.outbox_event_id)
+        .await
+        .expect("dead letter");
+    assert_eq!(
+        dead_letter.event.last_error.as_deref(),
+        Some("retry budget exhausted after 2 attempts")
+    );
+    assert_eq!(dead_letter.relay_attempts.len(), 2);
+    assert_eq!(worker.adapter().captured_raw_events().len(), 4);
+
+    let mut events = Vec::new();
+    while let Ok(event) = log.try_recv() {
+        events.push(event);
+    }
+    assert_eq!(
+        events.first(),
+        Some(&RadrootsOutboxWorkerEvent::Started {
+            worker_id: "market-worker".to_owned(),
+            recovered_claims: 1,
+        })
+    );
+    assert_eq!(
+        events.last(),
+        Some(&RadrootsOutboxWorkerEvent::Stopped(report))
+    );
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, RadrootsOutboxWorkerEvent::Signed { .. }))
+            .count(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn outbox_worker_reports_relay_health_errors_and_still_publishes() {
+    let outbox = RadrootsOutbox::open_memory().await.expect("outbox");
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let queued = outbox
+        .enqueue_operation(worker_operation("health", RELAY_PRIMARY_WSS))
+        .await
+        .expect("enqueue");
+    let seen = signed_post("seen elsewhere");
+    store
+        .ingest_event(RadrootsEventIngest::new(
+            radroots_event_from_nostr(
+                &nostr::Event::from_json(seen.raw_json.as_str()).expect("event json"),
+            ),
+            1_000,
+        ))
+        .await
+        .expect("ingest seen");
+    sqlx::query(
+        "INSERT INTO relay_event_seen(event_id, relay_url, observation_type, first_seen_at_ms, last_seen_at_ms, observation_count) VALUES (?, ?, 'fetch', 1000, 'not a timestamp', 1)",
+    )
+    .bind(seen.id.as_str())
+    .bind(RELAY_PRIMARY_WSS)
+    .execute(store.pool())
+    .await
+    .expect("corrupt relay history");
+    let worker = RadrootsOutboxWorker::new(
+        outbox.clone(),
+        store,
+        RadrootsMockRelayPublishAdapter::new(),
+        fixture_keys(),
+        RadrootsOutboxWorkerConfig::default()
+            .with_relay_health(RadrootsRelayHealthPolicy::default())
+            .with_clock(worker_clock),
+    );
+    let mut log = worker.subscribe();
+    let mut progress = worker.subscribe();
+    let shutdown = async move {
+        while !matches!(
+            progress.recv().await.expect("progress event"),
+            RadrootsOutboxWorkerEvent::Published { .. }
+        ) {}
+    };
+    let report = tokio::time::timeout(Duration::from_secs(10), worker.run(shutdown))
+        .await
+        .expect("worker finished")
+        .expect("worker report");
+
+    assert_eq!(report.published, 1);
+    assert_eq!(report.retried, 0);
+    assert_eq!(report.errors, 1);
+    let mut errors = Vec::new();
+    while let Ok(event) = log.try_recv() {
+        if let RadrootsOutboxWorkerEvent::Error {
+            outbox_event_id, ..
+        } = event
+        {
+            errors.push(outbox_event_id);
+        }
+    }
+    assert_eq!(errors, vec![Some(queued.outbox_event_id)]);
+}
+
+const FIXTURE_BOB_SECRET_KEY_HEX: &str =
+    "59392e9068f66431b12f70218fb61281cb6b433d7f27c55d61f1a63fe1a96ff8";
+const FIXTURE_BOB_PUBLIC_KEY_HEX: &str =
+    "e0266e3cfb0d2886f91c73f5f868f3b98273713e5fcd97c081663f5518a4b3af";
+const FIXTURE_CAROL_PUBLIC_KEY_HEX: &str =
+    "c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0";
+const RELAY_FALLBACK_WSS: &str = "wss://fallback.example.com";
+const RELAY_BOB_WRITE_WSS: &str = "wss://bob-write.example.com";
+
+async fn ingest_relay_list(
+    store: &RadrootsEventStore,
+    secret_key_hex: &str,
+    created_at: u64,
+    relays: &[(&str, Option<&str>)],
+) {
+    let tags = relays
+        .iter()
+        .map(|(relay, marker)| {
+            let mut tag = vec!["r".to_owned(), (*relay).to_owned()];
+            tag.extend(marker.map(str::to_owned));
+            tag
+        })
+        .collect::<Vec<_>>();
+    let keys = RadrootsNostrKeys::new(
+        RadrootsNostrSecretKey::from_hex(secret_key_hex).expect("secret key"),
+    );
+    let event = radroots_nostr_build_event(KIND_LIST_READ_WRITE_RELAYS, "", tags)
+        .expect("relay list builder")
+        .custom_created_at(RadrootsNostrTimestamp::from_secs(created_at))
+        .sign_with_keys(&keys)
+        .expect("signed relay list");
+    store
+        .ingest_event(RadrootsEventIngest::new(
+            radroots_event_from_nostr(&event),
+            1_000,
+        ))
+        .await
+        .expect("ingest relay list");
+}
+
+#[tokio::test]
+async fn relay_routing_follows_nip65_lists_with_caps_and_fallback() {
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    ingest_relay_list(
+        &store,
+        FIXTURE_ALICE_SECRET_KEY_HEX,
+        1_700_000_000,
+        &[(RELAY_TERTIARY_WSS, None)],
+    )
+    .await;
+    ingest_relay_list(
+        &store,
+        FIXTURE_ALICE_SECRET_KEY_HEX,
+        1_700_000_100,
+        &[
+            (RELAY_PRIMARY_WSS, Some("write")),
+            (RELAY_SECONDARY_WSS, Some("read")),
+            ("https://not-a-relay.example.com", None),
+        ],
+    )
+    .await;
+    ingest_relay_list(
+        &store,
+        FIXTURE_BOB_SECRET_KEY_HEX,
+        1_700_000_000,
+        &[
+            (RELAY_TERTIARY_WSS, Some("read")),
+            (RELAY_BOB_WRITE_WSS, Some("write")),
+            (RELAY_PRIMARY_WSS, None),
+        ],
+    )
+    .await;
+
+    let participants = vec![
+        FIXTURE_ALICE_PUBLIC_KEY_HEX.to_owned(),
+        FIXTURE_BOB_PUBLIC_KEY_HEX.to_owned(),
+        FIXTURE_CAROL_PUBLIC_KEY_HEX.to_owned(),
+    ];
+    let fallback =
+        RadrootsRelayTargetSet::new([RELAY_FALLBACK_WSS], RadrootsRelayUrlPolicy::Public)
+            .expect("fallback");
+    let router = resolve_relay_router(
+        &store,
+        participants.as_slice(),
+        fallback,
+        RadrootsRelayRoutingPolicy {
+            max_relays_per_pubkey: 1,
+            max_authors_per_request: 1,
+            ..RadrootsRelayRoutingPolicy::default()
+        },
+        1_700_000_200,
+    )
+    .await
+    .expect("router");
+
+    let alice = router
+        .relay_list(FIXTURE_ALICE_PUBLIC_KEY_HEX)
+        .expect("alice relay list");
+    assert_eq!(alice.created_at, 1_700_000_100);
+    assert_eq!(alice.write.len(), 1);
+    assert_eq!(alice.read.len(), 1);
+    assert!(router.relay_list(FIXTURE_CAROL_PUBLIC_KEY_HEX).is_none());
+
+    let order = RadrootsNostrEvent {
+        id: "0".repeat(64),
+        author: FIXTURE_ALICE_PUBLIC_KEY_HEX.to_owned(),
+        created_at: 1_700_000_150,
+        kind: KIND_POST,
+        tags: vec![
+            vec!["p".to_owned(), FIXTURE_BOB_PUBLIC_KEY_HEX.to_owned()],
+            vec!["p".to_owned(), FIXTURE_CAROL_PUBLIC_KEY_HEX.to_owned()],
+        ],
+        content: String::new(),
+        sig: "0".repeat(128),
+    };
+    assert_eq!(
+        router
+            .publish_targets_for_event(&order)
+            .expect("publish targets")
+            .relay_strings(),
+        vec![
+            RELAY_FALLBACK_WSS.to_owned(),
+            RELAY_TERTIARY_WSS.to_owned(),
+            RELAY_PRIMARY_WSS.to_owned(),
+        ]
+    );
+
+    let routes = router.fetch_routes(participants.as_slice());
+    let routed = routes
+        .iter()
+        .map(|route| (route.relay_url.as_str(), route.authors.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        routed,
+        vec![
+            (
+                RELAY_FALLBACK_WSS,
+                vec![FIXTURE_CAROL_PUBLIC_KEY_HEX.to_owned()]
+            ),
+            (
+                RELAY_PRIMARY_WSS,
+                vec![FIXTURE_ALICE_PUBLIC_KEY_HEX.to_owned()]
+            ),
+            (
+                RELAY_PRIMARY_WSS,
+                vec![FIXTURE_BOB_PUBLIC_KEY_HEX.to_owned()]
+            ),
+        ]
+    );
+
+    let relay_lists = resolve_relay_lists(
+        &store,
+        participants.as_slice(),
+        RadrootsRelayUrlPolicy::Public,
+        1_700_000_200,
+    )
+    .await
+    .expect("relay lists");
+    let bounded = |max_total_relays| {
+        RadrootsRelayRouter::new(
+            router.fallback().clone(),
+            RadrootsRelayRoutingPolicy {
+                max_total_relays,
+                ..RadrootsRelayRoutingPolicy::default()
+            },
+        )
+        .with_relay_lists(relay_lists.clone())
+        .fetch_routes(participants.as_slice())
+    };
+    let bounded_routes = bounded(2);
+    assert_eq!(bounded_routes.len(), 2);
+    assert_eq!(bounded_routes[0].relay_url.as_str(), RELAY_FALLBACK_WSS);
+    assert_eq!(bounded_routes[1].relay_url.as_str(), RELAY_PRIMARY_WSS);
+    assert_eq!(bounded_routes[1].authors.len(), 2);
+
+    let single_route = bounded(1);
+    assert_eq!(single_route.len(), 1);
+    assert_eq!(single_route[0].relay_url.as_str(), RELAY_FALLBACK_WSS);
+    assert_eq!(single_route[0].authors.len(), participants.len());
+}
+
+const AUTH_RELAY_WSS: &str = "wss://members.example.com";
+const OFFLINE_RELAY_WSS: &str = "wss://offline.example.com";
+
+#[derive(Default)]
+struct ScriptedAuthRelayState {
+    connection: u32,
+    greeted: bool,
+    authenticated_pubkey: Option<String>,
+    events: Vec<String>,
+    frames: Vec<String>,
+}
+
+#[derive(Default)]
+struct ScriptedAuthRelay {
+    state: Mutex<ScriptedAuthRelayState>,
+}
+
+impl ScriptedAuthRelay {
+    fn reconnect(&self) {
+        let mut state = self.state.lock().expect("relay state");
+        state.connection += 1;
+        state.greeted = false;
+        state.authenticated_pubkey = None;
+    }
+
+    fn frame_labels(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .expect("relay state")
+            .frames
+            .iter()
+            .map(|frame| {
+                serde_json::from_str::<serde_json::Value>(frame).expect("frame")[0]
+                    .as_str()
+                    .expect("frame label")
+                    .to_owned()
+            })
+            .collect()
+    }
+}
+
+impl RadrootsRelayConnection for ScriptedAuthRelay {
+    fn exchange<'a>(
+        &'a self,
+        relay_url: &'a str,
+        message: RadrootsRelayClientMessage,
+    ) -> BoxFuture<'a, Result<Vec<RadrootsRelayServerMessage>, RadrootsRelayTransportError>> {
+        Box::pin(async move {
+            if relay_url == OFFLINE_RELAY_WSS {
+                return Err(RadrootsRelayTransportError::Transport(format!(
+                    "relay disconnected: {relay_url}"
+                )));
+            }
+            let mut state = self.state.lock().expect("relay state");
+            state.frames.push(message.to_json()?);
+            let challenge = format!("challenge-{}", state.connection);
+            let mut replies = Vec::new();
+            if !state.greeted {
+                state.greeted = true;
+                replies.push(RadrootsRelayServerMessage::Auth {
+                    challenge: challenge.clone(),
+                });
+            }
+            match message {
+                RadrootsRelayClientMessage::Auth { raw_json } => {
+                    let event = nostr::Event::from_json(raw_json.as_str()).expect("auth event");
+                    let tag = |name: &str| {
+                        event.tags.iter().find_map(|tag| {
+                            let values = tag.as_slice();
+                            (values.first().map(String::as_str) == Some(name))
+                                .then(|| values.get(1).cloned())
+                                .flatten()
+                        })
+                    };
+                    let valid = event.verify().is_ok()
+                        && event.kind.as_u16() == 22242
+                        && tag("relay").as_deref() == Some(relay_url)
+                        && tag("challenge") == Some(challenge);
+                    if valid {
+                        state.authenticated_pubkey = Some(event.pubkey.to_hex());
+                    }
+                    replies.push(RadrootsRelayServerMessage::Ok {
+                        event_id: event.id.to_hex(),
+                        accepted: valid,
+                        message: if valid {
+                            String::new()
+                        } else {
+                            "invalid: bad auth".to_owned()
+                        },
+                    });
+                }
+                RadrootsRelayClientMessage::Event { raw_json } => {
+                    let event = nostr::Event::from_json(raw_json.as_str()).expect("event");
+                    let accepted = state.authenticated_pubkey.is_some();
+                    if accepted {
+                        state.events.push(raw_json);
+                    }
+                    replies.push(RadrootsRelayServerMessage::Ok {
+                        event_id: event.id.to_hex(),
+                        accepted,
+                        message: if accepted {
+                            String::new()
+                        } else {
+                            "auth-required: members only".to_owned()
+                        },
+                    });
+                }
+                RadrootsRelayClientMessage::Req {
+                    subscription_id, ..
+                } => {
+                    if state.authenticated_pubkey.is_none() {
+                        replies.push(RadrootsRelayServerMessage::Closed {
+                            subscription_id,
+                            message: "auth-required: members only".to_owned(),
+                        });
+                    } else {
+                        replies.extend(state.events.iter().map(|raw_json| {
+                            RadrootsRelayServerMessage::Event {
+                                subscription_id: subscription_id.clone(),
+                                raw_json: raw_json.clone(),
+                            }
+                        }));
+                        replies.push(RadrootsRelayServerMessage::Eose { subscription_id });
+                    }
+                }
+                RadrootsRelayClientMessage::Close { .. } => {}
+            }
+            Ok(replies)
+        })
+    }
+
+    fn connection_id(&self, _relay_url: &str) -> u64 {
+        u64::from(self.state.lock().expect("relay state").connection)
+    }
+}
+
+fn auth_clock() -> u32 {
+    1_700_000_500
+}
+
+#[test]
+fn relay_frames_round_trip_through_json() {
+    let signed = signed_post("frame");
+    let frames = [
+        RadrootsRelayServerMessage::Ok {
+            event_id: signed.id.clone(),
+            accepted: false,
+            message: "auth-required: members only".to_owned(),
+        },
+        RadrootsRelayServerMessage::Event {
+            subscription_id: "sub".to_owned(),
+            raw_json: signed.raw_json.clone(),
+        },
+        RadrootsRelayServerMessage::Closed {
+            subscription_id: "sub".to_owned(),
+            message: "auth-required: members only".to_owned(),
+        },
+        RadrootsRelayServerMessage::Auth {
+            challenge: "challenge".to_owned(),
+        },
+    ];
+    for frame in frames {
+        let json = frame.to_json().expect("server frame json");
+        let parsed = RadrootsRelayServerMessage::from_json(json.as_str()).expect("server frame");
+        assert_eq!(parsed.to_json().expect("server frame json"), json);
+    }
+    let req = RadrootsRelayClientMessage::Req {
+        subscription_id: "sub".to_owned(),
+        filters: vec![serde_json::json!({"kinds": [1]})],
+    };
+    assert_eq!(
+        RadrootsRelayClientMessage::from_json(req.to_json().expect("req json").as_str())
+            .expect("req"),
+        req
+    );
+    assert!(RadrootsRelayServerMessage::from_json(r#"["OK","id"]"#).is_err());
+}
+
+#[tokio::test]
+async fn auth_publish_keeps_accepted_receipts_when_another_relay_fails() {
+    let authenticator =
+        RadrootsRelayAuthenticator::new(ScriptedAuthRelay::default(), fixture_keys())
+            .with_clock(auth_clock);
+    let adapter = RadrootsAuthRelayPublishAdapter::new(authenticator);
+    let targets = RadrootsRelayTargetSet::new(
+        [AUTH_RELAY_WSS, OFFLINE_RELAY_WSS],
+        RadrootsRelayUrlPolicy::Public,
+    )
+    .expect("targets");
+
+    let receipt = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed_post("partial"), targets, 1_000),
+    )
+    .await
+    .expect("publish");
+    assert_eq!(receipt.attempted_count, 2);
+    assert_eq!(receipt.accepted_count, 1);
+    assert!(!receipt.quorum_met);
+    let offline = receipt
+        .relays
+        .iter()
+        .find(|relay| relay.relay_url == OFFLINE_RELAY_WSS)
+        .expect("offline receipt");
+    assert_eq!(
+        offline.outcome.kind,
+        RadrootsRelayOutcomeKind::ConnectionFailed
+    );
+}
+
+#[tokio::test]
+async fn auth_adapters_answer_challenges_retry_and_cache_sessions() {
+    let authenticator =
+        RadrootsRelayAuthenticator::new(ScriptedAuthRelay::default(), fixture_keys())
+            .with_clock(auth_clock);
+    let adapter = RadrootsAuthRelayPublishAdapter::new(authenticator.clone());
+    let targets = RadrootsRelayTargetSet::new([AUTH_RELAY_WSS], RadrootsRelayUrlPolicy::Public)
+        .expect("targets");
+
+    let first = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed_post("members first"), targets.clone(), 1_000),
+    )
+    .await
+    .expect("first publish");
+    assert!(first.quorum_met);
+    assert!(
+        authenticator
+            .is_authenticated(AUTH_RELAY_WSS)
+            .expect("auth state")
+    );
+    let second = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed_post("members second"), targets.clone(), 2_000),
+    )
+    .await
+    .expect("second publish");
+    assert!(second.quorum_met);
+    assert_eq!(
+        authenticator.connection().frame_labels(),
+        vec!["EVENT", "AUTH", "EVENT", "EVENT"]
+    );
+
+    authenticator.connection().reconnect();
+    assert!(
+        !authenticator
+            .is_authenticated(AUTH_RELAY_WSS)
+            .expect("auth state")
+    );
+    let fetch_adapter = RadrootsAuthRelayFetchAdapter::new(
+        authenticator.clone(),
+        targets.clone(),
+        vec![serde_json::json!({"kinds": [KIND_POST]})],
+    );
+    let store = RadrootsEventStore::open_memory().await.expect("store");
+    let receipt = fetch_and_ingest_relay_events(
+        &fetch_adapter,
+        &store,
+        RadrootsRelayFetchRequest::fetch(3_000, 10),
+    )
+    .await
+    .expect("fetch");
+    assert_eq!(receipt.inserted_count, 2);
+    assert_eq!(receipt.eose_count, 1);
+    assert_eq!(receipt.closed_count, 0);
+    assert_eq!(
+        authenticator.connection().frame_labels()[4..],
+        ["REQ", "AUTH", "REQ", "CLOSE"]
+    );
+
+    authenticator.connection().reconnect();
+    authenticator
+        .exchange(
+            AUTH_RELAY_WSS,
+            RadrootsRelayClientMessage::Close {
+                subscription_id: "greeting".to_owned(),
+            },
+        )
+        .await
+        .expect("greeting");
+    let third = publish_signed_event(
+        &adapter,
+        RadrootsRelayPublishRequest::new(signed_post("members third"), targets, 4_000),
+    )
+    .await
+    .expect("third publish");
+    assert!(third.quorum_met);
+    assert_eq!(
+        authenticator.connection().frame_labels()[8..],
+        ["CLOSE", "AUTH", "EVENT"]
+    );
+
+    let stranger = RadrootsRelayAuthenticator::new(
+        ScriptedAuthRelay::default(),
+        RadrootsNostrKeys::new(
+            RadrootsNostrSecretKey::from_hex(FIXTURE_BOB_SECRET_KEY_HEX).expect("secret key"),
+        ),
+    )
+    .with_clock(auth_clock);
+    let outcome = stranger
+        .authenticate(AUTH_RELAY_WSS)
+        .await
+        .expect("authenticate without challenge");
+    assert_eq!(outcome.kind, RadrootsRelayOutcomeKind::AuthRequired);
+    assert!(stranger.connection().frame_labels().is_empty());
+}
+
+fn signed_post_at(created_at: u64, content: &str) -> RadrootsEventIngest {
+    let event = radroots_nostr_build_event(KIND_POST, content, Vec::new())
+        .expect("post builder")
+        .custom_created_at(RadrootsNostrTimestamp::from_secs(created_at))
+        .sign_with_keys(&fixture_keys())
+        .expect("signed post");
+    RadrootsEventIngest::new(radroots_event_from_nostr(&event), 1_000)
+        .with_raw_json(event.as_json())
+}
+
+#[tokio::test]
+async fn backfill_walks_until_windows_splits_truncated_pages_and_resumes() {
+    let origin = RadrootsEventStore::open_memory().await.expect("origin");
+    let mut origin_events = Vec::new();
+    for index in 0..10 {
+        origin_events.push(signed_post_at(1_700_000_000 + index * 1_000, "history"));
+    }
+    for index in 0..4 {
+        origin_events.push(signed_post_at(1_699_995_000, &format!("burst {index}")));
+    }
+    for ingest in origin_events {
+        origin.ingest_event(ingest).await.expect("seed origin");
+    }
+    let peer = RadrootsInProcessBackfillPeer::new(origin).with_result_cap(3);
+    let local = RadrootsEventStore::open_memory().await.expect("local");
+    let filter = RadrootsRelayBackfillFilter {
+        kinds: vec![KIND_POST],
+        since: Some(1_699_990_000),
+        until: Some(1_700_020_000),
+        ..RadrootsRelayBackfillFilter::default()
+    };
+    let policy = RadrootsRelayBackfillPolicy {
+        page_limit: 3,
+        initial_window_secs: 4_000,
+        max_pages_per_run: 3,
+        ..RadrootsRelayBackfillPolicy::default()
+    };
+
+    let interrupted = backfill_relay_events(
+        &peer,
+        &local,
+        RELAY_PRIMARY_WSS,
+        &filter,
+        &policy,
+        1_700_030_000,
+        5_000,
+    )
+    .await
+    .expect("interrupted backfill");
+    assert_eq!(interrupted.pages, 3);
+    assert!(!interrupted.completed());
+    let saved = local
+        .relay_backfill_checkpoint(RELAY_PRIMARY_WSS, filter.key().expect("key").as_str())
+        .await
+        .expect("checkpoint")
+        .expect("saved checkpoint");
+    assert_eq!(saved, interrupted.checkpoint);
+    assert!(saved.until_cursor < 1_700_020_000);
+
+    let resumed = backfill_relay_events(
+        &peer,
+        &local,
+        RELAY_PRIMARY_WSS,
+        &filter,
+        &RadrootsRelayBackfillPolicy {
+            max_pages_per_run: 64,
+            ..policy
+        },
+        1_700_030_000,
+        6_000,
+    )
+    .await
+    .expect("resumed backfill");
+    assert!(resumed.completed());
+    assert!(resumed.truncated_pages > 0);
+    assert_eq!(resumed.checkpoint.truncated_seconds, 1);
+    assert_eq!(
+        resumed.checkpoint.pages,
+        u64::try_from(interrupted.pages + resumed.pages).expect("pages")
+    );
+    let backfilled = local
+        .query_events(
+            &radroots_event_store::RadrootsEventQuery {
+                kinds: vec![KIND_POST],
+                ..radroots_event_store::RadrootsEventQuery::default()
+            },
+            1_700_030_000,
+        )
+        .await
+        .expect("local events");
+    assert_eq!(backfilled.len(), 13);
+    let requested = peer.requested_pages().expect("requested pages");
+    let span = |page: &RadrootsRelayBackfillPage| {
+        page.filter.until.unwrap_or(0) - page.filter.since.unwrap_or(0)
+    };
+    assert!(
+        requested
+            .iter()
+            .all(|page| page.filter.since >= filter.since && page.filter.until <= filter.until)
+    );
+    assert!(requested.iter().any(|page| span(page) < 3_999));
+    assert!(requested.iter().any(|page| span(page) > 3_999));
+    assert_eq!(
+        requested[0].filter.to_relay_filter(3),
+        serde_json::json!({
+            "kinds": [KIND_POST],
+            "since": 1_700_016_001,
+            "until": 1_700_020_000,
+            "limit": 3,
+        })
+    );
+
+    let finished = backfill_relay_events(
+        &peer,
+        &local,
+        RELAY_PRIMARY_WSS,
+        &filter,
+        &policy,
+        1_700_030_000,
+        7_000,
+    )
+    .await
+    .expect("finished backfill");
+    assert_eq!(finished.pages, 0);
+    assert!(finished.completed());
+
+    let capped_peer =
+        RadrootsInProcessBackfillPeer::new(peer.into_event_store()).with_result_cap(2);
+    let capped_local = RadrootsEventStore::open_memory()
+        .await
+        .expect("capped local");
+    let capped = backfill_relay_events(
+        &capped_peer,
+        &capped_local,
+        RELAY_PRIMARY_WSS,
+        &filter,
+        &RadrootsRelayBackfillPolicy {
+            max_pages_per_run: 256,
+            ..policy
+        },
+        1_700_030_000,
+        8_000,
+    )
+    .await
+    .expect("capped backfill");
+    assert!(capped.completed());
+    assert_eq!(capped.truncated_pages, 0);
+    let capped_events = capped_local
+        .query_events(
+            &radroots_event_store::RadrootsEventQuery {
+                kinds: vec![KIND_POST],
+                ..radroots_event_store::RadrootsEventQuery::default()
+            },
+            1_700_030_000,
+        )
+        .await
+        .expect("capped events");
+    assert_eq!(capped_events.len(), 12);
+}
+
+fn breaker_publish_request(now_ms: i64) -> RadrootsRelayPublishRequest {
+    RadrootsRelayPublishRequest::new(
+        signed_post("breaker"),
+        RadrootsRelayTargetSet::new(
+            vec![RELAY_PRIMARY_WSS, RELAY_SECONDARY_WSS],
+            RadrootsRelayUrlPolicy::Public,
+        )
+        .expect("targets"),
+        now_ms,
+    )
+}
+
+#[tokio::test]
+async fn circuit_breaker_pauses_rate_limited_relays_and_recovers_through_half_open() {
+    let breaker = RadrootsRelayCircuitBreaker::new(RadrootsRelayCircuitPolicy {
+        failure_threshold: 3,
+        cool_down_ms: 10_000,
+        max_cool_down_ms: 60_000,
+        cool_down_jitter_ms: 0,
+        bucket_capacity: 2.0,
+        refill_per_sec: 1.0,
+        min_refill_per_sec: 0.1,
+        rate_limited_factor: 0.5,
+        recovery_factor: 2.0,
+    });
+    let limited = RadrootsCircuitBreakerPublishAdapter::new(
+        RadrootsMockRelayPublishAdapter::new().with_outcome(
+            RELAY_SECONDARY_WSS,
+            RadrootsRelayOutcome::classify("rate-limited: slow down"),
+        ),
+        breaker.clone(),
+    );
+
+    let receipts = limited
+        .publish(breaker_publish_request(1_000))
+        .await
+        .expect("first publish");
+    assert!(receipts.iter().all(|receipt| receipt.attempted));
+    let secondary = breaker
+        .snapshot(RELAY_SECONDARY_WSS, 1_000)
+        .expect("snapshot")
+        .expect("secondary circuit");
+    assert_eq!(secondary.state, RadrootsRelayCircuitState::Closed);
+    assert_eq!(secondary.consecutive_failures, 1);
+    assert_eq!(secondary.refill_per_sec, 0.5);
+    assert_eq!(
+        breaker.peek(RELAY_SECONDARY_WSS, 1_000).expect("peek"),
+        RadrootsRelayAdmission::Throttled { retry_at_ms: 3_000 }
+    );
+
+    let receipts = limited
+        .publish(breaker_publish_request(1_000))
+        .await
+        .expect("throttled publish");
+    let throttled = receipts
+        .iter()
+        .find(|receipt| receipt.relay_url == RELAY_SECONDARY_WSS)
+        .expect("secondary receipt");
+    assert!(!throttled.attempted);
+    assert_eq!(
+        throttled.outcome.kind,
+        RadrootsRelayOutcomeKind::RateLimited
+    );
+    assert!(
+        receipts
+            .iter()
+            .any(|receipt| receipt.relay_url == RELAY_PRIMARY_WSS && receipt.attempted)
+    );
+
+    limited
+        .publish(breaker_publish_request(3_000))
+        .await
+        .expect("second failure");
+    limited
+        .publish(breaker_publish_request(7_000))
+        .await
+        .expect("third failure");
+    let paused = breaker
+        .snapshot(RELAY_SECONDARY_WSS, 8_000)
+        .expect("snapshot")
+        .expect("secondary circuit");
+    assert_eq!(paused.state, RadrootsRelayCircuitState::Open);
+    assert_eq!(paused.trips, 1);
+    assert_eq!(paused.paused_until_ms, Some(17_000));
+    assert_eq!(
+        paused.last_outcome,
+        Some(RadrootsRelayOutcomeKind::RateLimited)
+    );
+    serde_json::to_string(&breaker.snapshots(8_000).expect("snapshots")).expect("snapshot json");
+    assert_eq!(
+        breaker.blocked_relays(8_000).expect("blocked"),
+        vec![RELAY_SECONDARY_WSS.to_owned()]
+    );
+
+    let receipts = limited
+        .publish(breaker_publish_request(8_000))
+        .await
+        .expect("paused publish");
+    let skipped = receipts
+        .iter()
+        .find(|receipt| receipt.relay_url == RELAY_SECONDARY_WSS)
+        .expect("secondary receipt");
+    assert!(!skipped.attempted);
+    assert!(
+        skipped
+            .outcome
+            .message
+            .as_deref()
+            .is_some_and(|message| message.contains("until 17000"))
+    );
+
+    let signed = signed_post("fetched");
+    let fetch = RadrootsCircuitBreakerFetchAdapter::new(
+        RadrootsMockRelayFetchAdapter::new(vec![
+            RadrootsRelayFetchItem::Event {
+                relay_url: RELAY_PRIMARY_WSS.to_owned(),
+                raw_json: signed.raw_json.clone(),
+                observed_at_ms: 8_000,
+            },
+            RadrootsRelayFetchItem::Event {
+                relay_url: RELAY_SECONDARY_WSS.to_owned(),
+                raw_json: signed.raw_json.clone(),
+                observed_at_ms: 8_000,
+            },
+            RadrootsRelayFetchItem::Eose {
+                relay_url: RELAY_SECONDARY_WSS.to_owned(),
+            },
+        ]),
+        breaker.clone(),
+        RadrootsRelayTargetSet::new(
+            vec![RELAY_PRIMARY_WSS, RELAY_SECONDARY_WSS, RELAY_TERTIARY_WSS],
+            RadrootsRelayUrlPolicy::Public,
+        )
+        .expect("fetch targets"),
+    );
+    let items = fetch
+        .fetch(RadrootsRelayFetchRequest::fetch(8_000, 10))
+        .await
+        .expect("fetch");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].relay_url(), RELAY_PRIMARY_WSS);
+    let silent = breaker
+        .snapshot(RELAY_TERTIARY_WSS, 8_000)
+        .expect("snapshot")
+        .expect("tertiary circuit");
+    assert_eq!(silent.consecutive_failures, 1);
+    assert_eq!(silent.last_outcome, Some(RadrootsRelayOutcomeKind::Timeout));
+    let answered = breaker
+        .snapshot(RELAY_PRIMARY_WSS, 8_000)
+        .expect("snapshot")
+        .expect("primary circuit");
+    assert_eq!(
+        answered.last_outcome,
+        Some(RadrootsRelayOutcomeKind::Accepted)
+    );
+
+    assert_eq!(
+        breaker
+            .snapshot(RELAY_SECONDARY_WSS, 17_000)
+            .expect("snapshot")
+            .expect("secondary circuit")
+            .state,
+        RadrootsRelayCircuitState::HalfOpen
+    );
+    limited
+        .publish(breaker_publish_request(17_000))
+        .await
+        .expect("failed probe");
+    let reopened = breaker
+        .snapshot(RELAY_SECONDARY_WSS, 17_000)
+        .expect("snapshot")
+        .expect("secondary circuit");
+    assert_eq!(reopened.state, RadrootsRelayCircuitState::Open);
+    assert_eq!(reopened.trips, 2);
+    assert_eq!(reopened.paused_until_ms, Some(37_000));
+
+    let healthy =
+        RadrootsCircuitBreakerPublishAdapter::new(RadrootsMockRelayPublishAdapter::new(), breaker);
+    let receipts = healthy
+        .publish(breaker_publish_request(37_000))
+        .await
+        .expect("successful probe");
+    assert!(receipts.iter().all(|receipt| receipt.attempted));
+    let recovered = healthy
+        .breaker()
+        .snapshot(RELAY_SECONDARY_WSS, 37_000)
+        .expect("snapshot")
+        .expect("secondary circuit");
+    assert_eq!(recovered.state, RadrootsRelayCircuitState::Closed);
+    assert_eq!(recovered.consecutive_failures, 0);
+    assert_eq!(recovered.paused_until_ms, None);
+    assert_eq!(recovered.refill_per_sec, 0.2);
+}
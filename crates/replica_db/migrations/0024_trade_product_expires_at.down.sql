@@ -0,0 +1 @@
+ALTER TABLE trade_product DROP COLUMN expires_at;
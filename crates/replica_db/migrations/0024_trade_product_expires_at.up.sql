@@ -0,0 +1 @@
+ALTER TABLE trade_product ADD COLUMN expires_at INTEGER;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS coop_tag (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    coop_id CHAR(36) NOT NULL,
+    tag TEXT NOT NULL,
+    FOREIGN KEY (coop_id) REFERENCES coop(id) ON DELETE CASCADE,
+    UNIQUE (coop_id, tag)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS document (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    d_tag TEXT NOT NULL,
+    pubkey TEXT NOT NULL,
+    doc_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    version TEXT NOT NULL,
+    summary TEXT,
+    effective_at INTEGER,
+    body_markdown TEXT,
+    subject_pubkey TEXT NOT NULL,
+    subject_address TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS document_pubkey_d_tag_idx ON document(pubkey, d_tag);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS document_tag (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    document_id CHAR(36) NOT NULL,
+    tag TEXT NOT NULL,
+    FOREIGN KEY (document_id) REFERENCES document(id) ON DELETE CASCADE,
+    UNIQUE (document_id, tag)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS resource_area_tag (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    resource_area_id CHAR(36) NOT NULL,
+    tag TEXT NOT NULL,
+    FOREIGN KEY (resource_area_id) REFERENCES resource_area(id) ON DELETE CASCADE,
+    UNIQUE (resource_area_id, tag)
+);
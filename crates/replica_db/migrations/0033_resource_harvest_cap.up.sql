@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS resource_harvest_cap (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    d_tag TEXT NOT NULL,
+    pubkey TEXT NOT NULL,
+    resource_area_id CHAR(36) NOT NULL,
+    product_key TEXT NOT NULL,
+    product_category TEXT,
+    start_at INTEGER NOT NULL,
+    end_at INTEGER NOT NULL,
+    cap_amount TEXT NOT NULL,
+    cap_unit TEXT NOT NULL,
+    cap_label TEXT,
+    display_amount TEXT,
+    display_unit TEXT,
+    display_label TEXT,
+    FOREIGN KEY (resource_area_id) REFERENCES resource_area(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS resource_harvest_cap_pubkey_d_tag_idx ON resource_harvest_cap(pubkey, d_tag);
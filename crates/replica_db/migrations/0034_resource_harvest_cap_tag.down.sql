@@ -0,0 +1 @@
+DROP TABLE IF EXISTS resource_harvest_cap_tag;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS resource_harvest_cap_tag (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    resource_harvest_cap_id CHAR(36) NOT NULL,
+    tag TEXT NOT NULL,
+    FOREIGN KEY (resource_harvest_cap_id) REFERENCES resource_harvest_cap(id) ON DELETE CASCADE,
+    UNIQUE (resource_harvest_cap_id, tag)
+);
@@ -0,0 +1,26 @@
+CREATE TABLE IF NOT EXISTS trade_order (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    order_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    listing_addr TEXT,
+    buyer_pubkey TEXT,
+    seller_pubkey TEXT,
+    request_event_id TEXT,
+    decision_event_id TEXT,
+    agreement_event_id TEXT,
+    fulfillment_event_id TEXT,
+    fulfillment_status TEXT,
+    cancellation_event_id TEXT,
+    receipt_event_id TEXT,
+    last_event_id TEXT,
+    last_event_at INTEGER,
+    issue_count INTEGER NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS trade_order_order_id_idx ON trade_order(order_id);
+CREATE INDEX IF NOT EXISTS trade_order_buyer_pubkey_idx ON trade_order(buyer_pubkey);
+CREATE INDEX IF NOT EXISTS trade_order_seller_pubkey_idx ON trade_order(seller_pubkey);
+CREATE INDEX IF NOT EXISTS trade_order_status_idx ON trade_order(status);
+CREATE INDEX IF NOT EXISTS trade_order_listing_addr_idx ON trade_order(listing_addr);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS trade_order_item (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    trade_order_id CHAR(36) NOT NULL,
+    position INTEGER NOT NULL,
+    bin_id TEXT NOT NULL,
+    bin_count INTEGER NOT NULL,
+    FOREIGN KEY (trade_order_id) REFERENCES trade_order(id) ON DELETE CASCADE,
+    UNIQUE (trade_order_id, position)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS trade_order_status (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    trade_order_id CHAR(36) NOT NULL,
+    status TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    event_created_at INTEGER NOT NULL,
+    FOREIGN KEY (trade_order_id) REFERENCES trade_order(id) ON DELETE CASCADE
+);
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS trade_order_payment (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    trade_order_id CHAR(36) NOT NULL,
+    state TEXT NOT NULL,
+    settlement_state TEXT NOT NULL,
+    payment_event_id TEXT,
+    settlement_event_id TEXT,
+    quote_id TEXT,
+    quote_version INTEGER,
+    amount TEXT,
+    currency TEXT,
+    method TEXT,
+    reference TEXT,
+    paid_at INTEGER,
+    reason TEXT,
+    FOREIGN KEY (trade_order_id) REFERENCES trade_order(id) ON DELETE CASCADE,
+    UNIQUE (trade_order_id)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS trade_order_event (
+    id CHAR(36) PRIMARY KEY NOT NULL UNIQUE CHECK(length(id) = 36),
+    created_at DATETIME NOT NULL CHECK(length(created_at) = 24),
+    updated_at DATETIME NOT NULL CHECK(length(updated_at) = 24),
+    event_id TEXT NOT NULL,
+    order_id TEXT NOT NULL,
+    kind INTEGER NOT NULL,
+    pubkey TEXT NOT NULL,
+    event_created_at INTEGER NOT NULL,
+    tags TEXT NOT NULL,
+    content TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS trade_order_event_event_id_idx ON trade_order_event(event_id);
+CREATE INDEX IF NOT EXISTS trade_order_event_order_id_idx ON trade_order_event(order_id);
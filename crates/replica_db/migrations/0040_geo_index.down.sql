@@ -0,0 +1 @@
+DROP INDEX IF EXISTS gcs_location_lat_lng_idx;
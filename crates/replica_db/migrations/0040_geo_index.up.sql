@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS gcs_location_lat_lng_idx ON gcs_location(lat, lng);
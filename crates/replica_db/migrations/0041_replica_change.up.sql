@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS replica_change (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL,
+    entity_id CHAR(36) NOT NULL,
+    op TEXT NOT NULL CHECK(op IN ('insert', 'update', 'delete'))
+);
+
+CREATE INDEX IF NOT EXISTS replica_change_entity_idx ON replica_change(entity_type, entity_id);
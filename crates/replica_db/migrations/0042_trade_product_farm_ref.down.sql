@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS trade_product_farm_ref_idx;
+ALTER TABLE trade_product DROP COLUMN farm_d_tag;
+ALTER TABLE trade_product DROP COLUMN farm_pubkey;
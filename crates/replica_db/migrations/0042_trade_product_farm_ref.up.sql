@@ -0,0 +1,3 @@
+ALTER TABLE trade_product ADD COLUMN farm_pubkey TEXT;
+ALTER TABLE trade_product ADD COLUMN farm_d_tag TEXT;
+CREATE INDEX IF NOT EXISTS trade_product_farm_ref_idx ON trade_product(farm_pubkey, farm_d_tag);
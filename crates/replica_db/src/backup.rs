@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::collections::{BTreeMap, HashMap};
 
+use crate::fts::{TRADE_PRODUCT_FTS_PREFIX, is_trade_product_fts_object};
+
 pub const DATABASE_BACKUP_VERSION: &str = "1.0.0";
 pub const REPLICA_DB_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -61,27 +63,42 @@ pub fn restore_database_backup(
     backup: &DatabaseBackup,
 ) -> Result<(), SqlError> {
     validate_backup_version(backup)?;
+    write_with_foreign_keys_off(executor, || restore_backup_rows(executor, backup))
+}
+
+pub(crate) fn restore_backup_rows(
+    executor: &dyn SqlExecutor,
+    backup: &DatabaseBackup,
+) -> Result<(), SqlError> {
+    drop_existing_objects(executor)?;
+    create_schema_from_backup(executor, &backup.schema)?;
+    insert_rows_from_backup(executor, backup)
+}
+
+pub(crate) fn write_with_foreign_keys_off<T>(
+    executor: &dyn SqlExecutor,
+    write: impl FnOnce() -> Result<T, SqlError>,
+) -> Result<T, SqlError> {
     executor.exec("PRAGMA foreign_keys = OFF;", "[]")?;
-    executor.begin()?;
     let result = (|| {
-        drop_existing_objects(executor)?;
-        create_schema_from_backup(executor, &backup.schema)?;
-        insert_rows_from_backup(executor, backup)?;
-        Ok(())
-    })();
-
-    match result {
-        Ok(()) => {
-            executor.commit()?;
-            let _ = executor.exec("PRAGMA foreign_keys = ON;", "[]")?;
-            Ok(())
-        }
-        Err(err) => {
+        executor.begin()?;
+        let value = match write() {
+            Ok(value) => value,
+            Err(err) => {
+                let _ = executor.rollback();
+                return Err(err);
+            }
+        };
+        if let Err(err) = executor.commit() {
             let _ = executor.rollback();
-            let _ = executor.exec("PRAGMA foreign_keys = ON;", "[]");
-            Err(err)
+            return Err(err);
         }
-    }
+        Ok(value)
+    })();
+    let restored = executor.exec("PRAGMA foreign_keys = ON;", "[]");
+    let value = result?;
+    restored?;
+    Ok(value)
 }
 
 pub fn restore_database_backup_json(
@@ -110,6 +127,12 @@ fn drop_existing_objects(executor: &dyn SqlExecutor) -> Result<(), SqlError> {
             Some(n) => n,
             None => continue,
         };
+        if obj_type == "table"
+            && is_trade_product_fts_object(&name)
+            && name != TRADE_PRODUCT_FTS_PREFIX
+        {
+            continue;
+        }
         groups.entry(obj_type).or_default().push(name);
     }
 
@@ -170,6 +193,15 @@ fn insert_row(
     executor: &dyn SqlExecutor,
     table: &str,
     row: &Map<String, Value>,
+) -> Result<(), SqlError> {
+    write_row(executor, "INSERT", table, row)
+}
+
+pub(crate) fn write_row(
+    executor: &dyn SqlExecutor,
+    verb: &str,
+    table: &str,
+    row: &Map<String, Value>,
 ) -> Result<(), SqlError> {
     if row.is_empty() {
         return Ok(());
@@ -186,7 +218,8 @@ fn insert_row(
         .collect::<Vec<_>>()
         .join(",");
     let sql = format!(
-        "INSERT INTO {} ({}) VALUES ({});",
+        "{} INTO {} ({}) VALUES ({});",
+        verb,
         escape_identifier(table),
         column_names
             .iter()
@@ -218,6 +251,9 @@ pub(crate) fn load_schema(executor: &dyn SqlExecutor) -> Result<Vec<SchemaEntry>
         .into_iter()
         .filter_map(|row| {
             let name = row.name?;
+            if is_trade_product_fts_object(&name) {
+                return None;
+            }
             let object_type = row.object_type.unwrap_or_default();
             Some(SchemaEntry {
                 object_type,
@@ -270,7 +306,7 @@ pub(crate) fn escape_identifier(name: &str) -> String {
     escaped
 }
 
-fn validate_backup_version(backup: &DatabaseBackup) -> Result<(), SqlError> {
+pub(crate) fn validate_backup_version(backup: &DatabaseBackup) -> Result<(), SqlError> {
     if backup.format_version != DATABASE_BACKUP_VERSION {
         return Err(SqlError::InvalidArgument(format!(
             "unsupported backup format {}, expected {}",
@@ -882,6 +918,10 @@ mod tests {
             restore_database_backup(&executor, &backup),
             "ERR_INVALID_QUERY",
         );
+        assert_eq!(
+            executor.exec_calls().last().map(String::as_str),
+            Some("PRAGMA foreign_keys = ON;")
+        );
     }
 
     #[test]
@@ -973,6 +1013,11 @@ mod tests {
             restore_database_backup(&executor, &backup),
             "ERR_INVALID_QUERY",
         );
+        assert_eq!(executor.rollback_count(), 1);
+        assert_eq!(
+            executor.exec_calls().last().map(String::as_str),
+            Some("PRAGMA foreign_keys = ON;")
+        );
     }
 
     #[test]
@@ -0,0 +1,691 @@
+use radroots_sql_core::{SqlExecutor, error::SqlError, utils};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::ReplicaSql;
+use crate::backup::{
+    DatabaseBackup, REPLICA_DB_VERSION, TableData, escape_identifier, export_database_backup,
+    load_schema, restore_backup_rows, validate_backup_version, write_row,
+    write_with_foreign_keys_off,
+};
+use crate::change::{self, ReplicaChange, ReplicaChangeOp};
+
+pub const DATABASE_BACKUP_INCREMENT_VERSION: &str = "1.0.0";
+pub const DATABASE_BACKUP_MANIFEST_VERSION: &str = "1.0.0";
+
+const CHANGE_TABLE: &str = "replica_change";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseBackupEncoding {
+    Plain,
+    Protected,
+}
+
+pub trait DatabaseBackupCodec {
+    fn encoding(&self) -> DatabaseBackupEncoding;
+
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, SqlError>;
+
+    fn open(&self, stored: &[u8]) -> Result<Vec<u8>, SqlError>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainDatabaseBackupCodec;
+
+impl DatabaseBackupCodec for PlainDatabaseBackupCodec {
+    fn encoding(&self) -> DatabaseBackupEncoding {
+        DatabaseBackupEncoding::Plain
+    }
+
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, SqlError> {
+        Ok(plaintext.to_vec())
+    }
+
+    fn open(&self, stored: &[u8]) -> Result<Vec<u8>, SqlError> {
+        Ok(stored.to_vec())
+    }
+}
+
+#[cfg(feature = "protected-backup")]
+pub struct ProtectedDatabaseBackupCodec<'a, V> {
+    vault: &'a V,
+    key_slot: String,
+}
+
+#[cfg(feature = "protected-backup")]
+impl<'a, V: radroots_secret_vault::RadrootsSecretKeyWrapping> ProtectedDatabaseBackupCodec<'a, V> {
+    pub fn new(vault: &'a V, key_slot: impl Into<String>) -> Self {
+        Self {
+            vault,
+            key_slot: key_slot.into(),
+        }
+    }
+}
+
+#[cfg(feature = "protected-backup")]
+impl<V: radroots_secret_vault::RadrootsSecretKeyWrapping> DatabaseBackupCodec
+    for ProtectedDatabaseBackupCodec<'_, V>
+{
+    fn encoding(&self) -> DatabaseBackupEncoding {
+        DatabaseBackupEncoding::Protected
+    }
+
+    fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, SqlError> {
+        radroots_protected_store::RadrootsProtectedStoreEnvelope::seal_with_wrapped_key(
+            self.vault,
+            &self.key_slot,
+            plaintext,
+        )
+        .and_then(|envelope| envelope.encode_json())
+        .map_err(protected_store_error)
+    }
+
+    fn open(&self, stored: &[u8]) -> Result<Vec<u8>, SqlError> {
+        let envelope =
+            radroots_protected_store::RadrootsProtectedStoreEnvelope::decode_json(stored)
+                .map_err(protected_store_error)?;
+        if envelope.header.key_slot != self.key_slot {
+            return Err(SqlError::InvalidArgument(format!(
+                "backup part is sealed for key slot {}, expected {}",
+                envelope.header.key_slot, self.key_slot
+            )));
+        }
+        envelope
+            .open_with_wrapped_key(self.vault)
+            .map_err(protected_store_error)
+    }
+}
+
+#[cfg(feature = "protected-backup")]
+fn protected_store_error(
+    err: radroots_protected_store::error::RadrootsProtectedStoreError,
+) -> SqlError {
+    SqlError::InvalidArgument(format!("protected backup: {err}"))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBackupIncrement {
+    pub format_version: String,
+    pub replica_db_version: String,
+    pub from_seq: i64,
+    pub to_seq: i64,
+    pub changes: Vec<ReplicaChange>,
+    pub data: Vec<TableData>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseBackupPartKind {
+    Base,
+    Increment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatabaseBackupManifestPart {
+    pub kind: DatabaseBackupPartKind,
+    pub name: String,
+    pub from_seq: i64,
+    pub to_seq: i64,
+    pub encoding: DatabaseBackupEncoding,
+    pub sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DatabaseBackupManifest {
+    pub format_version: String,
+    pub replica_db_version: String,
+    pub parts: Vec<DatabaseBackupManifestPart>,
+}
+
+impl DatabaseBackupManifest {
+    pub fn head_seq(&self) -> Option<i64> {
+        self.parts.last().map(|part| part.to_seq)
+    }
+}
+
+pub fn export_database_backup_base(
+    executor: &dyn SqlExecutor,
+    codec: &dyn DatabaseBackupCodec,
+    name: &str,
+) -> Result<(DatabaseBackupManifest, Vec<u8>), SqlError> {
+    let (seq, backup) = read_consistent(executor, |executor| {
+        let seq = change::latest_change_seq(executor)?;
+        let backup = export_database_backup(executor)?;
+        Ok((seq, backup))
+    })?;
+    let plaintext = serde_json::to_vec(&backup).map_err(SqlError::from)?;
+    let stored = codec.seal(&plaintext)?;
+    let manifest = DatabaseBackupManifest {
+        format_version: DATABASE_BACKUP_MANIFEST_VERSION.to_string(),
+        replica_db_version: REPLICA_DB_VERSION.to_string(),
+        parts: vec![DatabaseBackupManifestPart {
+            kind: DatabaseBackupPartKind::Base,
+            name: name.to_string(),
+            from_seq: 0,
+            to_seq: seq,
+            encoding: codec.encoding(),
+            sha256: sha256_hex(&stored),
+            parent_sha256: None,
+        }],
+    };
+    Ok((manifest, stored))
+}
+
+pub fn export_database_backup_increment_part(
+    executor: &dyn SqlExecutor,
+    codec: &dyn DatabaseBackupCodec,
+    manifest: &mut DatabaseBackupManifest,
+    name: &str,
+) -> Result<Vec<u8>, SqlError> {
+    validate_manifest_version(manifest)?;
+    let (since_seq, parent_sha256) = match manifest.parts.last() {
+        Some(part) => (part.to_seq, part.sha256.clone()),
+        None => {
+            return Err(SqlError::InvalidArgument(String::from(
+                "backup manifest has no base part",
+            )));
+        }
+    };
+    let increment = export_database_backup_increment(executor, since_seq)?;
+    let plaintext = serde_json::to_vec(&increment).map_err(SqlError::from)?;
+    let stored = codec.seal(&plaintext)?;
+    manifest.parts.push(DatabaseBackupManifestPart {
+        kind: DatabaseBackupPartKind::Increment,
+        name: name.to_string(),
+        from_seq: increment.from_seq,
+        to_seq: increment.to_seq,
+        encoding: codec.encoding(),
+        sha256: sha256_hex(&stored),
+        parent_sha256: Some(parent_sha256),
+    });
+    Ok(stored)
+}
+
+pub fn verify_database_backup_chain(
+    manifest: &DatabaseBackupManifest,
+    parts: &[&[u8]],
+) -> Result<(), SqlError> {
+    validate_manifest_version(manifest)?;
+    if manifest.parts.len() != parts.len() {
+        return Err(SqlError::InvalidArgument(format!(
+            "backup manifest lists {} parts, got {}",
+            manifest.parts.len(),
+            parts.len()
+        )));
+    }
+    let mut previous: Option<&DatabaseBackupManifestPart> = None;
+    for (entry, bytes) in manifest.parts.iter().zip(parts.iter()) {
+        match previous {
+            None => {
+                if entry.kind != DatabaseBackupPartKind::Base || entry.parent_sha256.is_some() {
+                    return Err(SqlError::InvalidArgument(format!(
+                        "backup part {} must be a base",
+                        entry.name
+                    )));
+                }
+            }
+            Some(parent) => {
+                if entry.kind != DatabaseBackupPartKind::Increment
+                    || entry.parent_sha256.as_deref() != Some(parent.sha256.as_str())
+                    || entry.from_seq != parent.to_seq
+                {
+                    return Err(SqlError::InvalidArgument(format!(
+                        "backup part {} does not follow {}",
+                        entry.name, parent.name
+                    )));
+                }
+            }
+        }
+        if sha256_hex(bytes) != entry.sha256 {
+            return Err(SqlError::InvalidArgument(format!(
+                "backup part {} failed integrity check",
+                entry.name
+            )));
+        }
+        previous = Some(entry);
+    }
+    Ok(())
+}
+
+pub fn seal_database_backup_manifest(
+    codec: &dyn DatabaseBackupCodec,
+    manifest: &DatabaseBackupManifest,
+) -> Result<Vec<u8>, SqlError> {
+    validate_manifest_version(manifest)?;
+    let plaintext = serde_json::to_vec(manifest).map_err(SqlError::from)?;
+    codec.seal(&plaintext)
+}
+
+pub fn open_database_backup_manifest(
+    codec: &dyn DatabaseBackupCodec,
+    stored: &[u8],
+) -> Result<DatabaseBackupManifest, SqlError> {
+    let plaintext = codec.open(stored)?;
+    let manifest: DatabaseBackupManifest =
+        serde_json::from_slice(&plaintext).map_err(SqlError::from)?;
+    validate_manifest_version(&manifest)?;
+    Ok(manifest)
+}
+
+pub fn restore_database_backup_chain(
+    executor: &dyn SqlExecutor,
+    codec: &dyn DatabaseBackupCodec,
+    sealed_manifest: &[u8],
+    parts: &[&[u8]],
+) -> Result<(), SqlError> {
+    let manifest = open_database_backup_manifest(codec, sealed_manifest)?;
+    verify_database_backup_chain(&manifest, parts)?;
+    let mut base: Option<(DatabaseBackup, i64)> = None;
+    let mut increments = Vec::with_capacity(parts.len().saturating_sub(1));
+    for (entry, bytes) in manifest.parts.iter().zip(parts.iter()) {
+        if entry.encoding != codec.encoding() {
+            return Err(SqlError::InvalidArgument(format!(
+                "backup part {} is {:?}, codec expects {:?}",
+                entry.name,
+                entry.encoding,
+                codec.encoding()
+            )));
+        }
+        let plaintext = codec.open(bytes)?;
+        match entry.kind {
+            DatabaseBackupPartKind::Base => {
+                let backup: DatabaseBackup =
+                    serde_json::from_slice(&plaintext).map_err(SqlError::from)?;
+                validate_backup_version(&backup)?;
+                base = Some((backup, entry.to_seq));
+            }
+            DatabaseBackupPartKind::Increment => {
+                let increment: DatabaseBackupIncrement =
+                    serde_json::from_slice(&plaintext).map_err(SqlError::from)?;
+                validate_increment_version(&increment)?;
+                if increment.from_seq != entry.from_seq || increment.to_seq != entry.to_seq {
+                    return Err(SqlError::InvalidArgument(format!(
+                        "backup part {} does not match its manifest sequence range",
+                        entry.name
+                    )));
+                }
+                increments.push(increment);
+            }
+        }
+    }
+    let Some((backup, base_seq)) = base else {
+        return Err(SqlError::InvalidArgument(String::from(
+            "backup manifest has no base part",
+        )));
+    };
+    write_with_foreign_keys_off(executor, || {
+        restore_backup_rows(executor, &backup)?;
+        change::advance_change_seq(executor, base_seq)?;
+        for increment in &increments {
+            apply_increment_rows(executor, increment)?;
+        }
+        Ok(())
+    })
+}
+
+pub fn export_database_backup_increment(
+    executor: &dyn SqlExecutor,
+    since_seq: i64,
+) -> Result<DatabaseBackupIncrement, SqlError> {
+    read_consistent(executor, |executor| {
+        let to_seq = change::latest_change_seq(executor)?;
+        let changes = change::changes_since(executor, since_seq, None)?;
+        if to_seq > since_seq && changes.first().is_none_or(|row| row.seq != since_seq + 1) {
+            return Err(SqlError::InvalidArgument(format!(
+                "changes after seq {since_seq} are no longer retained"
+            )));
+        }
+        let layout = BackupLayout::load(executor)?;
+        let mut data: BTreeMap<String, BTreeMap<String, Map<String, Value>>> = BTreeMap::new();
+        for (entity_type, entity_id, op) in collapse_changes(&changes) {
+            layout.require_table(entity_type)?;
+            if op != ReplicaChangeOp::Delete {
+                layout.collect_entity_rows(executor, entity_type, entity_id, &mut data)?;
+            }
+        }
+        Ok(DatabaseBackupIncrement {
+            format_version: DATABASE_BACKUP_INCREMENT_VERSION.to_string(),
+            replica_db_version: REPLICA_DB_VERSION.to_string(),
+            from_seq: since_seq,
+            to_seq,
+            changes,
+            data: data
+                .into_iter()
+                .map(|(name, rows)| TableData {
+                    name,
+                    rows: rows.into_values().collect(),
+                })
+                .collect(),
+        })
+    })
+}
+
+pub fn apply_database_backup_increment(
+    executor: &dyn SqlExecutor,
+    increment: &DatabaseBackupIncrement,
+) -> Result<(), SqlError> {
+    validate_increment_version(increment)?;
+    write_with_foreign_keys_off(executor, || apply_increment_rows(executor, increment))
+}
+
+fn apply_increment_rows(
+    executor: &dyn SqlExecutor,
+    increment: &DatabaseBackupIncrement,
+) -> Result<(), SqlError> {
+    let current_seq = change::latest_change_seq(executor)?;
+    if current_seq != increment.from_seq {
+        return Err(SqlError::InvalidArgument(format!(
+            "increment starts at seq {} but replica is at seq {}",
+            increment.from_seq, current_seq
+        )));
+    }
+    let layout = BackupLayout::load(executor)?;
+    for (entity_type, entity_id, op) in collapse_changes(&increment.changes) {
+        layout.require_table(entity_type)?;
+        if op == ReplicaChangeOp::Delete {
+            layout.delete_entity(executor, entity_type, entity_id)?;
+        } else {
+            layout.delete_children(executor, entity_type, entity_id)?;
+        }
+    }
+    for table in &increment.data {
+        layout.require_table(&table.name)?;
+        for row in &table.rows {
+            write_row(executor, "INSERT OR REPLACE", &table.name, row)?;
+        }
+    }
+    for change in &increment.changes {
+        let row = match serde_json::to_value(change).map_err(SqlError::from)? {
+            Value::Object(row) => row,
+            _ => return Err(SqlError::Internal),
+        };
+        write_row(executor, "INSERT OR REPLACE", CHANGE_TABLE, &row)?;
+    }
+    change::advance_change_seq(executor, increment.to_seq)
+}
+
+impl<E: SqlExecutor> ReplicaSql<E> {
+    pub fn backup_database_increment(
+        &self,
+        since_seq: i64,
+    ) -> Result<DatabaseBackupIncrement, SqlError> {
+        export_database_backup_increment(self.executor(), since_seq)
+    }
+
+    pub fn apply_database_backup_increment(
+        &self,
+        increment: &DatabaseBackupIncrement,
+    ) -> Result<(), SqlError> {
+        apply_database_backup_increment(self.executor(), increment)
+    }
+}
+
+struct ForeignKey {
+    child_table: String,
+    child_column: String,
+    parent_table: String,
+}
+
+struct BackupLayout {
+    tables: BTreeSet<String>,
+    foreign_keys: Vec<ForeignKey>,
+}
+
+impl BackupLayout {
+    fn load(executor: &dyn SqlExecutor) -> Result<Self, SqlError> {
+        #[derive(Deserialize)]
+        struct ForeignKeyRow {
+            parent_table: String,
+            child_column: String,
+        }
+        let tables = load_schema(executor)?
+            .into_iter()
+            .filter(|entry| entry.object_type == "table")
+            .map(|entry| entry.name)
+            .collect::<BTreeSet<_>>();
+        let mut foreign_keys = Vec::new();
+        for table in &tables {
+            let params_json = utils::to_params_json(vec![Value::from(table.as_str())])?;
+            let json = executor.query_raw(
+                "SELECT \"table\" AS parent_table, \"from\" AS child_column FROM pragma_foreign_key_list(?) ORDER BY id, seq;",
+                &params_json,
+            )?;
+            let rows: Vec<ForeignKeyRow> = utils::parse_json(&json)?;
+            foreign_keys.extend(rows.into_iter().map(|row| ForeignKey {
+                child_table: table.clone(),
+                child_column: row.child_column,
+                parent_table: row.parent_table,
+            }));
+        }
+        Ok(Self {
+            tables,
+            foreign_keys,
+        })
+    }
+
+    fn require_table(&self, table: &str) -> Result<(), SqlError> {
+        if self.tables.contains(table) {
+            Ok(())
+        } else {
+            Err(SqlError::InvalidArgument(format!(
+                "backup references unknown table {table}"
+            )))
+        }
+    }
+
+    fn children_of<'a>(&'a self, table: &'a str) -> impl Iterator<Item = &'a ForeignKey> + 'a {
+        self.foreign_keys
+            .iter()
+            .filter(move |fk| fk.parent_table == table)
+    }
+
+    fn collect_entity_rows(
+        &self,
+        executor: &dyn SqlExecutor,
+        table: &str,
+        id: &str,
+        data: &mut BTreeMap<String, BTreeMap<String, Map<String, Value>>>,
+    ) -> Result<(), SqlError> {
+        let rows = select_rows(executor, table, Some(("id", &Value::from(id))))?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        push_rows(data, table, rows);
+        for fk in self.children_of(table) {
+            let children = select_rows(
+                executor,
+                &fk.child_table,
+                Some((&fk.child_column, &Value::from(id))),
+            )?;
+            for child in &children {
+                for parent_fk in self.foreign_keys.iter().filter(|other| {
+                    other.child_table == fk.child_table && other.parent_table != table
+                }) {
+                    if let Some(parent_id) = child.get(&parent_fk.child_column)
+                        && !parent_id.is_null()
+                    {
+                        let parents = select_rows(
+                            executor,
+                            &parent_fk.parent_table,
+                            Some(("id", parent_id)),
+                        )?;
+                        push_rows(data, &parent_fk.parent_table, parents);
+                    }
+                }
+            }
+            push_rows(data, &fk.child_table, children);
+        }
+        Ok(())
+    }
+
+    fn delete_children(
+        &self,
+        executor: &dyn SqlExecutor,
+        table: &str,
+        id: &str,
+    ) -> Result<(), SqlError> {
+        for fk in self.children_of(table) {
+            delete_where(executor, &fk.child_table, &fk.child_column, id)?;
+        }
+        Ok(())
+    }
+
+    fn delete_entity(
+        &self,
+        executor: &dyn SqlExecutor,
+        table: &str,
+        id: &str,
+    ) -> Result<(), SqlError> {
+        #[derive(Deserialize)]
+        struct IdRow {
+            id: String,
+        }
+        for fk in self.children_of(table) {
+            if self.children_of(&fk.child_table).next().is_some() {
+                let params_json = utils::to_params_json(vec![Value::from(id)])?;
+                let json = executor.query_raw(
+                    &format!(
+                        "SELECT id FROM {} WHERE {} = ?;",
+                        escape_identifier(&fk.child_table),
+                        escape_identifier(&fk.child_column)
+                    ),
+                    &params_json,
+                )?;
+                let rows: Vec<IdRow> = utils::parse_json(&json)?;
+                for row in rows {
+                    self.delete_entity(executor, &fk.child_table, &row.id)?;
+                }
+            }
+            delete_where(executor, &fk.child_table, &fk.child_column, id)?;
+        }
+        delete_where(executor, table, "id", id)
+    }
+}
+
+fn read_consistent<T>(
+    executor: &dyn SqlExecutor,
+    read: impl FnOnce(&dyn SqlExecutor) -> Result<T, SqlError>,
+) -> Result<T, SqlError> {
+    executor.begin()?;
+    match read(executor) {
+        Ok(value) => {
+            executor.commit()?;
+            Ok(value)
+        }
+        Err(err) => {
+            let _ = executor.rollback();
+            Err(err)
+        }
+    }
+}
+
+fn collapse_changes(changes: &[ReplicaChange]) -> Vec<(&str, &str, ReplicaChangeOp)> {
+    let mut latest: BTreeMap<(&str, &str), (i64, ReplicaChangeOp)> = BTreeMap::new();
+    for change in changes {
+        latest.insert(
+            (change.entity_type.as_str(), change.entity_id.as_str()),
+            (change.seq, change.op),
+        );
+    }
+    let mut collapsed = latest
+        .into_iter()
+        .map(|((entity_type, entity_id), (seq, op))| (seq, entity_type, entity_id, op))
+        .collect::<Vec<_>>();
+    collapsed.sort_by_key(|(seq, ..)| *seq);
+    collapsed
+        .into_iter()
+        .map(|(_, entity_type, entity_id, op)| (entity_type, entity_id, op))
+        .collect()
+}
+
+fn select_rows(
+    executor: &dyn SqlExecutor,
+    table: &str,
+    filter: Option<(&str, &Value)>,
+) -> Result<Vec<Map<String, Value>>, SqlError> {
+    let json = match filter {
+        Some((column, value)) => executor.query_raw(
+            &format!(
+                "SELECT * FROM {} WHERE {} = ?;",
+                escape_identifier(table),
+                escape_identifier(column)
+            ),
+            &utils::to_params_json(vec![value.clone()])?,
+        )?,
+        None => executor.query_raw(
+            &format!("SELECT * FROM {};", escape_identifier(table)),
+            "[]",
+        )?,
+    };
+    utils::parse_json(&json)
+}
+
+fn delete_where(
+    executor: &dyn SqlExecutor,
+    table: &str,
+    column: &str,
+    id: &str,
+) -> Result<(), SqlError> {
+    let params_json = utils::to_params_json(vec![Value::from(id)])?;
+    executor.exec(
+        &format!(
+            "DELETE FROM {} WHERE {} = ?;",
+            escape_identifier(table),
+            escape_identifier(column)
+        ),
+        &params_json,
+    )?;
+    Ok(())
+}
+
+fn push_rows(
+    data: &mut BTreeMap<String, BTreeMap<String, Map<String, Value>>>,
+    table: &str,
+    rows: Vec<Map<String, Value>>,
+) {
+    let entries = data.entry(table.to_string()).or_default();
+    for row in rows {
+        entries.insert(Value::Object(row.clone()).to_string(), row);
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn validate_manifest_version(manifest: &DatabaseBackupManifest) -> Result<(), SqlError> {
+    if manifest.format_version != DATABASE_BACKUP_MANIFEST_VERSION {
+        return Err(SqlError::InvalidArgument(format!(
+            "unsupported backup manifest format {}, expected {}",
+            manifest.format_version, DATABASE_BACKUP_MANIFEST_VERSION
+        )));
+    }
+    if manifest.replica_db_version != REPLICA_DB_VERSION {
+        return Err(SqlError::InvalidArgument(format!(
+            "unsupported replica-db version {}, expected {}",
+            manifest.replica_db_version, REPLICA_DB_VERSION
+        )));
+    }
+    Ok(())
+}
+
+fn validate_increment_version(increment: &DatabaseBackupIncrement) -> Result<(), SqlError> {
+    if increment.format_version != DATABASE_BACKUP_INCREMENT_VERSION {
+        return Err(SqlError::InvalidArgument(format!(
+            "unsupported backup increment format {}, expected {}",
+            increment.format_version, DATABASE_BACKUP_INCREMENT_VERSION
+        )));
+    }
+    if increment.replica_db_version != REPLICA_DB_VERSION {
+        return Err(SqlError::InvalidArgument(format!(
+            "unsupported replica-db version {}, expected {}",
+            increment.replica_db_version, REPLICA_DB_VERSION
+        )));
+    }
+    Ok(())
+}
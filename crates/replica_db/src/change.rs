@@ -0,0 +1,118 @@
+use radroots_sql_core::utils;
+use radroots_types::types::IError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ReplicaSql, SqlError, SqlExecutor};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplicaChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl ReplicaChangeOp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ReplicaChange {
+    pub seq: i64,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub op: ReplicaChangeOp,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReplicaChangeSeqRow {
+    seq: Option<i64>,
+}
+
+pub fn record_change(
+    exec: &dyn SqlExecutor,
+    entity_type: &str,
+    entity_id: &str,
+    op: ReplicaChangeOp,
+) -> Result<i64, IError<SqlError>> {
+    let params_json = utils::to_params_json(vec![
+        Value::from(entity_type),
+        Value::from(entity_id),
+        Value::from(op.as_str()),
+    ])?;
+    let outcome = exec.exec(
+        "INSERT INTO replica_change (entity_type, entity_id, op) VALUES (?, ?, ?);",
+        &params_json,
+    )?;
+    Ok(outcome.last_insert_id)
+}
+
+pub fn changes_since(
+    exec: &dyn SqlExecutor,
+    since_seq: i64,
+    limit: Option<u32>,
+) -> Result<Vec<ReplicaChange>, SqlError> {
+    let mut bind_values = vec![Value::from(since_seq)];
+    let limit_clause = match limit {
+        Some(limit) => {
+            bind_values.push(Value::from(limit));
+            " LIMIT ?"
+        }
+        None => "",
+    };
+    let sql = format!(
+        "SELECT seq, entity_type, entity_id, op FROM replica_change WHERE seq > ? ORDER BY seq ASC{limit_clause};"
+    );
+    let params_json = utils::to_params_json(bind_values)?;
+    let json = exec.query_raw(&sql, &params_json)?;
+    serde_json::from_str(&json).map_err(SqlError::from)
+}
+
+pub fn latest_change_seq(exec: &dyn SqlExecutor) -> Result<i64, SqlError> {
+    let json = exec.query_raw(
+        "SELECT COALESCE((SELECT seq FROM sqlite_sequence WHERE name = 'replica_change'), (SELECT MAX(seq) FROM replica_change)) AS seq;",
+        "[]",
+    )?;
+    let rows: Vec<ReplicaChangeSeqRow> = serde_json::from_str(&json).map_err(SqlError::from)?;
+    Ok(rows.into_iter().next().and_then(|row| row.seq).unwrap_or(0))
+}
+
+pub fn advance_change_seq(exec: &dyn SqlExecutor, seq: i64) -> Result<(), SqlError> {
+    let params_json = utils::to_params_json(vec![Value::from(seq)])?;
+    let _ = exec.exec(
+        "UPDATE sqlite_sequence SET seq = MAX(seq, ?) WHERE name = 'replica_change';",
+        &params_json,
+    )?;
+    let _ = exec.exec(
+        "INSERT INTO sqlite_sequence (name, seq) SELECT 'replica_change', ? WHERE NOT EXISTS (SELECT 1 FROM sqlite_sequence WHERE name = 'replica_change');",
+        &params_json,
+    )?;
+    Ok(())
+}
+
+pub fn prune_changes_through(exec: &dyn SqlExecutor, seq: i64) -> Result<i64, SqlError> {
+    let params_json = utils::to_params_json(vec![Value::from(seq)])?;
+    let outcome = exec.exec("DELETE FROM replica_change WHERE seq <= ?;", &params_json)?;
+    Ok(outcome.changes)
+}
+
+impl<E: SqlExecutor> ReplicaSql<E> {
+    pub fn changes_since(
+        &self,
+        since_seq: i64,
+        limit: Option<u32>,
+    ) -> Result<Vec<ReplicaChange>, SqlError> {
+        changes_since(self.executor(), since_seq, limit)
+    }
+
+    pub fn latest_change_seq(&self) -> Result<i64, SqlError> {
+        latest_change_seq(self.executor())
+    }
+}
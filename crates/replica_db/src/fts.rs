@@ -0,0 +1,283 @@
+use radroots_sql_core::utils;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::query::ReplicaTradeProductSummaryRow;
+use crate::{ReplicaSql, SqlError, SqlExecutor};
+
+pub(crate) const TRADE_PRODUCT_FTS_PREFIX: &str = "trade_product_fts";
+
+const TRADE_PRODUCT_FTS_DROP_SQL: &str = "DROP TRIGGER IF EXISTS trade_product_fts_ai;
+DROP TRIGGER IF EXISTS trade_product_fts_au;
+DROP TRIGGER IF EXISTS trade_product_fts_ad;
+DROP TRIGGER IF EXISTS trade_product_fts_farm_ai;
+DROP TRIGGER IF EXISTS trade_product_fts_farm_au;
+DROP TRIGGER IF EXISTS trade_product_fts_farm_ad;
+DROP TABLE IF EXISTS trade_product_fts;";
+
+const TRADE_PRODUCT_FTS_CREATE_SQL: &str = "CREATE VIRTUAL TABLE trade_product_fts USING fts5(
+    trade_product_id UNINDEXED,
+    title,
+    category,
+    summary,
+    farm_name,
+    tokenize = 'porter unicode61 remove_diacritics 2',
+    prefix = '2 3'
+);
+
+CREATE TRIGGER trade_product_fts_ai AFTER INSERT ON trade_product BEGIN
+    INSERT INTO trade_product_fts(trade_product_id, title, category, summary, farm_name)
+    VALUES (new.id, new.title, new.category, new.summary, (SELECT f.name FROM farm f WHERE f.pubkey = new.farm_pubkey AND f.d_tag = new.farm_d_tag));
+END;
+
+CREATE TRIGGER trade_product_fts_au AFTER UPDATE ON trade_product BEGIN
+    DELETE FROM trade_product_fts WHERE trade_product_id = old.id;
+    INSERT INTO trade_product_fts(trade_product_id, title, category, summary, farm_name)
+    VALUES (new.id, new.title, new.category, new.summary, (SELECT f.name FROM farm f WHERE f.pubkey = new.farm_pubkey AND f.d_tag = new.farm_d_tag));
+END;
+
+CREATE TRIGGER trade_product_fts_ad AFTER DELETE ON trade_product BEGIN
+    DELETE FROM trade_product_fts WHERE trade_product_id = old.id;
+END;
+
+CREATE TRIGGER trade_product_fts_farm_ai AFTER INSERT ON farm BEGIN
+    UPDATE trade_product_fts
+    SET farm_name = new.name
+    WHERE trade_product_id IN (SELECT tp.id FROM trade_product tp WHERE tp.farm_pubkey = new.pubkey AND tp.farm_d_tag = new.d_tag);
+END;
+
+CREATE TRIGGER trade_product_fts_farm_au AFTER UPDATE ON farm BEGIN
+    UPDATE trade_product_fts
+    SET farm_name = NULL
+    WHERE trade_product_id IN (SELECT tp.id FROM trade_product tp WHERE tp.farm_pubkey = old.pubkey AND tp.farm_d_tag = old.d_tag);
+    UPDATE trade_product_fts
+    SET farm_name = new.name
+    WHERE trade_product_id IN (SELECT tp.id FROM trade_product tp WHERE tp.farm_pubkey = new.pubkey AND tp.farm_d_tag = new.d_tag);
+END;
+
+CREATE TRIGGER trade_product_fts_farm_ad AFTER DELETE ON farm BEGIN
+    UPDATE trade_product_fts
+    SET farm_name = NULL
+    WHERE trade_product_id IN (SELECT tp.id FROM trade_product tp WHERE tp.farm_pubkey = old.pubkey AND tp.farm_d_tag = old.d_tag);
+END;
+
+INSERT INTO trade_product_fts(trade_product_id, title, category, summary, farm_name)
+SELECT tp.id, tp.title, tp.category, tp.summary, f.name
+FROM trade_product tp
+LEFT JOIN farm f ON f.pubkey = tp.farm_pubkey AND f.d_tag = tp.farm_d_tag;";
+
+const LIKE_TITLE_WEIGHT: f64 = 10.0;
+const LIKE_CATEGORY_WEIGHT: f64 = 4.0;
+const LIKE_SUMMARY_WEIGHT: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplicaTradeProductSearchMode {
+    #[default]
+    Fts,
+    Like,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaTradeProductTextQuery {
+    pub query_terms: Vec<String>,
+    pub now_unix: u64,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub mode: ReplicaTradeProductSearchMode,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaTradeProductRankedRow {
+    #[serde(flatten)]
+    pub product: ReplicaTradeProductSummaryRow,
+    pub farm_name: Option<String>,
+    pub rank: f64,
+    pub title_highlight: Option<String>,
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaTradeProductRankedSearch {
+    pub mode: ReplicaTradeProductSearchMode,
+    pub rows: Vec<ReplicaTradeProductRankedRow>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReplicaCompileOptionRow {
+    enabled: i64,
+}
+
+impl<E: SqlExecutor> ReplicaSql<E> {
+    pub fn trade_product_fts_setup(&self) -> Result<ReplicaTradeProductSearchMode, SqlError> {
+        let json = self.executor().query_raw(
+            "SELECT sqlite_compileoption_used('ENABLE_FTS5') AS enabled;",
+            "[]",
+        )?;
+        let rows: Vec<ReplicaCompileOptionRow> =
+            serde_json::from_str(&json).map_err(SqlError::from)?;
+        let fts5 = rows.first().is_some_and(|row| row.enabled != 0);
+
+        self.executor().begin()?;
+        let result = (|| {
+            trade_product_fts_drop(self.executor())?;
+            if fts5 {
+                let _ = self.executor().exec(TRADE_PRODUCT_FTS_CREATE_SQL, "[]")?;
+            }
+            Ok(())
+        })();
+        match result {
+            Ok(()) => self.executor().commit()?,
+            Err(err) => {
+                let _ = self.executor().rollback();
+                return Err(err);
+            }
+        }
+        Ok(if fts5 {
+            ReplicaTradeProductSearchMode::Fts
+        } else {
+            ReplicaTradeProductSearchMode::Like
+        })
+    }
+
+    pub fn trade_product_search_ranked(
+        &self,
+        query: &ReplicaTradeProductTextQuery,
+    ) -> Result<ReplicaTradeProductRankedSearch, SqlError> {
+        if query.mode == ReplicaTradeProductSearchMode::Like {
+            return self.trade_product_search_like_ranked(query);
+        }
+
+        let match_expr = fts_match_expression(&query.query_terms);
+        if match_expr.is_empty() {
+            return Ok(ReplicaTradeProductRankedSearch {
+                mode: ReplicaTradeProductSearchMode::Fts,
+                rows: Vec::new(),
+            });
+        }
+        let mut bind_values = vec![Value::from(match_expr), Value::from(query.now_unix)];
+        let limit_clause = match query.limit {
+            Some(limit) => {
+                bind_values.push(Value::from(limit));
+                " LIMIT ?"
+            }
+            None => "",
+        };
+        let sql = format!(
+            "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.expires_at, tp.notes, loc.location_primary, trade_product_fts.farm_name, \
+                 bm25(trade_product_fts, 0.0, 10.0, 4.0, 2.0, 3.0) AS rank, \
+                 highlight(trade_product_fts, 1, '<mark>', '</mark>') AS title_highlight, \
+                 snippet(trade_product_fts, -1, '<mark>', '</mark>', '…', 12) AS snippet \
+             FROM trade_product_fts \
+             JOIN trade_product tp ON tp.id = trade_product_fts.trade_product_id \
+             LEFT JOIN (\
+                 SELECT tpl.tb_tp AS trade_product_id, MIN(COALESCE(gl.label, gl.gc_name, gl.gc_admin1_name, gl.gc_country_name, gl.d_tag)) AS location_primary \
+                 FROM trade_product_location tpl \
+                 JOIN gcs_location gl ON gl.id = tpl.tb_gl \
+                 GROUP BY tpl.tb_tp\
+             ) loc ON loc.trade_product_id = tp.id \
+             WHERE trade_product_fts MATCH ? AND (tp.expires_at IS NULL OR tp.expires_at > ?) \
+             ORDER BY rank ASC, tp.id ASC{limit_clause};"
+        );
+        let params_json = utils::to_params_json(bind_values)?;
+        let json = self.executor().query_raw(&sql, &params_json)?;
+        let rows = serde_json::from_str(&json).map_err(SqlError::from)?;
+        Ok(ReplicaTradeProductRankedSearch {
+            mode: ReplicaTradeProductSearchMode::Fts,
+            rows,
+        })
+    }
+
+    pub fn trade_product_search_like_ranked(
+        &self,
+        query: &ReplicaTradeProductTextQuery,
+    ) -> Result<ReplicaTradeProductRankedSearch, SqlError> {
+        let terms = query
+            .query_terms
+            .iter()
+            .map(|term| term.trim().to_lowercase())
+            .filter(|term| !term.is_empty())
+            .collect::<Vec<_>>();
+        let mut rows = self
+            .trade_product_search_at(&query.query_terms, query.now_unix)?
+            .into_iter()
+            .map(|product| ReplicaTradeProductRankedRow {
+                rank: like_rank(&product, &terms),
+                product,
+                farm_name: None,
+                title_highlight: None,
+                snippet: None,
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| {
+            a.rank
+                .total_cmp(&b.rank)
+                .then_with(|| a.product.id.cmp(&b.product.id))
+        });
+        if let Some(limit) = query.limit {
+            rows.truncate(limit as usize);
+        }
+        Ok(ReplicaTradeProductRankedSearch {
+            mode: ReplicaTradeProductSearchMode::Like,
+            rows,
+        })
+    }
+}
+
+pub(crate) fn trade_product_fts_drop(executor: &dyn SqlExecutor) -> Result<(), SqlError> {
+    executor.exec(TRADE_PRODUCT_FTS_DROP_SQL, "[]").map(|_| ())
+}
+
+pub(crate) fn is_trade_product_fts_object(name: &str) -> bool {
+    name.starts_with(TRADE_PRODUCT_FTS_PREFIX)
+}
+
+fn like_rank(product: &ReplicaTradeProductSummaryRow, terms: &[String]) -> f64 {
+    let title = product.title.to_lowercase();
+    let category = product.category.to_lowercase();
+    let summary = product.summary.to_lowercase();
+    -terms
+        .iter()
+        .map(|term| {
+            let mut score = 0.0;
+            if title.contains(term.as_str()) {
+                score += LIKE_TITLE_WEIGHT;
+            }
+            if category.contains(term.as_str()) {
+                score += LIKE_CATEGORY_WEIGHT;
+            }
+            if summary.contains(term.as_str()) {
+                score += LIKE_SUMMARY_WEIGHT;
+            }
+            score
+        })
+        .sum::<f64>()
+}
+
+fn fts_match_expression(query_terms: &[String]) -> String {
+    let mut tokens = Vec::new();
+    for term in query_terms {
+        for token in term
+            .split(|ch: char| !ch.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+        {
+            tokens.push(format!("\"{}\"*", token.to_lowercase()));
+        }
+    }
+    tokens.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_match_expression_quotes_prefix_tokens() {
+        assert_eq!(
+            fts_match_expression(&["Heirloom tomatoes".to_owned(), "NEAR(\"x".to_owned()]),
+            "\"heirloom\"* \"tomatoes\"* \"near\"* \"x\"*"
+        );
+        assert_eq!(fts_match_expression(&["--".to_owned()]), "");
+    }
+}
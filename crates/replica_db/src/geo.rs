@@ -0,0 +1,492 @@
+use radroots_sql_core::utils;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::query::{ReplicaTradeProductSummaryRow, push_trade_product_term_filters};
+use crate::{ReplicaSql, SqlError, SqlExecutor};
+
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+const GEO_PROBE_STEPS: i32 = 6;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplicaGeoArea {
+    Radius {
+        lat: f64,
+        lng: f64,
+        radius_m: f64,
+    },
+    BoundingBox {
+        min_lat: f64,
+        min_lng: f64,
+        max_lat: f64,
+        max_lng: f64,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaTradeProductGeoQuery {
+    pub area: ReplicaGeoArea,
+    #[serde(default)]
+    pub query_terms: Vec<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    pub now_unix: u64,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaFarmGeoQuery {
+    pub area: ReplicaGeoArea,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaTradeProductGeoRow {
+    #[serde(flatten)]
+    pub product: ReplicaTradeProductSummaryRow,
+    pub gcs_location_id: String,
+    pub lat: f64,
+    pub lng: f64,
+    #[serde(default)]
+    pub distance_m: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ReplicaFarmGeoRow {
+    pub id: String,
+    pub d_tag: String,
+    pub pubkey: String,
+    pub name: String,
+    pub location_primary: Option<String>,
+    pub gcs_location_id: String,
+    pub lat: f64,
+    pub lng: f64,
+    #[serde(default)]
+    pub distance_m: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GeoBounds {
+    min_lat: f64,
+    max_lat: f64,
+    lng_ranges: [Option<(f64, f64)>; 2],
+}
+
+impl ReplicaGeoArea {
+    fn validate(&self) -> Result<(), SqlError> {
+        let valid = match *self {
+            Self::Radius { lat, lng, radius_m } => {
+                valid_lat(lat) && valid_lng(lng) && radius_m.is_finite() && radius_m >= 0.0
+            }
+            Self::BoundingBox {
+                min_lat,
+                min_lng,
+                max_lat,
+                max_lng,
+            } => {
+                valid_lat(min_lat)
+                    && valid_lat(max_lat)
+                    && valid_lng(min_lng)
+                    && valid_lng(max_lng)
+                    && min_lat <= max_lat
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(SqlError::InvalidArgument("geo area".to_owned()))
+        }
+    }
+
+    fn origin(&self) -> (f64, f64) {
+        match *self {
+            Self::Radius { lat, lng, .. } => (lat, lng),
+            Self::BoundingBox {
+                min_lat,
+                min_lng,
+                max_lat,
+                max_lng,
+            } => {
+                let span = if min_lng <= max_lng {
+                    max_lng - min_lng
+                } else {
+                    max_lng + 360.0 - min_lng
+                };
+                let mut lng = min_lng + span / 2.0;
+                if lng > 180.0 {
+                    lng -= 360.0;
+                }
+                ((min_lat + max_lat) / 2.0, lng)
+            }
+        }
+    }
+
+    fn bounds(&self) -> GeoBounds {
+        match *self {
+            Self::Radius { lat, lng, radius_m } => {
+                let dlat = (radius_m / EARTH_RADIUS_M).to_degrees();
+                let min_lat = (lat - dlat).max(-90.0);
+                let max_lat = (lat + dlat).min(90.0);
+                let cos_lat = lat.abs().max(min_lat.abs()).max(max_lat.abs());
+                let cos_lat = cos_lat.to_radians().cos();
+                if min_lat <= -90.0 || max_lat >= 90.0 || cos_lat <= f64::EPSILON {
+                    return GeoBounds {
+                        min_lat,
+                        max_lat,
+                        lng_ranges: [Some((-180.0, 180.0)), None],
+                    };
+                }
+                let dlng = dlat / cos_lat;
+                if dlng >= 180.0 {
+                    return GeoBounds {
+                        min_lat,
+                        max_lat,
+                        lng_ranges: [Some((-180.0, 180.0)), None],
+                    };
+                }
+                let min_lng = lng - dlng;
+                let max_lng = lng + dlng;
+                let lng_ranges = if min_lng < -180.0 {
+                    [Some((min_lng + 360.0, 180.0)), Some((-180.0, max_lng))]
+                } else if max_lng > 180.0 {
+                    [Some((min_lng, 180.0)), Some((-180.0, max_lng - 360.0))]
+                } else {
+                    [Some((min_lng, max_lng)), None]
+                };
+                GeoBounds {
+                    min_lat,
+                    max_lat,
+                    lng_ranges,
+                }
+            }
+            Self::BoundingBox {
+                min_lat,
+                min_lng,
+                max_lat,
+                max_lng,
+            } => {
+                let lng_ranges = if min_lng <= max_lng {
+                    [Some((min_lng, max_lng)), None]
+                } else {
+                    [Some((min_lng, 180.0)), Some((-180.0, max_lng))]
+                };
+                GeoBounds {
+                    min_lat,
+                    max_lat,
+                    lng_ranges,
+                }
+            }
+        }
+    }
+
+    fn reach_m(&self) -> f64 {
+        match *self {
+            Self::Radius { radius_m, .. } => radius_m,
+            Self::BoundingBox {
+                min_lat,
+                min_lng,
+                max_lat,
+                max_lng,
+            } => {
+                let (lat, lng) = self.origin();
+                [
+                    (min_lat, min_lng),
+                    (min_lat, max_lng),
+                    (max_lat, min_lng),
+                    (max_lat, max_lng),
+                ]
+                .into_iter()
+                .map(|(corner_lat, corner_lng)| haversine_m(lat, lng, corner_lat, corner_lng))
+                .fold(0.0, f64::max)
+            }
+        }
+    }
+
+    fn contains(&self, lat: f64, lng: f64) -> bool {
+        match *self {
+            Self::Radius {
+                lat: center_lat,
+                lng: center_lng,
+                radius_m,
+            } => haversine_m(center_lat, center_lng, lat, lng) <= radius_m,
+            Self::BoundingBox { .. } => self.bounds().contains(lat, lng),
+        }
+    }
+}
+
+impl GeoBounds {
+    fn ranges(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.lng_ranges.iter().flatten().copied()
+    }
+
+    fn contains(&self, lat: f64, lng: f64) -> bool {
+        lat >= self.min_lat
+            && lat <= self.max_lat
+            && self
+                .ranges()
+                .any(|(min_lng, max_lng)| lng >= min_lng && lng <= max_lng)
+    }
+
+    fn push_filters(&self, where_clauses: &mut Vec<String>, bind_values: &mut Vec<Value>) {
+        where_clauses.push("gl.lat BETWEEN ? AND ?".to_owned());
+        bind_values.push(Value::from(self.min_lat));
+        bind_values.push(Value::from(self.max_lat));
+        let mut lng_clauses = Vec::with_capacity(2);
+        for (min_lng, max_lng) in self.ranges() {
+            lng_clauses.push("gl.lng BETWEEN ? AND ?");
+            bind_values.push(Value::from(min_lng));
+            bind_values.push(Value::from(max_lng));
+        }
+        where_clauses.push(format!("({})", lng_clauses.join(" OR ")));
+    }
+}
+
+impl<E: SqlExecutor> ReplicaSql<E> {
+    pub fn trade_product_geo_search(
+        &self,
+        query: &ReplicaTradeProductGeoQuery,
+    ) -> Result<Vec<ReplicaTradeProductGeoRow>, SqlError> {
+        query.area.validate()?;
+        let fetch = |bounds: &[GeoBounds]| {
+            let mut where_clauses = Vec::new();
+            let mut bind_values = Vec::<Value>::new();
+            for bounds in bounds {
+                bounds.push_filters(&mut where_clauses, &mut bind_values);
+            }
+            push_trade_product_term_filters(
+                &query.query_terms,
+                &mut where_clauses,
+                &mut bind_values,
+            );
+            if let Some(category) = query.category.as_deref() {
+                where_clauses.push("lower(tp.category) = ?".to_owned());
+                bind_values.push(Value::from(category.to_lowercase()));
+            }
+            where_clauses.push("(tp.expires_at IS NULL OR tp.expires_at > ?)".to_owned());
+            bind_values.push(Value::from(query.now_unix));
+
+            let sql = format!(
+                "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.expires_at, tp.notes, COALESCE(gl.label, gl.gc_name, gl.gc_admin1_name, gl.gc_country_name, gl.d_tag) AS location_primary, gl.id AS gcs_location_id, gl.lat, gl.lng \
+                 FROM gcs_location gl \
+                 JOIN trade_product_location tpl ON tpl.tb_gl = gl.id \
+                 JOIN trade_product tp ON tp.id = tpl.tb_tp \
+                 WHERE {};",
+                where_clauses.join(" AND ")
+            );
+            let params_json = utils::to_params_json(bind_values)?;
+            let json = self.executor().query_raw(&sql, &params_json)?;
+            serde_json::from_str::<Vec<ReplicaTradeProductGeoRow>>(&json).map_err(SqlError::from)
+        };
+        nearest_search(
+            &query.area,
+            query.limit,
+            fetch,
+            |row| (row.lat, row.lng),
+            |row| row.product.id.clone(),
+            |row, distance_m| row.distance_m = distance_m,
+        )
+    }
+
+    pub fn farm_geo_search(
+        &self,
+        query: &ReplicaFarmGeoQuery,
+    ) -> Result<Vec<ReplicaFarmGeoRow>, SqlError> {
+        query.area.validate()?;
+        let fetch = |bounds: &[GeoBounds]| {
+            let mut where_clauses = Vec::new();
+            let mut bind_values = Vec::<Value>::new();
+            for bounds in bounds {
+                bounds.push_filters(&mut where_clauses, &mut bind_values);
+            }
+
+            let sql = format!(
+                "SELECT f.id, f.d_tag, f.pubkey, f.name, f.location_primary, gl.id AS gcs_location_id, gl.lat, gl.lng \
+                 FROM gcs_location gl \
+                 JOIN farm_gcs_location fgl ON fgl.gcs_location_id = gl.id \
+                 JOIN farm f ON f.id = fgl.farm_id \
+                 WHERE {};",
+                where_clauses.join(" AND ")
+            );
+            let params_json = utils::to_params_json(bind_values)?;
+            let json = self.executor().query_raw(&sql, &params_json)?;
+            serde_json::from_str::<Vec<ReplicaFarmGeoRow>>(&json).map_err(SqlError::from)
+        };
+        nearest_search(
+            &query.area,
+            query.limit,
+            fetch,
+            |row| (row.lat, row.lng),
+            |row| row.id.clone(),
+            |row, distance_m| row.distance_m = distance_m,
+        )
+    }
+}
+
+fn nearest_search<T>(
+    area: &ReplicaGeoArea,
+    limit: Option<u32>,
+    fetch: impl Fn(&[GeoBounds]) -> Result<Vec<T>, SqlError>,
+    point: impl Fn(&T) -> (f64, f64),
+    key: impl Fn(&T) -> String,
+    set_distance: impl Fn(&mut T, f64),
+) -> Result<Vec<T>, SqlError> {
+    let bounds = area.bounds();
+    if let Some(limit) = limit {
+        // Probe growing circles around the origin so a limited search only loads the
+        // rows nearest to it; the full area is the last probe.
+        let (lat, lng) = area.origin();
+        let reach_m = area.reach_m();
+        let mut radius_m = reach_m / 2f64.powi(GEO_PROBE_STEPS);
+        while radius_m < reach_m {
+            let probe = ReplicaGeoArea::Radius { lat, lng, radius_m };
+            let rows = nearest_rows(
+                area,
+                Some(&probe),
+                fetch(&[bounds, probe.bounds()])?,
+                Some(limit),
+                &point,
+                &key,
+                &set_distance,
+            );
+            if rows.len() >= limit as usize {
+                return Ok(rows);
+            }
+            radius_m *= 2.0;
+        }
+    }
+    Ok(nearest_rows(
+        area,
+        None,
+        fetch(&[bounds])?,
+        limit,
+        &point,
+        &key,
+        &set_distance,
+    ))
+}
+
+fn nearest_rows<T>(
+    area: &ReplicaGeoArea,
+    probe: Option<&ReplicaGeoArea>,
+    rows: Vec<T>,
+    limit: Option<u32>,
+    point: impl Fn(&T) -> (f64, f64),
+    key: impl Fn(&T) -> String,
+    set_distance: impl Fn(&mut T, f64),
+) -> Vec<T> {
+    let (origin_lat, origin_lng) = area.origin();
+    let mut matched = Vec::with_capacity(rows.len());
+    for mut row in rows {
+        let (lat, lng) = point(&row);
+        if !area.contains(lat, lng) || probe.is_some_and(|probe| !probe.contains(lat, lng)) {
+            continue;
+        }
+        let distance_m = haversine_m(origin_lat, origin_lng, lat, lng);
+        set_distance(&mut row, distance_m);
+        matched.push((distance_m, key(&row), row));
+    }
+    matched.sort_by(|(a_distance, a_key, _), (b_distance, b_key, _)| {
+        a_distance
+            .total_cmp(b_distance)
+            .then_with(|| a_key.cmp(b_key))
+    });
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    for (_, row_key, row) in matched {
+        if !seen.insert(row_key) {
+            continue;
+        }
+        out.push(row);
+        if limit.is_some_and(|limit| out.len() >= limit as usize) {
+            break;
+        }
+    }
+    out
+}
+
+pub fn haversine_m(lat_a: f64, lng_a: f64, lat_b: f64, lng_b: f64) -> f64 {
+    let d_lat = (lat_b - lat_a).to_radians();
+    let d_lng = (lng_b - lng_a).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2)
+        + lat_a.to_radians().cos() * lat_b.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * h.sqrt().min(1.0).asin()
+}
+
+fn valid_lat(lat: f64) -> bool {
+    lat.is_finite() && (-90.0..=90.0).contains(&lat)
+}
+
+fn valid_lng(lng: f64) -> bool {
+    lng.is_finite() && (-180.0..=180.0).contains(&lng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haversine_matches_known_distance() {
+        let distance = haversine_m(59.3293, 18.0686, 57.7089, 11.9746);
+        assert!((distance - 397_000.0).abs() < 2_000.0);
+    }
+
+    #[test]
+    fn limited_search_stops_at_the_first_probe_with_enough_rows() {
+        let points = [
+            (59.3300, 18.0700, "a"),
+            (59.3301, 18.0701, "a"),
+            (59.3310, 18.0710, "b"),
+            (59.9000, 18.9000, "c"),
+        ];
+        let area = ReplicaGeoArea::Radius {
+            lat: 59.33,
+            lng: 18.07,
+            radius_m: 100_000.0,
+        };
+        let fetches = std::cell::Cell::new(0);
+        let fetch = |bounds: &[GeoBounds]| {
+            fetches.set(fetches.get() + 1);
+            Ok(points
+                .iter()
+                .filter(|(lat, lng, _)| bounds.iter().all(|bounds| bounds.contains(*lat, *lng)))
+                .copied()
+                .collect::<Vec<_>>())
+        };
+        let search = |limit| {
+            nearest_search(
+                &area,
+                limit,
+                fetch,
+                |row| (row.0, row.1),
+                |row| row.2.to_owned(),
+                |_, _| {},
+            )
+            .expect("nearest search")
+            .into_iter()
+            .map(|row| row.2)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(search(Some(2)), vec!["a", "b"]);
+        assert_eq!(fetches.get(), 1);
+        fetches.set(0);
+        assert_eq!(search(Some(5)), vec!["a", "b", "c"]);
+        assert_eq!(fetches.get(), GEO_PROBE_STEPS as usize + 1);
+    }
+
+    #[test]
+    fn radius_bounds_wrap_the_antimeridian() {
+        let bounds = ReplicaGeoArea::Radius {
+            lat: 0.0,
+            lng: 179.9,
+            radius_m: 50_000.0,
+        }
+        .bounds();
+        assert_eq!(bounds.ranges().count(), 2);
+        assert!(bounds.contains(0.0, -179.9));
+    }
+}
@@ -96,6 +96,107 @@ use radroots_replica_db_schema::plot_tag::{
     IPlotTagUpdateResolve,
 };
 
+use radroots_replica_db_schema::coop::{
+    ICoopCreate, ICoopCreateResolve, ICoopDelete, ICoopDeleteResolve, ICoopFindMany,
+    ICoopFindManyResolve, ICoopFindOne, ICoopFindOneResolve, ICoopUpdate, ICoopUpdateResolve,
+};
+
+use radroots_replica_db_schema::coop_tag::{
+    ICoopTagCreate, ICoopTagCreateResolve, ICoopTagDelete, ICoopTagDeleteResolve, ICoopTagFindMany,
+    ICoopTagFindManyResolve, ICoopTagFindOne, ICoopTagFindOneResolve, ICoopTagUpdate,
+    ICoopTagUpdateResolve,
+};
+
+use radroots_replica_db_schema::coop_gcs_location::{
+    ICoopGcsLocationCreate, ICoopGcsLocationCreateResolve, ICoopGcsLocationDelete,
+    ICoopGcsLocationDeleteResolve, ICoopGcsLocationFindMany, ICoopGcsLocationFindManyResolve,
+    ICoopGcsLocationFindOne, ICoopGcsLocationFindOneResolve, ICoopGcsLocationUpdate,
+    ICoopGcsLocationUpdateResolve,
+};
+
+use radroots_replica_db_schema::document::{
+    IDocumentCreate, IDocumentCreateResolve, IDocumentDelete, IDocumentDeleteResolve,
+    IDocumentFindMany, IDocumentFindManyResolve, IDocumentFindOne, IDocumentFindOneResolve,
+    IDocumentUpdate, IDocumentUpdateResolve,
+};
+
+use radroots_replica_db_schema::document_tag::{
+    IDocumentTagCreate, IDocumentTagCreateResolve, IDocumentTagDelete, IDocumentTagDeleteResolve,
+    IDocumentTagFindMany, IDocumentTagFindManyResolve, IDocumentTagFindOne,
+    IDocumentTagFindOneResolve, IDocumentTagUpdate, IDocumentTagUpdateResolve,
+};
+
+use radroots_replica_db_schema::resource_area::{
+    IResourceAreaCreate, IResourceAreaCreateResolve, IResourceAreaDelete,
+    IResourceAreaDeleteResolve, IResourceAreaFindMany, IResourceAreaFindManyResolve,
+    IResourceAreaFindOne, IResourceAreaFindOneResolve, IResourceAreaUpdate,
+    IResourceAreaUpdateResolve,
+};
+
+use radroots_replica_db_schema::resource_area_tag::{
+    IResourceAreaTagCreate, IResourceAreaTagCreateResolve, IResourceAreaTagDelete,
+    IResourceAreaTagDeleteResolve, IResourceAreaTagFindMany, IResourceAreaTagFindManyResolve,
+    IResourceAreaTagFindOne, IResourceAreaTagFindOneResolve, IResourceAreaTagUpdate,
+    IResourceAreaTagUpdateResolve,
+};
+
+use radroots_replica_db_schema::resource_area_gcs_location::{
+    IResourceAreaGcsLocationCreate, IResourceAreaGcsLocationCreateResolve,
+    IResourceAreaGcsLocationDelete, IResourceAreaGcsLocationDeleteResolve,
+    IResourceAreaGcsLocationFindMany, IResourceAreaGcsLocationFindManyResolve,
+    IResourceAreaGcsLocationFindOne, IResourceAreaGcsLocationFindOneResolve,
+    IResourceAreaGcsLocationUpdate, IResourceAreaGcsLocationUpdateResolve,
+};
+
+use radroots_replica_db_schema::resource_harvest_cap::{
+    IResourceHarvestCapCreate, IResourceHarvestCapCreateResolve, IResourceHarvestCapDelete,
+    IResourceHarvestCapDeleteResolve, IResourceHarvestCapFindMany,
+    IResourceHarvestCapFindManyResolve, IResourceHarvestCapFindOne,
+    IResourceHarvestCapFindOneResolve, IResourceHarvestCapUpdate, IResourceHarvestCapUpdateResolve,
+};
+
+use radroots_replica_db_schema::resource_harvest_cap_tag::{
+    IResourceHarvestCapTagCreate, IResourceHarvestCapTagCreateResolve,
+    IResourceHarvestCapTagDelete, IResourceHarvestCapTagDeleteResolve,
+    IResourceHarvestCapTagFindMany, IResourceHarvestCapTagFindManyResolve,
+    IResourceHarvestCapTagFindOne, IResourceHarvestCapTagFindOneResolve,
+    IResourceHarvestCapTagUpdate, IResourceHarvestCapTagUpdateResolve,
+};
+
+use radroots_replica_db_schema::trade_order::{
+    ITradeOrderCreate, ITradeOrderCreateResolve, ITradeOrderDelete, ITradeOrderDeleteResolve,
+    ITradeOrderFindMany, ITradeOrderFindManyResolve, ITradeOrderFindOne, ITradeOrderFindOneResolve,
+    ITradeOrderUpdate, ITradeOrderUpdateResolve,
+};
+
+use radroots_replica_db_schema::trade_order_item::{
+    ITradeOrderItemCreate, ITradeOrderItemCreateResolve, ITradeOrderItemDelete,
+    ITradeOrderItemDeleteResolve, ITradeOrderItemFindMany, ITradeOrderItemFindManyResolve,
+    ITradeOrderItemFindOne, ITradeOrderItemFindOneResolve, ITradeOrderItemUpdate,
+    ITradeOrderItemUpdateResolve,
+};
+
+use radroots_replica_db_schema::trade_order_status::{
+    ITradeOrderStatusCreate, ITradeOrderStatusCreateResolve, ITradeOrderStatusDelete,
+    ITradeOrderStatusDeleteResolve, ITradeOrderStatusFindMany, ITradeOrderStatusFindManyResolve,
+    ITradeOrderStatusFindOne, ITradeOrderStatusFindOneResolve, ITradeOrderStatusUpdate,
+    ITradeOrderStatusUpdateResolve,
+};
+
+use radroots_replica_db_schema::trade_order_payment::{
+    ITradeOrderPaymentCreate, ITradeOrderPaymentCreateResolve, ITradeOrderPaymentDelete,
+    ITradeOrderPaymentDeleteResolve, ITradeOrderPaymentFindMany, ITradeOrderPaymentFindManyResolve,
+    ITradeOrderPaymentFindOne, ITradeOrderPaymentFindOneResolve, ITradeOrderPaymentUpdate,
+    ITradeOrderPaymentUpdateResolve,
+};
+
+use radroots_replica_db_schema::trade_order_event::{
+    ITradeOrderEventCreate, ITradeOrderEventCreateResolve, ITradeOrderEventDelete,
+    ITradeOrderEventDeleteResolve, ITradeOrderEventFindMany, ITradeOrderEventFindManyResolve,
+    ITradeOrderEventFindOne, ITradeOrderEventFindOneResolve, ITradeOrderEventUpdate,
+    ITradeOrderEventUpdateResolve,
+};
+
 use radroots_replica_db_schema::nostr_profile_relay::{
     INostrProfileRelayRelation, INostrProfileRelayResolve,
 };
@@ -109,14 +210,33 @@ use radroots_replica_db_schema::trade_product_media::{
 };
 
 pub mod backup;
+pub mod backup_chain;
+pub mod change;
 pub mod export;
+pub mod fts;
+pub mod geo;
 pub mod migrations;
 pub mod models;
 pub mod query;
 pub use backup::{DatabaseBackup, MigrationBackup, SchemaEntry};
+#[cfg(feature = "protected-backup")]
+pub use backup_chain::ProtectedDatabaseBackupCodec;
+pub use backup_chain::{
+    DatabaseBackupCodec, DatabaseBackupEncoding, DatabaseBackupIncrement, DatabaseBackupManifest,
+    DatabaseBackupManifestPart, DatabaseBackupPartKind, PlainDatabaseBackupCodec,
+};
+pub use change::{ReplicaChange, ReplicaChangeOp};
 pub use export::{
     REPLICA_DB_EXPORT_VERSION, ReplicaDbExportManifestRs, TableCount, export_manifest,
 };
+pub use fts::{
+    ReplicaTradeProductRankedRow, ReplicaTradeProductRankedSearch, ReplicaTradeProductSearchMode,
+    ReplicaTradeProductTextQuery,
+};
+pub use geo::{
+    ReplicaFarmGeoQuery, ReplicaFarmGeoRow, ReplicaGeoArea, ReplicaTradeProductGeoQuery,
+    ReplicaTradeProductGeoRow,
+};
 pub use models::*;
 pub use query::ReplicaTradeProductSummaryRow;
 
@@ -140,10 +260,12 @@ impl<E: SqlExecutor> ReplicaSql<E> {
     }
 
     pub fn migrate_up(&self) -> Result<(), SqlError> {
-        crate::migrations::run_all_up(self.executor())
+        crate::migrations::run_all_up(self.executor())?;
+        self.trade_product_fts_setup().map(|_| ())
     }
 
     pub fn migrate_down(&self) -> Result<(), SqlError> {
+        crate::fts::trade_product_fts_drop(self.executor())?;
         crate::migrations::run_all_down(self.executor())
     }
 
@@ -156,11 +278,13 @@ impl<E: SqlExecutor> ReplicaSql<E> {
     }
 
     pub fn restore_database(&self, backup: &DatabaseBackup) -> Result<(), SqlError> {
-        crate::backup::restore_database_backup(self.executor(), backup)
+        crate::backup::restore_database_backup(self.executor(), backup)?;
+        self.trade_product_fts_setup().map(|_| ())
     }
 
     pub fn restore_database_json(&self, backup_json: &str) -> Result<(), SqlError> {
-        crate::backup::restore_database_backup_json(self.executor(), backup_json)
+        crate::backup::restore_database_backup_json(self.executor(), backup_json)?;
+        self.trade_product_fts_setup().map(|_| ())
     }
 
     pub fn farm_create(&self, opts: &IFarmCreate) -> Result<IFarmCreateResolve, IError<SqlError>> {
@@ -390,6 +514,522 @@ impl<E: SqlExecutor> ReplicaSql<E> {
         models::plot_tag::delete(self.executor(), opts)
     }
 
+    pub fn coop_create(&self, opts: &ICoopCreate) -> Result<ICoopCreateResolve, IError<SqlError>> {
+        models::coop::create(self.executor(), opts)
+    }
+
+    pub fn coop_find_many(
+        &self,
+        opts: &ICoopFindMany,
+    ) -> Result<ICoopFindManyResolve, IError<SqlError>> {
+        models::coop::find_many(self.executor(), opts)
+    }
+
+    pub fn coop_find_one(
+        &self,
+        opts: &ICoopFindOne,
+    ) -> Result<ICoopFindOneResolve, IError<SqlError>> {
+        models::coop::find_one(self.executor(), opts)
+    }
+
+    pub fn coop_update(&self, opts: &ICoopUpdate) -> Result<ICoopUpdateResolve, IError<SqlError>> {
+        models::coop::update(self.executor(), opts)
+    }
+
+    pub fn coop_delete(&self, opts: &ICoopDelete) -> Result<ICoopDeleteResolve, IError<SqlError>> {
+        models::coop::delete(self.executor(), opts)
+    }
+
+    pub fn coop_tag_create(
+        &self,
+        opts: &ICoopTagCreate,
+    ) -> Result<ICoopTagCreateResolve, IError<SqlError>> {
+        models::coop_tag::create(self.executor(), opts)
+    }
+
+    pub fn coop_tag_find_many(
+        &self,
+        opts: &ICoopTagFindMany,
+    ) -> Result<ICoopTagFindManyResolve, IError<SqlError>> {
+        models::coop_tag::find_many(self.executor(), opts)
+    }
+
+    pub fn coop_tag_find_one(
+        &self,
+        opts: &ICoopTagFindOne,
+    ) -> Result<ICoopTagFindOneResolve, IError<SqlError>> {
+        models::coop_tag::find_one(self.executor(), opts)
+    }
+
+    pub fn coop_tag_update(
+        &self,
+        opts: &ICoopTagUpdate,
+    ) -> Result<ICoopTagUpdateResolve, IError<SqlError>> {
+        models::coop_tag::update(self.executor(), opts)
+    }
+
+    pub fn coop_tag_delete(
+        &self,
+        opts: &ICoopTagDelete,
+    ) -> Result<ICoopTagDeleteResolve, IError<SqlError>> {
+        models::coop_tag::delete(self.executor(), opts)
+    }
+
+    pub fn coop_gcs_location_create(
+        &self,
+        opts: &ICoopGcsLocationCreate,
+    ) -> Result<ICoopGcsLocationCreateResolve, IError<SqlError>> {
+        models::coop_gcs_location::create(self.executor(), opts)
+    }
+
+    pub fn coop_gcs_location_find_many(
+        &self,
+        opts: &ICoopGcsLocationFindMany,
+    ) -> Result<ICoopGcsLocationFindManyResolve, IError<SqlError>> {
+        models::coop_gcs_location::find_many(self.executor(), opts)
+    }
+
+    pub fn coop_gcs_location_find_one(
+        &self,
+        opts: &ICoopGcsLocationFindOne,
+    ) -> Result<ICoopGcsLocationFindOneResolve, IError<SqlError>> {
+        models::coop_gcs_location::find_one(self.executor(), opts)
+    }
+
+    pub fn coop_gcs_location_update(
+        &self,
+        opts: &ICoopGcsLocationUpdate,
+    ) -> Result<ICoopGcsLocationUpdateResolve, IError<SqlError>> {
+        models::coop_gcs_location::update(self.executor(), opts)
+    }
+
+    pub fn coop_gcs_location_delete(
+        &self,
+        opts: &ICoopGcsLocationDelete,
+    ) -> Result<ICoopGcsLocationDeleteResolve, IError<SqlError>> {
+        models::coop_gcs_location::delete(self.executor(), opts)
+    }
+
+    pub fn document_create(
+        &self,
+        opts: &IDocumentCreate,
+    ) -> Result<IDocumentCreateResolve, IError<SqlError>> {
+        models::document::create(self.executor(), opts)
+    }
+
+    pub fn document_find_many(
+        &self,
+        opts: &IDocumentFindMany,
+    ) -> Result<IDocumentFindManyResolve, IError<SqlError>> {
+        models::document::find_many(self.executor(), opts)
+    }
+
+    pub fn document_find_one(
+        &self,
+        opts: &IDocumentFindOne,
+    ) -> Result<IDocumentFindOneResolve, IError<SqlError>> {
+        models::document::find_one(self.executor(), opts)
+    }
+
+    pub fn document_update(
+        &self,
+        opts: &IDocumentUpdate,
+    ) -> Result<IDocumentUpdateResolve, IError<SqlError>> {
+        models::document::update(self.executor(), opts)
+    }
+
+    pub fn document_delete(
+        &self,
+        opts: &IDocumentDelete,
+    ) -> Result<IDocumentDeleteResolve, IError<SqlError>> {
+        models::document::delete(self.executor(), opts)
+    }
+
+    pub fn document_tag_create(
+        &self,
+        opts: &IDocumentTagCreate,
+    ) -> Result<IDocumentTagCreateResolve, IError<SqlError>> {
+        models::document_tag::create(self.executor(), opts)
+    }
+
+    pub fn document_tag_find_many(
+        &self,
+        opts: &IDocumentTagFindMany,
+    ) -> Result<IDocumentTagFindManyResolve, IError<SqlError>> {
+        models::document_tag::find_many(self.executor(), opts)
+    }
+
+    pub fn document_tag_find_one(
+        &self,
+        opts: &IDocumentTagFindOne,
+    ) -> Result<IDocumentTagFindOneResolve, IError<SqlError>> {
+        models::document_tag::find_one(self.executor(), opts)
+    }
+
+    pub fn document_tag_update(
+        &self,
+        opts: &IDocumentTagUpdate,
+    ) -> Result<IDocumentTagUpdateResolve, IError<SqlError>> {
+        models::document_tag::update(self.executor(), opts)
+    }
+
+    pub fn document_tag_delete(
+        &self,
+        opts: &IDocumentTagDelete,
+    ) -> Result<IDocumentTagDeleteResolve, IError<SqlError>> {
+        models::document_tag::delete(self.executor(), opts)
+    }
+
+    pub fn resource_area_create(
+        &self,
+        opts: &IResourceAreaCreate,
+    ) -> Result<IResourceAreaCreateResolve, IError<SqlError>> {
+        models::resource_area::create(self.executor(), opts)
+    }
+
+    pub fn resource_area_find_many(
+        &self,
+        opts: &IResourceAreaFindMany,
+    ) -> Result<IResourceAreaFindManyResolve, IError<SqlError>> {
+        models::resource_area::find_many(self.executor(), opts)
+    }
+
+    pub fn resource_area_find_one(
+        &self,
+        opts: &IResourceAreaFindOne,
+    ) -> Result<IResourceAreaFindOneResolve, IError<SqlError>> {
+        models::resource_area::find_one(self.executor(), opts)
+    }
+
+    pub fn resource_area_update(
+        &self,
+        opts: &IResourceAreaUpdate,
+    ) -> Result<IResourceAreaUpdateResolve, IError<SqlError>> {
+        models::resource_area::update(self.executor(), opts)
+    }
+
+    pub fn resource_area_delete(
+        &self,
+        opts: &IResourceAreaDelete,
+    ) -> Result<IResourceAreaDeleteResolve, IError<SqlError>> {
+        models::resource_area::delete(self.executor(), opts)
+    }
+
+    pub fn resource_area_tag_create(
+        &self,
+        opts: &IResourceAreaTagCreate,
+    ) -> Result<IResourceAreaTagCreateResolve, IError<SqlError>> {
+        models::resource_area_tag::create(self.executor(), opts)
+    }
+
+    pub fn resource_area_tag_find_many(
+        &self,
+        opts: &IResourceAreaTagFindMany,
+    ) -> Result<IResourceAreaTagFindManyResolve, IError<SqlError>> {
+        models::resource_area_tag::find_many(self.executor(), opts)
+    }
+
+    pub fn resource_area_tag_find_one(
+        &self,
+        opts: &IResourceAreaTagFindOne,
+    ) -> Result<IResourceAreaTagFindOneResolve, IError<SqlError>> {
+        models::resource_area_tag::find_one(self.executor(), opts)
+    }
+
+    pub fn resource_area_tag_update(
+        &self,
+        opts: &IResourceAreaTagUpdate,
+    ) -> Result<IResourceAreaTagUpdateResolve, IError<SqlError>> {
+        models::resource_area_tag::update(self.executor(), opts)
+    }
+
+    pub fn resource_area_tag_delete(
+        &self,
+        opts: &IResourceAreaTagDelete,
+    ) -> Result<IResourceAreaTagDeleteResolve, IError<SqlError>> {
+        models::resource_area_tag::delete(self.executor(), opts)
+    }
+
+    pub fn resource_area_gcs_location_create(
+        &self,
+        opts: &IResourceAreaGcsLocationCreate,
+    ) -> Result<IResourceAreaGcsLocationCreateResolve, IError<SqlError>> {
+        models::resource_area_gcs_location::create(self.executor(), opts)
+    }
+
+    pub fn resource_area_gcs_location_find_many(
+        &self,
+        opts: &IResourceAreaGcsLocationFindMany,
+    ) -> Result<IResourceAreaGcsLocationFindManyResolve, IError<SqlError>> {
+        models::resource_area_gcs_location::find_many(self.executor(), opts)
+    }
+
+    pub fn resource_area_gcs_location_find_one(
+        &self,
+        opts: &IResourceAreaGcsLocationFindOne,
+    ) -> Result<IResourceAreaGcsLocationFindOneResolve, IError<SqlError>> {
+        models::resource_area_gcs_location::find_one(self.executor(), opts)
+    }
+
+    pub fn resource_area_gcs_location_update(
+        &self,
+        opts: &IResourceAreaGcsLocationUpdate,
+    ) -> Result<IResourceAreaGcsLocationUpdateResolve, IError<SqlError>> {
+        models::resource_area_gcs_location::update(self.executor(), opts)
+    }
+
+    pub fn resource_area_gcs_location_delete(
+        &self,
+        opts: &IResourceAreaGcsLocationDelete,
+    ) -> Result<IResourceAreaGcsLocationDeleteResolve, IError<SqlError>> {
+        models::resource_area_gcs_location::delete(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_create(
+        &self,
+        opts: &IResourceHarvestCapCreate,
+    ) -> Result<IResourceHarvestCapCreateResolve, IError<SqlError>> {
+        models::resource_harvest_cap::create(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_find_many(
+        &self,
+        opts: &IResourceHarvestCapFindMany,
+    ) -> Result<IResourceHarvestCapFindManyResolve, IError<SqlError>> {
+        models::resource_harvest_cap::find_many(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_find_one(
+        &self,
+        opts: &IResourceHarvestCapFindOne,
+    ) -> Result<IResourceHarvestCapFindOneResolve, IError<SqlError>> {
+        models::resource_harvest_cap::find_one(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_update(
+        &self,
+        opts: &IResourceHarvestCapUpdate,
+    ) -> Result<IResourceHarvestCapUpdateResolve, IError<SqlError>> {
+        models::resource_harvest_cap::update(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_delete(
+        &self,
+        opts: &IResourceHarvestCapDelete,
+    ) -> Result<IResourceHarvestCapDeleteResolve, IError<SqlError>> {
+        models::resource_harvest_cap::delete(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_tag_create(
+        &self,
+        opts: &IResourceHarvestCapTagCreate,
+    ) -> Result<IResourceHarvestCapTagCreateResolve, IError<SqlError>> {
+        models::resource_harvest_cap_tag::create(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_tag_find_many(
+        &self,
+        opts: &IResourceHarvestCapTagFindMany,
+    ) -> Result<IResourceHarvestCapTagFindManyResolve, IError<SqlError>> {
+        models::resource_harvest_cap_tag::find_many(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_tag_find_one(
+        &self,
+        opts: &IResourceHarvestCapTagFindOne,
+    ) -> Result<IResourceHarvestCapTagFindOneResolve, IError<SqlError>> {
+        models::resource_harvest_cap_tag::find_one(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_tag_update(
+        &self,
+        opts: &IResourceHarvestCapTagUpdate,
+    ) -> Result<IResourceHarvestCapTagUpdateResolve, IError<SqlError>> {
+        models::resource_harvest_cap_tag::update(self.executor(), opts)
+    }
+
+    pub fn resource_harvest_cap_tag_delete(
+        &self,
+        opts: &IResourceHarvestCapTagDelete,
+    ) -> Result<IResourceHarvestCapTagDeleteResolve, IError<SqlError>> {
+        models::resource_harvest_cap_tag::delete(self.executor(), opts)
+    }
+
+    pub fn trade_order_create(
+        &self,
+        opts: &ITradeOrderCreate,
+    ) -> Result<ITradeOrderCreateResolve, IError<SqlError>> {
+        models::trade_order::create(self.executor(), opts)
+    }
+
+    pub fn trade_order_find_many(
+        &self,
+        opts: &ITradeOrderFindMany,
+    ) -> Result<ITradeOrderFindManyResolve, IError<SqlError>> {
+        models::trade_order::find_many(self.executor(), opts)
+    }
+
+    pub fn trade_order_find_one(
+        &self,
+        opts: &ITradeOrderFindOne,
+    ) -> Result<ITradeOrderFindOneResolve, IError<SqlError>> {
+        models::trade_order::find_one(self.executor(), opts)
+    }
+
+    pub fn trade_order_update(
+        &self,
+        opts: &ITradeOrderUpdate,
+    ) -> Result<ITradeOrderUpdateResolve, IError<SqlError>> {
+        models::trade_order::update(self.executor(), opts)
+    }
+
+    pub fn trade_order_delete(
+        &self,
+        opts: &ITradeOrderDelete,
+    ) -> Result<ITradeOrderDeleteResolve, IError<SqlError>> {
+        models::trade_order::delete(self.executor(), opts)
+    }
+
+    pub fn trade_order_item_create(
+        &self,
+        opts: &ITradeOrderItemCreate,
+    ) -> Result<ITradeOrderItemCreateResolve, IError<SqlError>> {
+        models::trade_order_item::create(self.executor(), opts)
+    }
+
+    pub fn trade_order_item_find_many(
+        &self,
+        opts: &ITradeOrderItemFindMany,
+    ) -> Result<ITradeOrderItemFindManyResolve, IError<SqlError>> {
+        models::trade_order_item::find_many(self.executor(), opts)
+    }
+
+    pub fn trade_order_item_find_one(
+        &self,
+        opts: &ITradeOrderItemFindOne,
+    ) -> Result<ITradeOrderItemFindOneResolve, IError<SqlError>> {
+        models::trade_order_item::find_one(self.executor(), opts)
+    }
+
+    pub fn trade_order_item_update(
+        &self,
+        opts: &ITradeOrderItemUpdate,
+    ) -> Result<ITradeOrderItemUpdateResolve, IError<SqlError>> {
+        models::trade_order_item::update(self.executor(), opts)
+    }
+
+    pub fn trade_order_item_delete(
+        &self,
+        opts: &ITradeOrderItemDelete,
+    ) -> Result<ITradeOrderItemDeleteResolve, IError<SqlError>> {
+        models::trade_order_item::delete(self.executor(), opts)
+    }
+
+    pub fn trade_order_status_create(
+        &self,
+        opts: &ITradeOrderStatusCreate,
+    ) -> Result<ITradeOrderStatusCreateResolve, IError<SqlError>> {
+        models::trade_order_status::create(self.executor(), opts)
+    }
+
+    pub fn trade_order_status_find_many(
+        &self,
+        opts: &ITradeOrderStatusFindMany,
+    ) -> Result<ITradeOrderStatusFindManyResolve, IError<SqlError>> {
+        models::trade_order_status::find_many(self.executor(), opts)
+    }
+
+    pub fn trade_order_status_find_one(
+        &self,
+        opts: &ITradeOrderStatusFindOne,
+    ) -> Result<ITradeOrderStatusFindOneResolve, IError<SqlError>> {
+        models::trade_order_status::find_one(self.executor(), opts)
+    }
+
+    pub fn trade_order_status_update(
+        &self,
+        opts: &ITradeOrderStatusUpdate,
+    ) -> Result<ITradeOrderStatusUpdateResolve, IError<SqlError>> {
+        models::trade_order_status::update(self.executor(), opts)
+    }
+
+    pub fn trade_order_status_delete(
+        &self,
+        opts: &ITradeOrderStatusDelete,
+    ) -> Result<ITradeOrderStatusDeleteResolve, IError<SqlError>> {
+        models::trade_order_status::delete(self.executor(), opts)
+    }
+
+    pub fn trade_order_payment_create(
+        &self,
+        opts: &ITradeOrderPaymentCreate,
+    ) -> Result<ITradeOrderPaymentCreateResolve, IError<SqlError>> {
+        models::trade_order_payment::create(self.executor(), opts)
+    }
+
+    pub fn trade_order_payment_find_many(
+        &self,
+        opts: &ITradeOrderPaymentFindMany,
+    ) -> Result<ITradeOrderPaymentFindManyResolve, IError<SqlError>> {
+        models::trade_order_payment::find_many(self.executor(), opts)
+    }
+
+    pub fn trade_order_payment_find_one(
+        &self,
+        opts: &ITradeOrderPaymentFindOne,
+    ) -> Result<ITradeOrderPaymentFindOneResolve, IError<SqlError>> {
+        models::trade_order_payment::find_one(self.executor(), opts)
+    }
+
+    pub fn trade_order_payment_update(
+        &self,
+        opts: &ITradeOrderPaymentUpdate,
+    ) -> Result<ITradeOrderPaymentUpdateResolve, IError<SqlError>> {
+        models::trade_order_payment::update(self.executor(), opts)
+    }
+
+    pub fn trade_order_payment_delete(
+        &self,
+        opts: &ITradeOrderPaymentDelete,
+    ) -> Result<ITradeOrderPaymentDeleteResolve, IError<SqlError>> {
+        models::trade_order_payment::delete(self.executor(), opts)
+    }
+
+    pub fn trade_order_event_create(
+        &self,
+        opts: &ITradeOrderEventCreate,
+    ) -> Result<ITradeOrderEventCreateResolve, IError<SqlError>> {
+        models::trade_order_event::create(self.executor(), opts)
+    }
+
+    pub fn trade_order_event_find_many(
+        &self,
+        opts: &ITradeOrderEventFindMany,
+    ) -> Result<ITradeOrderEventFindManyResolve, IError<SqlError>> {
+        models::trade_order_event::find_many(self.executor(), opts)
+    }
+
+    pub fn trade_order_event_find_one(
+        &self,
+        opts: &ITradeOrderEventFindOne,
+    ) -> Result<ITradeOrderEventFindOneResolve, IError<SqlError>> {
+        models::trade_order_event::find_one(self.executor(), opts)
+    }
+
+    pub fn trade_order_event_update(
+        &self,
+        opts: &ITradeOrderEventUpdate,
+    ) -> Result<ITradeOrderEventUpdateResolve, IError<SqlError>> {
+        models::trade_order_event::update(self.executor(), opts)
+    }
+
+    pub fn trade_order_event_delete(
+        &self,
+        opts: &ITradeOrderEventDelete,
+    ) -> Result<ITradeOrderEventDeleteResolve, IError<SqlError>> {
+        models::trade_order_event::delete(self.executor(), opts)
+    }
+
     pub fn farm_member_create(
         &self,
         opts: &IFarmMemberCreate,
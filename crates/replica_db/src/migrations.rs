@@ -123,6 +123,101 @@ pub static MIGRATIONS: &[Migration] = &[
         up_sql: include_str!("../migrations/0023_trade_product_verified_primary_bin.up.sql"),
         down_sql: include_str!("../migrations/0023_trade_product_verified_primary_bin.down.sql"),
     },
+    Migration {
+        name: "0024_trade_product_expires_at",
+        up_sql: include_str!("../migrations/0024_trade_product_expires_at.up.sql"),
+        down_sql: include_str!("../migrations/0024_trade_product_expires_at.down.sql"),
+    },
+    Migration {
+        name: "0025_coop",
+        up_sql: include_str!("../migrations/0025_coop.up.sql"),
+        down_sql: include_str!("../migrations/0025_coop.down.sql"),
+    },
+    Migration {
+        name: "0026_coop_tag",
+        up_sql: include_str!("../migrations/0026_coop_tag.up.sql"),
+        down_sql: include_str!("../migrations/0026_coop_tag.down.sql"),
+    },
+    Migration {
+        name: "0027_coop_gcs_location",
+        up_sql: include_str!("../migrations/0027_coop_gcs_location.up.sql"),
+        down_sql: include_str!("../migrations/0027_coop_gcs_location.down.sql"),
+    },
+    Migration {
+        name: "0028_document",
+        up_sql: include_str!("../migrations/0028_document.up.sql"),
+        down_sql: include_str!("../migrations/0028_document.down.sql"),
+    },
+    Migration {
+        name: "0029_document_tag",
+        up_sql: include_str!("../migrations/0029_document_tag.up.sql"),
+        down_sql: include_str!("../migrations/0029_document_tag.down.sql"),
+    },
+    Migration {
+        name: "0030_resource_area",
+        up_sql: include_str!("../migrations/0030_resource_area.up.sql"),
+        down_sql: include_str!("../migrations/0030_resource_area.down.sql"),
+    },
+    Migration {
+        name: "0031_resource_area_tag",
+        up_sql: include_str!("../migrations/0031_resource_area_tag.up.sql"),
+        down_sql: include_str!("../migrations/0031_resource_area_tag.down.sql"),
+    },
+    Migration {
+        name: "0032_resource_area_gcs_location",
+        up_sql: include_str!("../migrations/0032_resource_area_gcs_location.up.sql"),
+        down_sql: include_str!("../migrations/0032_resource_area_gcs_location.down.sql"),
+    },
+    Migration {
+        name: "0033_resource_harvest_cap",
+        up_sql: include_str!("../migrations/0033_resource_harvest_cap.up.sql"),
+        down_sql: include_str!("../migrations/0033_resource_harvest_cap.down.sql"),
+    },
+    Migration {
+        name: "0034_resource_harvest_cap_tag",
+        up_sql: include_str!("../migrations/0034_resource_harvest_cap_tag.up.sql"),
+        down_sql: include_str!("../migrations/0034_resource_harvest_cap_tag.down.sql"),
+    },
+    Migration {
+        name: "0035_trade_order",
+        up_sql: include_str!("../migrations/0035_trade_order.up.sql"),
+        down_sql: include_str!("../migrations/0035_trade_order.down.sql"),
+    },
+    Migration {
+        name: "0036_trade_order_item",
+        up_sql: include_str!("../migrations/0036_trade_order_item.up.sql"),
+        down_sql: include_str!("../migrations/0036_trade_order_item.down.sql"),
+    },
+    Migration {
+        name: "0037_trade_order_status",
+        up_sql: include_str!("../migrations/0037_trade_order_status.up.sql"),
+        down_sql: include_str!("../migrations/0037_trade_order_status.down.sql"),
+    },
+    Migration {
+        name: "0038_trade_order_payment",
+        up_sql: include_str!("../migrations/0038_trade_order_payment.up.sql"),
+        down_sql: include_str!("../migrations/0038_trade_order_payment.down.sql"),
+    },
+    Migration {
+        name: "0039_trade_order_event",
+        up_sql: include_str!("../migrations/0039_trade_order_event.up.sql"),
+        down_sql: include_str!("../migrations/0039_trade_order_event.down.sql"),
+    },
+    Migration {
+        name: "0040_geo_index",
+        up_sql: include_str!("../migrations/0040_geo_index.up.sql"),
+        down_sql: include_str!("../migrations/0040_geo_index.down.sql"),
+    },
+    Migration {
+        name: "0041_replica_change",
+        up_sql: include_str!("../migrations/0041_replica_change.up.sql"),
+        down_sql: include_str!("../migrations/0041_replica_change.down.sql"),
+    },
+    Migration {
+        name: "0042_trade_product_farm_ref",
+        up_sql: include_str!("../migrations/0042_trade_product_farm_ref.up.sql"),
+        down_sql: include_str!("../migrations/0042_trade_product_farm_ref.down.sql"),
+    },
 ];
 
 pub fn run_all_up<E>(executor: &E) -> Result<(), SqlError>
@@ -1,3 +1,8 @@
+pub mod coop;
+pub mod coop_gcs_location;
+pub mod coop_tag;
+pub mod document;
+pub mod document_tag;
 pub mod farm;
 pub mod farm_gcs_location;
 pub mod farm_member;
@@ -13,6 +18,16 @@ pub mod nostr_relay;
 pub mod plot;
 pub mod plot_gcs_location;
 pub mod plot_tag;
+pub mod resource_area;
+pub mod resource_area_gcs_location;
+pub mod resource_area_tag;
+pub mod resource_harvest_cap;
+pub mod resource_harvest_cap_tag;
+pub mod trade_order;
+pub mod trade_order_event;
+pub mod trade_order_item;
+pub mod trade_order_payment;
+pub mod trade_order_status;
 pub mod trade_product;
 pub mod trade_product_location;
 pub mod trade_product_media;
@@ -1,10 +1,10 @@
 use radroots_sql_core::utils;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{ReplicaSql, SqlError, SqlExecutor};
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ReplicaTradeProductSummaryRow {
     pub id: String,
     pub key: String,
@@ -25,10 +25,28 @@ pub struct ReplicaTradeProductSummaryRow {
     pub listing_addr: Option<String>,
     pub primary_bin_id: Option<String>,
     pub verified_primary_bin_id: Option<String>,
+    pub expires_at: Option<i64>,
     pub notes: Option<String>,
     pub location_primary: Option<String>,
 }
 
+pub(crate) fn push_trade_product_term_filters(
+    query_terms: &[String],
+    where_clauses: &mut Vec<String>,
+    bind_values: &mut Vec<Value>,
+) {
+    for term in query_terms {
+        let pattern = format!("%{}%", term.to_lowercase());
+        where_clauses.push(
+            "(lower(tp.title) LIKE ? OR lower(tp.summary) LIKE ? OR lower(tp.category) LIKE ? OR lower(tp.key) LIKE ? OR lower(COALESCE(tp.notes, '')) LIKE ?)"
+                .to_owned(),
+        );
+        for _ in 0..5 {
+            bind_values.push(Value::from(pattern.clone()));
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct ReplicaFarmDTagRow {
     d_tag: String,
@@ -44,7 +62,23 @@ impl<E: SqlExecutor> ReplicaSql<E> {
         &self,
         lookup: &str,
     ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
-        let sql = "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.notes, loc.location_primary \
+        self.trade_product_lookup_live(lookup, None)
+    }
+
+    pub fn trade_product_lookup_at(
+        &self,
+        lookup: &str,
+        now_unix: u64,
+    ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
+        self.trade_product_lookup_live(lookup, Some(now_unix))
+    }
+
+    fn trade_product_lookup_live(
+        &self,
+        lookup: &str,
+        now_unix: Option<u64>,
+    ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
+        let sql = "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.expires_at, tp.notes, loc.location_primary \
              FROM trade_product tp \
              LEFT JOIN (\
                  SELECT tpl.tb_tp AS trade_product_id, MIN(COALESCE(gl.label, gl.gc_name, gl.gc_admin1_name, gl.gc_country_name, gl.d_tag)) AS location_primary \
@@ -52,11 +86,13 @@ impl<E: SqlExecutor> ReplicaSql<E> {
                  JOIN gcs_location gl ON gl.id = tpl.tb_gl \
                  GROUP BY tpl.tb_tp\
              ) loc ON loc.trade_product_id = tp.id \
-             WHERE tp.id = ? OR tp.key = ? \
+             WHERE (tp.id = ? OR tp.key = ?) AND (? IS NULL OR tp.expires_at IS NULL OR tp.expires_at > ?) \
              ORDER BY lower(tp.title) ASC, tp.id ASC;";
         let params_json = utils::to_params_json(vec![
             Value::from(lookup.to_owned()),
             Value::from(lookup.to_owned()),
+            Value::from(now_unix),
+            Value::from(now_unix),
         ])?;
         let json = self.executor().query_raw(sql, &params_json)?;
         serde_json::from_str(&json).map_err(SqlError::from)
@@ -65,26 +101,36 @@ impl<E: SqlExecutor> ReplicaSql<E> {
     pub fn trade_product_search(
         &self,
         query_terms: &[String],
+    ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
+        self.trade_product_search_live(query_terms, None)
+    }
+
+    pub fn trade_product_search_at(
+        &self,
+        query_terms: &[String],
+        now_unix: u64,
+    ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
+        self.trade_product_search_live(query_terms, Some(now_unix))
+    }
+
+    fn trade_product_search_live(
+        &self,
+        query_terms: &[String],
+        now_unix: Option<u64>,
     ) -> Result<Vec<ReplicaTradeProductSummaryRow>, SqlError> {
         if query_terms.is_empty() {
             return Ok(Vec::new());
         }
 
-        let mut where_clauses = Vec::with_capacity(query_terms.len());
-        let mut bind_values = Vec::<Value>::with_capacity(query_terms.len() * 5);
-        for term in query_terms {
-            let pattern = format!("%{}%", term.to_lowercase());
-            where_clauses.push(
-                "(lower(tp.title) LIKE ? OR lower(tp.summary) LIKE ? OR lower(tp.category) LIKE ? OR lower(tp.key) LIKE ? OR lower(COALESCE(tp.notes, '')) LIKE ?)"
-                    .to_owned(),
-            );
-            for _ in 0..5 {
-                bind_values.push(Value::from(pattern.clone()));
-            }
-        }
+        let mut where_clauses = Vec::with_capacity(query_terms.len() + 1);
+        let mut bind_values = Vec::<Value>::with_capacity(query_terms.len() * 5 + 2);
+        push_trade_product_term_filters(query_terms, &mut where_clauses, &mut bind_values);
+        where_clauses.push("(? IS NULL OR tp.expires_at IS NULL OR tp.expires_at > ?)".to_owned());
+        bind_values.push(Value::from(now_unix));
+        bind_values.push(Value::from(now_unix));
 
         let sql = format!(
-            "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.notes, loc.location_primary \
+            "SELECT tp.id, tp.key, tp.category, tp.title, tp.summary, tp.qty_amt, tp.qty_amt_exact, tp.qty_unit, tp.qty_label, tp.qty_avail, tp.price_amt, tp.price_amt_exact, tp.price_currency, tp.price_qty_amt, tp.price_qty_amt_exact, tp.price_qty_unit, tp.listing_addr, tp.primary_bin_id, tp.verified_primary_bin_id, tp.expires_at, tp.notes, loc.location_primary \
              FROM trade_product tp \
              LEFT JOIN (\
                  SELECT tpl.tb_tp AS trade_product_id, MIN(COALESCE(gl.label, gl.gc_name, gl.gc_admin1_name, gl.gc_country_name, gl.d_tag)) AS location_primary \
@@ -1,4 +1,17 @@
-use radroots_replica_db::{ReplicaSql, export_manifest};
+use radroots_protected_store::RadrootsProtectedFileKeySource;
+use radroots_replica_db::backup::TableData;
+use radroots_replica_db::backup_chain::{
+    export_database_backup_base, export_database_backup_increment,
+    export_database_backup_increment_part, restore_database_backup_chain,
+    seal_database_backup_manifest, verify_database_backup_chain,
+};
+use radroots_replica_db::change::record_change;
+use radroots_replica_db::{
+    DatabaseBackupManifestPart, DatabaseBackupPartKind, PlainDatabaseBackupCodec,
+    ProtectedDatabaseBackupCodec, ReplicaChangeOp, ReplicaFarmGeoQuery, ReplicaGeoArea, ReplicaSql,
+    ReplicaTradeProductGeoQuery, ReplicaTradeProductRankedSearch, ReplicaTradeProductSearchMode,
+    ReplicaTradeProductTextQuery, export_manifest,
+};
 use radroots_replica_db_schema::farm::{
     IFarmCreate, IFarmDelete, IFarmFindMany, IFarmFindOne, IFarmUpdate,
 };
@@ -58,10 +71,11 @@ use radroots_replica_db_schema::trade_product::{
 };
 use radroots_replica_db_schema::trade_product_location::ITradeProductLocationRelation;
 use radroots_replica_db_schema::trade_product_media::ITradeProductMediaRelation;
-use radroots_sql_core::{SqlError, SqliteExecutor};
+use radroots_sql_core::{SqlError, SqlExecutor, SqliteExecutor};
 use radroots_types::types::IError;
 use serde::de::DeserializeOwned;
 use serde_json::json;
+use sha2::{Digest, Sha256};
 
 fn parse_json<T: DeserializeOwned>(value: serde_json::Value) -> T {
     serde_json::from_value(value).expect("valid test payload")
@@ -143,6 +157,7 @@ fn full_mode_shaped_query_helpers_cover_cli_reads() {
         "listing_addr": listing_addr.clone(),
         "primary_bin_id": "bin-a",
         "verified_primary_bin_id": "bin-a",
+        "expires_at": 1_782_777_600,
         "notes": "fresh coffee"
     }));
     let trade_product_created = db
@@ -170,17 +185,18 @@ fn full_mode_shaped_query_helpers_cover_cli_reads() {
         .expect("nostr event state create");
 
     let rows = db
-        .trade_product_search(&["coffee".to_owned()])
+        .trade_product_search_at(&["coffee".to_owned()], 1_781_895_600)
         .expect("trade product search");
     assert_eq!(rows.len(), 1);
     assert_eq!(rows[0].key, "product-a");
     assert_eq!(rows[0].listing_addr.as_deref(), Some(listing_addr.as_str()));
     assert_eq!(rows[0].primary_bin_id.as_deref(), Some("bin-a"));
     assert_eq!(rows[0].verified_primary_bin_id.as_deref(), Some("bin-a"));
+    assert_eq!(rows[0].expires_at, Some(1_782_777_600));
     assert_eq!(rows[0].location_primary.as_deref(), Some("stockholm"));
 
     let lookup_rows = db
-        .trade_product_lookup("product-a")
+        .trade_product_lookup_at("product-a", 1_781_895_600)
         .expect("trade product lookup");
     assert_eq!(lookup_rows.len(), 1);
     assert_eq!(lookup_rows[0].id, trade_product_created.id);
@@ -194,6 +210,22 @@ fn full_mode_shaped_query_helpers_cover_cli_reads() {
         Some("bin-a")
     );
 
+    assert!(
+        db.trade_product_search_at(&["coffee".to_owned()], 1_782_777_600)
+            .expect("expired search")
+            .is_empty()
+    );
+    assert!(
+        db.trade_product_lookup_at("product-a", 1_782_777_600)
+            .expect("expired lookup")
+            .is_empty()
+    );
+    assert_eq!(
+        db.trade_product_lookup("product-a")
+            .expect("unfiltered lookup")
+            .len(),
+        1
+    );
     assert_eq!(
         db.trade_product_search(&[]).expect("empty search"),
         Vec::new()
@@ -210,6 +242,643 @@ fn full_mode_shaped_query_helpers_cover_cli_reads() {
     );
 }
 
+fn create_geo_location(db: &ReplicaSql<SqliteExecutor>, d_tag: &str, lat: f64, lng: f64) -> String {
+    create_geo_location_with_geohash(db, d_tag, lat, lng, "u6sce4f")
+}
+
+fn create_geo_location_with_geohash(
+    db: &ReplicaSql<SqliteExecutor>,
+    d_tag: &str,
+    lat: f64,
+    lng: f64,
+    geohash: &str,
+) -> String {
+    let gcs_location: IGcsLocationCreate = parse_json(json!({
+        "d_tag": d_tag,
+        "lat": lat,
+        "lng": lng,
+        "geohash": geohash,
+        "point": format!("POINT({lng} {lat})"),
+        "polygon": format!("POLYGON(({lng} {lat},{lng} {lat},{lng} {lat},{lng} {lat}))"),
+        "label": d_tag
+    }));
+    db.gcs_location_create(&gcs_location)
+        .expect("gcs create")
+        .result
+        .id
+}
+
+fn create_product(
+    db: &ReplicaSql<SqliteExecutor>,
+    key: &str,
+    title: &str,
+    category: &str,
+    summary: &str,
+    listing_addr: Option<&str>,
+) -> String {
+    let trade_product: ITradeProductCreate = parse_json(json!({
+        "key": key,
+        "category": category,
+        "title": title,
+        "summary": summary,
+        "process": "raw",
+        "lot": "lot",
+        "profile": "profile",
+        "year": 2024,
+        "qty_amt": 1,
+        "qty_amt_exact": "1",
+        "qty_unit": "kg",
+        "qty_label": null,
+        "qty_avail": null,
+        "price_amt": 5.0,
+        "price_amt_exact": "5",
+        "price_currency": "USD",
+        "price_qty_amt": 1,
+        "price_qty_amt_exact": "1",
+        "price_qty_unit": "kg",
+        "listing_addr": listing_addr,
+        "primary_bin_id": null,
+        "verified_primary_bin_id": null,
+        "expires_at": null,
+        "notes": null
+    }));
+    db.trade_product_create(&trade_product)
+        .expect("trade product create")
+        .result
+        .id
+}
+
+fn create_geo_product(
+    db: &ReplicaSql<SqliteExecutor>,
+    key: &str,
+    category: &str,
+    gcs_location_ids: &[&str],
+) -> String {
+    let id = create_product(
+        db,
+        key,
+        &format!("{category} {key}"),
+        category,
+        "local lot",
+        None,
+    );
+    for gcs_location_id in gcs_location_ids {
+        let rel: ITradeProductLocationRelation = parse_json(json!({
+            "trade_product": { "id": id },
+            "gcs_location": { "id": gcs_location_id }
+        }));
+        db.trade_product_location_set(&rel)
+            .expect("product location set");
+    }
+    id
+}
+
+#[test]
+fn full_mode_geo_search_ranks_products_and_farms_by_distance() {
+    let db = open_db();
+
+    let stockholm = create_geo_location(&db, "stockholm", 59.3293, 18.0686);
+    let uppsala = create_geo_location(&db, "uppsala", 59.8586, 17.6389);
+    let gothenburg = create_geo_location(&db, "gothenburg", 57.7089, 11.9746);
+    let near = create_geo_product(&db, "near", "coffee", &[&stockholm, &gothenburg]);
+    let mid = create_geo_product(&db, "mid", "coffee", &[&uppsala]);
+    let far = create_geo_product(&db, "far", "tea", &[&gothenburg]);
+
+    let mut query = ReplicaTradeProductGeoQuery {
+        area: ReplicaGeoArea::Radius {
+            lat: 59.33,
+            lng: 18.07,
+            radius_m: 100_000.0,
+        },
+        query_terms: Vec::new(),
+        category: None,
+        now_unix: 0,
+        limit: None,
+    };
+    let rows = db.trade_product_geo_search(&query).expect("radius search");
+    let ids = rows
+        .iter()
+        .map(|row| row.product.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![near.as_str(), mid.as_str()]);
+    assert_eq!(rows[0].gcs_location_id, stockholm);
+    assert_eq!(
+        rows[0].product.location_primary.as_deref(),
+        Some("stockholm")
+    );
+    assert!(rows[0].distance_m < 1_000.0);
+    assert!(rows[1].distance_m > 50_000.0 && rows[1].distance_m < 100_000.0);
+
+    query.area = ReplicaGeoArea::Radius {
+        lat: 57.7,
+        lng: 11.97,
+        radius_m: 500_000.0,
+    };
+    query.query_terms = vec!["coffee".to_owned()];
+    let rows = db
+        .trade_product_geo_search(&query)
+        .expect("filtered search");
+    let ids = rows
+        .iter()
+        .map(|row| row.product.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![near.as_str(), mid.as_str()]);
+    assert_eq!(rows[0].gcs_location_id, gothenburg);
+
+    query.query_terms.clear();
+    query.category = Some("TEA".to_owned());
+    let rows = db
+        .trade_product_geo_search(&query)
+        .expect("category search");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].product.id, far);
+
+    query.category = None;
+    query.limit = Some(1);
+    query.area = ReplicaGeoArea::BoundingBox {
+        min_lat: 59.0,
+        min_lng: 17.0,
+        max_lat: 60.0,
+        max_lng: 19.0,
+    };
+    let rows = db.trade_product_geo_search(&query).expect("bbox search");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].product.id, near);
+
+    query.area = ReplicaGeoArea::BoundingBox {
+        min_lat: 10.0,
+        min_lng: 170.0,
+        max_lat: 11.0,
+        max_lng: -170.0,
+    };
+    assert!(
+        db.trade_product_geo_search(&query)
+            .expect("antimeridian search")
+            .is_empty()
+    );
+
+    query.area = ReplicaGeoArea::Radius {
+        lat: 91.0,
+        lng: 0.0,
+        radius_m: 1.0,
+    };
+    assert!(matches!(
+        db.trade_product_geo_search(&query),
+        Err(SqlError::InvalidArgument(_))
+    ));
+
+    let farm: IFarmCreate = parse_json(json!({
+        "d_tag": "farm-geo",
+        "pubkey": hex64('a'),
+        "name": "geo farm"
+    }));
+    let farm_id = db.farm_create(&farm).expect("farm create").result.id;
+    for (gcs_location_id, role) in [(&uppsala, "primary"), (&gothenburg, "field")] {
+        let rel: IFarmGcsLocationCreate = parse_json(json!({
+            "farm_id": farm_id,
+            "gcs_location_id": gcs_location_id,
+            "role": role
+        }));
+        db.farm_gcs_location_create(&rel)
+            .expect("farm location create");
+    }
+    let farms = db
+        .farm_geo_search(&ReplicaFarmGeoQuery {
+            area: ReplicaGeoArea::Radius {
+                lat: 59.33,
+                lng: 18.07,
+                radius_m: 1_000_000.0,
+            },
+            limit: None,
+        })
+        .expect("farm search");
+    assert_eq!(farms.len(), 1);
+    assert_eq!(farms[0].id, farm_id);
+    assert_eq!(farms[0].gcs_location_id, uppsala);
+}
+
+#[test]
+fn full_mode_geo_search_ignores_author_supplied_geohash_precision() {
+    let db = open_db();
+
+    let coarse = create_geo_location_with_geohash(&db, "coarse", 59.3293, 18.0686, "u6");
+    let mislabelled = create_geo_location_with_geohash(&db, "mislabelled", 59.3300, 18.0700, "s0");
+    let coarse_product = create_geo_product(&db, "coarse", "coffee", &[&coarse]);
+    let mislabelled_product = create_geo_product(&db, "mislabelled", "coffee", &[&mislabelled]);
+
+    let mut query = ReplicaTradeProductGeoQuery {
+        area: ReplicaGeoArea::Radius {
+            lat: 59.33,
+            lng: 18.07,
+            radius_m: 2_000.0,
+        },
+        query_terms: Vec::new(),
+        category: None,
+        now_unix: 0,
+        limit: None,
+    };
+    let rows = db.trade_product_geo_search(&query).expect("radius search");
+    let ids = rows
+        .iter()
+        .map(|row| row.product.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![mislabelled_product.as_str(), coarse_product.as_str()]
+    );
+
+    query.area = ReplicaGeoArea::BoundingBox {
+        min_lat: 59.32,
+        min_lng: 18.06,
+        max_lat: 59.34,
+        max_lng: 18.08,
+    };
+    assert_eq!(
+        db.trade_product_geo_search(&query)
+            .expect("bbox search")
+            .len(),
+        2
+    );
+}
+
+fn table_rows(db: &ReplicaSql<SqliteExecutor>, table: &str) -> String {
+    db.executor()
+        .query_raw(&format!("SELECT * FROM {table} ORDER BY 1;"), "[]")
+        .expect("table rows")
+}
+
+#[test]
+fn full_mode_backup_chain_replays_protected_increments() {
+    let db = open_db();
+    let key_dir = tempfile::tempdir().expect("tempdir");
+    let vault = RadrootsProtectedFileKeySource::new(key_dir.path().join("backup.key"));
+    let codec = ProtectedDatabaseBackupCodec::new(&vault, "replica/backup");
+
+    let farm: IFarmCreate = parse_json(json!({
+        "d_tag": "farm-a",
+        "pubkey": hex64('a'),
+        "name": "farm a"
+    }));
+    let farm_id = db.farm_create(&farm).expect("farm create").result.id;
+    record_change(db.executor(), "farm", &farm_id, ReplicaChangeOp::Insert).expect("change");
+    let north = create_geo_location(&db, "north", 59.33, 18.06);
+    let first = create_geo_product(&db, "first", "coffee", &[&north]);
+    record_change(
+        db.executor(),
+        "trade_product",
+        &first,
+        ReplicaChangeOp::Insert,
+    )
+    .expect("change");
+
+    let claim = |member: char| -> IFarmMemberClaimCreate {
+        parse_json(json!({
+            "member_pubkey": hex64(member),
+            "farm_pubkey": hex64('a')
+        }))
+    };
+    db.farm_member_claim_create(&claim('b'))
+        .expect("base claim create");
+
+    let (mut manifest, base) =
+        export_database_backup_base(db.executor(), &codec, "base").expect("base export");
+    assert_eq!(manifest.head_seq(), Some(2));
+    assert!(!base.windows(6).any(|window| window == b"farm a"));
+
+    let farm_tag: IFarmTagCreate = parse_json(json!({ "farm_id": farm_id, "tag": "organic" }));
+    db.farm_tag_create(&farm_tag).expect("farm tag create");
+    db.executor()
+        .exec(
+            "UPDATE farm SET name = 'farm a renamed' WHERE id = ?;",
+            &json!([farm_id]).to_string(),
+        )
+        .expect("farm rename");
+    record_change(db.executor(), "farm", &farm_id, ReplicaChangeOp::Update).expect("change");
+    let south = create_geo_location(&db, "south", 55.60, 13.00);
+    let second = create_geo_product(&db, "second", "tea", &[&south]);
+    record_change(
+        db.executor(),
+        "trade_product",
+        &second,
+        ReplicaChangeOp::Insert,
+    )
+    .expect("change");
+    let new_claim = db
+        .farm_member_claim_create(&claim('c'))
+        .expect("increment claim create")
+        .result
+        .id;
+    record_change(
+        db.executor(),
+        "farm_member_claim",
+        &new_claim,
+        ReplicaChangeOp::Insert,
+    )
+    .expect("change");
+    let pending = export_database_backup_increment(db.executor(), 2).expect("pending increment");
+    let claim_rows = pending
+        .data
+        .iter()
+        .find(|table| table.name == "farm_member_claim")
+        .map(|table| table.rows.len());
+    assert_eq!(claim_rows, Some(1));
+    let daily_one =
+        export_database_backup_increment_part(db.executor(), &codec, &mut manifest, "d1")
+            .expect("first increment");
+
+    let delete: ITradeProductDelete = parse_json(json!({ "on": { "id": first } }));
+    db.trade_product_delete(&delete).expect("delete first");
+    record_change(
+        db.executor(),
+        "trade_product",
+        &first,
+        ReplicaChangeOp::Delete,
+    )
+    .expect("change");
+    let daily_two =
+        export_database_backup_increment_part(db.executor(), &codec, &mut manifest, "d2")
+            .expect("second increment");
+
+    assert_eq!(
+        manifest
+            .parts
+            .iter()
+            .map(|part| (part.kind, part.from_seq, part.to_seq))
+            .collect::<Vec<_>>(),
+        vec![
+            (DatabaseBackupPartKind::Base, 0, 2),
+            (DatabaseBackupPartKind::Increment, 2, 5),
+            (DatabaseBackupPartKind::Increment, 5, 6),
+        ]
+    );
+    let parts: [&[u8]; 3] = [&base, &daily_one, &daily_two];
+    verify_database_backup_chain(&manifest, &parts).expect("verify chain");
+    let sealed_manifest = seal_database_backup_manifest(&codec, &manifest).expect("seal manifest");
+    assert!(!sealed_manifest.windows(2).any(|window| window == b"d1"));
+
+    let restored = ReplicaSql::new(SqliteExecutor::open_memory().expect("open sqlite memory"));
+    restore_database_backup_chain(restored.executor(), &codec, &sealed_manifest, &parts)
+        .expect("restore chain");
+    for table in [
+        "farm",
+        "farm_tag",
+        "gcs_location",
+        "trade_product",
+        "trade_product_location",
+        "farm_member_claim",
+        "replica_change",
+    ] {
+        assert_eq!(
+            table_rows(&restored, table),
+            table_rows(&db, table),
+            "{table}"
+        );
+    }
+    assert_eq!(restored.latest_change_seq().expect("latest seq"), 6);
+
+    let mut tampered = daily_one.clone();
+    let last = tampered.len() - 2;
+    tampered[last] ^= 1;
+    let tampered_parts: [&[u8]; 3] = [&base, &tampered, &daily_two];
+    assert!(matches!(
+        verify_database_backup_chain(&manifest, &tampered_parts),
+        Err(SqlError::InvalidArgument(message)) if message.contains("integrity")
+    ));
+    let reordered: [&[u8]; 3] = [&base, &daily_two, &daily_one];
+    assert!(verify_database_backup_chain(&manifest, &reordered).is_err());
+    assert!(
+        restore_database_backup_chain(
+            restored.executor(),
+            &PlainDatabaseBackupCodec,
+            &sealed_manifest,
+            &parts
+        )
+        .is_err()
+    );
+    let mut tampered_manifest = sealed_manifest.clone();
+    let last = tampered_manifest.len() - 2;
+    tampered_manifest[last] ^= 1;
+    assert!(
+        restore_database_backup_chain(restored.executor(), &codec, &tampered_manifest, &parts)
+            .is_err()
+    );
+    let unsealed_manifest =
+        seal_database_backup_manifest(&PlainDatabaseBackupCodec, &manifest).expect("plain seal");
+    assert!(
+        restore_database_backup_chain(restored.executor(), &codec, &unsealed_manifest, &parts)
+            .is_err()
+    );
+
+    let (mut broken_manifest, plain_base) =
+        export_database_backup_base(db.executor(), &PlainDatabaseBackupCodec, "plain")
+            .expect("plain base");
+    let mut broken = export_database_backup_increment(db.executor(), 6).expect("empty increment");
+    broken.to_seq = 7;
+    broken.data.push(TableData {
+        name: String::from("missing_table"),
+        rows: Vec::new(),
+    });
+    let broken_part = serde_json::to_vec(&broken).expect("broken increment");
+    broken_manifest.parts.push(DatabaseBackupManifestPart {
+        kind: DatabaseBackupPartKind::Increment,
+        name: String::from("broken"),
+        from_seq: 6,
+        to_seq: 7,
+        encoding: broken_manifest.parts[0].encoding,
+        sha256: hex::encode(Sha256::digest(&broken_part)),
+        parent_sha256: Some(broken_manifest.parts[0].sha256.clone()),
+    });
+    let before = table_rows(&restored, "trade_product");
+    restored
+        .executor()
+        .exec("DELETE FROM farm_tag;", "[]")
+        .expect("diverge restored");
+    let diverged = table_rows(&restored, "farm_tag");
+    assert!(
+        restore_database_backup_chain(
+            restored.executor(),
+            &PlainDatabaseBackupCodec,
+            &seal_database_backup_manifest(&PlainDatabaseBackupCodec, &broken_manifest)
+                .expect("seal broken manifest"),
+            &[&plain_base, &broken_part]
+        )
+        .is_err()
+    );
+    assert_eq!(table_rows(&restored, "trade_product"), before);
+    assert_eq!(table_rows(&restored, "farm_tag"), diverged);
+    assert_eq!(restored.latest_change_seq().expect("latest seq"), 6);
+
+    db.executor()
+        .exec("DELETE FROM replica_change WHERE seq <= 6;", "[]")
+        .expect("prune");
+    record_change(db.executor(), "farm", &farm_id, ReplicaChangeOp::Update).expect("change");
+    assert!(
+        export_database_backup_increment_part(db.executor(), &codec, &mut manifest.clone(), "d3")
+            .is_ok()
+    );
+    assert!(matches!(
+        export_database_backup_increment(db.executor(), 2),
+        Err(SqlError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn full_mode_ranked_search_uses_fts_index() {
+    let db = open_db();
+    assert_eq!(
+        db.trade_product_fts_setup().expect("fts setup"),
+        ReplicaTradeProductSearchMode::Fts
+    );
+    let seller = hex64('a');
+    let listing = |d_tag: &str| format!("30402:{seller}:{d_tag}");
+
+    let tomato = create_product(
+        &db,
+        "tomato",
+        "Heirloom Tomato",
+        "vegetables",
+        "vine ripened",
+        Some(&listing("tomato")),
+    );
+    let sauce = create_product(
+        &db,
+        "sauce",
+        "Pasta sauce",
+        "pantry",
+        "slow cooked from ripe tomatoes and basil",
+        Some(&listing("sauce")),
+    );
+    for product_id in [&tomato, &sauce] {
+        let attach_farm: ITradeProductUpdate = parse_json(json!({
+            "on": { "id": product_id },
+            "fields": { "farm_pubkey": seller, "farm_d_tag": "farm-fts" }
+        }));
+        db.trade_product_update(&attach_farm)
+            .expect("attach farm ref");
+    }
+    let query = |terms: &[&str]| ReplicaTradeProductTextQuery {
+        query_terms: terms.iter().map(|term| (*term).to_owned()).collect(),
+        now_unix: 0,
+        limit: None,
+        mode: ReplicaTradeProductSearchMode::Fts,
+    };
+    let ids = |search: &ReplicaTradeProductRankedSearch| {
+        search
+            .rows
+            .iter()
+            .map(|row| row.product.id.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let search = db
+        .trade_product_search_ranked(&query(&["tomatoes"]))
+        .expect("stemmed search");
+    assert_eq!(search.mode, ReplicaTradeProductSearchMode::Fts);
+    assert_eq!(ids(&search), vec![tomato.clone(), sauce.clone()]);
+    assert!(search.rows[0].rank < search.rows[1].rank);
+    assert_eq!(
+        search.rows[0].title_highlight.as_deref(),
+        Some("Heirloom <mark>Tomato</mark>")
+    );
+    assert!(
+        search.rows[1]
+            .snippet
+            .as_deref()
+            .is_some_and(|snippet| snippet.contains("<mark>tomatoes</mark>"))
+    );
+
+    let search = db
+        .trade_product_search_ranked(&query(&["heirl"]))
+        .expect("prefix search");
+    assert_eq!(ids(&search), vec![tomato.clone()]);
+
+    let other_farm: IFarmCreate = parse_json(json!({
+        "d_tag": "a-orchard",
+        "pubkey": seller,
+        "name": "Orchard Hill"
+    }));
+    db.farm_create(&other_farm).expect("other farm create");
+    let farm: IFarmCreate = parse_json(json!({
+        "d_tag": "farm-fts",
+        "pubkey": seller,
+        "name": "Sunny Acres"
+    }));
+    let farm_id = db.farm_create(&farm).expect("farm create").result.id;
+    assert!(
+        db.trade_product_search_ranked(&query(&["orchard"]))
+            .expect("other farm search")
+            .rows
+            .is_empty()
+    );
+    let search = db
+        .trade_product_search_ranked(&query(&["sunny"]))
+        .expect("farm name search");
+    let mut farm_ids = ids(&search);
+    farm_ids.sort();
+    let mut expected = vec![sauce.clone(), tomato.clone()];
+    expected.sort();
+    assert_eq!(farm_ids, expected);
+    assert!(
+        search
+            .rows
+            .iter()
+            .all(|row| row.farm_name.as_deref() == Some("Sunny Acres"))
+    );
+
+    let farm_update: IFarmUpdate =
+        parse_json(json!({ "on": { "id": farm_id }, "fields": { "name": "Meadow Farm" } }));
+    db.farm_update(&farm_update).expect("farm update");
+    assert!(
+        db.trade_product_search_ranked(&query(&["sunny"]))
+            .expect("stale farm name")
+            .rows
+            .is_empty()
+    );
+
+    let eggs = create_product(&db, "eggs", "Pasture eggs", "dairy", "free range", None);
+    let delete: ITradeProductDelete = parse_json(json!({ "on": { "id": sauce } }));
+    db.trade_product_delete(&delete).expect("delete sauce");
+    let search = db
+        .trade_product_search_ranked(&query(&["tomato"]))
+        .expect("after delete");
+    assert_eq!(ids(&search), vec![tomato.clone()]);
+    let search = db
+        .trade_product_search_ranked(&ReplicaTradeProductTextQuery {
+            limit: Some(1),
+            ..query(&["meadow eggs"])
+        })
+        .expect("multi term search");
+    assert!(search.rows.is_empty());
+    let search = db
+        .trade_product_search_ranked(&query(&["pasture", "\"eggs"]))
+        .expect("quoted search");
+    assert_eq!(ids(&search), vec![eggs]);
+
+    let backup = db.backup_database().expect("backup");
+    assert!(
+        backup
+            .schema
+            .iter()
+            .all(|entry| !entry.name.starts_with("trade_product_fts"))
+    );
+    db.restore_database(&backup).expect("restore");
+    let search = db
+        .trade_product_search_ranked(&query(&["meadow"]))
+        .expect("rebuilt search");
+    assert_eq!(ids(&search), vec![tomato]);
+
+    let like = db
+        .trade_product_search_ranked(&ReplicaTradeProductTextQuery {
+            mode: ReplicaTradeProductSearchMode::Like,
+            ..query(&["ripened"])
+        })
+        .expect("like search");
+    assert_eq!(like.mode, ReplicaTradeProductSearchMode::Like);
+    assert_eq!(like.rows.len(), 1);
+    assert_eq!(like.rows[0].snippet, None);
+    assert!(like.rows[0].rank < 0.0);
+}
+
 #[test]
 fn full_mode_crud_and_relation_paths() {
     let db = open_db();
@@ -0,0 +1,138 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct Coop {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub d_tag: String,
+    pub pubkey: String,
+    pub name: String,
+    pub about: Option<String>,
+    pub website: Option<String>,
+    pub picture: Option<String>,
+    pub banner: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopFields {
+    pub d_tag: String,
+    pub pubkey: String,
+    pub name: String,
+    pub about: Option<String>,
+    pub website: Option<String>,
+    pub picture: Option<String>,
+    pub banner: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopFieldsPartial {
+    pub d_tag: Option<serde_json::Value>,
+    pub pubkey: Option<serde_json::Value>,
+    pub name: Option<serde_json::Value>,
+    pub about: Option<serde_json::Value>,
+    pub website: Option<serde_json::Value>,
+    pub picture: Option<serde_json::Value>,
+    pub banner: Option<serde_json::Value>,
+    pub location_primary: Option<serde_json::Value>,
+    pub location_city: Option<serde_json::Value>,
+    pub location_region: Option<serde_json::Value>,
+    pub location_country: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub d_tag: Option<String>,
+    pub pubkey: Option<String>,
+    pub name: Option<String>,
+    pub about: Option<String>,
+    pub website: Option<String>,
+    pub picture: Option<String>,
+    pub banner: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CoopQueryBindValues {
+    Id { id: String },
+    DTag { d_tag: String },
+    Pubkey { pubkey: String },
+}
+impl CoopQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::DTag { d_tag } => ("d_tag", Value::from(d_tag.clone())),
+            Self::Pubkey { pubkey } => ("pubkey", Value::from(pubkey.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::DTag { d_tag } => d_tag.clone(),
+            Self::Pubkey { pubkey } => pubkey.clone(),
+        }
+    }
+}
+
+pub struct ICoopCreateTs;
+pub type ICoopCreate = ICoopFields;
+pub struct ICoopCreateResolveTs;
+pub type ICoopCreateResolve = IResult<Coop>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopFindOneArgs {
+    pub on: CoopQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ICoopFindOne {
+    On(ICoopFindOneArgs),
+}
+
+pub struct ICoopFindOneResolveTs;
+pub type ICoopFindOneResolve = IResult<Option<Coop>>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopFindManyArgs {
+    pub filter: Option<ICoopFieldsFilter>,
+}
+pub type ICoopFindMany = ICoopFindManyArgs;
+pub struct ICoopFindManyResolveTs;
+pub type ICoopFindManyResolve = IResultList<Coop>;
+pub struct ICoopDeleteTs;
+pub type ICoopDelete = ICoopFindOne;
+pub struct ICoopDeleteResolveTs;
+pub type ICoopDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopUpdateArgs {
+    pub on: CoopQueryBindValues,
+    pub fields: ICoopFieldsPartial,
+}
+pub type ICoopUpdate = ICoopUpdateArgs;
+pub struct ICoopUpdateResolveTs;
+pub type ICoopUpdateResolve = IResult<Coop>;
@@ -0,0 +1,108 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct CoopGcsLocation {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub coop_id: String,
+    pub gcs_location_id: String,
+    pub role: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopGcsLocationFields {
+    pub coop_id: String,
+    pub gcs_location_id: String,
+    pub role: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopGcsLocationFieldsPartial {
+    pub coop_id: Option<serde_json::Value>,
+    pub gcs_location_id: Option<serde_json::Value>,
+    pub role: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopGcsLocationFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub coop_id: Option<String>,
+    pub gcs_location_id: Option<String>,
+    pub role: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CoopGcsLocationQueryBindValues {
+    Id { id: String },
+    CoopId { coop_id: String },
+    GcsLocationId { gcs_location_id: String },
+}
+impl CoopGcsLocationQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::CoopId { coop_id } => ("coop_id", Value::from(coop_id.clone())),
+            Self::GcsLocationId { gcs_location_id } => {
+                ("gcs_location_id", Value::from(gcs_location_id.clone()))
+            }
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::CoopId { coop_id } => coop_id.clone(),
+            Self::GcsLocationId { gcs_location_id } => gcs_location_id.clone(),
+        }
+    }
+}
+
+pub struct ICoopGcsLocationCreateTs;
+pub type ICoopGcsLocationCreate = ICoopGcsLocationFields;
+pub struct ICoopGcsLocationCreateResolveTs;
+pub type ICoopGcsLocationCreateResolve = IResult<CoopGcsLocation>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopGcsLocationFindOneArgs {
+    pub on: CoopGcsLocationQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ICoopGcsLocationFindOne {
+    On(ICoopGcsLocationFindOneArgs),
+}
+
+pub struct ICoopGcsLocationFindOneResolveTs;
+pub type ICoopGcsLocationFindOneResolve = IResult<Option<CoopGcsLocation>>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopGcsLocationFindManyArgs {
+    pub filter: Option<ICoopGcsLocationFieldsFilter>,
+}
+pub type ICoopGcsLocationFindMany = ICoopGcsLocationFindManyArgs;
+pub struct ICoopGcsLocationFindManyResolveTs;
+pub type ICoopGcsLocationFindManyResolve = IResultList<CoopGcsLocation>;
+pub struct ICoopGcsLocationDeleteTs;
+pub type ICoopGcsLocationDelete = ICoopGcsLocationFindOne;
+pub struct ICoopGcsLocationDeleteResolveTs;
+pub type ICoopGcsLocationDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopGcsLocationUpdateArgs {
+    pub on: CoopGcsLocationQueryBindValues,
+    pub fields: ICoopGcsLocationFieldsPartial,
+}
+pub type ICoopGcsLocationUpdate = ICoopGcsLocationUpdateArgs;
+pub struct ICoopGcsLocationUpdateResolveTs;
+pub type ICoopGcsLocationUpdateResolve = IResult<CoopGcsLocation>;
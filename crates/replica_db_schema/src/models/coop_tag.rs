@@ -0,0 +1,102 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct CoopTag {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub coop_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopTagFields {
+    pub coop_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopTagFieldsPartial {
+    pub coop_id: Option<serde_json::Value>,
+    pub tag: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ICoopTagFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub coop_id: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum CoopTagQueryBindValues {
+    Id { id: String },
+    CoopId { coop_id: String },
+    Tag { tag: String },
+}
+impl CoopTagQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::CoopId { coop_id } => ("coop_id", Value::from(coop_id.clone())),
+            Self::Tag { tag } => ("tag", Value::from(tag.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::CoopId { coop_id } => coop_id.clone(),
+            Self::Tag { tag } => tag.clone(),
+        }
+    }
+}
+
+pub struct ICoopTagCreateTs;
+pub type ICoopTagCreate = ICoopTagFields;
+pub struct ICoopTagCreateResolveTs;
+pub type ICoopTagCreateResolve = IResult<CoopTag>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopTagFindOneArgs {
+    pub on: CoopTagQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ICoopTagFindOne {
+    On(ICoopTagFindOneArgs),
+}
+
+pub struct ICoopTagFindOneResolveTs;
+pub type ICoopTagFindOneResolve = IResult<Option<CoopTag>>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopTagFindManyArgs {
+    pub filter: Option<ICoopTagFieldsFilter>,
+}
+pub type ICoopTagFindMany = ICoopTagFindManyArgs;
+pub struct ICoopTagFindManyResolveTs;
+pub type ICoopTagFindManyResolve = IResultList<CoopTag>;
+pub struct ICoopTagDeleteTs;
+pub type ICoopTagDelete = ICoopTagFindOne;
+pub struct ICoopTagDeleteResolveTs;
+pub type ICoopTagDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ICoopTagUpdateArgs {
+    pub on: CoopTagQueryBindValues,
+    pub fields: ICoopTagFieldsPartial,
+}
+pub type ICoopTagUpdate = ICoopTagUpdateArgs;
+pub struct ICoopTagUpdateResolveTs;
+pub type ICoopTagUpdateResolve = IResult<CoopTag>;
@@ -0,0 +1,144 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct Document {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub d_tag: String,
+    pub pubkey: String,
+    pub doc_type: String,
+    pub title: String,
+    pub version: String,
+    pub summary: Option<String>,
+    pub effective_at: Option<u32>,
+    pub body_markdown: Option<String>,
+    pub subject_pubkey: String,
+    pub subject_address: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentFields {
+    pub d_tag: String,
+    pub pubkey: String,
+    pub doc_type: String,
+    pub title: String,
+    pub version: String,
+    pub summary: Option<String>,
+    pub effective_at: Option<u32>,
+    pub body_markdown: Option<String>,
+    pub subject_pubkey: String,
+    pub subject_address: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentFieldsPartial {
+    pub d_tag: Option<serde_json::Value>,
+    pub pubkey: Option<serde_json::Value>,
+    pub doc_type: Option<serde_json::Value>,
+    pub title: Option<serde_json::Value>,
+    pub version: Option<serde_json::Value>,
+    pub summary: Option<serde_json::Value>,
+    pub effective_at: Option<serde_json::Value>,
+    pub body_markdown: Option<serde_json::Value>,
+    pub subject_pubkey: Option<serde_json::Value>,
+    pub subject_address: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub d_tag: Option<String>,
+    pub pubkey: Option<String>,
+    pub doc_type: Option<String>,
+    pub title: Option<String>,
+    pub version: Option<String>,
+    pub summary: Option<String>,
+    pub effective_at: Option<u32>,
+    pub body_markdown: Option<String>,
+    pub subject_pubkey: Option<String>,
+    pub subject_address: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum DocumentQueryBindValues {
+    Id { id: String },
+    DTag { d_tag: String },
+    Pubkey { pubkey: String },
+    SubjectPubkey { subject_pubkey: String },
+    SubjectAddress { subject_address: String },
+}
+impl DocumentQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::DTag { d_tag } => ("d_tag", Value::from(d_tag.clone())),
+            Self::Pubkey { pubkey } => ("pubkey", Value::from(pubkey.clone())),
+            Self::SubjectPubkey { subject_pubkey } => {
+                ("subject_pubkey", Value::from(subject_pubkey.clone()))
+            }
+            Self::SubjectAddress { subject_address } => {
+                ("subject_address", Value::from(subject_address.clone()))
+            }
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::DTag { d_tag } => d_tag.clone(),
+            Self::Pubkey { pubkey } => pubkey.clone(),
+            Self::SubjectPubkey { subject_pubkey } => subject_pubkey.clone(),
+            Self::SubjectAddress { subject_address } => subject_address.clone(),
+        }
+    }
+}
+
+pub struct IDocumentCreateTs;
+pub type IDocumentCreate = IDocumentFields;
+pub struct IDocumentCreateResolveTs;
+pub type IDocumentCreateResolve = IResult<Document>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentFindOneArgs {
+    pub on: DocumentQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IDocumentFindOne {
+    On(IDocumentFindOneArgs),
+}
+
+pub struct IDocumentFindOneResolveTs;
+pub type IDocumentFindOneResolve = IResult<Option<Document>>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentFindManyArgs {
+    pub filter: Option<IDocumentFieldsFilter>,
+}
+pub type IDocumentFindMany = IDocumentFindManyArgs;
+pub struct IDocumentFindManyResolveTs;
+pub type IDocumentFindManyResolve = IResultList<Document>;
+pub struct IDocumentDeleteTs;
+pub type IDocumentDelete = IDocumentFindOne;
+pub struct IDocumentDeleteResolveTs;
+pub type IDocumentDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentUpdateArgs {
+    pub on: DocumentQueryBindValues,
+    pub fields: IDocumentFieldsPartial,
+}
+pub type IDocumentUpdate = IDocumentUpdateArgs;
+pub struct IDocumentUpdateResolveTs;
+pub type IDocumentUpdateResolve = IResult<Document>;
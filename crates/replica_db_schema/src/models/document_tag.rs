@@ -0,0 +1,102 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct DocumentTag {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub document_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentTagFields {
+    pub document_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentTagFieldsPartial {
+    pub document_id: Option<serde_json::Value>,
+    pub tag: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IDocumentTagFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub document_id: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum DocumentTagQueryBindValues {
+    Id { id: String },
+    DocumentId { document_id: String },
+    Tag { tag: String },
+}
+impl DocumentTagQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::DocumentId { document_id } => ("document_id", Value::from(document_id.clone())),
+            Self::Tag { tag } => ("tag", Value::from(tag.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::DocumentId { document_id } => document_id.clone(),
+            Self::Tag { tag } => tag.clone(),
+        }
+    }
+}
+
+pub struct IDocumentTagCreateTs;
+pub type IDocumentTagCreate = IDocumentTagFields;
+pub struct IDocumentTagCreateResolveTs;
+pub type IDocumentTagCreateResolve = IResult<DocumentTag>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentTagFindOneArgs {
+    pub on: DocumentTagQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IDocumentTagFindOne {
+    On(IDocumentTagFindOneArgs),
+}
+
+pub struct IDocumentTagFindOneResolveTs;
+pub type IDocumentTagFindOneResolve = IResult<Option<DocumentTag>>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentTagFindManyArgs {
+    pub filter: Option<IDocumentTagFieldsFilter>,
+}
+pub type IDocumentTagFindMany = IDocumentTagFindManyArgs;
+pub struct IDocumentTagFindManyResolveTs;
+pub type IDocumentTagFindManyResolve = IResultList<DocumentTag>;
+pub struct IDocumentTagDeleteTs;
+pub type IDocumentTagDelete = IDocumentTagFindOne;
+pub struct IDocumentTagDeleteResolveTs;
+pub type IDocumentTagDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IDocumentTagUpdateArgs {
+    pub on: DocumentTagQueryBindValues,
+    pub fields: IDocumentTagFieldsPartial,
+}
+pub type IDocumentTagUpdate = IDocumentTagUpdateArgs;
+pub struct IDocumentTagUpdateResolveTs;
+pub type IDocumentTagUpdateResolve = IResult<DocumentTag>;
@@ -0,0 +1,126 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct ResourceArea {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub d_tag: String,
+    pub pubkey: String,
+    pub name: String,
+    pub about: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaFields {
+    pub d_tag: String,
+    pub pubkey: String,
+    pub name: String,
+    pub about: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaFieldsPartial {
+    pub d_tag: Option<serde_json::Value>,
+    pub pubkey: Option<serde_json::Value>,
+    pub name: Option<serde_json::Value>,
+    pub about: Option<serde_json::Value>,
+    pub location_primary: Option<serde_json::Value>,
+    pub location_city: Option<serde_json::Value>,
+    pub location_region: Option<serde_json::Value>,
+    pub location_country: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub d_tag: Option<String>,
+    pub pubkey: Option<String>,
+    pub name: Option<String>,
+    pub about: Option<String>,
+    pub location_primary: Option<String>,
+    pub location_city: Option<String>,
+    pub location_region: Option<String>,
+    pub location_country: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ResourceAreaQueryBindValues {
+    Id { id: String },
+    DTag { d_tag: String },
+    Pubkey { pubkey: String },
+}
+impl ResourceAreaQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::DTag { d_tag } => ("d_tag", Value::from(d_tag.clone())),
+            Self::Pubkey { pubkey } => ("pubkey", Value::from(pubkey.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::DTag { d_tag } => d_tag.clone(),
+            Self::Pubkey { pubkey } => pubkey.clone(),
+        }
+    }
+}
+
+pub struct IResourceAreaCreateTs;
+pub type IResourceAreaCreate = IResourceAreaFields;
+pub struct IResourceAreaCreateResolveTs;
+pub type IResourceAreaCreateResolve = IResult<ResourceArea>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaFindOneArgs {
+    pub on: ResourceAreaQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IResourceAreaFindOne {
+    On(IResourceAreaFindOneArgs),
+}
+
+pub struct IResourceAreaFindOneResolveTs;
+pub type IResourceAreaFindOneResolve = IResult<Option<ResourceArea>>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaFindManyArgs {
+    pub filter: Option<IResourceAreaFieldsFilter>,
+}
+pub type IResourceAreaFindMany = IResourceAreaFindManyArgs;
+pub struct IResourceAreaFindManyResolveTs;
+pub type IResourceAreaFindManyResolve = IResultList<ResourceArea>;
+pub struct IResourceAreaDeleteTs;
+pub type IResourceAreaDelete = IResourceAreaFindOne;
+pub struct IResourceAreaDeleteResolveTs;
+pub type IResourceAreaDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaUpdateArgs {
+    pub on: ResourceAreaQueryBindValues,
+    pub fields: IResourceAreaFieldsPartial,
+}
+pub type IResourceAreaUpdate = IResourceAreaUpdateArgs;
+pub struct IResourceAreaUpdateResolveTs;
+pub type IResourceAreaUpdateResolve = IResult<ResourceArea>;
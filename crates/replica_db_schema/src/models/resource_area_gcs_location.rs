@@ -0,0 +1,110 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct ResourceAreaGcsLocation {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub resource_area_id: String,
+    pub gcs_location_id: String,
+    pub role: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationFields {
+    pub resource_area_id: String,
+    pub gcs_location_id: String,
+    pub role: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationFieldsPartial {
+    pub resource_area_id: Option<serde_json::Value>,
+    pub gcs_location_id: Option<serde_json::Value>,
+    pub role: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub resource_area_id: Option<String>,
+    pub gcs_location_id: Option<String>,
+    pub role: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ResourceAreaGcsLocationQueryBindValues {
+    Id { id: String },
+    ResourceAreaId { resource_area_id: String },
+    GcsLocationId { gcs_location_id: String },
+}
+impl ResourceAreaGcsLocationQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::ResourceAreaId { resource_area_id } => {
+                ("resource_area_id", Value::from(resource_area_id.clone()))
+            }
+            Self::GcsLocationId { gcs_location_id } => {
+                ("gcs_location_id", Value::from(gcs_location_id.clone()))
+            }
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::ResourceAreaId { resource_area_id } => resource_area_id.clone(),
+            Self::GcsLocationId { gcs_location_id } => gcs_location_id.clone(),
+        }
+    }
+}
+
+pub struct IResourceAreaGcsLocationCreateTs;
+pub type IResourceAreaGcsLocationCreate = IResourceAreaGcsLocationFields;
+pub struct IResourceAreaGcsLocationCreateResolveTs;
+pub type IResourceAreaGcsLocationCreateResolve = IResult<ResourceAreaGcsLocation>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationFindOneArgs {
+    pub on: ResourceAreaGcsLocationQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IResourceAreaGcsLocationFindOne {
+    On(IResourceAreaGcsLocationFindOneArgs),
+}
+
+pub struct IResourceAreaGcsLocationFindOneResolveTs;
+pub type IResourceAreaGcsLocationFindOneResolve = IResult<Option<ResourceAreaGcsLocation>>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationFindManyArgs {
+    pub filter: Option<IResourceAreaGcsLocationFieldsFilter>,
+}
+pub type IResourceAreaGcsLocationFindMany = IResourceAreaGcsLocationFindManyArgs;
+pub struct IResourceAreaGcsLocationFindManyResolveTs;
+pub type IResourceAreaGcsLocationFindManyResolve = IResultList<ResourceAreaGcsLocation>;
+pub struct IResourceAreaGcsLocationDeleteTs;
+pub type IResourceAreaGcsLocationDelete = IResourceAreaGcsLocationFindOne;
+pub struct IResourceAreaGcsLocationDeleteResolveTs;
+pub type IResourceAreaGcsLocationDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaGcsLocationUpdateArgs {
+    pub on: ResourceAreaGcsLocationQueryBindValues,
+    pub fields: IResourceAreaGcsLocationFieldsPartial,
+}
+pub type IResourceAreaGcsLocationUpdate = IResourceAreaGcsLocationUpdateArgs;
+pub struct IResourceAreaGcsLocationUpdateResolveTs;
+pub type IResourceAreaGcsLocationUpdateResolve = IResult<ResourceAreaGcsLocation>;
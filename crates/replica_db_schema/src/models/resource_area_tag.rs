@@ -0,0 +1,104 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct ResourceAreaTag {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub resource_area_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaTagFields {
+    pub resource_area_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaTagFieldsPartial {
+    pub resource_area_id: Option<serde_json::Value>,
+    pub tag: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceAreaTagFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub resource_area_id: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ResourceAreaTagQueryBindValues {
+    Id { id: String },
+    ResourceAreaId { resource_area_id: String },
+    Tag { tag: String },
+}
+impl ResourceAreaTagQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::ResourceAreaId { resource_area_id } => {
+                ("resource_area_id", Value::from(resource_area_id.clone()))
+            }
+            Self::Tag { tag } => ("tag", Value::from(tag.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::ResourceAreaId { resource_area_id } => resource_area_id.clone(),
+            Self::Tag { tag } => tag.clone(),
+        }
+    }
+}
+
+pub struct IResourceAreaTagCreateTs;
+pub type IResourceAreaTagCreate = IResourceAreaTagFields;
+pub struct IResourceAreaTagCreateResolveTs;
+pub type IResourceAreaTagCreateResolve = IResult<ResourceAreaTag>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaTagFindOneArgs {
+    pub on: ResourceAreaTagQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IResourceAreaTagFindOne {
+    On(IResourceAreaTagFindOneArgs),
+}
+
+pub struct IResourceAreaTagFindOneResolveTs;
+pub type IResourceAreaTagFindOneResolve = IResult<Option<ResourceAreaTag>>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaTagFindManyArgs {
+    pub filter: Option<IResourceAreaTagFieldsFilter>,
+}
+pub type IResourceAreaTagFindMany = IResourceAreaTagFindManyArgs;
+pub struct IResourceAreaTagFindManyResolveTs;
+pub type IResourceAreaTagFindManyResolve = IResultList<ResourceAreaTag>;
+pub struct IResourceAreaTagDeleteTs;
+pub type IResourceAreaTagDelete = IResourceAreaTagFindOne;
+pub struct IResourceAreaTagDeleteResolveTs;
+pub type IResourceAreaTagDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceAreaTagUpdateArgs {
+    pub on: ResourceAreaTagQueryBindValues,
+    pub fields: IResourceAreaTagFieldsPartial,
+}
+pub type IResourceAreaTagUpdate = IResourceAreaTagUpdateArgs;
+pub struct IResourceAreaTagUpdateResolveTs;
+pub type IResourceAreaTagUpdateResolve = IResult<ResourceAreaTag>;
@@ -0,0 +1,154 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct ResourceHarvestCap {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub d_tag: String,
+    pub pubkey: String,
+    pub resource_area_id: String,
+    pub product_key: String,
+    pub product_category: Option<String>,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub cap_amount: String,
+    pub cap_unit: String,
+    pub cap_label: Option<String>,
+    pub display_amount: Option<String>,
+    pub display_unit: Option<String>,
+    pub display_label: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapFields {
+    pub d_tag: String,
+    pub pubkey: String,
+    pub resource_area_id: String,
+    pub product_key: String,
+    pub product_category: Option<String>,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub cap_amount: String,
+    pub cap_unit: String,
+    pub cap_label: Option<String>,
+    pub display_amount: Option<String>,
+    pub display_unit: Option<String>,
+    pub display_label: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapFieldsPartial {
+    pub d_tag: Option<serde_json::Value>,
+    pub pubkey: Option<serde_json::Value>,
+    pub resource_area_id: Option<serde_json::Value>,
+    pub product_key: Option<serde_json::Value>,
+    pub product_category: Option<serde_json::Value>,
+    pub start_at: Option<serde_json::Value>,
+    pub end_at: Option<serde_json::Value>,
+    pub cap_amount: Option<serde_json::Value>,
+    pub cap_unit: Option<serde_json::Value>,
+    pub cap_label: Option<serde_json::Value>,
+    pub display_amount: Option<serde_json::Value>,
+    pub display_unit: Option<serde_json::Value>,
+    pub display_label: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub d_tag: Option<String>,
+    pub pubkey: Option<String>,
+    pub resource_area_id: Option<String>,
+    pub product_key: Option<String>,
+    pub product_category: Option<String>,
+    pub start_at: Option<i64>,
+    pub end_at: Option<i64>,
+    pub cap_amount: Option<String>,
+    pub cap_unit: Option<String>,
+    pub cap_label: Option<String>,
+    pub display_amount: Option<String>,
+    pub display_unit: Option<String>,
+    pub display_label: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ResourceHarvestCapQueryBindValues {
+    Id { id: String },
+    DTag { d_tag: String },
+    Pubkey { pubkey: String },
+    ResourceAreaId { resource_area_id: String },
+    ProductKey { product_key: String },
+}
+impl ResourceHarvestCapQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::DTag { d_tag } => ("d_tag", Value::from(d_tag.clone())),
+            Self::Pubkey { pubkey } => ("pubkey", Value::from(pubkey.clone())),
+            Self::ResourceAreaId { resource_area_id } => {
+                ("resource_area_id", Value::from(resource_area_id.clone()))
+            }
+            Self::ProductKey { product_key } => ("product_key", Value::from(product_key.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::DTag { d_tag } => d_tag.clone(),
+            Self::Pubkey { pubkey } => pubkey.clone(),
+            Self::ResourceAreaId { resource_area_id } => resource_area_id.clone(),
+            Self::ProductKey { product_key } => product_key.clone(),
+        }
+    }
+}
+
+pub struct IResourceHarvestCapCreateTs;
+pub type IResourceHarvestCapCreate = IResourceHarvestCapFields;
+pub struct IResourceHarvestCapCreateResolveTs;
+pub type IResourceHarvestCapCreateResolve = IResult<ResourceHarvestCap>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapFindOneArgs {
+    pub on: ResourceHarvestCapQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IResourceHarvestCapFindOne {
+    On(IResourceHarvestCapFindOneArgs),
+}
+
+pub struct IResourceHarvestCapFindOneResolveTs;
+pub type IResourceHarvestCapFindOneResolve = IResult<Option<ResourceHarvestCap>>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapFindManyArgs {
+    pub filter: Option<IResourceHarvestCapFieldsFilter>,
+}
+pub type IResourceHarvestCapFindMany = IResourceHarvestCapFindManyArgs;
+pub struct IResourceHarvestCapFindManyResolveTs;
+pub type IResourceHarvestCapFindManyResolve = IResultList<ResourceHarvestCap>;
+pub struct IResourceHarvestCapDeleteTs;
+pub type IResourceHarvestCapDelete = IResourceHarvestCapFindOne;
+pub struct IResourceHarvestCapDeleteResolveTs;
+pub type IResourceHarvestCapDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapUpdateArgs {
+    pub on: ResourceHarvestCapQueryBindValues,
+    pub fields: IResourceHarvestCapFieldsPartial,
+}
+pub type IResourceHarvestCapUpdate = IResourceHarvestCapUpdateArgs;
+pub struct IResourceHarvestCapUpdateResolveTs;
+pub type IResourceHarvestCapUpdateResolve = IResult<ResourceHarvestCap>;
@@ -0,0 +1,109 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct ResourceHarvestCapTag {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub resource_harvest_cap_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagFields {
+    pub resource_harvest_cap_id: String,
+    pub tag: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagFieldsPartial {
+    pub resource_harvest_cap_id: Option<serde_json::Value>,
+    pub tag: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub resource_harvest_cap_id: Option<String>,
+    pub tag: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ResourceHarvestCapTagQueryBindValues {
+    Id { id: String },
+    ResourceHarvestCapId { resource_harvest_cap_id: String },
+    Tag { tag: String },
+}
+impl ResourceHarvestCapTagQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::ResourceHarvestCapId {
+                resource_harvest_cap_id,
+            } => (
+                "resource_harvest_cap_id",
+                Value::from(resource_harvest_cap_id.clone()),
+            ),
+            Self::Tag { tag } => ("tag", Value::from(tag.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::ResourceHarvestCapId {
+                resource_harvest_cap_id,
+            } => resource_harvest_cap_id.clone(),
+            Self::Tag { tag } => tag.clone(),
+        }
+    }
+}
+
+pub struct IResourceHarvestCapTagCreateTs;
+pub type IResourceHarvestCapTagCreate = IResourceHarvestCapTagFields;
+pub struct IResourceHarvestCapTagCreateResolveTs;
+pub type IResourceHarvestCapTagCreateResolve = IResult<ResourceHarvestCapTag>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagFindOneArgs {
+    pub on: ResourceHarvestCapTagQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IResourceHarvestCapTagFindOne {
+    On(IResourceHarvestCapTagFindOneArgs),
+}
+
+pub struct IResourceHarvestCapTagFindOneResolveTs;
+pub type IResourceHarvestCapTagFindOneResolve = IResult<Option<ResourceHarvestCapTag>>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagFindManyArgs {
+    pub filter: Option<IResourceHarvestCapTagFieldsFilter>,
+}
+pub type IResourceHarvestCapTagFindMany = IResourceHarvestCapTagFindManyArgs;
+pub struct IResourceHarvestCapTagFindManyResolveTs;
+pub type IResourceHarvestCapTagFindManyResolve = IResultList<ResourceHarvestCapTag>;
+pub struct IResourceHarvestCapTagDeleteTs;
+pub type IResourceHarvestCapTagDelete = IResourceHarvestCapTagFindOne;
+pub struct IResourceHarvestCapTagDeleteResolveTs;
+pub type IResourceHarvestCapTagDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct IResourceHarvestCapTagUpdateArgs {
+    pub on: ResourceHarvestCapTagQueryBindValues,
+    pub fields: IResourceHarvestCapTagFieldsPartial,
+}
+pub type IResourceHarvestCapTagUpdate = IResourceHarvestCapTagUpdateArgs;
+pub struct IResourceHarvestCapTagUpdateResolveTs;
+pub type IResourceHarvestCapTagUpdateResolve = IResult<ResourceHarvestCapTag>;
@@ -0,0 +1,169 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradeOrder {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub order_id: String,
+    pub status: String,
+    pub listing_addr: Option<String>,
+    pub buyer_pubkey: Option<String>,
+    pub seller_pubkey: Option<String>,
+    pub request_event_id: Option<String>,
+    pub decision_event_id: Option<String>,
+    pub agreement_event_id: Option<String>,
+    pub fulfillment_event_id: Option<String>,
+    pub fulfillment_status: Option<String>,
+    pub cancellation_event_id: Option<String>,
+    pub receipt_event_id: Option<String>,
+    pub last_event_id: Option<String>,
+    pub last_event_at: Option<u32>,
+    pub issue_count: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderFields {
+    pub order_id: String,
+    pub status: String,
+    pub listing_addr: Option<String>,
+    pub buyer_pubkey: Option<String>,
+    pub seller_pubkey: Option<String>,
+    pub request_event_id: Option<String>,
+    pub decision_event_id: Option<String>,
+    pub agreement_event_id: Option<String>,
+    pub fulfillment_event_id: Option<String>,
+    pub fulfillment_status: Option<String>,
+    pub cancellation_event_id: Option<String>,
+    pub receipt_event_id: Option<String>,
+    pub last_event_id: Option<String>,
+    pub last_event_at: Option<u32>,
+    pub issue_count: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderFieldsPartial {
+    pub order_id: Option<serde_json::Value>,
+    pub status: Option<serde_json::Value>,
+    pub listing_addr: Option<serde_json::Value>,
+    pub buyer_pubkey: Option<serde_json::Value>,
+    pub seller_pubkey: Option<serde_json::Value>,
+    pub request_event_id: Option<serde_json::Value>,
+    pub decision_event_id: Option<serde_json::Value>,
+    pub agreement_event_id: Option<serde_json::Value>,
+    pub fulfillment_event_id: Option<serde_json::Value>,
+    pub fulfillment_status: Option<serde_json::Value>,
+    pub cancellation_event_id: Option<serde_json::Value>,
+    pub receipt_event_id: Option<serde_json::Value>,
+    pub last_event_id: Option<serde_json::Value>,
+    pub last_event_at: Option<serde_json::Value>,
+    pub issue_count: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub order_id: Option<String>,
+    pub status: Option<String>,
+    pub listing_addr: Option<String>,
+    pub buyer_pubkey: Option<String>,
+    pub seller_pubkey: Option<String>,
+    pub request_event_id: Option<String>,
+    pub decision_event_id: Option<String>,
+    pub agreement_event_id: Option<String>,
+    pub fulfillment_event_id: Option<String>,
+    pub fulfillment_status: Option<String>,
+    pub cancellation_event_id: Option<String>,
+    pub receipt_event_id: Option<String>,
+    pub last_event_id: Option<String>,
+    pub last_event_at: Option<u32>,
+    pub issue_count: Option<u32>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TradeOrderQueryBindValues {
+    Id { id: String },
+    OrderId { order_id: String },
+    Status { status: String },
+    ListingAddr { listing_addr: String },
+    BuyerPubkey { buyer_pubkey: String },
+    SellerPubkey { seller_pubkey: String },
+}
+impl TradeOrderQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::OrderId { order_id } => ("order_id", Value::from(order_id.clone())),
+            Self::Status { status } => ("status", Value::from(status.clone())),
+            Self::ListingAddr { listing_addr } => {
+                ("listing_addr", Value::from(listing_addr.clone()))
+            }
+            Self::BuyerPubkey { buyer_pubkey } => {
+                ("buyer_pubkey", Value::from(buyer_pubkey.clone()))
+            }
+            Self::SellerPubkey { seller_pubkey } => {
+                ("seller_pubkey", Value::from(seller_pubkey.clone()))
+            }
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::OrderId { order_id } => order_id.clone(),
+            Self::Status { status } => status.clone(),
+            Self::ListingAddr { listing_addr } => listing_addr.clone(),
+            Self::BuyerPubkey { buyer_pubkey } => buyer_pubkey.clone(),
+            Self::SellerPubkey { seller_pubkey } => seller_pubkey.clone(),
+        }
+    }
+}
+
+pub struct ITradeOrderCreateTs;
+pub type ITradeOrderCreate = ITradeOrderFields;
+pub struct ITradeOrderCreateResolveTs;
+pub type ITradeOrderCreateResolve = IResult<TradeOrder>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderFindOneArgs {
+    pub on: TradeOrderQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ITradeOrderFindOne {
+    On(ITradeOrderFindOneArgs),
+}
+
+pub struct ITradeOrderFindOneResolveTs;
+pub type ITradeOrderFindOneResolve = IResult<Option<TradeOrder>>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderFindManyArgs {
+    pub filter: Option<ITradeOrderFieldsFilter>,
+}
+pub type ITradeOrderFindMany = ITradeOrderFindManyArgs;
+pub struct ITradeOrderFindManyResolveTs;
+pub type ITradeOrderFindManyResolve = IResultList<TradeOrder>;
+pub struct ITradeOrderDeleteTs;
+pub type ITradeOrderDelete = ITradeOrderFindOne;
+pub struct ITradeOrderDeleteResolveTs;
+pub type ITradeOrderDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderUpdateArgs {
+    pub on: TradeOrderQueryBindValues,
+    pub fields: ITradeOrderFieldsPartial,
+}
+pub type ITradeOrderUpdate = ITradeOrderUpdateArgs;
+pub struct ITradeOrderUpdateResolveTs;
+pub type ITradeOrderUpdateResolve = IResult<TradeOrder>;
@@ -0,0 +1,125 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradeOrderEvent {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub event_id: String,
+    pub order_id: String,
+    pub kind: u32,
+    pub pubkey: String,
+    pub event_created_at: u32,
+    pub tags: String,
+    pub content: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderEventFields {
+    pub event_id: String,
+    pub order_id: String,
+    pub kind: u32,
+    pub pubkey: String,
+    pub event_created_at: u32,
+    pub tags: String,
+    pub content: String,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderEventFieldsPartial {
+    pub event_id: Option<serde_json::Value>,
+    pub order_id: Option<serde_json::Value>,
+    pub kind: Option<serde_json::Value>,
+    pub pubkey: Option<serde_json::Value>,
+    pub event_created_at: Option<serde_json::Value>,
+    pub tags: Option<serde_json::Value>,
+    pub content: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderEventFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub event_id: Option<String>,
+    pub order_id: Option<String>,
+    pub kind: Option<u32>,
+    pub pubkey: Option<String>,
+    pub event_created_at: Option<u32>,
+    pub tags: Option<String>,
+    pub content: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TradeOrderEventQueryBindValues {
+    Id { id: String },
+    EventId { event_id: String },
+    OrderId { order_id: String },
+    Pubkey { pubkey: String },
+}
+impl TradeOrderEventQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::EventId { event_id } => ("event_id", Value::from(event_id.clone())),
+            Self::OrderId { order_id } => ("order_id", Value::from(order_id.clone())),
+            Self::Pubkey { pubkey } => ("pubkey", Value::from(pubkey.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::EventId { event_id } => event_id.clone(),
+            Self::OrderId { order_id } => order_id.clone(),
+            Self::Pubkey { pubkey } => pubkey.clone(),
+        }
+    }
+}
+
+pub struct ITradeOrderEventCreateTs;
+pub type ITradeOrderEventCreate = ITradeOrderEventFields;
+pub struct ITradeOrderEventCreateResolveTs;
+pub type ITradeOrderEventCreateResolve = IResult<TradeOrderEvent>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderEventFindOneArgs {
+    pub on: TradeOrderEventQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ITradeOrderEventFindOne {
+    On(ITradeOrderEventFindOneArgs),
+}
+
+pub struct ITradeOrderEventFindOneResolveTs;
+pub type ITradeOrderEventFindOneResolve = IResult<Option<TradeOrderEvent>>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderEventFindManyArgs {
+    pub filter: Option<ITradeOrderEventFieldsFilter>,
+}
+pub type ITradeOrderEventFindMany = ITradeOrderEventFindManyArgs;
+pub struct ITradeOrderEventFindManyResolveTs;
+pub type ITradeOrderEventFindManyResolve = IResultList<TradeOrderEvent>;
+pub struct ITradeOrderEventDeleteTs;
+pub type ITradeOrderEventDelete = ITradeOrderEventFindOne;
+pub struct ITradeOrderEventDeleteResolveTs;
+pub type ITradeOrderEventDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderEventUpdateArgs {
+    pub on: TradeOrderEventQueryBindValues,
+    pub fields: ITradeOrderEventFieldsPartial,
+}
+pub type ITradeOrderEventUpdate = ITradeOrderEventUpdateArgs;
+pub struct ITradeOrderEventUpdateResolveTs;
+pub type ITradeOrderEventUpdateResolve = IResult<TradeOrderEvent>;
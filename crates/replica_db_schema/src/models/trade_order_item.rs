@@ -0,0 +1,112 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradeOrderItem {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub trade_order_id: String,
+    pub position: u32,
+    pub bin_id: String,
+    pub bin_count: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderItemFields {
+    pub trade_order_id: String,
+    pub position: u32,
+    pub bin_id: String,
+    pub bin_count: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderItemFieldsPartial {
+    pub trade_order_id: Option<serde_json::Value>,
+    pub position: Option<serde_json::Value>,
+    pub bin_id: Option<serde_json::Value>,
+    pub bin_count: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderItemFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub trade_order_id: Option<String>,
+    pub position: Option<u32>,
+    pub bin_id: Option<String>,
+    pub bin_count: Option<u32>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TradeOrderItemQueryBindValues {
+    Id { id: String },
+    TradeOrderId { trade_order_id: String },
+    BinId { bin_id: String },
+}
+impl TradeOrderItemQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::TradeOrderId { trade_order_id } => {
+                ("trade_order_id", Value::from(trade_order_id.clone()))
+            }
+            Self::BinId { bin_id } => ("bin_id", Value::from(bin_id.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::TradeOrderId { trade_order_id } => trade_order_id.clone(),
+            Self::BinId { bin_id } => bin_id.clone(),
+        }
+    }
+}
+
+pub struct ITradeOrderItemCreateTs;
+pub type ITradeOrderItemCreate = ITradeOrderItemFields;
+pub struct ITradeOrderItemCreateResolveTs;
+pub type ITradeOrderItemCreateResolve = IResult<TradeOrderItem>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderItemFindOneArgs {
+    pub on: TradeOrderItemQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ITradeOrderItemFindOne {
+    On(ITradeOrderItemFindOneArgs),
+}
+
+pub struct ITradeOrderItemFindOneResolveTs;
+pub type ITradeOrderItemFindOneResolve = IResult<Option<TradeOrderItem>>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderItemFindManyArgs {
+    pub filter: Option<ITradeOrderItemFieldsFilter>,
+}
+pub type ITradeOrderItemFindMany = ITradeOrderItemFindManyArgs;
+pub struct ITradeOrderItemFindManyResolveTs;
+pub type ITradeOrderItemFindManyResolve = IResultList<TradeOrderItem>;
+pub struct ITradeOrderItemDeleteTs;
+pub type ITradeOrderItemDelete = ITradeOrderItemFindOne;
+pub struct ITradeOrderItemDeleteResolveTs;
+pub type ITradeOrderItemDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderItemUpdateArgs {
+    pub on: TradeOrderItemQueryBindValues,
+    pub fields: ITradeOrderItemFieldsPartial,
+}
+pub type ITradeOrderItemUpdate = ITradeOrderItemUpdateArgs;
+pub struct ITradeOrderItemUpdateResolveTs;
+pub type ITradeOrderItemUpdateResolve = IResult<TradeOrderItem>;
@@ -0,0 +1,153 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradeOrderPayment {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub trade_order_id: String,
+    pub state: String,
+    pub settlement_state: String,
+    pub payment_event_id: Option<String>,
+    pub settlement_event_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub quote_version: Option<u32>,
+    pub amount: Option<String>,
+    pub currency: Option<String>,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub paid_at: Option<i64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderPaymentFields {
+    pub trade_order_id: String,
+    pub state: String,
+    pub settlement_state: String,
+    pub payment_event_id: Option<String>,
+    pub settlement_event_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub quote_version: Option<u32>,
+    pub amount: Option<String>,
+    pub currency: Option<String>,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub paid_at: Option<i64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderPaymentFieldsPartial {
+    pub trade_order_id: Option<serde_json::Value>,
+    pub state: Option<serde_json::Value>,
+    pub settlement_state: Option<serde_json::Value>,
+    pub payment_event_id: Option<serde_json::Value>,
+    pub settlement_event_id: Option<serde_json::Value>,
+    pub quote_id: Option<serde_json::Value>,
+    pub quote_version: Option<serde_json::Value>,
+    pub amount: Option<serde_json::Value>,
+    pub currency: Option<serde_json::Value>,
+    pub method: Option<serde_json::Value>,
+    pub reference: Option<serde_json::Value>,
+    pub paid_at: Option<serde_json::Value>,
+    pub reason: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderPaymentFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub trade_order_id: Option<String>,
+    pub state: Option<String>,
+    pub settlement_state: Option<String>,
+    pub payment_event_id: Option<String>,
+    pub settlement_event_id: Option<String>,
+    pub quote_id: Option<String>,
+    pub quote_version: Option<u32>,
+    pub amount: Option<String>,
+    pub currency: Option<String>,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub paid_at: Option<i64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TradeOrderPaymentQueryBindValues {
+    Id { id: String },
+    TradeOrderId { trade_order_id: String },
+    PaymentEventId { payment_event_id: String },
+    State { state: String },
+}
+impl TradeOrderPaymentQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::TradeOrderId { trade_order_id } => {
+                ("trade_order_id", Value::from(trade_order_id.clone()))
+            }
+            Self::PaymentEventId { payment_event_id } => {
+                ("payment_event_id", Value::from(payment_event_id.clone()))
+            }
+            Self::State { state } => ("state", Value::from(state.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::TradeOrderId { trade_order_id } => trade_order_id.clone(),
+            Self::PaymentEventId { payment_event_id } => payment_event_id.clone(),
+            Self::State { state } => state.clone(),
+        }
+    }
+}
+
+pub struct ITradeOrderPaymentCreateTs;
+pub type ITradeOrderPaymentCreate = ITradeOrderPaymentFields;
+pub struct ITradeOrderPaymentCreateResolveTs;
+pub type ITradeOrderPaymentCreateResolve = IResult<TradeOrderPayment>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderPaymentFindOneArgs {
+    pub on: TradeOrderPaymentQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ITradeOrderPaymentFindOne {
+    On(ITradeOrderPaymentFindOneArgs),
+}
+
+pub struct ITradeOrderPaymentFindOneResolveTs;
+pub type ITradeOrderPaymentFindOneResolve = IResult<Option<TradeOrderPayment>>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderPaymentFindManyArgs {
+    pub filter: Option<ITradeOrderPaymentFieldsFilter>,
+}
+pub type ITradeOrderPaymentFindMany = ITradeOrderPaymentFindManyArgs;
+pub struct ITradeOrderPaymentFindManyResolveTs;
+pub type ITradeOrderPaymentFindManyResolve = IResultList<TradeOrderPayment>;
+pub struct ITradeOrderPaymentDeleteTs;
+pub type ITradeOrderPaymentDelete = ITradeOrderPaymentFindOne;
+pub struct ITradeOrderPaymentDeleteResolveTs;
+pub type ITradeOrderPaymentDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderPaymentUpdateArgs {
+    pub on: TradeOrderPaymentQueryBindValues,
+    pub fields: ITradeOrderPaymentFieldsPartial,
+}
+pub type ITradeOrderPaymentUpdate = ITradeOrderPaymentUpdateArgs;
+pub struct ITradeOrderPaymentUpdateResolveTs;
+pub type ITradeOrderPaymentUpdateResolve = IResult<TradeOrderPayment>;
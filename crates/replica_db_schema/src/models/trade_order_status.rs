@@ -0,0 +1,115 @@
+use radroots_types::types::{IResult, IResultList};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize)]
+pub struct TradeOrderStatus {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub trade_order_id: String,
+    pub status: String,
+    pub event_id: String,
+    pub event_created_at: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderStatusFields {
+    pub trade_order_id: String,
+    pub status: String,
+    pub event_id: String,
+    pub event_created_at: u32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderStatusFieldsPartial {
+    pub trade_order_id: Option<serde_json::Value>,
+    pub status: Option<serde_json::Value>,
+    pub event_id: Option<serde_json::Value>,
+    pub event_created_at: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ITradeOrderStatusFieldsFilter {
+    pub id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub trade_order_id: Option<String>,
+    pub status: Option<String>,
+    pub event_id: Option<String>,
+    pub event_created_at: Option<u32>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TradeOrderStatusQueryBindValues {
+    Id { id: String },
+    TradeOrderId { trade_order_id: String },
+    Status { status: String },
+    EventId { event_id: String },
+}
+impl TradeOrderStatusQueryBindValues {
+    pub fn to_filter_param(&self) -> (&'static str, Value) {
+        match self {
+            Self::Id { id } => ("id", Value::from(id.clone())),
+            Self::TradeOrderId { trade_order_id } => {
+                ("trade_order_id", Value::from(trade_order_id.clone()))
+            }
+            Self::Status { status } => ("status", Value::from(status.clone())),
+            Self::EventId { event_id } => ("event_id", Value::from(event_id.clone())),
+        }
+    }
+
+    pub fn primary_key(&self) -> Option<String> {
+        match self {
+            Self::Id { id } => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn lookup_key(&self) -> String {
+        match self {
+            Self::Id { id } => id.clone(),
+            Self::TradeOrderId { trade_order_id } => trade_order_id.clone(),
+            Self::Status { status } => status.clone(),
+            Self::EventId { event_id } => event_id.clone(),
+        }
+    }
+}
+
+pub struct ITradeOrderStatusCreateTs;
+pub type ITradeOrderStatusCreate = ITradeOrderStatusFields;
+pub struct ITradeOrderStatusCreateResolveTs;
+pub type ITradeOrderStatusCreateResolve = IResult<TradeOrderStatus>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderStatusFindOneArgs {
+    pub on: TradeOrderStatusQueryBindValues,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ITradeOrderStatusFindOne {
+    On(ITradeOrderStatusFindOneArgs),
+}
+
+pub struct ITradeOrderStatusFindOneResolveTs;
+pub type ITradeOrderStatusFindOneResolve = IResult<Option<TradeOrderStatus>>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderStatusFindManyArgs {
+    pub filter: Option<ITradeOrderStatusFieldsFilter>,
+}
+pub type ITradeOrderStatusFindMany = ITradeOrderStatusFindManyArgs;
+pub struct ITradeOrderStatusFindManyResolveTs;
+pub type ITradeOrderStatusFindManyResolve = IResultList<TradeOrderStatus>;
+pub struct ITradeOrderStatusDeleteTs;
+pub type ITradeOrderStatusDelete = ITradeOrderStatusFindOne;
+pub struct ITradeOrderStatusDeleteResolveTs;
+pub type ITradeOrderStatusDeleteResolve = IResult<String>;
+#[derive(Deserialize, Serialize)]
+pub struct ITradeOrderStatusUpdateArgs {
+    pub on: TradeOrderStatusQueryBindValues,
+    pub fields: ITradeOrderStatusFieldsPartial,
+}
+pub type ITradeOrderStatusUpdate = ITradeOrderStatusUpdateArgs;
+pub struct ITradeOrderStatusUpdateResolveTs;
+pub type ITradeOrderStatusUpdateResolve = IResult<TradeOrderStatus>;
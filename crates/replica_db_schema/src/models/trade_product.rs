@@ -26,8 +26,11 @@ pub struct TradeProduct {
     pub price_qty_amt_exact: Option<String>,
     pub price_qty_unit: String,
     pub listing_addr: Option<String>,
+    pub farm_pubkey: Option<String>,
+    pub farm_d_tag: Option<String>,
     pub primary_bin_id: Option<String>,
     pub verified_primary_bin_id: Option<String>,
+    pub expires_at: Option<i64>,
     pub notes: Option<String>,
 }
 #[derive(Clone, Deserialize, Serialize)]
@@ -52,8 +55,11 @@ pub struct ITradeProductFields {
     pub price_qty_amt_exact: String,
     pub price_qty_unit: String,
     pub listing_addr: Option<String>,
+    pub farm_pubkey: Option<String>,
+    pub farm_d_tag: Option<String>,
     pub primary_bin_id: Option<String>,
     pub verified_primary_bin_id: Option<String>,
+    pub expires_at: Option<i64>,
     pub notes: Option<String>,
 }
 #[derive(Clone, Deserialize, Serialize)]
@@ -78,8 +84,11 @@ pub struct ITradeProductFieldsPartial {
     pub price_qty_amt_exact: Option<serde_json::Value>,
     pub price_qty_unit: Option<serde_json::Value>,
     pub listing_addr: Option<serde_json::Value>,
+    pub farm_pubkey: Option<serde_json::Value>,
+    pub farm_d_tag: Option<serde_json::Value>,
     pub primary_bin_id: Option<serde_json::Value>,
     pub verified_primary_bin_id: Option<serde_json::Value>,
+    pub expires_at: Option<serde_json::Value>,
     pub notes: Option<serde_json::Value>,
 }
 #[derive(Clone, Deserialize, Serialize)]
@@ -107,8 +116,11 @@ pub struct ITradeProductFieldsFilter {
     pub price_qty_amt_exact: Option<String>,
     pub price_qty_unit: Option<String>,
     pub listing_addr: Option<String>,
+    pub farm_pubkey: Option<String>,
+    pub farm_d_tag: Option<String>,
     pub primary_bin_id: Option<String>,
     pub verified_primary_bin_id: Option<String>,
+    pub expires_at: Option<i64>,
     pub notes: Option<String>,
 }
 #[derive(Clone, Deserialize, Serialize)]
@@ -1,6 +1,8 @@
 use crate::utils::value_to_js;
-use radroots_replica_db::migrations;
-use radroots_replica_db::{ReplicaDbExportManifestRs, export_manifest};
+use radroots_replica_db::{
+    ReplicaDbExportManifestRs, ReplicaFarmGeoQuery, ReplicaSql, ReplicaTradeProductGeoQuery,
+    ReplicaTradeProductTextQuery, export_manifest,
+};
 use radroots_replica_sync::radroots_replica_sync_status;
 use radroots_sql_core::{
     WasmSqlExecutor, export_lock_begin, export_lock_end, with_export_lock_bypass,
@@ -79,6 +81,79 @@ use radroots_replica_db_schema::plot_tag::{
     IPlotTagCreate, IPlotTagDelete, IPlotTagFindMany, IPlotTagFindOne, IPlotTagUpdate,
 };
 
+use radroots_replica_db_schema::coop::{
+    ICoopCreate, ICoopDelete, ICoopFindMany, ICoopFindOne, ICoopUpdate,
+};
+
+use radroots_replica_db_schema::coop_tag::{
+    ICoopTagCreate, ICoopTagDelete, ICoopTagFindMany, ICoopTagFindOne, ICoopTagUpdate,
+};
+
+use radroots_replica_db_schema::coop_gcs_location::{
+    ICoopGcsLocationCreate, ICoopGcsLocationDelete, ICoopGcsLocationFindMany,
+    ICoopGcsLocationFindOne, ICoopGcsLocationUpdate,
+};
+
+use radroots_replica_db_schema::document::{
+    IDocumentCreate, IDocumentDelete, IDocumentFindMany, IDocumentFindOne, IDocumentUpdate,
+};
+
+use radroots_replica_db_schema::document_tag::{
+    IDocumentTagCreate, IDocumentTagDelete, IDocumentTagFindMany, IDocumentTagFindOne,
+    IDocumentTagUpdate,
+};
+
+use radroots_replica_db_schema::resource_area::{
+    IResourceAreaCreate, IResourceAreaDelete, IResourceAreaFindMany, IResourceAreaFindOne,
+    IResourceAreaUpdate,
+};
+
+use radroots_replica_db_schema::resource_area_tag::{
+    IResourceAreaTagCreate, IResourceAreaTagDelete, IResourceAreaTagFindMany,
+    IResourceAreaTagFindOne, IResourceAreaTagUpdate,
+};
+
+use radroots_replica_db_schema::resource_area_gcs_location::{
+    IResourceAreaGcsLocationCreate, IResourceAreaGcsLocationDelete,
+    IResourceAreaGcsLocationFindMany, IResourceAreaGcsLocationFindOne,
+    IResourceAreaGcsLocationUpdate,
+};
+
+use radroots_replica_db_schema::resource_harvest_cap::{
+    IResourceHarvestCapCreate, IResourceHarvestCapDelete, IResourceHarvestCapFindMany,
+    IResourceHarvestCapFindOne, IResourceHarvestCapUpdate,
+};
+
+use radroots_replica_db_schema::resource_harvest_cap_tag::{
+    IResourceHarvestCapTagCreate, IResourceHarvestCapTagDelete, IResourceHarvestCapTagFindMany,
+    IResourceHarvestCapTagFindOne, IResourceHarvestCapTagUpdate,
+};
+
+use radroots_replica_db_schema::trade_order::{
+    ITradeOrderCreate, ITradeOrderDelete, ITradeOrderFindMany, ITradeOrderFindOne,
+    ITradeOrderUpdate,
+};
+
+use radroots_replica_db_schema::trade_order_item::{
+    ITradeOrderItemCreate, ITradeOrderItemDelete, ITradeOrderItemFindMany, ITradeOrderItemFindOne,
+    ITradeOrderItemUpdate,
+};
+
+use radroots_replica_db_schema::trade_order_status::{
+    ITradeOrderStatusCreate, ITradeOrderStatusDelete, ITradeOrderStatusFindMany,
+    ITradeOrderStatusFindOne, ITradeOrderStatusUpdate,
+};
+
+use radroots_replica_db_schema::trade_order_payment::{
+    ITradeOrderPaymentCreate, ITradeOrderPaymentDelete, ITradeOrderPaymentFindMany,
+    ITradeOrderPaymentFindOne, ITradeOrderPaymentUpdate,
+};
+
+use radroots_replica_db_schema::trade_order_event::{
+    ITradeOrderEventCreate, ITradeOrderEventDelete, ITradeOrderEventFindMany,
+    ITradeOrderEventFindOne, ITradeOrderEventUpdate,
+};
+
 use radroots_replica_db_schema::nostr_profile_relay::INostrProfileRelayRelation;
 
 use radroots_replica_db_schema::trade_product_location::ITradeProductLocationRelation;
@@ -87,14 +162,16 @@ use radroots_replica_db_schema::trade_product_media::ITradeProductMediaRelation;
 
 #[wasm_bindgen(js_name = replica_db_run_migrations)]
 pub fn replica_db_run_migrations() -> Result<(), JsValue> {
-    let exec = WasmSqlExecutor::new();
-    migrations::run_all_up(&exec).map_err(err_js)
+    ReplicaSql::new(WasmSqlExecutor::new())
+        .migrate_up()
+        .map_err(err_js)
 }
 
 #[wasm_bindgen(js_name = replica_db_reset_database)]
 pub fn replica_db_reset_database() -> Result<(), JsValue> {
-    let exec = WasmSqlExecutor::new();
-    migrations::run_all_down(&exec).map_err(err_js)
+    ReplicaSql::new(WasmSqlExecutor::new())
+        .migrate_down()
+        .map_err(err_js)
 }
 
 #[wasm_bindgen(js_name = replica_db_export_json)]
@@ -106,8 +183,9 @@ pub fn replica_db_export_json() -> Result<JsValue, JsValue> {
 
 #[wasm_bindgen(js_name = replica_db_import_json)]
 pub fn replica_db_import_json(dump_json: &str) -> Result<(), JsValue> {
-    let exec = WasmSqlExecutor::new();
-    radroots_replica_db::backup::restore_database_backup_json(&exec, dump_json).map_err(err_js)
+    ReplicaSql::new(WasmSqlExecutor::new())
+        .restore_database_json(dump_json)
+        .map_err(err_js)
 }
 
 #[wasm_bindgen(js_name = replica_db_export_begin)]
@@ -130,6 +208,37 @@ pub fn replica_db_export_finish() -> Result<(), JsValue> {
     Ok(())
 }
 
+#[wasm_bindgen(js_name = replica_db_trade_product_fts_setup)]
+pub fn replica_db_trade_product_fts_setup() -> Result<JsValue, JsValue> {
+    let db = ReplicaSql::new(WasmSqlExecutor::new());
+    let out = db.trade_product_fts_setup().map_err(err_js)?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_product_search_ranked)]
+pub fn replica_db_trade_product_search_ranked(query_json: &str) -> Result<JsValue, JsValue> {
+    let query: ReplicaTradeProductTextQuery = parse_json(query_json).map_err(err_js)?;
+    let db = ReplicaSql::new(WasmSqlExecutor::new());
+    let out = db.trade_product_search_ranked(&query).map_err(err_js)?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_product_geo_search)]
+pub fn replica_db_trade_product_geo_search(query_json: &str) -> Result<JsValue, JsValue> {
+    let query: ReplicaTradeProductGeoQuery = parse_json(query_json).map_err(err_js)?;
+    let db = ReplicaSql::new(WasmSqlExecutor::new());
+    let out = db.trade_product_geo_search(&query).map_err(err_js)?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_farm_geo_search)]
+pub fn replica_db_farm_geo_search(query_json: &str) -> Result<JsValue, JsValue> {
+    let query: ReplicaFarmGeoQuery = parse_json(query_json).map_err(err_js)?;
+    let db = ReplicaSql::new(WasmSqlExecutor::new());
+    let out = db.farm_geo_search(&query).map_err(err_js)?;
+    value_to_js(out)
+}
+
 fn export_snapshot(exec: &WasmSqlExecutor) -> Result<JsValue, JsValue> {
     let status = radroots_replica_sync_status(exec).map_err(|err| {
         err_js(radroots_sql_core::SqlError::InvalidArgument(
@@ -836,6 +945,662 @@ pub fn replica_db_trade_product_delete(opts_json: &str) -> Result<JsValue, JsVal
     value_to_js(out)
 }
 
+#[wasm_bindgen(js_name = replica_db_coop_create)]
+pub fn replica_db_coop_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_find_one)]
+pub fn replica_db_coop_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_find_many)]
+pub fn replica_db_coop_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_update)]
+pub fn replica_db_coop_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_delete)]
+pub fn replica_db_coop_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_tag_create)]
+pub fn replica_db_coop_tag_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopTagCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_tag::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_tag_find_one)]
+pub fn replica_db_coop_tag_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopTagFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_tag::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_tag_find_many)]
+pub fn replica_db_coop_tag_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopTagFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_tag::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_tag_update)]
+pub fn replica_db_coop_tag_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopTagUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_tag::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_tag_delete)]
+pub fn replica_db_coop_tag_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopTagDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_tag::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_gcs_location_create)]
+pub fn replica_db_coop_gcs_location_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopGcsLocationCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::coop_gcs_location::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_gcs_location_find_one)]
+pub fn replica_db_coop_gcs_location_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopGcsLocationFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_gcs_location::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_gcs_location_find_many)]
+pub fn replica_db_coop_gcs_location_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopGcsLocationFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::coop_gcs_location::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_gcs_location_update)]
+pub fn replica_db_coop_gcs_location_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopGcsLocationUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::coop_gcs_location::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_coop_gcs_location_delete)]
+pub fn replica_db_coop_gcs_location_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ICoopGcsLocationDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::coop_gcs_location::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_create)]
+pub fn replica_db_document_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_find_one)]
+pub fn replica_db_document_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_find_many)]
+pub fn replica_db_document_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_update)]
+pub fn replica_db_document_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_delete)]
+pub fn replica_db_document_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_tag_create)]
+pub fn replica_db_document_tag_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentTagCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document_tag::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_tag_find_one)]
+pub fn replica_db_document_tag_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentTagFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::document_tag::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_tag_find_many)]
+pub fn replica_db_document_tag_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentTagFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::document_tag::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_tag_update)]
+pub fn replica_db_document_tag_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentTagUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document_tag::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_document_tag_delete)]
+pub fn replica_db_document_tag_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IDocumentTagDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::document_tag::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_create)]
+pub fn replica_db_resource_area_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_find_one)]
+pub fn replica_db_resource_area_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_find_many)]
+pub fn replica_db_resource_area_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_update)]
+pub fn replica_db_resource_area_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_delete)]
+pub fn replica_db_resource_area_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_tag_create)]
+pub fn replica_db_resource_area_tag_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaTagCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area_tag::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_tag_find_one)]
+pub fn replica_db_resource_area_tag_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaTagFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_tag::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_tag_find_many)]
+pub fn replica_db_resource_area_tag_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaTagFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_tag::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_tag_update)]
+pub fn replica_db_resource_area_tag_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaTagUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area_tag::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_tag_delete)]
+pub fn replica_db_resource_area_tag_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaTagDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::resource_area_tag::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_gcs_location_create)]
+pub fn replica_db_resource_area_gcs_location_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaGcsLocationCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_gcs_location::create(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_gcs_location_find_one)]
+pub fn replica_db_resource_area_gcs_location_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaGcsLocationFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_gcs_location::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_gcs_location_find_many)]
+pub fn replica_db_resource_area_gcs_location_find_many(
+    opts_json: &str,
+) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaGcsLocationFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_gcs_location::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_gcs_location_update)]
+pub fn replica_db_resource_area_gcs_location_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaGcsLocationUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_gcs_location::update(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_area_gcs_location_delete)]
+pub fn replica_db_resource_area_gcs_location_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceAreaGcsLocationDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_area_gcs_location::delete(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_create)]
+pub fn replica_db_resource_harvest_cap_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap::create(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_find_one)]
+pub fn replica_db_resource_harvest_cap_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_find_many)]
+pub fn replica_db_resource_harvest_cap_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_update)]
+pub fn replica_db_resource_harvest_cap_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap::update(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_delete)]
+pub fn replica_db_resource_harvest_cap_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap::delete(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_tag_create)]
+pub fn replica_db_resource_harvest_cap_tag_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapTagCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap_tag::create(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_tag_find_one)]
+pub fn replica_db_resource_harvest_cap_tag_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapTagFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap_tag::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_tag_find_many)]
+pub fn replica_db_resource_harvest_cap_tag_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapTagFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap_tag::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_tag_update)]
+pub fn replica_db_resource_harvest_cap_tag_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapTagUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap_tag::update(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_resource_harvest_cap_tag_delete)]
+pub fn replica_db_resource_harvest_cap_tag_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: IResourceHarvestCapTagDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::resource_harvest_cap_tag::delete(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_create)]
+pub fn replica_db_trade_order_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_find_one)]
+pub fn replica_db_trade_order_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_find_many)]
+pub fn replica_db_trade_order_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order::find_many(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_update)]
+pub fn replica_db_trade_order_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_delete)]
+pub fn replica_db_trade_order_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_item_create)]
+pub fn replica_db_trade_order_item_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderItemCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_item::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_item_find_one)]
+pub fn replica_db_trade_order_item_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderItemFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_item::find_one(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_item_find_many)]
+pub fn replica_db_trade_order_item_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderItemFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_item::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_item_update)]
+pub fn replica_db_trade_order_item_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderItemUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_item::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_item_delete)]
+pub fn replica_db_trade_order_item_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderItemDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_item::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_status_create)]
+pub fn replica_db_trade_order_status_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderStatusCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_status::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_status_find_one)]
+pub fn replica_db_trade_order_status_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderStatusFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_status::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_status_find_many)]
+pub fn replica_db_trade_order_status_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderStatusFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_status::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_status_update)]
+pub fn replica_db_trade_order_status_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderStatusUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_status::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_status_delete)]
+pub fn replica_db_trade_order_status_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderStatusDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_status::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_payment_create)]
+pub fn replica_db_trade_order_payment_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderPaymentCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_payment::create(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_payment_find_one)]
+pub fn replica_db_trade_order_payment_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderPaymentFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_payment::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_payment_find_many)]
+pub fn replica_db_trade_order_payment_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderPaymentFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_payment::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_payment_update)]
+pub fn replica_db_trade_order_payment_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderPaymentUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_payment::update(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_payment_delete)]
+pub fn replica_db_trade_order_payment_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderPaymentDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_payment::delete(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_event_create)]
+pub fn replica_db_trade_order_event_create(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderEventCreate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_event::create(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_event_find_one)]
+pub fn replica_db_trade_order_event_find_one(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderEventFindOne = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_event::find_one(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_event_find_many)]
+pub fn replica_db_trade_order_event_find_many(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderEventFindMany = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out = radroots_replica_db::trade_order_event::find_many(&exec, &opts)
+        .map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_event_update)]
+pub fn replica_db_trade_order_event_update(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderEventUpdate = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_event::update(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
+#[wasm_bindgen(js_name = replica_db_trade_order_event_delete)]
+pub fn replica_db_trade_order_event_delete(opts_json: &str) -> Result<JsValue, JsValue> {
+    let opts: ITradeOrderEventDelete = parse_json(opts_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let out =
+        radroots_replica_db::trade_order_event::delete(&exec, &opts).map_err(|e| err_js(e.err))?;
+    value_to_js(out)
+}
+
 #[wasm_bindgen(js_name = replica_db_nostr_profile_relay_set)]
 pub fn replica_db_nostr_profile_relay_set(opts_json: &str) -> Result<JsValue, JsValue> {
     let opts: INostrProfileRelayRelation = parse_json(opts_json).map_err(err_js)?;
@@ -0,0 +1,46 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use radroots_replica_db::ReplicaChange;
+use radroots_sql_core::SqlExecutor;
+use radroots_types::types::IError;
+use serde::{Deserialize, Serialize};
+
+use crate::error::RadrootsReplicaEventsError;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RadrootsReplicaChangeBatch {
+    pub since_seq: i64,
+    pub next_seq: i64,
+    pub entity_types: Vec<String>,
+    pub changes: Vec<ReplicaChange>,
+}
+
+pub fn radroots_replica_changes_since<E: SqlExecutor>(
+    exec: &E,
+    since_seq: i64,
+    limit: Option<u32>,
+) -> Result<RadrootsReplicaChangeBatch, RadrootsReplicaEventsError> {
+    let changes = radroots_replica_db::change::changes_since(exec, since_seq, limit)
+        .map_err(|err| RadrootsReplicaEventsError::from(IError::from(err)))?;
+    let next_seq = changes.last().map_or(since_seq, |change| change.seq);
+    let mut entity_types = changes
+        .iter()
+        .map(|change| change.entity_type.clone())
+        .collect::<Vec<_>>();
+    entity_types.sort();
+    entity_types.dedup();
+    Ok(RadrootsReplicaChangeBatch {
+        since_seq,
+        next_seq,
+        entity_types,
+        changes,
+    })
+}
+
+pub fn radroots_replica_latest_change_seq<E: SqlExecutor>(
+    exec: &E,
+) -> Result<i64, RadrootsReplicaEventsError> {
+    radroots_replica_db::change::latest_change_seq(exec)
+        .map_err(|err| RadrootsReplicaEventsError::from(IError::from(err)))
+}
@@ -9,25 +9,50 @@ use alloc::{
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 
+use radroots_core::{RadrootsCoreDecimal, RadrootsCoreQuantity, RadrootsCoreUnit};
+use radroots_events::coop::{RadrootsCoop, RadrootsCoopLocation};
+use radroots_events::document::{RadrootsDocument, RadrootsDocumentSubject};
 use radroots_events::farm::{
     RadrootsFarm, RadrootsFarmLocation, RadrootsFarmRef, RadrootsGcsLocation, RadrootsGeoJsonPoint,
     RadrootsGeoJsonPolygon,
 };
-use radroots_events::kinds::{KIND_FARM, KIND_LIST_SET_GENERIC, KIND_PLOT};
+use radroots_events::kinds::{
+    KIND_COOP, KIND_DOCUMENT, KIND_FARM, KIND_LIST_SET_GENERIC, KIND_PLOT, KIND_RESOURCE_AREA,
+    KIND_RESOURCE_HARVEST_CAP,
+};
 use radroots_events::plot::RadrootsPlot;
 use radroots_events::profile::{
     RADROOTS_PROFILE_TYPE_TAG_KEY, RadrootsProfile, RadrootsProfileType,
     radroots_profile_type_from_tag_value, radroots_profile_type_tag_value,
 };
+use radroots_events::resource_area::{
+    RadrootsResourceArea, RadrootsResourceAreaLocation, RadrootsResourceAreaRef,
+};
+use radroots_events::resource_cap::{RadrootsResourceHarvestCap, RadrootsResourceHarvestProduct};
+use radroots_events_codec::coop::encode as coop_encode;
+use radroots_events_codec::document::encode as document_encode;
 use radroots_events_codec::farm::encode as farm_encode;
 use radroots_events_codec::farm::list_sets as farm_list_sets;
 use radroots_events_codec::list_set::encode as list_set_encode;
 use radroots_events_codec::plot::encode as plot_encode;
+use radroots_events_codec::resource_area::encode as resource_area_encode;
+use radroots_events_codec::resource_cap::encode as resource_cap_encode;
 use radroots_events_codec::wire::WireEventParts;
 use radroots_replica_db::{
-    farm, farm_gcs_location, farm_member, farm_member_claim, farm_tag, gcs_location, nostr_profile,
-    plot, plot_gcs_location, plot_tag,
+    coop, coop_gcs_location, coop_tag, document, document_tag, farm, farm_gcs_location,
+    farm_member, farm_member_claim, farm_tag, gcs_location, nostr_profile, plot, plot_gcs_location,
+    plot_tag, resource_area, resource_area_gcs_location, resource_area_tag, resource_harvest_cap,
+    resource_harvest_cap_tag,
+};
+use radroots_replica_db_schema::coop::{
+    Coop, CoopQueryBindValues, ICoopFieldsFilter, ICoopFindMany, ICoopFindOne, ICoopFindOneArgs,
+};
+use radroots_replica_db_schema::coop_gcs_location::{
+    CoopGcsLocation, ICoopGcsLocationFieldsFilter, ICoopGcsLocationFindMany,
 };
+use radroots_replica_db_schema::coop_tag::{ICoopTagFieldsFilter, ICoopTagFindMany};
+use radroots_replica_db_schema::document::{IDocumentFieldsFilter, IDocumentFindMany};
+use radroots_replica_db_schema::document_tag::{IDocumentTagFieldsFilter, IDocumentTagFindMany};
 use radroots_replica_db_schema::farm::{
     Farm, IFarmFieldsFilter, IFarmFindMany, IFarmFindOne, IFarmFindOneArgs,
 };
@@ -52,6 +77,22 @@ use radroots_replica_db_schema::plot_gcs_location::{
     IPlotGcsLocationFieldsFilter, IPlotGcsLocationFindMany, PlotGcsLocation,
 };
 use radroots_replica_db_schema::plot_tag::{IPlotTagFieldsFilter, IPlotTagFindMany};
+use radroots_replica_db_schema::resource_area::{
+    IResourceAreaFieldsFilter, IResourceAreaFindMany, IResourceAreaFindOne,
+    IResourceAreaFindOneArgs, ResourceAreaQueryBindValues,
+};
+use radroots_replica_db_schema::resource_area_gcs_location::{
+    IResourceAreaGcsLocationFieldsFilter, IResourceAreaGcsLocationFindMany, ResourceAreaGcsLocation,
+};
+use radroots_replica_db_schema::resource_area_tag::{
+    IResourceAreaTagFieldsFilter, IResourceAreaTagFindMany,
+};
+use radroots_replica_db_schema::resource_harvest_cap::{
+    IResourceHarvestCapFieldsFilter, IResourceHarvestCapFindMany,
+};
+use radroots_replica_db_schema::resource_harvest_cap_tag::{
+    IResourceHarvestCapTagFieldsFilter, IResourceHarvestCapTagFindMany,
+};
 use radroots_sql_core::SqlExecutor;
 use serde_json::Value;
 
@@ -59,8 +100,9 @@ use crate::canonical::canonical_json_string;
 use crate::error::RadrootsReplicaEventsError;
 use crate::geo::{geojson_point_from_lat_lng, geojson_polygon_circle_wgs84};
 use crate::types::{
-    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaEventDraft, RadrootsReplicaFarmSelector,
-    RadrootsReplicaSyncBundle, RadrootsReplicaSyncOptions, RadrootsReplicaSyncRequest,
+    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaCoopSelector, RadrootsReplicaCoopSyncRequest,
+    RadrootsReplicaEventDraft, RadrootsReplicaFarmSelector, RadrootsReplicaSyncBundle,
+    RadrootsReplicaSyncOptions, RadrootsReplicaSyncRequest,
 };
 
 const ROLE_PRIMARY: &str = "primary";
@@ -282,6 +324,271 @@ pub fn radroots_replica_membership_claim_events(
     Ok(events)
 }
 
+pub fn radroots_replica_coop_sync_all(
+    exec: &dyn SqlExecutor,
+    request: &RadrootsReplicaCoopSyncRequest,
+) -> Result<RadrootsReplicaSyncBundle, RadrootsReplicaEventsError> {
+    let coop = resolve_coop(exec, &request.coop)?;
+    let mut events = Vec::new();
+    events.push(radroots_replica_coop_event(exec, &coop)?);
+    events.extend(radroots_replica_document_events(exec, &coop.pubkey)?);
+    events.extend(radroots_replica_resource_area_events(exec, &coop.pubkey)?);
+    events.extend(radroots_replica_resource_harvest_cap_events(
+        exec,
+        &coop.pubkey,
+    )?);
+    Ok(RadrootsReplicaSyncBundle {
+        version: RADROOTS_REPLICA_TRANSFER_VERSION,
+        events,
+    })
+}
+
+pub fn radroots_replica_coop_event(
+    exec: &dyn SqlExecutor,
+    coop: &Coop,
+) -> Result<RadrootsReplicaEventDraft, RadrootsReplicaEventsError> {
+    let tags = collect_coop_tags(exec, &coop.id)?;
+    let location =
+        load_relation_by_role(exec, &coop.id, ROLE_PRIMARY, RelationType::Coop)?.map(|gcs| {
+            RadrootsCoopLocation {
+                primary: coop.location_primary.clone(),
+                city: coop.location_city.clone(),
+                region: coop.location_region.clone(),
+                country: coop.location_country.clone(),
+                gcs,
+            }
+        });
+    let coop_event = RadrootsCoop {
+        d_tag: coop.d_tag.clone(),
+        name: coop.name.clone(),
+        about: coop.about.clone(),
+        website: coop.website.clone(),
+        picture: coop.picture.clone(),
+        banner: coop.banner.clone(),
+        location,
+        tags: if tags.is_empty() { None } else { Some(tags) },
+    };
+    let tags = coop_encode::coop_build_tags(&coop_event)?;
+    let content = canonical_json_string(&coop_event)?;
+    let parts = WireEventParts {
+        kind: KIND_COOP,
+        content,
+        tags,
+    };
+    Ok(parts_to_draft(&coop.pubkey, parts))
+}
+
+pub fn radroots_replica_document_events(
+    exec: &dyn SqlExecutor,
+    pubkey: &str,
+) -> Result<Vec<RadrootsReplicaEventDraft>, RadrootsReplicaEventsError> {
+    let filter = IDocumentFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: None,
+        pubkey: Some(pubkey.to_string()),
+        doc_type: None,
+        title: None,
+        version: None,
+        summary: None,
+        effective_at: None,
+        body_markdown: None,
+        subject_pubkey: None,
+        subject_address: None,
+    };
+    let mut documents = document::find_many(
+        exec,
+        &IDocumentFindMany {
+            filter: Some(filter),
+        },
+    )?
+    .results;
+    documents.sort_by(|a, b| a.d_tag.cmp(&b.d_tag));
+
+    let mut events = Vec::new();
+    for row in documents {
+        let tags = collect_document_tags(exec, &row.id)?;
+        let document_event = RadrootsDocument {
+            d_tag: row.d_tag,
+            doc_type: row.doc_type,
+            title: row.title,
+            version: row.version,
+            summary: row.summary,
+            effective_at: row.effective_at,
+            body_markdown: row.body_markdown,
+            subject: RadrootsDocumentSubject {
+                pubkey: row.subject_pubkey,
+                address: row.subject_address,
+            },
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+        let tags = document_encode::document_build_tags(&document_event)?;
+        let content = canonical_json_string(&document_event)?;
+        let parts = WireEventParts {
+            kind: KIND_DOCUMENT,
+            content,
+            tags,
+        };
+        events.push(parts_to_draft(&row.pubkey, parts));
+    }
+    Ok(events)
+}
+
+pub fn radroots_replica_resource_area_events(
+    exec: &dyn SqlExecutor,
+    pubkey: &str,
+) -> Result<Vec<RadrootsReplicaEventDraft>, RadrootsReplicaEventsError> {
+    let filter = IResourceAreaFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: None,
+        pubkey: Some(pubkey.to_string()),
+        name: None,
+        about: None,
+        location_primary: None,
+        location_city: None,
+        location_region: None,
+        location_country: None,
+    };
+    let mut areas = resource_area::find_many(
+        exec,
+        &IResourceAreaFindMany {
+            filter: Some(filter),
+        },
+    )?
+    .results;
+    areas.sort_by(|a, b| a.d_tag.cmp(&b.d_tag));
+
+    let mut events = Vec::new();
+    for row in areas {
+        let tags = collect_resource_area_tags(exec, &row.id)?;
+        let Some(gcs) =
+            load_relation_by_role(exec, &row.id, ROLE_PRIMARY, RelationType::ResourceArea)?
+        else {
+            return Err(RadrootsReplicaEventsError::InvalidData(format!(
+                "resource area location not found: {}",
+                row.d_tag
+            )));
+        };
+        let area_event = RadrootsResourceArea {
+            d_tag: row.d_tag,
+            name: row.name,
+            about: row.about,
+            location: RadrootsResourceAreaLocation {
+                primary: row.location_primary,
+                city: row.location_city,
+                region: row.location_region,
+                country: row.location_country,
+                gcs,
+            },
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+        let tags = resource_area_encode::resource_area_build_tags(&area_event)?;
+        let content = canonical_json_string(&area_event)?;
+        let parts = WireEventParts {
+            kind: KIND_RESOURCE_AREA,
+            content,
+            tags,
+        };
+        events.push(parts_to_draft(&row.pubkey, parts));
+    }
+    Ok(events)
+}
+
+pub fn radroots_replica_resource_harvest_cap_events(
+    exec: &dyn SqlExecutor,
+    pubkey: &str,
+) -> Result<Vec<RadrootsReplicaEventDraft>, RadrootsReplicaEventsError> {
+    let filter = IResourceHarvestCapFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: None,
+        pubkey: Some(pubkey.to_string()),
+        resource_area_id: None,
+        product_key: None,
+        product_category: None,
+        start_at: None,
+        end_at: None,
+        cap_amount: None,
+        cap_unit: None,
+        cap_label: None,
+        display_amount: None,
+        display_unit: None,
+        display_label: None,
+    };
+    let mut caps = resource_harvest_cap::find_many(
+        exec,
+        &IResourceHarvestCapFindMany {
+            filter: Some(filter),
+        },
+    )?
+    .results;
+    caps.sort_by(|a, b| a.d_tag.cmp(&b.d_tag));
+
+    let mut events = Vec::new();
+    for row in caps {
+        let area = resource_area::find_one(
+            exec,
+            &IResourceAreaFindOne::On(IResourceAreaFindOneArgs {
+                on: ResourceAreaQueryBindValues::Id {
+                    id: row.resource_area_id.clone(),
+                },
+            }),
+        )?
+        .result
+        .ok_or_else(|| {
+            RadrootsReplicaEventsError::InvalidData("resource area not found".to_string())
+        })?;
+        let tags = collect_resource_harvest_cap_tags(exec, &row.id)?;
+        let cap_event = RadrootsResourceHarvestCap {
+            d_tag: row.d_tag,
+            resource_area: RadrootsResourceAreaRef {
+                pubkey: area.pubkey,
+                d_tag: area.d_tag,
+            },
+            product: RadrootsResourceHarvestProduct {
+                key: row.product_key,
+                category: row.product_category,
+            },
+            start: u64::try_from(row.start_at).map_err(|_| {
+                RadrootsReplicaEventsError::InvalidData("resource_harvest_cap.start_at".to_string())
+            })?,
+            end: u64::try_from(row.end_at).map_err(|_| {
+                RadrootsReplicaEventsError::InvalidData("resource_harvest_cap.end_at".to_string())
+            })?,
+            cap_quantity: RadrootsCoreQuantity {
+                amount: parse_decimal(&row.cap_amount, "resource_harvest_cap.cap_amount")?,
+                unit: parse_unit(&row.cap_unit, "resource_harvest_cap.cap_unit")?,
+                label: row.cap_label,
+            },
+            display_amount: row
+                .display_amount
+                .as_deref()
+                .map(|value| parse_decimal(value, "resource_harvest_cap.display_amount"))
+                .transpose()?,
+            display_unit: row
+                .display_unit
+                .as_deref()
+                .map(|value| parse_unit(value, "resource_harvest_cap.display_unit"))
+                .transpose()?,
+            display_label: row.display_label,
+            tags: if tags.is_empty() { None } else { Some(tags) },
+        };
+        let tags = resource_cap_encode::resource_harvest_cap_build_tags(&cap_event)?;
+        let content = canonical_json_string(&cap_event)?;
+        let parts = WireEventParts {
+            kind: KIND_RESOURCE_HARVEST_CAP,
+            content,
+            tags,
+        };
+        events.push(parts_to_draft(&row.pubkey, parts));
+    }
+    Ok(events)
+}
+
 fn resolve_farm(
     exec: &dyn SqlExecutor,
     selector: &RadrootsReplicaFarmSelector,
@@ -350,6 +657,171 @@ fn resolve_farm(
     ))
 }
 
+fn resolve_coop(
+    exec: &dyn SqlExecutor,
+    selector: &RadrootsReplicaCoopSelector,
+) -> Result<Coop, RadrootsReplicaEventsError> {
+    if let Some(id) = selector.id.as_ref().filter(|v| !v.trim().is_empty()) {
+        let result = coop::find_one(
+            exec,
+            &ICoopFindOne::On(ICoopFindOneArgs {
+                on: CoopQueryBindValues::Id { id: id.clone() },
+            }),
+        )?;
+        return result.result.ok_or_else(|| {
+            RadrootsReplicaEventsError::InvalidSelector(format!("coop not found: {id}"))
+        });
+    }
+
+    let d_tag = selector
+        .d_tag
+        .as_ref()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty());
+    let pubkey = selector
+        .pubkey
+        .as_ref()
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty());
+    let (Some(d_tag), Some(pubkey)) = (d_tag, pubkey) else {
+        return Err(RadrootsReplicaEventsError::InvalidSelector(
+            "coop selector requires id or (d_tag + pubkey)".to_string(),
+        ));
+    };
+
+    let filter = ICoopFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: Some(d_tag.to_string()),
+        pubkey: Some(pubkey.to_string()),
+        name: None,
+        about: None,
+        website: None,
+        picture: None,
+        banner: None,
+        location_primary: None,
+        location_city: None,
+        location_region: None,
+        location_country: None,
+    };
+    let mut results = coop::find_many(
+        exec,
+        &ICoopFindMany {
+            filter: Some(filter),
+        },
+    )?
+    .results;
+    match (results.pop(), results.is_empty()) {
+        (Some(coop), true) => Ok(coop),
+        _ => Err(RadrootsReplicaEventsError::InvalidSelector(
+            "coop selector did not resolve to a single coop".to_string(),
+        )),
+    }
+}
+
+fn collect_coop_tags(
+    exec: &dyn SqlExecutor,
+    coop_id: &str,
+) -> Result<Vec<String>, RadrootsReplicaEventsError> {
+    let filter = ICoopTagFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        coop_id: Some(coop_id.to_string()),
+        tag: None,
+    };
+    let result = coop_tag::find_many(
+        exec,
+        &ICoopTagFindMany {
+            filter: Some(filter),
+        },
+    )?;
+    Ok(sorted_tags(result.results.into_iter().map(|row| row.tag)))
+}
+
+fn collect_document_tags(
+    exec: &dyn SqlExecutor,
+    document_id: &str,
+) -> Result<Vec<String>, RadrootsReplicaEventsError> {
+    let filter = IDocumentTagFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        document_id: Some(document_id.to_string()),
+        tag: None,
+    };
+    let result = document_tag::find_many(
+        exec,
+        &IDocumentTagFindMany {
+            filter: Some(filter),
+        },
+    )?;
+    Ok(sorted_tags(result.results.into_iter().map(|row| row.tag)))
+}
+
+fn collect_resource_area_tags(
+    exec: &dyn SqlExecutor,
+    resource_area_id: &str,
+) -> Result<Vec<String>, RadrootsReplicaEventsError> {
+    let filter = IResourceAreaTagFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        resource_area_id: Some(resource_area_id.to_string()),
+        tag: None,
+    };
+    let result = resource_area_tag::find_many(
+        exec,
+        &IResourceAreaTagFindMany {
+            filter: Some(filter),
+        },
+    )?;
+    Ok(sorted_tags(result.results.into_iter().map(|row| row.tag)))
+}
+
+fn collect_resource_harvest_cap_tags(
+    exec: &dyn SqlExecutor,
+    resource_harvest_cap_id: &str,
+) -> Result<Vec<String>, RadrootsReplicaEventsError> {
+    let filter = IResourceHarvestCapTagFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        resource_harvest_cap_id: Some(resource_harvest_cap_id.to_string()),
+        tag: None,
+    };
+    let result = resource_harvest_cap_tag::find_many(
+        exec,
+        &IResourceHarvestCapTagFindMany {
+            filter: Some(filter),
+        },
+    )?;
+    Ok(sorted_tags(result.results.into_iter().map(|row| row.tag)))
+}
+
+fn sorted_tags(tags: impl Iterator<Item = String>) -> Vec<String> {
+    let mut tags = tags.collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn parse_decimal(
+    value: &str,
+    field: &str,
+) -> Result<RadrootsCoreDecimal, RadrootsReplicaEventsError> {
+    value
+        .parse::<RadrootsCoreDecimal>()
+        .map_err(|_| RadrootsReplicaEventsError::InvalidData(field.to_string()))
+}
+
+fn parse_unit(value: &str, field: &str) -> Result<RadrootsCoreUnit, RadrootsReplicaEventsError> {
+    value
+        .parse::<RadrootsCoreUnit>()
+        .map_err(|_| RadrootsReplicaEventsError::InvalidData(field.to_string()))
+}
+
 fn collect_farm_tags(
     exec: &dyn SqlExecutor,
     farm_id: &str,
@@ -545,6 +1017,8 @@ fn load_gcs_location_for_plot(
 enum RelationType {
     Farm,
     Plot,
+    Coop,
+    ResourceArea,
 }
 
 fn load_relation_by_role(
@@ -606,6 +1080,54 @@ fn load_relation_by_role(
                 .map(RelationRow::Plot)
                 .collect::<Vec<_>>()
         }
+        RelationType::Coop => {
+            let filter = ICoopGcsLocationFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                coop_id: Some(id.to_string()),
+                gcs_location_id: None,
+                role: if role.is_empty() {
+                    None
+                } else {
+                    Some(role.to_string())
+                },
+            };
+            coop_gcs_location::find_many(
+                exec,
+                &ICoopGcsLocationFindMany {
+                    filter: Some(filter),
+                },
+            )?
+            .results
+            .into_iter()
+            .map(RelationRow::Coop)
+            .collect::<Vec<_>>()
+        }
+        RelationType::ResourceArea => {
+            let filter = IResourceAreaGcsLocationFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                resource_area_id: Some(id.to_string()),
+                gcs_location_id: None,
+                role: if role.is_empty() {
+                    None
+                } else {
+                    Some(role.to_string())
+                },
+            };
+            resource_area_gcs_location::find_many(
+                exec,
+                &IResourceAreaGcsLocationFindMany {
+                    filter: Some(filter),
+                },
+            )?
+            .results
+            .into_iter()
+            .map(RelationRow::ResourceArea)
+            .collect::<Vec<_>>()
+        }
     };
 
     if rels.is_empty() {
@@ -654,6 +1176,8 @@ fn list_set_to_wire_parts(
 enum RelationRow {
     Farm(FarmGcsLocation),
     Plot(PlotGcsLocation),
+    Coop(CoopGcsLocation),
+    ResourceArea(ResourceAreaGcsLocation),
 }
 
 impl RelationRow {
@@ -661,6 +1185,8 @@ impl RelationRow {
         match self {
             Self::Farm(row) => row.gcs_location_id.as_str(),
             Self::Plot(row) => row.gcs_location_id.as_str(),
+            Self::Coop(row) => row.gcs_location_id.as_str(),
+            Self::ResourceArea(row) => row.gcs_location_id.as_str(),
         }
     }
 
@@ -668,6 +1194,8 @@ impl RelationRow {
         match self {
             Self::Farm(row) => row.role.as_str(),
             Self::Plot(row) => row.role.as_str(),
+            Self::Coop(row) => row.role.as_str(),
+            Self::ResourceArea(row) => row.role.as_str(),
         }
     }
 }
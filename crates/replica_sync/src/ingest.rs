@@ -20,23 +20,51 @@ use radroots_events::event_head::{
 };
 use radroots_events::ids::RadrootsEventId;
 use radroots_events::kinds::{
-    KIND_FARM, KIND_LISTING, KIND_PLOT, KIND_PROFILE, is_nip51_list_set_kind,
+    KIND_COOP, KIND_DELETION, KIND_DOCUMENT, KIND_FARM, KIND_LISTING, KIND_PLOT, KIND_PROFILE,
+    KIND_RESOURCE_AREA, KIND_RESOURCE_HARVEST_CAP, is_nip51_list_set_kind, is_order_event_kind,
 };
 use radroots_events::listing::{
     RadrootsListing, RadrootsListingAvailability, RadrootsListingBin, RadrootsListingStatus,
 };
+use radroots_events_codec::coop::decode as coop_decode;
+use radroots_events_codec::deletion::decode as deletion_decode;
+use radroots_events_codec::document::decode as document_decode;
 use radroots_events_codec::farm::decode as farm_decode;
 use radroots_events_codec::list_set::decode as list_set_decode;
 use radroots_events_codec::listing::decode as listing_decode;
 use radroots_events_codec::plot::decode as plot_decode;
 use radroots_events_codec::profile::decode as profile_decode;
+use radroots_events_codec::resource_area::decode as resource_area_decode;
+use radroots_events_codec::resource_cap::decode as resource_cap_decode;
 use radroots_replica_db::{
-    farm, farm_gcs_location, farm_member, farm_member_claim, farm_tag, gcs_location,
-    nostr_event_head, nostr_profile, plot, plot_gcs_location, plot_tag, trade_product,
+    ReplicaChangeOp, change, coop, coop_gcs_location, coop_tag, document, document_tag, farm,
+    farm_gcs_location, farm_member, farm_member_claim, farm_tag, gcs_location, nostr_event_head,
+    nostr_profile, plot, plot_gcs_location, plot_tag, resource_area, resource_area_gcs_location,
+    resource_area_tag, resource_harvest_cap, resource_harvest_cap_tag, trade_product,
+};
+use radroots_replica_db_schema::coop::{
+    CoopQueryBindValues, ICoopDelete, ICoopFields, ICoopFieldsFilter, ICoopFieldsPartial,
+    ICoopFindMany, ICoopFindOneArgs, ICoopUpdate,
+};
+use radroots_replica_db_schema::coop_gcs_location::{
+    CoopGcsLocationQueryBindValues, ICoopGcsLocationDelete, ICoopGcsLocationFields,
+    ICoopGcsLocationFieldsFilter, ICoopGcsLocationFindMany, ICoopGcsLocationFindOneArgs,
+};
+use radroots_replica_db_schema::coop_tag::{
+    CoopTagQueryBindValues, ICoopTagDelete, ICoopTagFields, ICoopTagFieldsFilter, ICoopTagFindMany,
+    ICoopTagFindOneArgs,
+};
+use radroots_replica_db_schema::document::{
+    DocumentQueryBindValues, IDocumentDelete, IDocumentFields, IDocumentFieldsFilter,
+    IDocumentFieldsPartial, IDocumentFindMany, IDocumentFindOneArgs, IDocumentUpdate,
+};
+use radroots_replica_db_schema::document_tag::{
+    DocumentTagQueryBindValues, IDocumentTagDelete, IDocumentTagFields, IDocumentTagFieldsFilter,
+    IDocumentTagFindMany, IDocumentTagFindOneArgs,
 };
 use radroots_replica_db_schema::farm::{
-    FarmQueryBindValues, IFarmFields, IFarmFieldsFilter, IFarmFieldsPartial, IFarmFindMany,
-    IFarmUpdate,
+    FarmQueryBindValues, IFarmDelete, IFarmFields, IFarmFieldsFilter, IFarmFieldsPartial,
+    IFarmFindMany, IFarmFindOneArgs, IFarmUpdate,
 };
 use radroots_replica_db_schema::farm_gcs_location::{
     FarmGcsLocationQueryBindValues, IFarmGcsLocationDelete, IFarmGcsLocationFields,
@@ -56,17 +84,17 @@ use radroots_replica_db_schema::farm_tag::{
 };
 use radroots_replica_db_schema::gcs_location::IGcsLocationFields;
 use radroots_replica_db_schema::nostr_event_head::{
-    INostrEventHeadFields, INostrEventHeadFieldsPartial, INostrEventHeadFindOne,
-    INostrEventHeadFindOneArgs, INostrEventHeadUpdate, NostrEventHead,
-    NostrEventHeadQueryBindValues,
+    INostrEventHeadFields, INostrEventHeadFieldsFilter, INostrEventHeadFieldsPartial,
+    INostrEventHeadFindMany, INostrEventHeadFindOne, INostrEventHeadFindOneArgs,
+    INostrEventHeadUpdate, NostrEventHead, NostrEventHeadQueryBindValues,
 };
 use radroots_replica_db_schema::nostr_profile::{
     INostrProfileFields, INostrProfileFieldsPartial, INostrProfileFindOne,
     INostrProfileFindOneArgs, INostrProfileUpdate, NostrProfileQueryBindValues,
 };
 use radroots_replica_db_schema::plot::{
-    IPlotFields, IPlotFieldsFilter, IPlotFieldsPartial, IPlotFindMany, IPlotUpdate,
-    PlotQueryBindValues,
+    IPlotDelete, IPlotFields, IPlotFieldsFilter, IPlotFieldsPartial, IPlotFindMany,
+    IPlotFindOneArgs, IPlotUpdate, PlotQueryBindValues,
 };
 use radroots_replica_db_schema::plot_gcs_location::{
     IPlotGcsLocationDelete, IPlotGcsLocationFields, IPlotGcsLocationFieldsFilter,
@@ -76,6 +104,30 @@ use radroots_replica_db_schema::plot_tag::{
     IPlotTagDelete, IPlotTagFields, IPlotTagFieldsFilter, IPlotTagFindMany, IPlotTagFindOneArgs,
     PlotTagQueryBindValues,
 };
+use radroots_replica_db_schema::resource_area::{
+    IResourceAreaDelete, IResourceAreaFields, IResourceAreaFieldsFilter,
+    IResourceAreaFieldsPartial, IResourceAreaFindMany, IResourceAreaFindOneArgs,
+    IResourceAreaUpdate, ResourceAreaQueryBindValues,
+};
+use radroots_replica_db_schema::resource_area_gcs_location::{
+    IResourceAreaGcsLocationDelete, IResourceAreaGcsLocationFields,
+    IResourceAreaGcsLocationFieldsFilter, IResourceAreaGcsLocationFindMany,
+    IResourceAreaGcsLocationFindOneArgs, ResourceAreaGcsLocationQueryBindValues,
+};
+use radroots_replica_db_schema::resource_area_tag::{
+    IResourceAreaTagDelete, IResourceAreaTagFields, IResourceAreaTagFieldsFilter,
+    IResourceAreaTagFindMany, IResourceAreaTagFindOneArgs, ResourceAreaTagQueryBindValues,
+};
+use radroots_replica_db_schema::resource_harvest_cap::{
+    IResourceHarvestCapDelete, IResourceHarvestCapFields, IResourceHarvestCapFieldsFilter,
+    IResourceHarvestCapFieldsPartial, IResourceHarvestCapFindMany, IResourceHarvestCapFindOneArgs,
+    IResourceHarvestCapUpdate, ResourceHarvestCapQueryBindValues,
+};
+use radroots_replica_db_schema::resource_harvest_cap_tag::{
+    IResourceHarvestCapTagDelete, IResourceHarvestCapTagFields, IResourceHarvestCapTagFieldsFilter,
+    IResourceHarvestCapTagFindMany, IResourceHarvestCapTagFindOneArgs,
+    ResourceHarvestCapTagQueryBindValues,
+};
 use radroots_replica_db_schema::trade_product::{
     ITradeProductFields, ITradeProductFieldsFilter, ITradeProductFieldsPartial,
     ITradeProductFindMany, ITradeProductFindOne, ITradeProductFindOneArgs, ITradeProductUpdate,
@@ -195,7 +247,13 @@ fn ingest_event_inner(
         KIND_FARM => ingest_farm_event(exec, event, factory),
         KIND_PLOT => ingest_plot_event(exec, event, factory),
         KIND_LISTING => ingest_listing_event(exec, event),
+        KIND_COOP => ingest_coop_event(exec, event, factory),
+        KIND_DOCUMENT => ingest_document_event(exec, event),
+        KIND_RESOURCE_AREA => ingest_resource_area_event(exec, event, factory),
+        KIND_RESOURCE_HARVEST_CAP => ingest_resource_harvest_cap_event(exec, event),
+        KIND_DELETION => ingest_deletion_event(exec, event),
         kind if is_nip51_list_set_kind(kind) => ingest_list_set_event(exec, event),
+        kind if is_order_event_kind(kind) => crate::order::ingest_order_event(exec, event),
         _ => Err(RadrootsReplicaEventsError::InvalidData(format!(
             "unsupported kind {}",
             event.kind
@@ -266,11 +324,14 @@ fn ingest_profile_event(
             let update_result = nostr_profile::update(
                 exec,
                 &INostrProfileUpdate {
-                    on: NostrProfileQueryBindValues::Id { id: profile.id },
+                    on: NostrProfileQueryBindValues::Id {
+                        id: profile.id.clone(),
+                    },
                     fields,
                 },
             );
             let _updated = update_result?;
+            record_change(exec, "nostr_profile", &profile.id, ReplicaChangeOp::Update)?;
         }
         None => {
             let fields = INostrProfileFields {
@@ -286,7 +347,13 @@ fn ingest_profile_event(
                 lud06: data.data.profile.lud06,
                 lud16: data.data.profile.lud16,
             };
-            let _ = nostr_profile::create(exec, &fields)?;
+            let created = nostr_profile::create(exec, &fields)?;
+            record_change(
+                exec,
+                "nostr_profile",
+                &created.result.id,
+                ReplicaChangeOp::Insert,
+            )?;
         }
     }
 
@@ -331,7 +398,7 @@ fn ingest_farm_event(
     let location = farm.location.clone();
     let (location_primary, location_city, location_region, location_country) =
         unpack_farm_location_strings(location.as_ref());
-    let farm_id = if let Some(row) = existing.results.get(0) {
+    let (farm_id, change_op) = if let Some(row) = existing.results.get(0) {
         let fields = IFarmFieldsPartial {
             d_tag: Some(Value::from(farm.d_tag.clone())),
             pubkey: Some(Value::from(event.author.clone())),
@@ -353,7 +420,7 @@ fn ingest_farm_event(
             },
         );
         let _updated = update_result?;
-        row.id.clone()
+        (row.id.clone(), ReplicaChangeOp::Update)
     } else {
         let fields = IFarmFields {
             d_tag: farm.d_tag.clone(),
@@ -368,12 +435,16 @@ fn ingest_farm_event(
             location_region,
             location_country,
         };
-        farm::create(exec, &fields)?.result.id
+        (
+            farm::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
     };
 
     upsert_farm_tags(exec, &farm_id, farm.tags)?;
     upsert_farm_location(exec, &farm_id, location, factory)?;
 
+    record_change(exec, "farm", &farm_id, change_op)?;
     upsert_event_head(exec, &decision)?;
     Ok(RadrootsReplicaIngestOutcome::Applied)
 }
@@ -413,7 +484,7 @@ fn ingest_plot_event(
     let location = plot.location.clone();
     let (location_primary, location_city, location_region, location_country) =
         unpack_plot_location_strings(location.as_ref());
-    let plot_id = if let Some(row) = existing.results.get(0) {
+    let (plot_id, change_op) = if let Some(row) = existing.results.get(0) {
         let fields = IPlotFieldsPartial {
             d_tag: Some(Value::from(plot.d_tag.clone())),
             farm_id: Some(Value::from(farm.id.clone())),
@@ -432,7 +503,7 @@ fn ingest_plot_event(
             },
         );
         let _updated = update_result?;
-        row.id.clone()
+        (row.id.clone(), ReplicaChangeOp::Update)
     } else {
         let fields = IPlotFields {
             d_tag: plot.d_tag.clone(),
@@ -444,12 +515,327 @@ fn ingest_plot_event(
             location_region,
             location_country,
         };
-        plot::create(exec, &fields)?.result.id
+        (
+            plot::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
     };
 
     upsert_plot_tags(exec, &plot_id, plot.tags)?;
     upsert_plot_location(exec, &plot_id, location, factory)?;
 
+    record_change(exec, "plot", &plot_id, change_op)?;
+    upsert_event_head(exec, &decision)?;
+    Ok(RadrootsReplicaIngestOutcome::Applied)
+}
+
+fn ingest_coop_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+    factory: &dyn RadrootsReplicaIdFactory,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let coop = coop_decode::coop_from_event(event.kind, &event.tags, &event.content)?;
+    let decision = event_head_decision(exec, event)?;
+    if !decision.apply {
+        return Ok(RadrootsReplicaIngestOutcome::Skipped);
+    }
+
+    let existing = coop::find_many(
+        exec,
+        &ICoopFindMany {
+            filter: Some(coop_filter(&event.author, &coop.d_tag)),
+        },
+    )?;
+    let location = coop.location.clone();
+    let (location_primary, location_city, location_region, location_country) =
+        match location.as_ref() {
+            Some(location) => (
+                location.primary.clone(),
+                location.city.clone(),
+                location.region.clone(),
+                location.country.clone(),
+            ),
+            None => (None, None, None, None),
+        };
+    let (coop_id, change_op) = if let Some(row) = existing.results.first() {
+        let fields = ICoopFieldsPartial {
+            d_tag: Some(Value::from(coop.d_tag.clone())),
+            pubkey: Some(Value::from(event.author.clone())),
+            name: Some(Value::from(coop.name.clone())),
+            about: to_value_opt(coop.about.clone()),
+            website: to_value_opt(coop.website.clone()),
+            picture: to_value_opt(coop.picture.clone()),
+            banner: to_value_opt(coop.banner.clone()),
+            location_primary: to_value_opt(location_primary),
+            location_city: to_value_opt(location_city),
+            location_region: to_value_opt(location_region),
+            location_country: to_value_opt(location_country),
+        };
+        let _updated = coop::update(
+            exec,
+            &ICoopUpdate {
+                on: CoopQueryBindValues::Id { id: row.id.clone() },
+                fields,
+            },
+        )?;
+        (row.id.clone(), ReplicaChangeOp::Update)
+    } else {
+        let fields = ICoopFields {
+            d_tag: coop.d_tag.clone(),
+            pubkey: event.author.clone(),
+            name: coop.name.clone(),
+            about: coop.about.clone(),
+            website: coop.website.clone(),
+            picture: coop.picture.clone(),
+            banner: coop.banner.clone(),
+            location_primary,
+            location_city,
+            location_region,
+            location_country,
+        };
+        (
+            coop::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
+    };
+
+    upsert_coop_tags(exec, &coop_id, coop.tags)?;
+    clear_coop_locations(exec, &coop_id)?;
+    if let Some(location) = location {
+        let gcs_id = create_gcs_location(exec, location.gcs, factory)?;
+        let _ = coop_gcs_location::create(
+            exec,
+            &ICoopGcsLocationFields {
+                coop_id: coop_id.clone(),
+                gcs_location_id: gcs_id,
+                role: ROLE_PRIMARY.to_string(),
+            },
+        )?;
+    }
+
+    record_change(exec, "coop", &coop_id, change_op)?;
+    upsert_event_head(exec, &decision)?;
+    Ok(RadrootsReplicaIngestOutcome::Applied)
+}
+
+fn ingest_document_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let document = document_decode::document_from_event(event.kind, &event.tags, &event.content)?;
+    let decision = event_head_decision(exec, event)?;
+    if !decision.apply {
+        return Ok(RadrootsReplicaIngestOutcome::Skipped);
+    }
+
+    let existing = document::find_many(
+        exec,
+        &IDocumentFindMany {
+            filter: Some(document_filter(&event.author, &document.d_tag)),
+        },
+    )?;
+    let (document_id, change_op) = if let Some(row) = existing.results.first() {
+        let fields = IDocumentFieldsPartial {
+            d_tag: Some(Value::from(document.d_tag.clone())),
+            pubkey: Some(Value::from(event.author.clone())),
+            doc_type: Some(Value::from(document.doc_type.clone())),
+            title: Some(Value::from(document.title.clone())),
+            version: Some(Value::from(document.version.clone())),
+            summary: to_value_opt(document.summary.clone()),
+            effective_at: Some(document.effective_at.map_or(Value::Null, Value::from)),
+            body_markdown: to_value_opt(document.body_markdown.clone()),
+            subject_pubkey: Some(Value::from(document.subject.pubkey.clone())),
+            subject_address: to_value_opt(document.subject.address.clone()),
+        };
+        let _updated = document::update(
+            exec,
+            &IDocumentUpdate {
+                on: DocumentQueryBindValues::Id { id: row.id.clone() },
+                fields,
+            },
+        )?;
+        (row.id.clone(), ReplicaChangeOp::Update)
+    } else {
+        let fields = IDocumentFields {
+            d_tag: document.d_tag.clone(),
+            pubkey: event.author.clone(),
+            doc_type: document.doc_type.clone(),
+            title: document.title.clone(),
+            version: document.version.clone(),
+            summary: document.summary.clone(),
+            effective_at: document.effective_at,
+            body_markdown: document.body_markdown.clone(),
+            subject_pubkey: document.subject.pubkey.clone(),
+            subject_address: document.subject.address.clone(),
+        };
+        (
+            document::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
+    };
+
+    upsert_document_tags(exec, &document_id, document.tags)?;
+
+    record_change(exec, "document", &document_id, change_op)?;
+    upsert_event_head(exec, &decision)?;
+    Ok(RadrootsReplicaIngestOutcome::Applied)
+}
+
+fn ingest_resource_area_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+    factory: &dyn RadrootsReplicaIdFactory,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let area =
+        resource_area_decode::resource_area_from_event(event.kind, &event.tags, &event.content)?;
+    let decision = event_head_decision(exec, event)?;
+    if !decision.apply {
+        return Ok(RadrootsReplicaIngestOutcome::Skipped);
+    }
+
+    let existing = resource_area::find_many(
+        exec,
+        &IResourceAreaFindMany {
+            filter: Some(resource_area_filter(&event.author, &area.d_tag)),
+        },
+    )?;
+    let location = area.location;
+    let (area_id, change_op) = if let Some(row) = existing.results.first() {
+        let fields = IResourceAreaFieldsPartial {
+            d_tag: Some(Value::from(area.d_tag.clone())),
+            pubkey: Some(Value::from(event.author.clone())),
+            name: Some(Value::from(area.name.clone())),
+            about: to_value_opt(area.about.clone()),
+            location_primary: to_value_opt(location.primary.clone()),
+            location_city: to_value_opt(location.city.clone()),
+            location_region: to_value_opt(location.region.clone()),
+            location_country: to_value_opt(location.country.clone()),
+        };
+        let _updated = resource_area::update(
+            exec,
+            &IResourceAreaUpdate {
+                on: ResourceAreaQueryBindValues::Id { id: row.id.clone() },
+                fields,
+            },
+        )?;
+        (row.id.clone(), ReplicaChangeOp::Update)
+    } else {
+        let fields = IResourceAreaFields {
+            d_tag: area.d_tag.clone(),
+            pubkey: event.author.clone(),
+            name: area.name.clone(),
+            about: area.about.clone(),
+            location_primary: location.primary.clone(),
+            location_city: location.city.clone(),
+            location_region: location.region.clone(),
+            location_country: location.country.clone(),
+        };
+        (
+            resource_area::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
+    };
+
+    upsert_resource_area_tags(exec, &area_id, area.tags)?;
+    clear_resource_area_locations(exec, &area_id)?;
+    let gcs_id = create_gcs_location(exec, location.gcs, factory)?;
+    let _ = resource_area_gcs_location::create(
+        exec,
+        &IResourceAreaGcsLocationFields {
+            resource_area_id: area_id.clone(),
+            gcs_location_id: gcs_id,
+            role: ROLE_PRIMARY.to_string(),
+        },
+    )?;
+
+    record_change(exec, "resource_area", &area_id, change_op)?;
+    upsert_event_head(exec, &decision)?;
+    Ok(RadrootsReplicaIngestOutcome::Applied)
+}
+
+fn ingest_resource_harvest_cap_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let cap = resource_cap_decode::resource_harvest_cap_from_event(
+        event.kind,
+        &event.tags,
+        &event.content,
+    )?;
+    let decision = event_head_decision(exec, event)?;
+    if !decision.apply {
+        return Ok(RadrootsReplicaIngestOutcome::Skipped);
+    }
+
+    let area =
+        find_resource_area_by_ref(exec, &cap.resource_area.pubkey, &cap.resource_area.d_tag)?;
+    let start_at = i64::try_from(cap.start).map_err(|_| {
+        RadrootsReplicaEventsError::InvalidData("resource_harvest_cap.start".to_string())
+    })?;
+    let end_at = i64::try_from(cap.end).map_err(|_| {
+        RadrootsReplicaEventsError::InvalidData("resource_harvest_cap.end".to_string())
+    })?;
+    let display_amount = cap.display_amount.as_ref().map(ToString::to_string);
+    let display_unit = cap.display_unit.as_ref().map(ToString::to_string);
+    let existing = resource_harvest_cap::find_many(
+        exec,
+        &IResourceHarvestCapFindMany {
+            filter: Some(resource_harvest_cap_filter(
+                Some(&event.author),
+                Some(&cap.d_tag),
+                None,
+            )),
+        },
+    )?;
+    let (cap_id, change_op) = if let Some(row) = existing.results.first() {
+        let fields = IResourceHarvestCapFieldsPartial {
+            d_tag: Some(Value::from(cap.d_tag.clone())),
+            pubkey: Some(Value::from(event.author.clone())),
+            resource_area_id: Some(Value::from(area.id.clone())),
+            product_key: Some(Value::from(cap.product.key.clone())),
+            product_category: to_value_opt(cap.product.category.clone()),
+            start_at: Some(Value::from(start_at)),
+            end_at: Some(Value::from(end_at)),
+            cap_amount: Some(Value::from(cap.cap_quantity.amount.to_string())),
+            cap_unit: Some(Value::from(cap.cap_quantity.unit.to_string())),
+            cap_label: to_value_opt(cap.cap_quantity.label.clone()),
+            display_amount: to_value_opt(display_amount),
+            display_unit: to_value_opt(display_unit),
+            display_label: to_value_opt(cap.display_label.clone()),
+        };
+        let _updated = resource_harvest_cap::update(
+            exec,
+            &IResourceHarvestCapUpdate {
+                on: ResourceHarvestCapQueryBindValues::Id { id: row.id.clone() },
+                fields,
+            },
+        )?;
+        (row.id.clone(), ReplicaChangeOp::Update)
+    } else {
+        let fields = IResourceHarvestCapFields {
+            d_tag: cap.d_tag.clone(),
+            pubkey: event.author.clone(),
+            resource_area_id: area.id.clone(),
+            product_key: cap.product.key.clone(),
+            product_category: cap.product.category.clone(),
+            start_at,
+            end_at,
+            cap_amount: cap.cap_quantity.amount.to_string(),
+            cap_unit: cap.cap_quantity.unit.to_string(),
+            cap_label: cap.cap_quantity.label.clone(),
+            display_amount,
+            display_unit,
+            display_label: cap.display_label.clone(),
+        };
+        (
+            resource_harvest_cap::create(exec, &fields)?.result.id,
+            ReplicaChangeOp::Insert,
+        )
+    };
+
+    upsert_resource_harvest_cap_tags(exec, &cap_id, cap.tags)?;
+
+    record_change(exec, "resource_harvest_cap", &cap_id, change_op)?;
     upsert_event_head(exec, &decision)?;
     Ok(RadrootsReplicaIngestOutcome::Applied)
 }
@@ -530,6 +916,7 @@ fn ingest_list_set_event(
         }
         let farm = find_farm_by_ref(exec, &event.author, &farm_d_tag)?;
         upsert_farm_members(exec, &farm.id, role, &list_set)?;
+        record_change(exec, "farm", &farm.id, ReplicaChangeOp::Update)?;
         upsert_event_head(exec, &decision)?;
         return Ok(RadrootsReplicaIngestOutcome::Applied);
     }
@@ -539,6 +926,272 @@ fn ingest_list_set_event(
     ))
 }
 
+fn ingest_deletion_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let deletion = deletion_decode::deletion_from_tags(event.kind, &event.tags, &event.content)?;
+    let mut targets = Vec::new();
+    for address in &deletion.addresses {
+        let mut parts = address.splitn(3, ':');
+        let (Some(kind), Some(pubkey), Some(d_tag)) = (
+            parts.next().and_then(|kind| kind.parse::<u32>().ok()),
+            parts.next(),
+            parts.next(),
+        ) else {
+            continue;
+        };
+        if pubkey == event.author {
+            targets.push((kind, d_tag.to_string()));
+        }
+    }
+    for event_id in &deletion.event_ids {
+        let heads = nostr_event_head::find_many(
+            exec,
+            &INostrEventHeadFindMany {
+                filter: Some(INostrEventHeadFieldsFilter {
+                    id: None,
+                    created_at: None,
+                    updated_at: None,
+                    key: None,
+                    kind: None,
+                    pubkey: Some(event.author.clone()),
+                    d_tag: None,
+                    last_event_id: Some(event_id.clone()),
+                    last_created_at: None,
+                    content_hash: None,
+                }),
+            },
+        )?
+        .results;
+        targets.extend(heads.into_iter().map(|head| (head.kind, head.d_tag)));
+    }
+
+    let mut applied = false;
+    for (kind, d_tag) in targets {
+        applied |= delete_replica_coordinate(exec, event, kind, &d_tag)?;
+    }
+    Ok(if applied {
+        RadrootsReplicaIngestOutcome::Applied
+    } else {
+        RadrootsReplicaIngestOutcome::Skipped
+    })
+}
+
+fn delete_replica_coordinate(
+    exec: &dyn SqlExecutor,
+    deletion: &RadrootsNostrEvent,
+    kind: u32,
+    d_tag: &str,
+) -> Result<bool, RadrootsReplicaEventsError> {
+    if !matches!(
+        kind,
+        KIND_FARM
+            | KIND_PLOT
+            | KIND_LISTING
+            | KIND_COOP
+            | KIND_DOCUMENT
+            | KIND_RESOURCE_AREA
+            | KIND_RESOURCE_HARVEST_CAP
+    ) {
+        return Ok(false);
+    }
+    let key = event_head_key(kind, &deletion.author, d_tag);
+    let head = nostr_event_head::find_one(
+        exec,
+        &INostrEventHeadFindOne::On(INostrEventHeadFindOneArgs {
+            on: NostrEventHeadQueryBindValues::Key { key: key.clone() },
+        }),
+    )?
+    .result;
+    if let Some(head) = head.as_ref()
+        && (head.last_created_at > deletion.created_at || head.last_event_id == deletion.id)
+    {
+        return Ok(false);
+    }
+
+    match kind {
+        KIND_FARM => {
+            if let Ok(farm) = find_farm_by_ref(exec, &deletion.author, d_tag) {
+                delete_farm_rows(exec, &farm.id)?;
+            }
+        }
+        KIND_PLOT => {
+            for plot_id in find_plot_ids_for_author(exec, &deletion.author, d_tag)? {
+                delete_plot_rows(exec, &plot_id)?;
+            }
+        }
+        KIND_COOP => {
+            let coops = coop::find_many(
+                exec,
+                &ICoopFindMany {
+                    filter: Some(coop_filter(&deletion.author, d_tag)),
+                },
+            )?
+            .results;
+            for row in coops {
+                delete_coop_rows(exec, &row.id)?;
+            }
+        }
+        KIND_DOCUMENT => {
+            let documents = document::find_many(
+                exec,
+                &IDocumentFindMany {
+                    filter: Some(document_filter(&deletion.author, d_tag)),
+                },
+            )?
+            .results;
+            for row in documents {
+                delete_document_rows(exec, &row.id)?;
+            }
+        }
+        KIND_RESOURCE_AREA => {
+            if let Ok(area) = find_resource_area_by_ref(exec, &deletion.author, d_tag) {
+                delete_resource_area_rows(exec, &area.id)?;
+            }
+        }
+        KIND_RESOURCE_HARVEST_CAP => {
+            let caps = resource_harvest_cap::find_many(
+                exec,
+                &IResourceHarvestCapFindMany {
+                    filter: Some(resource_harvest_cap_filter(
+                        Some(&deletion.author),
+                        Some(d_tag),
+                        None,
+                    )),
+                },
+            )?
+            .results;
+            for row in caps {
+                delete_resource_harvest_cap_rows(exec, &row.id)?;
+            }
+        }
+        _ => {
+            delete_trade_products_for_listing_addr(exec, &key)?;
+        }
+    }
+
+    #[cfg(test)]
+    let content_hash = event_content_hash(&deletion.content, &deletion.tags)?;
+    #[cfg(not(test))]
+    let content_hash = event_content_hash(&deletion.content, &deletion.tags);
+    upsert_event_head(
+        exec,
+        &EventHeadDecision {
+            apply: true,
+            key,
+            kind,
+            pubkey: deletion.author.clone(),
+            d_tag: d_tag.to_string(),
+            last_event_id: deletion.id.clone(),
+            last_created_at: deletion.created_at,
+            content_hash,
+        },
+    )?;
+    Ok(true)
+}
+
+fn find_plot_ids_for_author(
+    exec: &dyn SqlExecutor,
+    pubkey: &str,
+    d_tag: &str,
+) -> Result<Vec<String>, RadrootsReplicaEventsError> {
+    let farms = farm::find_many(
+        exec,
+        &IFarmFindMany {
+            filter: Some(IFarmFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                d_tag: None,
+                pubkey: Some(pubkey.to_string()),
+                name: None,
+                about: None,
+                website: None,
+                picture: None,
+                banner: None,
+                location_primary: None,
+                location_city: None,
+                location_region: None,
+                location_country: None,
+            }),
+        },
+    )?
+    .results;
+    let mut plot_ids = Vec::new();
+    for farm in farms {
+        let plots = plot::find_many(
+            exec,
+            &IPlotFindMany {
+                filter: Some(plot_filter_for_farm(&farm.id, Some(d_tag))),
+            },
+        )?
+        .results;
+        plot_ids.extend(plots.into_iter().map(|plot| plot.id));
+    }
+    Ok(plot_ids)
+}
+
+fn plot_filter_for_farm(farm_id: &str, d_tag: Option<&str>) -> IPlotFieldsFilter {
+    IPlotFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: d_tag.map(ToString::to_string),
+        farm_id: Some(farm_id.to_string()),
+        name: None,
+        about: None,
+        location_primary: None,
+        location_city: None,
+        location_region: None,
+        location_country: None,
+    }
+}
+
+fn delete_farm_rows(
+    exec: &dyn SqlExecutor,
+    farm_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let plots = plot::find_many(
+        exec,
+        &IPlotFindMany {
+            filter: Some(plot_filter_for_farm(farm_id, None)),
+        },
+    )?
+    .results;
+    for plot in plots {
+        delete_plot_rows(exec, &plot.id)?;
+    }
+    upsert_farm_tags(exec, farm_id, None)?;
+    clear_farm_locations(exec, farm_id)?;
+    handle_delete_result(farm::delete(
+        exec,
+        &IFarmDelete::On(IFarmFindOneArgs {
+            on: FarmQueryBindValues::Id {
+                id: farm_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(exec, "farm", farm_id, ReplicaChangeOp::Delete)
+}
+
+fn delete_plot_rows(
+    exec: &dyn SqlExecutor,
+    plot_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    upsert_plot_tags(exec, plot_id, None)?;
+    clear_plot_locations(exec, plot_id)?;
+    handle_delete_result(plot::delete(
+        exec,
+        &IPlotDelete::On(IPlotFindOneArgs {
+            on: PlotQueryBindValues::Id {
+                id: plot_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(exec, "plot", plot_id, ReplicaChangeOp::Delete)
+}
+
 fn listing_event_addr(event: &RadrootsNostrEvent, listing: &RadrootsListing) -> String {
     format!("{}:{}:{}", event.kind, event.author, listing.d_tag)
 }
@@ -620,8 +1273,13 @@ fn trade_product_fields_from_listing(
         price_qty_amt_exact,
         price_qty_unit,
         listing_addr: Some(listing_addr.to_string()),
+        farm_pubkey: Some(listing.farm.pubkey.clone()),
+        farm_d_tag: Some(listing.farm.d_tag.clone()),
         primary_bin_id: Some(listing.primary_bin_id.to_string()),
         verified_primary_bin_id: Some(listing.primary_bin_id.to_string()),
+        expires_at: listing
+            .expiration
+            .and_then(|expiration| i64::try_from(expiration).ok()),
         notes: trade_product_notes_from_listing(listing)?,
     })
 }
@@ -711,8 +1369,11 @@ fn trade_product_listing_addr_filter(listing_addr: &str) -> ITradeProductFieldsF
         price_qty_amt_exact: None,
         price_qty_unit: None,
         listing_addr: Some(listing_addr.to_string()),
+        farm_pubkey: None,
+        farm_d_tag: None,
         primary_bin_id: None,
         verified_primary_bin_id: None,
+        expires_at: None,
         notes: None,
     }
 }
@@ -736,11 +1397,18 @@ fn upsert_trade_product_for_listing_addr(
             fields: trade_product_partial_from_fields(&fields),
         };
         let _ = trade_product::update(exec, &update)?;
+        record_change(exec, "trade_product", &row.id, ReplicaChangeOp::Update)?;
         for duplicate in existing.iter().skip(1) {
             delete_trade_product_by_id(exec, &duplicate.id)?;
         }
     } else {
-        let _ = trade_product::create(exec, &fields)?;
+        let created = trade_product::create(exec, &fields)?;
+        record_change(
+            exec,
+            "trade_product",
+            &created.result.id,
+            ReplicaChangeOp::Insert,
+        )?;
     }
 
     Ok(())
@@ -775,7 +1443,7 @@ fn delete_trade_product_by_id(
             on: TradeProductQueryBindValues::Id { id: id.to_string() },
         }),
     )?;
-    Ok(())
+    record_change(exec, "trade_product", id, ReplicaChangeOp::Delete)
 }
 
 fn trade_product_partial_from_fields(fields: &ITradeProductFields) -> ITradeProductFieldsPartial {
@@ -800,8 +1468,11 @@ fn trade_product_partial_from_fields(fields: &ITradeProductFields) -> ITradeProd
         price_qty_amt_exact: Some(Value::from(fields.price_qty_amt_exact.clone())),
         price_qty_unit: Some(Value::from(fields.price_qty_unit.clone())),
         listing_addr: to_value_opt(fields.listing_addr.clone()),
+        farm_pubkey: to_value_opt(fields.farm_pubkey.clone()),
+        farm_d_tag: to_value_opt(fields.farm_d_tag.clone()),
         primary_bin_id: to_value_opt(fields.primary_bin_id.clone()),
         verified_primary_bin_id: to_value_opt(fields.verified_primary_bin_id.clone()),
+        expires_at: fields.expires_at.map(Value::from).or(Some(Value::Null)),
         notes: to_value_opt(fields.notes.clone()),
     }
 }
@@ -846,11 +1517,14 @@ fn upsert_event_head(
             let update_result = nostr_event_head::update(
                 exec,
                 &INostrEventHeadUpdate {
-                    on: NostrEventHeadQueryBindValues::Id { id: state.id },
+                    on: NostrEventHeadQueryBindValues::Id {
+                        id: state.id.clone(),
+                    },
                     fields,
                 },
             );
             let _updated = update_result?;
+            record_change(exec, "nostr_event_head", &state.id, ReplicaChangeOp::Update)?;
         }
         None => {
             let fields = INostrEventHeadFields {
@@ -862,7 +1536,8 @@ fn upsert_event_head(
                 last_created_at: decision.last_created_at,
                 content_hash: decision.content_hash.clone(),
             };
-            let _ = nostr_event_head::create(exec, &fields)?;
+            let head_id = nostr_event_head::create(exec, &fields)?.result.id;
+            record_change(exec, "nostr_event_head", &head_id, ReplicaChangeOp::Insert)?;
         }
     }
 
@@ -1126,83 +1801,494 @@ fn upsert_farm_location(
     Ok(())
 }
 
-fn upsert_plot_location(
+fn upsert_plot_location(
+    exec: &dyn SqlExecutor,
+    plot_id: &str,
+    location: Option<radroots_events::plot::RadrootsPlotLocation>,
+    factory: &dyn RadrootsReplicaIdFactory,
+) -> Result<(), RadrootsReplicaEventsError> {
+    clear_plot_locations(exec, plot_id)?;
+    if let Some(location) = location {
+        let gcs_id = create_gcs_location(exec, location.gcs, factory)?;
+        let fields = IPlotGcsLocationFields {
+            plot_id: plot_id.to_string(),
+            gcs_location_id: gcs_id,
+            role: ROLE_PRIMARY.to_string(),
+        };
+        let _ = plot_gcs_location::create(exec, &fields)?;
+    }
+    Ok(())
+}
+
+fn clear_farm_locations(
+    exec: &dyn SqlExecutor,
+    farm_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing_query = farm_gcs_location::find_many(
+        exec,
+        &IFarmGcsLocationFindMany {
+            filter: Some(IFarmGcsLocationFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                farm_id: Some(farm_id.to_string()),
+                gcs_location_id: None,
+                role: None,
+            }),
+        },
+    );
+    let existing = existing_query?;
+    for row in existing.results {
+        handle_delete_result(farm_gcs_location::delete(
+            exec,
+            &IFarmGcsLocationDelete::On(IFarmGcsLocationFindOneArgs {
+                on: FarmGcsLocationQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+    Ok(())
+}
+
+fn clear_plot_locations(
+    exec: &dyn SqlExecutor,
+    plot_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing_query = plot_gcs_location::find_many(
+        exec,
+        &IPlotGcsLocationFindMany {
+            filter: Some(IPlotGcsLocationFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                plot_id: Some(plot_id.to_string()),
+                gcs_location_id: None,
+                role: None,
+            }),
+        },
+    );
+    let existing = existing_query?;
+    for row in existing.results {
+        handle_delete_result(plot_gcs_location::delete(
+            exec,
+            &IPlotGcsLocationDelete::On(IPlotGcsLocationFindOneArgs {
+                on: PlotGcsLocationQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+    Ok(())
+}
+
+fn coop_filter(pubkey: &str, d_tag: &str) -> ICoopFieldsFilter {
+    ICoopFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: Some(d_tag.to_string()),
+        pubkey: Some(pubkey.to_string()),
+        name: None,
+        about: None,
+        website: None,
+        picture: None,
+        banner: None,
+        location_primary: None,
+        location_city: None,
+        location_region: None,
+        location_country: None,
+    }
+}
+
+fn document_filter(pubkey: &str, d_tag: &str) -> IDocumentFieldsFilter {
+    IDocumentFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: Some(d_tag.to_string()),
+        pubkey: Some(pubkey.to_string()),
+        doc_type: None,
+        title: None,
+        version: None,
+        summary: None,
+        effective_at: None,
+        body_markdown: None,
+        subject_pubkey: None,
+        subject_address: None,
+    }
+}
+
+fn resource_area_filter(pubkey: &str, d_tag: &str) -> IResourceAreaFieldsFilter {
+    IResourceAreaFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: Some(d_tag.to_string()),
+        pubkey: Some(pubkey.to_string()),
+        name: None,
+        about: None,
+        location_primary: None,
+        location_city: None,
+        location_region: None,
+        location_country: None,
+    }
+}
+
+fn resource_harvest_cap_filter(
+    pubkey: Option<&str>,
+    d_tag: Option<&str>,
+    resource_area_id: Option<&str>,
+) -> IResourceHarvestCapFieldsFilter {
+    IResourceHarvestCapFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        d_tag: d_tag.map(ToString::to_string),
+        pubkey: pubkey.map(ToString::to_string),
+        resource_area_id: resource_area_id.map(ToString::to_string),
+        product_key: None,
+        product_category: None,
+        start_at: None,
+        end_at: None,
+        cap_amount: None,
+        cap_unit: None,
+        cap_label: None,
+        display_amount: None,
+        display_unit: None,
+        display_label: None,
+    }
+}
+
+fn find_resource_area_by_ref(
+    exec: &dyn SqlExecutor,
+    pubkey: &str,
+    d_tag: &str,
+) -> Result<radroots_replica_db_schema::resource_area::ResourceArea, RadrootsReplicaEventsError> {
+    let result = resource_area::find_many(
+        exec,
+        &IResourceAreaFindMany {
+            filter: Some(resource_area_filter(pubkey, d_tag)),
+        },
+    )?;
+    match result.results.into_iter().next() {
+        Some(area) => Ok(area),
+        None => Err(RadrootsReplicaEventsError::InvalidData(
+            "resource area not found".to_string(),
+        )),
+    }
+}
+
+fn upsert_coop_tags(
+    exec: &dyn SqlExecutor,
+    coop_id: &str,
+    tags: Option<Vec<String>>,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing = coop_tag::find_many(
+        exec,
+        &ICoopTagFindMany {
+            filter: Some(ICoopTagFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                coop_id: Some(coop_id.to_string()),
+                tag: None,
+            }),
+        },
+    )?;
+    for row in existing.results {
+        handle_delete_result(coop_tag::delete(
+            exec,
+            &ICoopTagDelete::On(ICoopTagFindOneArgs {
+                on: CoopTagQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+
+    let mut tags = tags.unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        if tag.trim().is_empty() {
+            continue;
+        }
+        let fields = ICoopTagFields {
+            coop_id: coop_id.to_string(),
+            tag,
+        };
+        let _ = coop_tag::create(exec, &fields)?;
+    }
+    Ok(())
+}
+
+fn upsert_document_tags(
+    exec: &dyn SqlExecutor,
+    document_id: &str,
+    tags: Option<Vec<String>>,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing = document_tag::find_many(
+        exec,
+        &IDocumentTagFindMany {
+            filter: Some(IDocumentTagFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                document_id: Some(document_id.to_string()),
+                tag: None,
+            }),
+        },
+    )?;
+    for row in existing.results {
+        handle_delete_result(document_tag::delete(
+            exec,
+            &IDocumentTagDelete::On(IDocumentTagFindOneArgs {
+                on: DocumentTagQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+
+    let mut tags = tags.unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        if tag.trim().is_empty() {
+            continue;
+        }
+        let fields = IDocumentTagFields {
+            document_id: document_id.to_string(),
+            tag,
+        };
+        let _ = document_tag::create(exec, &fields)?;
+    }
+    Ok(())
+}
+
+fn upsert_resource_area_tags(
+    exec: &dyn SqlExecutor,
+    resource_area_id: &str,
+    tags: Option<Vec<String>>,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing = resource_area_tag::find_many(
+        exec,
+        &IResourceAreaTagFindMany {
+            filter: Some(IResourceAreaTagFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                resource_area_id: Some(resource_area_id.to_string()),
+                tag: None,
+            }),
+        },
+    )?;
+    for row in existing.results {
+        handle_delete_result(resource_area_tag::delete(
+            exec,
+            &IResourceAreaTagDelete::On(IResourceAreaTagFindOneArgs {
+                on: ResourceAreaTagQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+
+    let mut tags = tags.unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        if tag.trim().is_empty() {
+            continue;
+        }
+        let fields = IResourceAreaTagFields {
+            resource_area_id: resource_area_id.to_string(),
+            tag,
+        };
+        let _ = resource_area_tag::create(exec, &fields)?;
+    }
+    Ok(())
+}
+
+fn upsert_resource_harvest_cap_tags(
     exec: &dyn SqlExecutor,
-    plot_id: &str,
-    location: Option<radroots_events::plot::RadrootsPlotLocation>,
-    factory: &dyn RadrootsReplicaIdFactory,
+    resource_harvest_cap_id: &str,
+    tags: Option<Vec<String>>,
 ) -> Result<(), RadrootsReplicaEventsError> {
-    clear_plot_locations(exec, plot_id)?;
-    if let Some(location) = location {
-        let gcs_id = create_gcs_location(exec, location.gcs, factory)?;
-        let fields = IPlotGcsLocationFields {
-            plot_id: plot_id.to_string(),
-            gcs_location_id: gcs_id,
-            role: ROLE_PRIMARY.to_string(),
+    let existing = resource_harvest_cap_tag::find_many(
+        exec,
+        &IResourceHarvestCapTagFindMany {
+            filter: Some(IResourceHarvestCapTagFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                resource_harvest_cap_id: Some(resource_harvest_cap_id.to_string()),
+                tag: None,
+            }),
+        },
+    )?;
+    for row in existing.results {
+        handle_delete_result(resource_harvest_cap_tag::delete(
+            exec,
+            &IResourceHarvestCapTagDelete::On(IResourceHarvestCapTagFindOneArgs {
+                on: ResourceHarvestCapTagQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+
+    let mut tags = tags.unwrap_or_default();
+    tags.sort();
+    tags.dedup();
+    for tag in tags {
+        if tag.trim().is_empty() {
+            continue;
+        }
+        let fields = IResourceHarvestCapTagFields {
+            resource_harvest_cap_id: resource_harvest_cap_id.to_string(),
+            tag,
         };
-        let _ = plot_gcs_location::create(exec, &fields)?;
+        let _ = resource_harvest_cap_tag::create(exec, &fields)?;
     }
     Ok(())
 }
 
-fn clear_farm_locations(
+fn clear_coop_locations(
     exec: &dyn SqlExecutor,
-    farm_id: &str,
+    coop_id: &str,
 ) -> Result<(), RadrootsReplicaEventsError> {
-    let existing_query = farm_gcs_location::find_many(
+    let existing = coop_gcs_location::find_many(
         exec,
-        &IFarmGcsLocationFindMany {
-            filter: Some(IFarmGcsLocationFieldsFilter {
+        &ICoopGcsLocationFindMany {
+            filter: Some(ICoopGcsLocationFieldsFilter {
                 id: None,
                 created_at: None,
                 updated_at: None,
-                farm_id: Some(farm_id.to_string()),
+                coop_id: Some(coop_id.to_string()),
                 gcs_location_id: None,
                 role: None,
             }),
         },
-    );
-    let existing = existing_query?;
+    )?;
     for row in existing.results {
-        handle_delete_result(farm_gcs_location::delete(
+        handle_delete_result(coop_gcs_location::delete(
             exec,
-            &IFarmGcsLocationDelete::On(IFarmGcsLocationFindOneArgs {
-                on: FarmGcsLocationQueryBindValues::Id { id: row.id },
+            &ICoopGcsLocationDelete::On(ICoopGcsLocationFindOneArgs {
+                on: CoopGcsLocationQueryBindValues::Id { id: row.id },
             }),
         ))?;
     }
     Ok(())
 }
 
-fn clear_plot_locations(
+fn clear_resource_area_locations(
     exec: &dyn SqlExecutor,
-    plot_id: &str,
+    resource_area_id: &str,
 ) -> Result<(), RadrootsReplicaEventsError> {
-    let existing_query = plot_gcs_location::find_many(
+    let existing = resource_area_gcs_location::find_many(
         exec,
-        &IPlotGcsLocationFindMany {
-            filter: Some(IPlotGcsLocationFieldsFilter {
+        &IResourceAreaGcsLocationFindMany {
+            filter: Some(IResourceAreaGcsLocationFieldsFilter {
                 id: None,
                 created_at: None,
                 updated_at: None,
-                plot_id: Some(plot_id.to_string()),
+                resource_area_id: Some(resource_area_id.to_string()),
                 gcs_location_id: None,
                 role: None,
             }),
         },
-    );
-    let existing = existing_query?;
+    )?;
     for row in existing.results {
-        handle_delete_result(plot_gcs_location::delete(
+        handle_delete_result(resource_area_gcs_location::delete(
             exec,
-            &IPlotGcsLocationDelete::On(IPlotGcsLocationFindOneArgs {
-                on: PlotGcsLocationQueryBindValues::Id { id: row.id },
+            &IResourceAreaGcsLocationDelete::On(IResourceAreaGcsLocationFindOneArgs {
+                on: ResourceAreaGcsLocationQueryBindValues::Id { id: row.id },
             }),
         ))?;
     }
     Ok(())
 }
 
+fn delete_coop_rows(
+    exec: &dyn SqlExecutor,
+    coop_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    upsert_coop_tags(exec, coop_id, None)?;
+    clear_coop_locations(exec, coop_id)?;
+    handle_delete_result(coop::delete(
+        exec,
+        &ICoopDelete::On(ICoopFindOneArgs {
+            on: CoopQueryBindValues::Id {
+                id: coop_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(exec, "coop", coop_id, ReplicaChangeOp::Delete)
+}
+
+fn delete_document_rows(
+    exec: &dyn SqlExecutor,
+    document_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    upsert_document_tags(exec, document_id, None)?;
+    handle_delete_result(document::delete(
+        exec,
+        &IDocumentDelete::On(IDocumentFindOneArgs {
+            on: DocumentQueryBindValues::Id {
+                id: document_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(exec, "document", document_id, ReplicaChangeOp::Delete)
+}
+
+fn delete_resource_area_rows(
+    exec: &dyn SqlExecutor,
+    resource_area_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let caps = resource_harvest_cap::find_many(
+        exec,
+        &IResourceHarvestCapFindMany {
+            filter: Some(resource_harvest_cap_filter(
+                None,
+                None,
+                Some(resource_area_id),
+            )),
+        },
+    )?
+    .results;
+    for cap in caps {
+        delete_resource_harvest_cap_rows(exec, &cap.id)?;
+    }
+    upsert_resource_area_tags(exec, resource_area_id, None)?;
+    clear_resource_area_locations(exec, resource_area_id)?;
+    handle_delete_result(resource_area::delete(
+        exec,
+        &IResourceAreaDelete::On(IResourceAreaFindOneArgs {
+            on: ResourceAreaQueryBindValues::Id {
+                id: resource_area_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(
+        exec,
+        "resource_area",
+        resource_area_id,
+        ReplicaChangeOp::Delete,
+    )
+}
+
+fn delete_resource_harvest_cap_rows(
+    exec: &dyn SqlExecutor,
+    resource_harvest_cap_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    upsert_resource_harvest_cap_tags(exec, resource_harvest_cap_id, None)?;
+    handle_delete_result(resource_harvest_cap::delete(
+        exec,
+        &IResourceHarvestCapDelete::On(IResourceHarvestCapFindOneArgs {
+            on: ResourceHarvestCapQueryBindValues::Id {
+                id: resource_harvest_cap_id.to_string(),
+            },
+        }),
+    ))?;
+    record_change(
+        exec,
+        "resource_harvest_cap",
+        resource_harvest_cap_id,
+        ReplicaChangeOp::Delete,
+    )
+}
+
 fn create_gcs_location(
     exec: &dyn SqlExecutor,
     gcs: radroots_events::farm::RadrootsGcsLocation,
@@ -1369,9 +2455,10 @@ fn upsert_member_claims(
         handle_delete_result(farm_member_claim::delete(
             exec,
             &IFarmMemberClaimDelete::On(IFarmMemberClaimFindOneArgs {
-                on: FarmMemberClaimQueryBindValues::Id { id: row.id },
+                on: FarmMemberClaimQueryBindValues::Id { id: row.id.clone() },
             }),
         ))?;
+        record_change(exec, "farm_member_claim", &row.id, ReplicaChangeOp::Delete)?;
     }
 
     let mut entries = Vec::new();
@@ -1388,12 +2475,18 @@ fn upsert_member_claims(
             member_pubkey: member_pubkey.to_string(),
             farm_pubkey,
         };
-        let _ = farm_member_claim::create(exec, &fields)?;
+        let claim_id = farm_member_claim::create(exec, &fields)?.result.id;
+        record_change(
+            exec,
+            "farm_member_claim",
+            &claim_id,
+            ReplicaChangeOp::Insert,
+        )?;
     }
     Ok(())
 }
 
-fn handle_delete_result<T>(
+pub(crate) fn handle_delete_result<T>(
     result: Result<T, radroots_types::types::IError<SqlError>>,
 ) -> Result<(), RadrootsReplicaEventsError> {
     match result {
@@ -1485,7 +2578,17 @@ fn parse_farm_list_set_d_tag(d_tag: &str) -> Option<(String, ListSetRole)> {
     Some((farm_d_tag, role))
 }
 
-fn to_value_opt(value: Option<String>) -> Option<Value> {
+pub(crate) fn record_change(
+    exec: &dyn SqlExecutor,
+    entity_type: &str,
+    entity_id: &str,
+    op: ReplicaChangeOp,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let _ = change::record_change(exec, entity_type, entity_id, op)?;
+    Ok(())
+}
+
+pub(crate) fn to_value_opt(value: Option<String>) -> Option<Value> {
     Some(match value {
         Some(value) => Value::from(value),
         None => Value::Null,
@@ -2425,6 +3528,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ingest_listing_records_expiration_and_search_hides_expired_products() {
+        let exec = SqliteExecutor::open_memory().expect("db");
+        migrations::run_all_up(&exec).expect("migrations");
+
+        let seller_pubkey = "c".repeat(64);
+        let mut seasonal = listing_event(
+            510,
+            &seller_pubkey,
+            10,
+            "AAAAAAAAAAAAAAAAAAAAAQ",
+            "active",
+            "June Strawberries",
+        );
+        seasonal
+            .tags
+            .push(vec!["expiration".to_string(), "1782777600".to_string()]);
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &seasonal).expect("seasonal ingest"),
+            RadrootsReplicaIngestOutcome::Applied
+        );
+
+        let replica = ReplicaSql::new(&exec);
+        let live = replica
+            .trade_product_search_at(&["strawberries".to_string()], 1_782_777_599)
+            .expect("search");
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].expires_at, Some(1_782_777_600));
+        assert!(
+            replica
+                .trade_product_search_at(&["strawberries".to_string()], 1_782_777_600)
+                .expect("search")
+                .is_empty()
+        );
+    }
+
     #[test]
     fn ingest_listing_projects_trade_product_and_removes_archived_replacements() {
         let exec = SqliteExecutor::open_memory().expect("db");
@@ -2519,6 +3658,14 @@ mod tests {
             product_rows[0].verified_primary_bin_id.as_deref(),
             Some("bin-a")
         );
+        assert_eq!(
+            product_rows[0].farm_pubkey.as_deref(),
+            Some(seller_pubkey.as_str())
+        );
+        assert_eq!(
+            product_rows[0].farm_d_tag.as_deref(),
+            Some("AAAAAAAAAAAAAAAAAAAAAA")
+        );
 
         let archived = listing_event(
             502,
@@ -2583,6 +3730,191 @@ mod tests {
         assert!(product_rows.is_empty());
     }
 
+    fn deletion_event(
+        id: u64,
+        author: &str,
+        created_at: u32,
+        tags: Vec<Vec<String>>,
+    ) -> RadrootsNostrEvent {
+        RadrootsNostrEvent {
+            id: format!("{id:064x}"),
+            author: author.to_string(),
+            created_at,
+            kind: KIND_DELETION,
+            tags,
+            content: String::new(),
+            sig: "f".repeat(128),
+        }
+    }
+
+    #[test]
+    fn ingest_deletion_removes_listing_rows_for_the_listing_author_only() {
+        let exec = SqliteExecutor::open_memory().expect("db");
+        migrations::run_all_up(&exec).expect("migrations");
+
+        let seller_pubkey = "c".repeat(64);
+        let listing_d_tag = "AAAAAAAAAAAAAAAAAAAAAQ";
+        let listing_addr = format!("{}:{}:{}", KIND_LISTING, seller_pubkey, listing_d_tag);
+        let active = listing_event(
+            600,
+            &seller_pubkey,
+            10,
+            listing_d_tag,
+            "active",
+            "Pasture Eggs",
+        );
+        radroots_replica_ingest_event(&exec, &active).expect("active ingest");
+
+        let foreign = deletion_event(
+            601,
+            &"d".repeat(64),
+            11,
+            vec![
+                vec!["a".to_string(), listing_addr.clone()],
+                vec!["e".to_string(), active.id.clone()],
+            ],
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &foreign).expect("foreign deletion"),
+            RadrootsReplicaIngestOutcome::Skipped
+        );
+        let product_rows = trade_product::find_many(
+            &exec,
+            &ITradeProductFindMany {
+                filter: Some(trade_product_listing_addr_filter(&listing_addr)),
+            },
+        )
+        .expect("product rows")
+        .results;
+        assert_eq!(product_rows.len(), 1);
+
+        let deletion = deletion_event(
+            602,
+            &seller_pubkey,
+            12,
+            vec![vec!["a".to_string(), listing_addr.clone()]],
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &deletion).expect("deletion"),
+            RadrootsReplicaIngestOutcome::Applied
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &deletion).expect("deletion replay"),
+            RadrootsReplicaIngestOutcome::Skipped
+        );
+        let product_rows = trade_product::find_many(
+            &exec,
+            &ITradeProductFindMany {
+                filter: Some(trade_product_listing_addr_filter(&listing_addr)),
+            },
+        )
+        .expect("deleted product rows")
+        .results;
+        assert!(product_rows.is_empty());
+
+        let stale = listing_event(
+            603,
+            &seller_pubkey,
+            12,
+            listing_d_tag,
+            "active",
+            "Stale Eggs",
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &stale).expect("stale ingest"),
+            RadrootsReplicaIngestOutcome::Skipped
+        );
+        let relisted = listing_event(
+            604,
+            &seller_pubkey,
+            13,
+            listing_d_tag,
+            "active",
+            "Fresh Eggs",
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &relisted).expect("relisted ingest"),
+            RadrootsReplicaIngestOutcome::Applied
+        );
+    }
+
+    #[test]
+    fn ingest_deletion_removes_farm_and_plot_rows_by_event_id() {
+        let exec = SqliteExecutor::open_memory().expect("db");
+        migrations::run_all_up(&exec).expect("migrations");
+
+        let farm_pubkey = "b".repeat(64);
+        let farm_d_tag = "AAAAAAAAAAAAAAAAAAAAAA";
+        let plot_d_tag = "AAAAAAAAAAAAAAAAAAAAAg";
+        let farm = farm_event(
+            700,
+            &farm_pubkey,
+            10,
+            farm_d_tag,
+            "farm",
+            None,
+            Some(vec!["orchard".to_string()]),
+        );
+        let plot = plot_event(
+            701,
+            &farm_pubkey,
+            11,
+            plot_d_tag,
+            RadrootsFarmRef {
+                pubkey: farm_pubkey.clone(),
+                d_tag: farm_d_tag.to_string(),
+            },
+            "plot",
+            None,
+            Some(vec!["north".to_string()]),
+        );
+        radroots_replica_ingest_event_with_factory(&exec, &farm, &FixedFactory).expect("farm");
+        radroots_replica_ingest_event_with_factory(&exec, &plot, &FixedFactory).expect("plot");
+
+        let plot_deletion = deletion_event(
+            702,
+            &farm_pubkey,
+            12,
+            vec![vec!["e".to_string(), plot.id.clone()]],
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &plot_deletion).expect("plot deletion"),
+            RadrootsReplicaIngestOutcome::Applied
+        );
+        let plots = plot::find_many(&exec, &IPlotFindMany { filter: None })
+            .expect("plots")
+            .results;
+        assert!(plots.is_empty());
+        let farms = farm::find_many(&exec, &IFarmFindMany { filter: None })
+            .expect("farms")
+            .results;
+        assert_eq!(farms.len(), 1);
+
+        let farm_deletion = deletion_event(
+            703,
+            &farm_pubkey,
+            13,
+            vec![vec!["e".to_string(), farm.id.clone()]],
+        );
+        assert_eq!(
+            radroots_replica_ingest_event(&exec, &farm_deletion).expect("farm deletion"),
+            RadrootsReplicaIngestOutcome::Applied
+        );
+        let farms = farm::find_many(&exec, &IFarmFindMany { filter: None })
+            .expect("farms")
+            .results;
+        assert!(farms.is_empty());
+        let farm_tags = farm_tag::find_many(&exec, &IFarmTagFindMany { filter: None })
+            .expect("farm tags")
+            .results;
+        assert!(farm_tags.is_empty());
+        assert_eq!(
+            radroots_replica_ingest_event_with_factory(&exec, &farm, &FixedFactory)
+                .expect("stale farm"),
+            RadrootsReplicaIngestOutcome::Skipped
+        );
+    }
+
     #[test]
     fn ingest_listing_preserves_fractional_exact_economics() {
         let exec = SqliteExecutor::open_memory().expect("db");
@@ -5,18 +5,25 @@
 extern crate alloc;
 
 mod canonical;
+pub mod changes;
 pub mod emit;
 pub mod error;
 mod event_head;
 mod geo;
 pub mod ingest;
+mod order;
 pub mod sync_state;
 pub mod types;
 
+pub use changes::{
+    RadrootsReplicaChangeBatch, radroots_replica_changes_since, radroots_replica_latest_change_seq,
+};
 pub use emit::{
+    radroots_replica_coop_event, radroots_replica_coop_sync_all, radroots_replica_document_events,
     radroots_replica_farm_event, radroots_replica_list_set_events,
     radroots_replica_membership_claim_events, radroots_replica_plot_events,
-    radroots_replica_profile_events, radroots_replica_sync_all,
+    radroots_replica_profile_events, radroots_replica_resource_area_events,
+    radroots_replica_resource_harvest_cap_events, radroots_replica_sync_all,
     radroots_replica_sync_all_with_options,
 };
 pub use error::RadrootsReplicaEventsError;
@@ -30,8 +37,9 @@ pub use sync_state::{
     radroots_replica_sync_status,
 };
 pub use types::{
-    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaEventDraft, RadrootsReplicaFarmSelector,
-    RadrootsReplicaSyncBundle, RadrootsReplicaSyncOptions, RadrootsReplicaSyncRequest,
+    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaCoopSelector, RadrootsReplicaCoopSyncRequest,
+    RadrootsReplicaEventDraft, RadrootsReplicaFarmSelector, RadrootsReplicaSyncBundle,
+    RadrootsReplicaSyncOptions, RadrootsReplicaSyncRequest,
 };
 
 #[cfg(feature = "std")]
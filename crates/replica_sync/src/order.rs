@@ -0,0 +1,388 @@
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use radroots_events::RadrootsNostrEvent;
+use radroots_events::ids::RadrootsOrderId;
+use radroots_replica_db::{
+    ReplicaChangeOp, trade_order, trade_order_event, trade_order_item, trade_order_payment,
+    trade_order_status,
+};
+use radroots_replica_db_schema::trade_order::{
+    ITradeOrderFields, ITradeOrderFieldsPartial, ITradeOrderFindOne, ITradeOrderFindOneArgs,
+    ITradeOrderUpdate, TradeOrder, TradeOrderQueryBindValues,
+};
+use radroots_replica_db_schema::trade_order_event::{
+    ITradeOrderEventFields, ITradeOrderEventFieldsFilter, ITradeOrderEventFindMany,
+    ITradeOrderEventFindOne, ITradeOrderEventFindOneArgs, TradeOrderEvent,
+    TradeOrderEventQueryBindValues,
+};
+use radroots_replica_db_schema::trade_order_item::{
+    ITradeOrderItemDelete, ITradeOrderItemFields, ITradeOrderItemFieldsFilter,
+    ITradeOrderItemFindMany, ITradeOrderItemFindOneArgs, TradeOrderItemQueryBindValues,
+};
+use radroots_replica_db_schema::trade_order_payment::{
+    ITradeOrderPaymentDelete, ITradeOrderPaymentFields, ITradeOrderPaymentFindOneArgs,
+    TradeOrderPaymentQueryBindValues,
+};
+use radroots_replica_db_schema::trade_order_status::ITradeOrderStatusFields;
+use radroots_sql_core::SqlExecutor;
+use radroots_trade::order::{
+    RadrootsOrderPaymentProjection, RadrootsOrderPaymentState, RadrootsOrderProjection,
+    RadrootsOrderSettlementState,
+};
+use radroots_trade::order_events::{
+    RadrootsOrderEventRecord, order_event_record_from_event, reduce_order_records,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::RadrootsReplicaEventsError;
+use crate::ingest::{
+    RadrootsReplicaIngestOutcome, handle_delete_result, record_change, to_value_opt,
+};
+
+pub(crate) fn ingest_order_event(
+    exec: &dyn SqlExecutor,
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsReplicaIngestOutcome, RadrootsReplicaEventsError> {
+    let record = order_record(event)?;
+    let order_id = record.order_id().clone();
+    let existing = trade_order_event::find_one(
+        exec,
+        &ITradeOrderEventFindOne::On(ITradeOrderEventFindOneArgs {
+            on: TradeOrderEventQueryBindValues::EventId {
+                event_id: event.id.clone(),
+            },
+        }),
+    )?;
+    if existing.result.is_some() {
+        return Ok(RadrootsReplicaIngestOutcome::Skipped);
+    }
+
+    let tags = serde_json::to_string(&event.tags)
+        .map_err(|_| RadrootsReplicaEventsError::InvalidData("order event tags".to_string()))?;
+    let order_event_id = trade_order_event::create(
+        exec,
+        &ITradeOrderEventFields {
+            event_id: event.id.clone(),
+            order_id: order_id.to_string(),
+            kind: event.kind,
+            pubkey: event.author.clone(),
+            event_created_at: event.created_at,
+            tags,
+            content: event.content.clone(),
+        },
+    )?
+    .result
+    .id;
+    record_change(
+        exec,
+        "trade_order_event",
+        &order_event_id,
+        ReplicaChangeOp::Insert,
+    )?;
+
+    let stored = load_order_events(exec, &order_id)?;
+    let mut records = Vec::with_capacity(stored.len());
+    for row in &stored {
+        records.push(order_record(&stored_order_event(row)?)?);
+    }
+    let items = records
+        .iter()
+        .find_map(|record| match record {
+            RadrootsOrderEventRecord::Request(request) => Some(request.payload.items.clone()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let projection = reduce_order_records(&order_id, records);
+    let last_event_at = projection.last_event_id.as_ref().and_then(|event_id| {
+        stored
+            .iter()
+            .find(|row| row.event_id == event_id.as_str())
+            .map(|row| row.event_created_at)
+    });
+
+    let previous = trade_order::find_one(
+        exec,
+        &ITradeOrderFindOne::On(ITradeOrderFindOneArgs {
+            on: TradeOrderQueryBindValues::OrderId {
+                order_id: order_id.to_string(),
+            },
+        }),
+    )?
+    .result;
+    let trade_order_id = upsert_trade_order(exec, previous.as_ref(), &projection, last_event_at)?;
+
+    if previous
+        .as_ref()
+        .is_none_or(|row| row.status != projection.status.as_str())
+    {
+        let _ = trade_order_status::create(
+            exec,
+            &ITradeOrderStatusFields {
+                trade_order_id: trade_order_id.clone(),
+                status: projection.status.as_str().to_string(),
+                event_id: event.id.clone(),
+                event_created_at: event.created_at,
+            },
+        )?;
+    }
+
+    clear_trade_order_items(exec, &trade_order_id)?;
+    for (position, item) in items.iter().enumerate() {
+        let _ = trade_order_item::create(
+            exec,
+            &ITradeOrderItemFields {
+                trade_order_id: trade_order_id.clone(),
+                position: u32::try_from(position).map_err(|_| {
+                    RadrootsReplicaEventsError::InvalidData("order item position".to_string())
+                })?,
+                bin_id: item.bin_id.to_string(),
+                bin_count: item.bin_count,
+            },
+        )?;
+    }
+
+    upsert_trade_order_payment(exec, &trade_order_id, &projection.payment)?;
+    let change_op = if previous.is_some() {
+        ReplicaChangeOp::Update
+    } else {
+        ReplicaChangeOp::Insert
+    };
+    record_change(exec, "trade_order", &trade_order_id, change_op)?;
+    Ok(RadrootsReplicaIngestOutcome::Applied)
+}
+
+fn order_record(
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsOrderEventRecord, RadrootsReplicaEventsError> {
+    order_event_record_from_event(event)
+        .map_err(|err| RadrootsReplicaEventsError::InvalidData(format!("order event: {err}")))
+}
+
+fn load_order_events(
+    exec: &dyn SqlExecutor,
+    order_id: &RadrootsOrderId,
+) -> Result<Vec<TradeOrderEvent>, RadrootsReplicaEventsError> {
+    let mut rows = trade_order_event::find_many(
+        exec,
+        &ITradeOrderEventFindMany {
+            filter: Some(ITradeOrderEventFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                event_id: None,
+                order_id: Some(order_id.to_string()),
+                kind: None,
+                pubkey: None,
+                event_created_at: None,
+                tags: None,
+                content: None,
+            }),
+        },
+    )?
+    .results;
+    rows.sort_by(|a, b| {
+        a.event_created_at
+            .cmp(&b.event_created_at)
+            .then_with(|| a.event_id.cmp(&b.event_id))
+    });
+    Ok(rows)
+}
+
+fn stored_order_event(
+    row: &TradeOrderEvent,
+) -> Result<RadrootsNostrEvent, RadrootsReplicaEventsError> {
+    let tags = serde_json::from_str(&row.tags)
+        .map_err(|_| RadrootsReplicaEventsError::InvalidData("order event tags".to_string()))?;
+    Ok(RadrootsNostrEvent {
+        id: row.event_id.clone(),
+        author: row.pubkey.clone(),
+        created_at: row.event_created_at,
+        kind: row.kind,
+        tags,
+        content: row.content.clone(),
+        sig: String::new(),
+    })
+}
+
+fn upsert_trade_order(
+    exec: &dyn SqlExecutor,
+    previous: Option<&TradeOrder>,
+    projection: &RadrootsOrderProjection,
+    last_event_at: Option<u32>,
+) -> Result<String, RadrootsReplicaEventsError> {
+    let issue_count = u32::try_from(projection.issues.len())
+        .map_err(|_| RadrootsReplicaEventsError::InvalidData("order issues".to_string()))?;
+    let fields = ITradeOrderFields {
+        order_id: projection.order_id.to_string(),
+        status: projection.status.as_str().to_string(),
+        listing_addr: projection.listing_addr.as_ref().map(ToString::to_string),
+        buyer_pubkey: projection.buyer_pubkey.as_ref().map(ToString::to_string),
+        seller_pubkey: projection.seller_pubkey.as_ref().map(ToString::to_string),
+        request_event_id: projection
+            .request_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        decision_event_id: projection
+            .decision_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        agreement_event_id: projection
+            .agreement_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        fulfillment_event_id: projection
+            .fulfillment_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        fulfillment_status: projection.fulfillment_status.as_ref().and_then(enum_str),
+        cancellation_event_id: projection
+            .cancellation_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        receipt_event_id: projection
+            .receipt_event_id
+            .as_ref()
+            .map(ToString::to_string),
+        last_event_id: projection.last_event_id.as_ref().map(ToString::to_string),
+        last_event_at,
+        issue_count,
+    };
+    let Some(row) = previous else {
+        return Ok(trade_order::create(exec, &fields)?.result.id);
+    };
+    let partial = ITradeOrderFieldsPartial {
+        order_id: Some(Value::from(fields.order_id)),
+        status: Some(Value::from(fields.status)),
+        listing_addr: to_value_opt(fields.listing_addr),
+        buyer_pubkey: to_value_opt(fields.buyer_pubkey),
+        seller_pubkey: to_value_opt(fields.seller_pubkey),
+        request_event_id: to_value_opt(fields.request_event_id),
+        decision_event_id: to_value_opt(fields.decision_event_id),
+        agreement_event_id: to_value_opt(fields.agreement_event_id),
+        fulfillment_event_id: to_value_opt(fields.fulfillment_event_id),
+        fulfillment_status: to_value_opt(fields.fulfillment_status),
+        cancellation_event_id: to_value_opt(fields.cancellation_event_id),
+        receipt_event_id: to_value_opt(fields.receipt_event_id),
+        last_event_id: to_value_opt(fields.last_event_id),
+        last_event_at: Some(fields.last_event_at.map_or(Value::Null, Value::from)),
+        issue_count: Some(Value::from(fields.issue_count)),
+    };
+    let _updated = trade_order::update(
+        exec,
+        &ITradeOrderUpdate {
+            on: TradeOrderQueryBindValues::Id { id: row.id.clone() },
+            fields: partial,
+        },
+    )?;
+    Ok(row.id.clone())
+}
+
+fn clear_trade_order_items(
+    exec: &dyn SqlExecutor,
+    trade_order_id: &str,
+) -> Result<(), RadrootsReplicaEventsError> {
+    let existing = trade_order_item::find_many(
+        exec,
+        &ITradeOrderItemFindMany {
+            filter: Some(ITradeOrderItemFieldsFilter {
+                id: None,
+                created_at: None,
+                updated_at: None,
+                trade_order_id: Some(trade_order_id.to_string()),
+                position: None,
+                bin_id: None,
+                bin_count: None,
+            }),
+        },
+    )?;
+    for row in existing.results {
+        handle_delete_result(trade_order_item::delete(
+            exec,
+            &ITradeOrderItemDelete::On(ITradeOrderItemFindOneArgs {
+                on: TradeOrderItemQueryBindValues::Id { id: row.id },
+            }),
+        ))?;
+    }
+    Ok(())
+}
+
+fn upsert_trade_order_payment(
+    exec: &dyn SqlExecutor,
+    trade_order_id: &str,
+    payment: &RadrootsOrderPaymentProjection,
+) -> Result<(), RadrootsReplicaEventsError> {
+    handle_delete_result(trade_order_payment::delete(
+        exec,
+        &ITradeOrderPaymentDelete::On(ITradeOrderPaymentFindOneArgs {
+            on: TradeOrderPaymentQueryBindValues::TradeOrderId {
+                trade_order_id: trade_order_id.to_string(),
+            },
+        }),
+    ))?;
+    if payment.state == RadrootsOrderPaymentState::NotRecorded {
+        return Ok(());
+    }
+    let paid_at = payment
+        .paid_at
+        .map(i64::try_from)
+        .transpose()
+        .map_err(|_| {
+            RadrootsReplicaEventsError::InvalidData("order payment paid_at".to_string())
+        })?;
+    let _ = trade_order_payment::create(
+        exec,
+        &ITradeOrderPaymentFields {
+            trade_order_id: trade_order_id.to_string(),
+            state: payment_state_str(&payment.state).to_string(),
+            settlement_state: settlement_state_str(&payment.settlement_state).to_string(),
+            payment_event_id: payment.payment_event_id.as_ref().map(ToString::to_string),
+            settlement_event_id: payment
+                .settlement_event_id
+                .as_ref()
+                .map(ToString::to_string),
+            quote_id: payment.quote_id.as_ref().map(ToString::to_string),
+            quote_version: payment.quote_version,
+            amount: payment.amount.as_ref().map(ToString::to_string),
+            currency: payment.currency.as_ref().map(ToString::to_string),
+            method: payment.method.as_ref().and_then(enum_str),
+            reference: payment.reference.clone(),
+            paid_at,
+            reason: payment.reason.clone(),
+        },
+    )?;
+    Ok(())
+}
+
+fn payment_state_str(state: &RadrootsOrderPaymentState) -> &'static str {
+    match state {
+        RadrootsOrderPaymentState::NotRecorded => "not_recorded",
+        RadrootsOrderPaymentState::Recorded => "recorded",
+        RadrootsOrderPaymentState::Settled => "settled",
+        RadrootsOrderPaymentState::Rejected => "rejected",
+        RadrootsOrderPaymentState::Invalid => "invalid",
+    }
+}
+
+fn settlement_state_str(state: &RadrootsOrderSettlementState) -> &'static str {
+    match state {
+        RadrootsOrderSettlementState::NotRequired => "not_required",
+        RadrootsOrderSettlementState::Pending => "pending",
+        RadrootsOrderSettlementState::Accepted => "accepted",
+        RadrootsOrderSettlementState::Rejected => "rejected",
+        RadrootsOrderSettlementState::Invalid => "invalid",
+    }
+}
+
+fn enum_str<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value) {
+        Ok(Value::String(value)) => Some(value),
+        _ => None,
+    }
+}
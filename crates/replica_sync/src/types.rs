@@ -38,3 +38,15 @@ pub struct RadrootsReplicaSyncRequest {
     pub farm: RadrootsReplicaFarmSelector,
     pub options: Option<RadrootsReplicaSyncOptions>,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RadrootsReplicaCoopSelector {
+    pub id: Option<String>,
+    pub d_tag: Option<String>,
+    pub pubkey: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RadrootsReplicaCoopSyncRequest {
+    pub coop: RadrootsReplicaCoopSelector,
+}
@@ -1,10 +1,14 @@
+use radroots_core::{RadrootsCoreDecimal, RadrootsCoreQuantity, RadrootsCoreUnit};
 use radroots_events::RadrootsNostrEvent;
+use radroots_events::coop::{RadrootsCoop, RadrootsCoopLocation};
+use radroots_events::document::{RadrootsDocument, RadrootsDocumentSubject};
 use radroots_events::farm::{
     RadrootsFarm, RadrootsFarmLocation, RadrootsFarmRef, RadrootsGcsLocation, RadrootsGeoJsonPoint,
     RadrootsGeoJsonPolygon,
 };
 use radroots_events::kinds::{
-    KIND_FARM, KIND_LIST_SET_FOLLOW, KIND_LIST_SET_GENERIC, KIND_PLOT, KIND_PROFILE,
+    KIND_COOP, KIND_DELETION, KIND_DOCUMENT, KIND_FARM, KIND_LIST_SET_FOLLOW,
+    KIND_LIST_SET_GENERIC, KIND_PLOT, KIND_PROFILE, KIND_RESOURCE_AREA, KIND_RESOURCE_HARVEST_CAP,
 };
 use radroots_events::list::RadrootsListEntry;
 use radroots_events::list_set::RadrootsListSet;
@@ -13,14 +17,23 @@ use radroots_events::profile::{
     RADROOTS_PROFILE_TYPE_TAG_KEY, RadrootsProfile, RadrootsProfileType,
     radroots_profile_type_tag_value,
 };
+use radroots_events::resource_area::{
+    RadrootsResourceArea, RadrootsResourceAreaLocation, RadrootsResourceAreaRef,
+};
+use radroots_events::resource_cap::{RadrootsResourceHarvestCap, RadrootsResourceHarvestProduct};
+use radroots_events_codec::coop::encode as coop_encode;
+use radroots_events_codec::document::encode as document_encode;
 use radroots_events_codec::error::{EventEncodeError, EventParseError};
 use radroots_events_codec::farm::encode as farm_encode;
 use radroots_events_codec::farm::list_sets as farm_list_sets;
 use radroots_events_codec::list_set::encode as list_set_encode;
 use radroots_events_codec::plot::encode as plot_encode;
+use radroots_events_codec::resource_area::encode as resource_area_encode;
+use radroots_events_codec::resource_cap::encode as resource_cap_encode;
 use radroots_replica_db::{
-    farm, farm_gcs_location, farm_member, farm_member_claim, farm_tag, gcs_location, migrations,
-    nostr_profile, plot, plot_gcs_location, plot_tag,
+    ReplicaChangeOp, farm, farm_gcs_location, farm_member, farm_member_claim, farm_tag,
+    gcs_location, migrations, nostr_profile, plot, plot_gcs_location, plot_tag, resource_area,
+    resource_harvest_cap,
 };
 use radroots_replica_db_schema::farm::{IFarmFields, IFarmFieldsFilter, IFarmFindMany};
 use radroots_replica_db_schema::farm_gcs_location::IFarmGcsLocationFields;
@@ -40,10 +53,15 @@ use radroots_replica_db_schema::plot_gcs_location::IPlotGcsLocationFields;
 use radroots_replica_db_schema::plot_tag::{
     IPlotTagFields, IPlotTagFieldsFilter, IPlotTagFindMany,
 };
+use radroots_replica_db_schema::resource_area::IResourceAreaFindMany;
+use radroots_replica_db_schema::resource_harvest_cap::IResourceHarvestCapFindMany;
 use radroots_replica_sync::{
-    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaEventDraft, RadrootsReplicaEventsError,
-    RadrootsReplicaFarmSelector, RadrootsReplicaIngestOutcome, RadrootsReplicaSyncOptions,
-    RadrootsReplicaSyncRequest, radroots_replica_ingest_event, radroots_replica_sync_all,
+    RADROOTS_REPLICA_TRANSFER_VERSION, RadrootsReplicaChangeBatch, RadrootsReplicaCoopSelector,
+    RadrootsReplicaCoopSyncRequest, RadrootsReplicaEventDraft, RadrootsReplicaEventsError,
+    RadrootsReplicaFarmSelector, RadrootsReplicaIngestOutcome, RadrootsReplicaSyncBundle,
+    RadrootsReplicaSyncOptions, RadrootsReplicaSyncRequest, radroots_replica_changes_since,
+    radroots_replica_coop_sync_all, radroots_replica_document_events,
+    radroots_replica_ingest_event, radroots_replica_latest_change_seq, radroots_replica_sync_all,
     radroots_replica_sync_status,
 };
 use radroots_sql_core::SqliteExecutor;
@@ -2183,3 +2201,292 @@ fn error_conversion_paths_are_exercised() {
         EventParseError::InvalidNumber("k", parse_number_err).into();
     assert!(parse.to_string().contains("replica_sync.parse"));
 }
+
+fn coop_resource_events(author: &str) -> Vec<RadrootsNostrEvent> {
+    let coop = RadrootsCoop {
+        d_tag: "BAAAAAAAAAAAAAAAAAAAAA".to_string(),
+        name: "valley coop".to_string(),
+        about: Some("shared harvest".to_string()),
+        website: None,
+        picture: None,
+        banner: None,
+        location: Some(RadrootsCoopLocation {
+            primary: Some("depot".to_string()),
+            city: Some("valley".to_string()),
+            region: None,
+            country: Some("CA".to_string()),
+            gcs: sample_gcs(49.1, -123.1, "c2b2q"),
+        }),
+        tags: Some(vec!["cooperative".to_string()]),
+    };
+    let area = RadrootsResourceArea {
+        d_tag: "BAAAAAAAAAAAAAAAAAAAAQ".to_string(),
+        name: "north forest".to_string(),
+        about: None,
+        location: RadrootsResourceAreaLocation {
+            primary: Some("north ridge".to_string()),
+            city: None,
+            region: Some("BC".to_string()),
+            country: Some("CA".to_string()),
+            gcs: sample_gcs(49.2, -123.2, "c2b2r"),
+        },
+        tags: Some(vec!["forest".to_string()]),
+    };
+    let cap = RadrootsResourceHarvestCap {
+        d_tag: "BAAAAAAAAAAAAAAAAAAAAg".to_string(),
+        resource_area: RadrootsResourceAreaRef {
+            pubkey: author.to_string(),
+            d_tag: area.d_tag.clone(),
+        },
+        product: RadrootsResourceHarvestProduct {
+            key: "chanterelle".to_string(),
+            category: Some("mushroom".to_string()),
+        },
+        start: 1_720_000_000,
+        end: 1_730_000_000,
+        cap_quantity: RadrootsCoreQuantity {
+            amount: "1250.5".parse::<RadrootsCoreDecimal>().expect("decimal"),
+            unit: RadrootsCoreUnit::MassKg,
+            label: None,
+        },
+        display_amount: None,
+        display_unit: None,
+        display_label: Some("season cap".to_string()),
+        tags: None,
+    };
+    let document = RadrootsDocument {
+        d_tag: "BAAAAAAAAAAAAAAAAAAAAw".to_string(),
+        doc_type: "bylaws".to_string(),
+        title: "coop bylaws".to_string(),
+        version: "1.0.0".to_string(),
+        summary: None,
+        effective_at: Some(1_720_000_000),
+        body_markdown: Some("# bylaws".to_string()),
+        subject: RadrootsDocumentSubject {
+            pubkey: author.to_string(),
+            address: Some(format!("{KIND_COOP}:{author}:{}", coop.d_tag)),
+        },
+        tags: Some(vec!["governance".to_string()]),
+    };
+    vec![
+        event_with_parts(
+            101,
+            author,
+            1_720_000_100,
+            KIND_COOP,
+            serde_json::to_string(&coop).expect("coop json"),
+            coop_encode::coop_build_tags(&coop).expect("coop tags"),
+        ),
+        event_with_parts(
+            102,
+            author,
+            1_720_000_100,
+            KIND_RESOURCE_AREA,
+            serde_json::to_string(&area).expect("area json"),
+            resource_area_encode::resource_area_build_tags(&area).expect("area tags"),
+        ),
+        event_with_parts(
+            103,
+            author,
+            1_720_000_100,
+            KIND_RESOURCE_HARVEST_CAP,
+            serde_json::to_string(&cap).expect("cap json"),
+            resource_cap_encode::resource_harvest_cap_build_tags(&cap).expect("cap tags"),
+        ),
+        event_with_parts(
+            104,
+            author,
+            1_720_000_100,
+            KIND_DOCUMENT,
+            serde_json::to_string(&document).expect("document json"),
+            document_encode::document_build_tags(&document).expect("document tags"),
+        ),
+    ]
+}
+
+#[test]
+fn coop_resource_replicas_roundtrip_and_delete() {
+    let author = "c".repeat(64);
+    let source = SqliteExecutor::open_memory().expect("source db");
+    migrations::run_all_up(&source).expect("source migrations");
+    for event in coop_resource_events(&author) {
+        let outcome = radroots_replica_ingest_event(&source, &event).expect("source ingest");
+        assert_eq!(outcome, RadrootsReplicaIngestOutcome::Applied);
+    }
+
+    let request = RadrootsReplicaCoopSyncRequest {
+        coop: RadrootsReplicaCoopSelector {
+            id: None,
+            d_tag: Some("BAAAAAAAAAAAAAAAAAAAAA".to_string()),
+            pubkey: Some(author.clone()),
+        },
+    };
+    let bundle = radroots_replica_coop_sync_all(&source, &request).expect("coop bundle");
+    let kinds = bundle
+        .events
+        .iter()
+        .map(|draft| draft.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            KIND_COOP,
+            KIND_DOCUMENT,
+            KIND_RESOURCE_AREA,
+            KIND_RESOURCE_HARVEST_CAP
+        ]
+    );
+
+    let target = SqliteExecutor::open_memory().expect("target db");
+    migrations::run_all_up(&target).expect("target migrations");
+    for (index, draft) in bundle.events.iter().enumerate() {
+        let event = draft_to_event(draft, index as u32);
+        let outcome = radroots_replica_ingest_event(&target, &event).expect("target ingest");
+        assert_eq!(outcome, RadrootsReplicaIngestOutcome::Applied);
+    }
+    let replay = radroots_replica_coop_sync_all(&target, &request).expect("replay bundle");
+    let contents = |bundle: &RadrootsReplicaSyncBundle| {
+        bundle
+            .events
+            .iter()
+            .map(|draft| (draft.content.clone(), draft.tags.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(contents(&replay), contents(&bundle));
+
+    let caps = unwrap_sql(
+        resource_harvest_cap::find_many(&target, &IResourceHarvestCapFindMany { filter: None }),
+        "caps",
+    )
+    .results;
+    assert_eq!(caps.len(), 1);
+    assert_eq!(caps[0].cap_amount, "1250.5");
+    assert_eq!(caps[0].cap_unit, "kg");
+    assert_eq!(caps[0].start_at, 1_720_000_000);
+
+    let deletion = event_with_parts(
+        200,
+        &author,
+        1_720_000_200,
+        KIND_DELETION,
+        String::new(),
+        vec![vec![
+            "a".to_string(),
+            format!("{KIND_RESOURCE_AREA}:{author}:BAAAAAAAAAAAAAAAAAAAAQ"),
+        ]],
+    );
+    let outcome = radroots_replica_ingest_event(&target, &deletion).expect("delete area");
+    assert_eq!(outcome, RadrootsReplicaIngestOutcome::Applied);
+    let areas = unwrap_sql(
+        resource_area::find_many(&target, &IResourceAreaFindMany { filter: None }),
+        "areas",
+    )
+    .results;
+    assert!(areas.is_empty());
+    let caps = unwrap_sql(
+        resource_harvest_cap::find_many(&target, &IResourceHarvestCapFindMany { filter: None }),
+        "caps after delete",
+    )
+    .results;
+    assert!(caps.is_empty());
+    let documents = radroots_replica_document_events(&target, &author).expect("documents");
+    assert_eq!(documents.len(), 1);
+}
+
+#[test]
+fn harvest_cap_requires_known_resource_area() {
+    let author = "d".repeat(64);
+    let exec = SqliteExecutor::open_memory().expect("db");
+    migrations::run_all_up(&exec).expect("migrations");
+    let events = coop_resource_events(&author);
+    let err = radroots_replica_ingest_event(&exec, &events[2]).expect_err("missing area");
+    assert!(err.to_string().contains("resource area not found"));
+}
+
+fn change_ops(batch: &RadrootsReplicaChangeBatch) -> Vec<(&str, ReplicaChangeOp)> {
+    batch
+        .changes
+        .iter()
+        .map(|change| (change.entity_type.as_str(), change.op))
+        .collect()
+}
+
+#[test]
+fn change_feed_tracks_ingest_and_deletes_in_sequence() {
+    let author = "e".repeat(64);
+    let exec = SqliteExecutor::open_memory().expect("db");
+    migrations::run_all_up(&exec).expect("migrations");
+    let events = coop_resource_events(&author);
+
+    radroots_replica_ingest_event(&exec, &events[2]).expect_err("missing area");
+    assert_eq!(radroots_replica_latest_change_seq(&exec).expect("seq"), 0);
+
+    for event in &events {
+        radroots_replica_ingest_event(&exec, event).expect("ingest");
+    }
+    let batch = radroots_replica_changes_since(&exec, 0, None).expect("changes");
+    assert_eq!(
+        change_ops(&batch),
+        vec![
+            ("coop", ReplicaChangeOp::Insert),
+            ("nostr_event_head", ReplicaChangeOp::Insert),
+            ("resource_area", ReplicaChangeOp::Insert),
+            ("nostr_event_head", ReplicaChangeOp::Insert),
+            ("resource_harvest_cap", ReplicaChangeOp::Insert),
+            ("nostr_event_head", ReplicaChangeOp::Insert),
+            ("document", ReplicaChangeOp::Insert),
+            ("nostr_event_head", ReplicaChangeOp::Insert),
+        ]
+    );
+    assert!(
+        batch
+            .changes
+            .windows(2)
+            .all(|pair| pair[0].seq < pair[1].seq)
+    );
+    assert_eq!(
+        batch.entity_types,
+        vec![
+            "coop",
+            "document",
+            "nostr_event_head",
+            "resource_area",
+            "resource_harvest_cap"
+        ]
+    );
+    let cursor = batch.next_seq;
+    assert_eq!(
+        radroots_replica_latest_change_seq(&exec).expect("latest"),
+        cursor
+    );
+
+    let page = radroots_replica_changes_since(&exec, 0, Some(2)).expect("page");
+    assert_eq!(page.changes.len(), 2);
+    assert_eq!(page.next_seq, batch.changes[1].seq);
+
+    let deletion = event_with_parts(
+        300,
+        &author,
+        1_720_000_300,
+        KIND_DELETION,
+        String::new(),
+        vec![vec![
+            "a".to_string(),
+            format!("{KIND_RESOURCE_AREA}:{author}:BAAAAAAAAAAAAAAAAAAAAQ"),
+        ]],
+    );
+    radroots_replica_ingest_event(&exec, &deletion).expect("delete area");
+    let batch = radroots_replica_changes_since(&exec, cursor, None).expect("delete changes");
+    assert_eq!(
+        change_ops(&batch),
+        vec![
+            ("resource_harvest_cap", ReplicaChangeOp::Delete),
+            ("resource_area", ReplicaChangeOp::Delete),
+            ("nostr_event_head", ReplicaChangeOp::Update),
+        ]
+    );
+
+    let empty = radroots_replica_changes_since(&exec, batch.next_seq, None).expect("empty");
+    assert!(empty.changes.is_empty());
+    assert_eq!(empty.next_seq, batch.next_seq);
+}
@@ -0,0 +1,241 @@
+use radroots_core::{
+    RadrootsCoreCurrency, RadrootsCoreDecimal, RadrootsCoreMoney, RadrootsCoreUnit,
+};
+use radroots_events::RadrootsNostrEvent;
+use radroots_events::RadrootsNostrEventPtr;
+use radroots_events::ids::{
+    RadrootsEventId, RadrootsInventoryBinId, RadrootsListingAddress, RadrootsOrderId,
+    RadrootsOrderQuoteId, RadrootsPublicKey,
+};
+use radroots_events::kinds::KIND_LISTING;
+use radroots_events::order::{
+    RadrootsOrderDecision, RadrootsOrderDecisionOutcome, RadrootsOrderEconomicItem,
+    RadrootsOrderEconomics, RadrootsOrderInventoryCommitment, RadrootsOrderItem,
+    RadrootsOrderPricingBasis, RadrootsOrderRequest,
+};
+use radroots_events_codec::order::{order_decision_event_build, order_request_event_build};
+use radroots_events_codec::wire::WireEventParts;
+use radroots_replica_db::{migrations, trade_order, trade_order_item, trade_order_status};
+use radroots_replica_db_schema::trade_order::{
+    ITradeOrderFieldsFilter, ITradeOrderFindMany, TradeOrder,
+};
+use radroots_replica_db_schema::trade_order_item::ITradeOrderItemFindMany;
+use radroots_replica_db_schema::trade_order_status::ITradeOrderStatusFindMany;
+use radroots_replica_sync::{RadrootsReplicaIngestOutcome, radroots_replica_ingest_event};
+use radroots_sql_core::SqliteExecutor;
+
+const SELLER: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+const BUYER: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+const REQUEST_EVENT_ID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+const DECISION_EVENT_ID: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+fn usd(raw: &str) -> RadrootsCoreMoney {
+    RadrootsCoreMoney::new(decimal(raw), RadrootsCoreCurrency::USD)
+}
+
+fn decimal(raw: &str) -> RadrootsCoreDecimal {
+    raw.parse().expect("decimal")
+}
+
+fn listing_address() -> RadrootsListingAddress {
+    RadrootsListingAddress::parse(format!("{KIND_LISTING}:{SELLER}:AAAAAAAAAAAAAAAAAAAAAg"))
+        .expect("listing address")
+}
+
+fn bin_id() -> RadrootsInventoryBinId {
+    RadrootsInventoryBinId::parse("bin-1").expect("bin id")
+}
+
+fn request_payload() -> RadrootsOrderRequest {
+    RadrootsOrderRequest {
+        order_id: RadrootsOrderId::parse("order-1").expect("order id"),
+        listing_addr: listing_address(),
+        buyer_pubkey: RadrootsPublicKey::parse(BUYER).expect("buyer"),
+        seller_pubkey: RadrootsPublicKey::parse(SELLER).expect("seller"),
+        items: vec![RadrootsOrderItem {
+            bin_id: bin_id(),
+            bin_count: 2,
+        }],
+        economics: RadrootsOrderEconomics {
+            quote_id: RadrootsOrderQuoteId::parse("quote-1").expect("quote id"),
+            quote_version: 1,
+            pricing_basis: RadrootsOrderPricingBasis::ListingEvent,
+            currency: RadrootsCoreCurrency::USD,
+            items: vec![RadrootsOrderEconomicItem {
+                bin_id: bin_id(),
+                bin_count: 2,
+                quantity_amount: decimal("1"),
+                quantity_unit: RadrootsCoreUnit::Each,
+                unit_price_amount: decimal("5"),
+                unit_price_currency: RadrootsCoreCurrency::USD,
+                line_subtotal: usd("10"),
+            }],
+            discounts: Vec::new(),
+            adjustments: Vec::new(),
+            subtotal: usd("10"),
+            discount_total: usd("0"),
+            adjustment_total: usd("0"),
+            total: usd("10"),
+        },
+    }
+}
+
+fn decision_payload() -> RadrootsOrderDecision {
+    RadrootsOrderDecision {
+        order_id: RadrootsOrderId::parse("order-1").expect("order id"),
+        listing_addr: listing_address(),
+        buyer_pubkey: RadrootsPublicKey::parse(BUYER).expect("buyer"),
+        seller_pubkey: RadrootsPublicKey::parse(SELLER).expect("seller"),
+        decision: RadrootsOrderDecisionOutcome::Accepted {
+            inventory_commitments: vec![RadrootsOrderInventoryCommitment {
+                bin_id: bin_id(),
+                bin_count: 2,
+            }],
+        },
+    }
+}
+
+fn event(parts: WireEventParts, id: &str, author: &str, created_at: u32) -> RadrootsNostrEvent {
+    RadrootsNostrEvent {
+        id: id.to_owned(),
+        author: author.to_owned(),
+        created_at,
+        kind: parts.kind,
+        tags: parts.tags,
+        content: parts.content,
+        sig: "f".repeat(128),
+    }
+}
+
+fn request_event() -> RadrootsNostrEvent {
+    let listing_event = RadrootsNostrEventPtr {
+        id: "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".to_owned(),
+        relays: None,
+    };
+    let parts =
+        order_request_event_build(&listing_event, &request_payload()).expect("request event");
+    event(parts, REQUEST_EVENT_ID, BUYER, 1_720_000_010)
+}
+
+fn decision_event() -> RadrootsNostrEvent {
+    let request_id = RadrootsEventId::parse(REQUEST_EVENT_ID).expect("request id");
+    let parts = order_decision_event_build(&request_id, &request_id, &decision_payload())
+        .expect("decision event");
+    event(parts, DECISION_EVENT_ID, SELLER, 1_720_000_011)
+}
+
+fn order_filter() -> ITradeOrderFieldsFilter {
+    ITradeOrderFieldsFilter {
+        id: None,
+        created_at: None,
+        updated_at: None,
+        order_id: None,
+        status: None,
+        listing_addr: None,
+        buyer_pubkey: None,
+        seller_pubkey: None,
+        request_event_id: None,
+        decision_event_id: None,
+        agreement_event_id: None,
+        fulfillment_event_id: None,
+        fulfillment_status: None,
+        cancellation_event_id: None,
+        receipt_event_id: None,
+        last_event_id: None,
+        last_event_at: None,
+        issue_count: None,
+    }
+}
+
+fn find_orders(exec: &SqliteExecutor, filter: ITradeOrderFieldsFilter) -> Vec<TradeOrder> {
+    trade_order::find_many(
+        exec,
+        &ITradeOrderFindMany {
+            filter: Some(filter),
+        },
+    )
+    .expect("orders")
+    .results
+}
+
+#[test]
+fn order_events_project_into_order_tables() {
+    let exec = SqliteExecutor::open_memory().expect("db");
+    migrations::run_all_up(&exec).expect("migrations");
+
+    let request = request_event();
+    let outcome = radroots_replica_ingest_event(&exec, &request).expect("ingest request");
+    assert_eq!(outcome, RadrootsReplicaIngestOutcome::Applied);
+    let outcome = radroots_replica_ingest_event(&exec, &request).expect("replay request");
+    assert_eq!(outcome, RadrootsReplicaIngestOutcome::Skipped);
+
+    let orders = find_orders(&exec, order_filter());
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].status, "requested");
+    assert_eq!(orders[0].issue_count, 0);
+
+    let outcome = radroots_replica_ingest_event(&exec, &decision_event()).expect("ingest decision");
+    assert_eq!(outcome, RadrootsReplicaIngestOutcome::Applied);
+
+    let accepted = find_orders(
+        &exec,
+        ITradeOrderFieldsFilter {
+            status: Some("accepted".to_string()),
+            buyer_pubkey: Some(BUYER.to_string()),
+            seller_pubkey: Some(SELLER.to_string()),
+            listing_addr: Some(listing_address().to_string()),
+            ..order_filter()
+        },
+    );
+    assert_eq!(accepted.len(), 1);
+    let order = &accepted[0];
+    assert_eq!(order.order_id, "order-1");
+    assert_eq!(order.decision_event_id.as_deref(), Some(DECISION_EVENT_ID));
+    assert_eq!(order.last_event_at, Some(1_720_000_011));
+
+    let requested = find_orders(
+        &exec,
+        ITradeOrderFieldsFilter {
+            status: Some("requested".to_string()),
+            ..order_filter()
+        },
+    );
+    assert!(requested.is_empty());
+
+    let items = trade_order_item::find_many(&exec, &ITradeOrderItemFindMany { filter: None })
+        .expect("items")
+        .results;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].trade_order_id, order.id);
+    assert_eq!(items[0].bin_id, "bin-1");
+    assert_eq!(items[0].bin_count, 2);
+
+    let mut history =
+        trade_order_status::find_many(&exec, &ITradeOrderStatusFindMany { filter: None })
+            .expect("status history")
+            .results;
+    history.sort_by_key(|row| row.event_created_at);
+    let statuses = history
+        .iter()
+        .map(|row| (row.status.as_str(), row.event_id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statuses,
+        vec![
+            ("requested", REQUEST_EVENT_ID),
+            ("accepted", DECISION_EVENT_ID)
+        ]
+    );
+}
+
+#[test]
+fn malformed_order_events_are_rejected() {
+    let exec = SqliteExecutor::open_memory().expect("db");
+    migrations::run_all_up(&exec).expect("migrations");
+
+    let mut request = request_event();
+    request.id = "not-an-event-id".to_string();
+    let err = radroots_replica_ingest_event(&exec, &request).expect_err("invalid id");
+    assert!(err.to_string().contains("order event"));
+    assert!(find_orders(&exec, order_filter()).is_empty());
+}
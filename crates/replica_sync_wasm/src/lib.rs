@@ -9,8 +9,10 @@ use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use radroots_events::RadrootsNostrEvent;
 #[cfg(target_arch = "wasm32")]
 use radroots_replica_sync::{
-    RadrootsReplicaIdFactory, RadrootsReplicaIngestOutcome, RadrootsReplicaSyncRequest,
-    radroots_replica_ingest_event_with_factory, radroots_replica_sync_all,
+    RadrootsReplicaCoopSyncRequest, RadrootsReplicaIdFactory, RadrootsReplicaIngestOutcome,
+    RadrootsReplicaSyncRequest, radroots_replica_changes_since, radroots_replica_coop_sync_all,
+    radroots_replica_ingest_event_with_factory, radroots_replica_latest_change_seq,
+    radroots_replica_sync_all,
 };
 #[cfg(target_arch = "wasm32")]
 use radroots_sql_core::WasmSqlExecutor;
@@ -88,6 +90,16 @@ pub fn replica_sync_sync_all(request_json: &str) -> Result<JsValue, JsValue> {
     serde_wasm_bindgen::to_value(&bundle).map_err(err_js)
 }
 
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_name = replica_sync_coop_sync_all)]
+pub fn replica_sync_coop_sync_all(request_json: &str) -> Result<JsValue, JsValue> {
+    let request: RadrootsReplicaCoopSyncRequest =
+        serde_json::from_str(request_json).map_err(err_js)?;
+    let exec = WasmSqlExecutor::new();
+    let bundle = radroots_replica_coop_sync_all(&exec, &request).map_err(err_js)?;
+    serde_wasm_bindgen::to_value(&bundle).map_err(err_js)
+}
+
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen(js_name = replica_sync_ingest_event)]
 pub fn replica_sync_ingest_event(event_json: &str) -> Result<JsValue, JsValue> {
@@ -103,6 +115,74 @@ pub fn replica_sync_ingest_event(event_json: &str) -> Result<JsValue, JsValue> {
     Ok(JsValue::from_str(value))
 }
 
+#[cfg(target_arch = "wasm32")]
+fn parse_seq(seq: f64) -> Result<i64, JsValue> {
+    if seq.is_finite() && seq >= 0.0 && seq.fract() == 0.0 && seq <= 9_007_199_254_740_991.0 {
+        Ok(seq as i64)
+    } else {
+        Err(JsValue::from_str("invalid change seq"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_name = replica_sync_changes_since)]
+pub fn replica_sync_changes_since(since_seq: f64, limit: Option<u32>) -> Result<JsValue, JsValue> {
+    let exec = WasmSqlExecutor::new();
+    let batch =
+        radroots_replica_changes_since(&exec, parse_seq(since_seq)?, limit).map_err(err_js)?;
+    serde_wasm_bindgen::to_value(&batch).map_err(err_js)
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_name = ReplicaSyncChangeSubscription)]
+pub struct ReplicaSyncChangeSubscription {
+    cursor: i64,
+    entity_types: Option<Vec<String>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_class = ReplicaSyncChangeSubscription)]
+impl ReplicaSyncChangeSubscription {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        since_seq: Option<f64>,
+        entity_types: Option<Vec<String>>,
+    ) -> Result<ReplicaSyncChangeSubscription, JsValue> {
+        let cursor = match since_seq {
+            Some(seq) => parse_seq(seq)?,
+            None => {
+                let exec = WasmSqlExecutor::new();
+                radroots_replica_latest_change_seq(&exec).map_err(err_js)?
+            }
+        };
+        Ok(Self {
+            cursor,
+            entity_types,
+        })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cursor(&self) -> f64 {
+        self.cursor as f64
+    }
+
+    pub fn poll(&mut self, limit: Option<u32>) -> Result<JsValue, JsValue> {
+        let exec = WasmSqlExecutor::new();
+        let mut batch =
+            radroots_replica_changes_since(&exec, self.cursor, limit).map_err(err_js)?;
+        self.cursor = batch.next_seq;
+        if let Some(entity_types) = self.entity_types.as_ref() {
+            batch
+                .changes
+                .retain(|change| entity_types.contains(&change.entity_type));
+            batch
+                .entity_types
+                .retain(|entity_type| entity_types.contains(entity_type));
+        }
+        serde_wasm_bindgen::to_value(&batch).map_err(err_js)
+    }
+}
+
 #[cfg(coverage_nightly)]
 pub fn coverage_branch_probe(input: bool) -> &'static str {
     if input {
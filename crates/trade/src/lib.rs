@@ -5,6 +5,8 @@ extern crate alloc;
 
 pub mod listing;
 pub mod order;
+#[cfg(feature = "serde_json")]
+pub mod order_events;
 pub mod prelude;
 #[cfg(feature = "serde_json")]
 pub mod validation_receipt;
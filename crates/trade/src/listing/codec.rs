@@ -18,7 +18,7 @@ use radroots_events::listing::{
 pub(crate) use radroots_events::order::RadrootsListingParseError as ListingParseError;
 use radroots_events::plot::RadrootsPlotRef;
 use radroots_events::resource_area::RadrootsResourceAreaRef;
-use radroots_events::tags::{TAG_D, TAG_PUBLISHED_AT};
+use radroots_events::tags::{TAG_D, TAG_EXPIRATION, TAG_PUBLISHED_AT};
 use radroots_events_codec::d_tag::is_d_tag_base64url;
 use radroots_events_codec::error::EventEncodeError;
 use radroots_events_codec::listing::tags::listing_tags_full;
@@ -202,6 +202,7 @@ fn listing_from_tags(
     let mut images: Vec<RadrootsListingImage> = Vec::new();
     let mut geohash: Option<String> = None;
     let mut published_at: Option<u64> = None;
+    let mut expiration: Option<u64> = None;
 
     let has_structured_location = tags
         .iter()
@@ -220,6 +221,9 @@ fn listing_from_tags(
             TAG_PUBLISHED_AT => {
                 published_at = Some(parse_u64_tag_value(tag.get(1), TAG_PUBLISHED_AT)?);
             }
+            TAG_EXPIRATION => {
+                expiration = Some(parse_u64_tag_value(tag.get(1), TAG_EXPIRATION)?);
+            }
             "process" => set_optional(&mut product.process, tag.get(1)),
             "lot" => set_optional(&mut product.lot, tag.get(1)),
             "location" => {
@@ -464,6 +468,7 @@ fn listing_from_tags(
     Ok(RadrootsListing {
         d_tag,
         published_at,
+        expiration,
         farm: farm_ref,
         product,
         primary_bin_id,
@@ -772,6 +777,42 @@ mod tests {
         assert_eq!(parse_error_tag(err), TAG_PUBLISHED_AT.to_string());
     }
 
+    #[test]
+    fn listing_from_tags_roundtrips_expiration_tag() {
+        let mut tags = base_trade_tags();
+        tags.push(vec![TAG_EXPIRATION.into(), "1782777600".into()]);
+
+        let listing = listing_from_tags(
+            &tags,
+            listing_d_tag(),
+            farm_ref(),
+            "seller".to_string(),
+            None,
+            None,
+        )
+        .expect("listing");
+
+        assert_eq!(listing.expiration, Some(1_782_777_600));
+
+        let expiration = tags
+            .iter_mut()
+            .find(|tag| tag.first().map(|value| value.as_str()) == Some(TAG_EXPIRATION))
+            .expect("expiration tag");
+        expiration[1] = "bad".to_string();
+
+        let err = listing_from_tags(
+            &tags,
+            listing_d_tag(),
+            farm_ref(),
+            "seller".to_string(),
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(parse_error_tag(err), TAG_EXPIRATION.to_string());
+    }
+
     #[test]
     fn listing_from_tags_rejects_invalid_d_tag() {
         let tags = base_trade_tags();
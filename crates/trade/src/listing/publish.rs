@@ -86,6 +86,7 @@ mod tests {
         RadrootsListing {
             d_tag: d_tag("AAAAAAAAAAAAAAAAAAAAAg"),
             published_at: None,
+            expiration: None,
             farm: RadrootsFarmRef {
                 pubkey: String::new(),
                 d_tag: "AAAAAAAAAAAAAAAAAAAAAA".into(),
@@ -142,6 +142,12 @@ pub fn validate_listing_event(
         .delivery_method
         .clone()
         .ok_or(TradeListingValidationError::MissingDeliveryMethod)?;
+    if listing
+        .expiration
+        .is_some_and(|expiration| expiration <= u64::from(event.created_at))
+    {
+        return Err(TradeListingValidationError::InvalidExpiration);
+    }
 
     Ok(RadrootsTradeListing {
         listing_id,
@@ -192,6 +198,7 @@ mod tests {
         RadrootsListing {
             d_tag: d_tag("AAAAAAAAAAAAAAAAAAAAAg"),
             published_at: None,
+            expiration: None,
             farm: RadrootsFarmRef {
                 pubkey: "seller".into(),
                 d_tag: "AAAAAAAAAAAAAAAAAAAAAA".into(),
@@ -479,6 +486,20 @@ mod tests {
         assert_validation_err(listing, TradeListingValidationError::MissingDeliveryMethod);
     }
 
+    #[test]
+    fn validate_listing_checks_expiration_against_created_at() {
+        let mut listing = base_listing();
+        listing.expiration = Some(1_782_777_600);
+        let mut event = base_event(&listing);
+        event.created_at = 1_781_895_600;
+        let validated = validate_listing_event(&event).expect("future expiration");
+        assert_eq!(validated.listing.expiration, Some(1_782_777_600));
+
+        event.created_at = 1_782_777_600;
+        let err = validate_listing_event(&event).unwrap_err();
+        assert_eq!(err, TradeListingValidationError::InvalidExpiration);
+    }
+
     #[test]
     fn validation_error_display_covers_all_variants() {
         let errors = vec![
@@ -509,6 +530,7 @@ mod tests {
             TradeListingValidationError::MissingAvailability,
             TradeListingValidationError::MissingLocation,
             TradeListingValidationError::MissingDeliveryMethod,
+            TradeListingValidationError::InvalidExpiration,
         ];
         for error in errors {
             assert!(!error.to_string().trim().is_empty());
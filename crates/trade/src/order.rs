@@ -146,6 +146,21 @@ pub enum RadrootsOrderStatus {
     Invalid,
 }
 
+impl RadrootsOrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Requested => "requested",
+            Self::Accepted => "accepted",
+            Self::Declined => "declined",
+            Self::Cancelled => "cancelled",
+            Self::Completed => "completed",
+            Self::Disputed => "disputed",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RadrootsOrderPaymentState {
     NotRecorded,
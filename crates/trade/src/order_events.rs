@@ -0,0 +1,455 @@
+#![forbid(unsafe_code)]
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use radroots_events::RadrootsNostrEvent;
+use radroots_events::ids::{RadrootsEventId, RadrootsOrderId, RadrootsPublicKey};
+use radroots_events::kinds::{
+    KIND_ORDER_CANCELLATION, KIND_ORDER_DECISION, KIND_ORDER_FULFILLMENT_UPDATE,
+    KIND_ORDER_PAYMENT_RECORD, KIND_ORDER_RECEIPT, KIND_ORDER_REQUEST,
+    KIND_ORDER_REVISION_DECISION, KIND_ORDER_REVISION_PROPOSAL, KIND_ORDER_SETTLEMENT_DECISION,
+};
+use radroots_events::order::RadrootsOrderEventType;
+use radroots_events::tags::{TAG_E_PREV, TAG_E_ROOT};
+use radroots_events_codec::order::{
+    RadrootsOrderEnvelopeParseError, order_cancellation_from_event, order_decision_from_event,
+    order_event_context_from_tags, order_fulfillment_update_from_event,
+    order_payment_record_from_event, order_receipt_from_event, order_request_from_event,
+    order_revision_decision_from_event, order_revision_proposal_from_event,
+    order_settlement_decision_from_event,
+};
+use thiserror::Error;
+
+use crate::order::{
+    RadrootsOrderCancellationRecord, RadrootsOrderDecisionRecord, RadrootsOrderFulfillmentRecord,
+    RadrootsOrderPaymentEventRecord, RadrootsOrderProjection, RadrootsOrderReceiptRecord,
+    RadrootsOrderRequestRecord, RadrootsOrderRevisionDecisionRecord,
+    RadrootsOrderRevisionProposalRecord, RadrootsOrderSettlementRecord, reduce_order_events,
+};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RadrootsOrderEventRecordError {
+    #[error("event kind {0} is not an order event")]
+    UnsupportedKind(u32),
+    #[error("order event id is invalid")]
+    InvalidEventId,
+    #[error("order event author is invalid")]
+    InvalidAuthor,
+    #[error("order event is missing tag {0}")]
+    MissingChainTag(&'static str),
+    #[error("order event could not be parsed: {0}")]
+    Envelope(RadrootsOrderEnvelopeParseError),
+}
+
+impl From<RadrootsOrderEnvelopeParseError> for RadrootsOrderEventRecordError {
+    fn from(error: RadrootsOrderEnvelopeParseError) -> Self {
+        Self::Envelope(error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RadrootsOrderEventRecord {
+    Request(RadrootsOrderRequestRecord),
+    Decision(RadrootsOrderDecisionRecord),
+    RevisionProposal(RadrootsOrderRevisionProposalRecord),
+    RevisionDecision(RadrootsOrderRevisionDecisionRecord),
+    Fulfillment(RadrootsOrderFulfillmentRecord),
+    Cancellation(RadrootsOrderCancellationRecord),
+    Receipt(RadrootsOrderReceiptRecord),
+    Payment(RadrootsOrderPaymentEventRecord),
+    Settlement(RadrootsOrderSettlementRecord),
+}
+
+impl RadrootsOrderEventRecord {
+    pub fn event_id(&self) -> &RadrootsEventId {
+        match self {
+            Self::Request(record) => &record.event_id,
+            Self::Decision(record) => &record.event_id,
+            Self::RevisionProposal(record) => &record.event_id,
+            Self::RevisionDecision(record) => &record.event_id,
+            Self::Fulfillment(record) => &record.event_id,
+            Self::Cancellation(record) => &record.event_id,
+            Self::Receipt(record) => &record.event_id,
+            Self::Payment(record) => &record.event_id,
+            Self::Settlement(record) => &record.event_id,
+        }
+    }
+
+    pub fn order_id(&self) -> &RadrootsOrderId {
+        match self {
+            Self::Request(record) => &record.payload.order_id,
+            Self::Decision(record) => &record.payload.order_id,
+            Self::RevisionProposal(record) => &record.payload.order_id,
+            Self::RevisionDecision(record) => &record.payload.order_id,
+            Self::Fulfillment(record) => &record.payload.order_id,
+            Self::Cancellation(record) => &record.payload.order_id,
+            Self::Receipt(record) => &record.payload.order_id,
+            Self::Payment(record) => &record.payload.order_id,
+            Self::Settlement(record) => &record.payload.order_id,
+        }
+    }
+}
+
+struct OrderChain {
+    event_id: RadrootsEventId,
+    author_pubkey: RadrootsPublicKey,
+    counterparty_pubkey: RadrootsPublicKey,
+    root_event_id: RadrootsEventId,
+    prev_event_id: RadrootsEventId,
+}
+
+pub fn order_event_record_from_event(
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsOrderEventRecord, RadrootsOrderEventRecordError> {
+    let record = match event.kind {
+        KIND_ORDER_REQUEST => {
+            let envelope = order_request_from_event(event)?;
+            RadrootsOrderEventRecord::Request(RadrootsOrderRequestRecord {
+                event_id: event_id(event)?,
+                author_pubkey: author_pubkey(event)?,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_DECISION => {
+            let envelope = order_decision_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Decision(RadrootsOrderDecisionRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_REVISION_PROPOSAL => {
+            let envelope = order_revision_proposal_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::RevisionProposal(RadrootsOrderRevisionProposalRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_REVISION_DECISION => {
+            let envelope = order_revision_decision_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::RevisionDecision(RadrootsOrderRevisionDecisionRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_FULFILLMENT_UPDATE => {
+            let envelope = order_fulfillment_update_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Fulfillment(RadrootsOrderFulfillmentRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_CANCELLATION => {
+            let envelope = order_cancellation_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Cancellation(RadrootsOrderCancellationRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_RECEIPT => {
+            let envelope = order_receipt_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Receipt(RadrootsOrderReceiptRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_PAYMENT_RECORD => {
+            let envelope = order_payment_record_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Payment(RadrootsOrderPaymentEventRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        KIND_ORDER_SETTLEMENT_DECISION => {
+            let envelope = order_settlement_decision_from_event(event)?;
+            let chain = order_chain(event, envelope.message_type)?;
+            RadrootsOrderEventRecord::Settlement(RadrootsOrderSettlementRecord {
+                event_id: chain.event_id,
+                author_pubkey: chain.author_pubkey,
+                counterparty_pubkey: chain.counterparty_pubkey,
+                root_event_id: chain.root_event_id,
+                prev_event_id: chain.prev_event_id,
+                payload: envelope.payload,
+            })
+        }
+        kind => return Err(RadrootsOrderEventRecordError::UnsupportedKind(kind)),
+    };
+    Ok(record)
+}
+
+pub fn reduce_order_records<I>(order_id: &RadrootsOrderId, records: I) -> RadrootsOrderProjection
+where
+    I: IntoIterator<Item = RadrootsOrderEventRecord>,
+{
+    let mut requests = Vec::new();
+    let mut decisions = Vec::new();
+    let mut revision_proposals = Vec::new();
+    let mut revision_decisions = Vec::new();
+    let mut fulfillments = Vec::new();
+    let mut cancellations = Vec::new();
+    let mut receipts = Vec::new();
+    let mut payments = Vec::new();
+    let mut settlements = Vec::new();
+    for record in records {
+        match record {
+            RadrootsOrderEventRecord::Request(record) => requests.push(record),
+            RadrootsOrderEventRecord::Decision(record) => decisions.push(record),
+            RadrootsOrderEventRecord::RevisionProposal(record) => revision_proposals.push(record),
+            RadrootsOrderEventRecord::RevisionDecision(record) => revision_decisions.push(record),
+            RadrootsOrderEventRecord::Fulfillment(record) => fulfillments.push(record),
+            RadrootsOrderEventRecord::Cancellation(record) => cancellations.push(record),
+            RadrootsOrderEventRecord::Receipt(record) => receipts.push(record),
+            RadrootsOrderEventRecord::Payment(record) => payments.push(record),
+            RadrootsOrderEventRecord::Settlement(record) => settlements.push(record),
+        }
+    }
+    reduce_order_events(
+        order_id,
+        requests,
+        decisions,
+        revision_proposals,
+        revision_decisions,
+        fulfillments,
+        cancellations,
+        receipts,
+        payments,
+        settlements,
+    )
+}
+
+fn event_id(event: &RadrootsNostrEvent) -> Result<RadrootsEventId, RadrootsOrderEventRecordError> {
+    RadrootsEventId::parse(&event.id).map_err(|_| RadrootsOrderEventRecordError::InvalidEventId)
+}
+
+fn author_pubkey(
+    event: &RadrootsNostrEvent,
+) -> Result<RadrootsPublicKey, RadrootsOrderEventRecordError> {
+    RadrootsPublicKey::parse(&event.author)
+        .map_err(|_| RadrootsOrderEventRecordError::InvalidAuthor)
+}
+
+fn order_chain(
+    event: &RadrootsNostrEvent,
+    message_type: RadrootsOrderEventType,
+) -> Result<OrderChain, RadrootsOrderEventRecordError> {
+    let context = order_event_context_from_tags(message_type, &event.tags)?;
+    Ok(OrderChain {
+        event_id: event_id(event)?,
+        author_pubkey: author_pubkey(event)?,
+        counterparty_pubkey: context.counterparty_pubkey,
+        root_event_id: context
+            .root_event_id
+            .ok_or(RadrootsOrderEventRecordError::MissingChainTag(TAG_E_ROOT))?,
+        prev_event_id: context
+            .prev_event_id
+            .ok_or(RadrootsOrderEventRecordError::MissingChainTag(TAG_E_PREV))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use radroots_core::{
+        RadrootsCoreCurrency, RadrootsCoreDecimal, RadrootsCoreMoney, RadrootsCoreUnit,
+    };
+    use radroots_events::RadrootsNostrEvent;
+    use radroots_events::RadrootsNostrEventPtr;
+    use radroots_events::ids::{
+        RadrootsEventId, RadrootsInventoryBinId, RadrootsListingAddress, RadrootsOrderId,
+        RadrootsOrderQuoteId, RadrootsPublicKey,
+    };
+    use radroots_events::kinds::{KIND_LISTING, KIND_POST};
+    use radroots_events::order::{
+        RadrootsOrderDecision, RadrootsOrderDecisionOutcome, RadrootsOrderEconomicItem,
+        RadrootsOrderEconomics, RadrootsOrderInventoryCommitment, RadrootsOrderItem,
+        RadrootsOrderPricingBasis, RadrootsOrderRequest,
+    };
+    use radroots_events::tags::TAG_E_ROOT;
+    use radroots_events_codec::order::{order_decision_event_build, order_request_event_build};
+    use radroots_events_codec::wire::WireEventParts;
+
+    use super::{
+        RadrootsOrderEventRecord, RadrootsOrderEventRecordError, order_event_record_from_event,
+        reduce_order_records,
+    };
+    use crate::order::RadrootsOrderStatus;
+
+    const SELLER: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+    const BUYER: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+    const REQUEST_EVENT_ID: &str =
+        "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const DECISION_EVENT_ID: &str =
+        "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+
+    fn usd(raw: &str) -> RadrootsCoreMoney {
+        RadrootsCoreMoney::new(decimal(raw), RadrootsCoreCurrency::USD)
+    }
+
+    fn decimal(raw: &str) -> RadrootsCoreDecimal {
+        raw.parse().unwrap()
+    }
+
+    fn listing_address() -> RadrootsListingAddress {
+        RadrootsListingAddress::parse(format!("{KIND_LISTING}:{SELLER}:AAAAAAAAAAAAAAAAAAAAAg"))
+            .unwrap()
+    }
+
+    fn request_payload() -> RadrootsOrderRequest {
+        RadrootsOrderRequest {
+            order_id: RadrootsOrderId::parse("order-1").unwrap(),
+            listing_addr: listing_address(),
+            buyer_pubkey: RadrootsPublicKey::parse(BUYER).unwrap(),
+            seller_pubkey: RadrootsPublicKey::parse(SELLER).unwrap(),
+            items: vec![RadrootsOrderItem {
+                bin_id: RadrootsInventoryBinId::parse("bin-1").unwrap(),
+                bin_count: 2,
+            }],
+            economics: RadrootsOrderEconomics {
+                quote_id: RadrootsOrderQuoteId::parse("quote-1").unwrap(),
+                quote_version: 1,
+                pricing_basis: RadrootsOrderPricingBasis::ListingEvent,
+                currency: RadrootsCoreCurrency::USD,
+                items: vec![RadrootsOrderEconomicItem {
+                    bin_id: RadrootsInventoryBinId::parse("bin-1").unwrap(),
+                    bin_count: 2,
+                    quantity_amount: decimal("1"),
+                    quantity_unit: RadrootsCoreUnit::Each,
+                    unit_price_amount: decimal("5"),
+                    unit_price_currency: RadrootsCoreCurrency::USD,
+                    line_subtotal: usd("10"),
+                }],
+                discounts: Vec::new(),
+                adjustments: Vec::new(),
+                subtotal: usd("10"),
+                discount_total: usd("0"),
+                adjustment_total: usd("0"),
+                total: usd("10"),
+            },
+        }
+    }
+
+    fn decision_payload() -> RadrootsOrderDecision {
+        RadrootsOrderDecision {
+            order_id: RadrootsOrderId::parse("order-1").unwrap(),
+            listing_addr: listing_address(),
+            buyer_pubkey: RadrootsPublicKey::parse(BUYER).unwrap(),
+            seller_pubkey: RadrootsPublicKey::parse(SELLER).unwrap(),
+            decision: RadrootsOrderDecisionOutcome::Accepted {
+                inventory_commitments: vec![RadrootsOrderInventoryCommitment {
+                    bin_id: RadrootsInventoryBinId::parse("bin-1").unwrap(),
+                    bin_count: 2,
+                }],
+            },
+        }
+    }
+
+    fn event(parts: WireEventParts, id: &str, author: &str, created_at: u32) -> RadrootsNostrEvent {
+        RadrootsNostrEvent {
+            id: id.to_owned(),
+            author: author.to_owned(),
+            created_at,
+            kind: parts.kind,
+            tags: parts.tags,
+            content: parts.content,
+            sig: String::new(),
+        }
+    }
+
+    fn request_event() -> RadrootsNostrEvent {
+        let listing_event = RadrootsNostrEventPtr {
+            id: "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".to_owned(),
+            relays: None,
+        };
+        let parts = order_request_event_build(&listing_event, &request_payload()).unwrap();
+        event(parts, REQUEST_EVENT_ID, BUYER, 10)
+    }
+
+    fn decision_event() -> RadrootsNostrEvent {
+        let request_id = RadrootsEventId::parse(REQUEST_EVENT_ID).unwrap();
+        let parts =
+            order_decision_event_build(&request_id, &request_id, &decision_payload()).unwrap();
+        event(parts, DECISION_EVENT_ID, SELLER, 11)
+    }
+
+    #[test]
+    fn order_events_decode_into_records_and_reduce() {
+        let request = order_event_record_from_event(&request_event()).unwrap();
+        let decision = order_event_record_from_event(&decision_event()).unwrap();
+
+        assert!(matches!(request, RadrootsOrderEventRecord::Request(_)));
+        assert!(matches!(decision, RadrootsOrderEventRecord::Decision(_)));
+        assert_eq!(request.order_id().as_str(), "order-1");
+        assert_eq!(decision.event_id().as_str(), DECISION_EVENT_ID);
+
+        let order_id = RadrootsOrderId::parse("order-1").unwrap();
+        let projection = reduce_order_records(&order_id, [decision, request]);
+
+        assert_eq!(projection.status, RadrootsOrderStatus::Accepted);
+        assert_eq!(
+            projection.request_event_id.as_ref().map(|id| id.as_str()),
+            Some(REQUEST_EVENT_ID)
+        );
+        assert_eq!(
+            projection.decision_event_id.as_ref().map(|id| id.as_str()),
+            Some(DECISION_EVENT_ID)
+        );
+        assert!(projection.issues.is_empty());
+    }
+
+    #[test]
+    fn order_event_records_reject_unsupported_or_unchained_events() {
+        let mut post = request_event();
+        post.kind = KIND_POST;
+        assert_eq!(
+            order_event_record_from_event(&post),
+            Err(RadrootsOrderEventRecordError::UnsupportedKind(KIND_POST))
+        );
+
+        let mut unchained = decision_event();
+        unchained
+            .tags
+            .retain(|tag| tag.first().map(String::as_str) != Some(TAG_E_ROOT));
+        assert!(order_event_record_from_event(&unchained).is_err());
+
+        let mut bad_id = request_event();
+        bad_id.id = "not-an-event-id".to_owned();
+        assert_eq!(
+            order_event_record_from_event(&bad_id),
+            Err(RadrootsOrderEventRecordError::InvalidEventId)
+        );
+    }
+}
@@ -1,4 +1,6 @@
 pub use crate::listing::*;
 pub use crate::order::*;
 #[cfg(feature = "serde_json")]
+pub use crate::order_events::*;
+#[cfg(feature = "serde_json")]
 pub use crate::validation_receipt::*;